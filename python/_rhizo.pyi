@@ -1,21 +1,42 @@
 """Type stubs for the _rhizo Rust extension module (rhizo-core)."""
 
-from typing import List, Dict, Optional, Tuple, Union
+import os
+from typing import Any, BinaryIO, Callable, List, Dict, Optional, Tuple, Union
 import pyarrow as pa
 
 class PyChunkStore:
-    def __init__(self, path: str) -> None: ...
+    def __init__(self, path: str, retries: int = 0, short_hash_len: int = 12) -> None: ...
+    def short_hash(self, full: str) -> str: ...
+    def resolve_prefix(self, prefix: str) -> str: ...
     def put(self, data: bytes) -> str: ...
+    def put_stream(self, fileobj: BinaryIO) -> str: ...
     def get(self, hash: str) -> bytes: ...
+    def put_with_meta(self, data: bytes, meta: Dict[str, str]) -> str: ...
+    def get_meta(self, hash: str) -> Dict[str, str]: ...
     def get_verified(self, hash: str) -> bytes: ...
     def exists(self, hash: str) -> bool: ...
+    def missing_from(self, hashes: List[str]) -> List[str]: ...
     def delete(self, hash: str) -> None: ...
+    def garbage_collect(self, live_hashes: List[str], prune_empty_dirs: bool = False) -> Dict[str, int]: ...
     def put_batch(self, chunks: List[bytes]) -> List[str]: ...
     def get_batch(self, hashes: List[str]) -> List[bytes]: ...
+    def get_batch_collect(self, hashes: List[str]) -> List[Union[bytes, Exception]]: ...
     def get_batch_verified(self, hashes: List[str]) -> List[bytes]: ...
     def get_mmap(self, hash: str) -> bytes: ...
     def get_mmap_batch(self, hashes: List[str]) -> List[bytes]: ...
 
+def configure_parallelism(n: int) -> None:
+    """Run the crate's parallel operations on a dedicated pool of n threads.
+
+    Call once at startup, before any batch operation. Passing 0 returns to
+    the shared pool sized to every CPU.
+    """
+    ...
+
+def current_parallelism() -> int:
+    """Number of threads the crate's parallel operations run on."""
+    ...
+
 class PyTableVersion:
     table_name: str
     version: int
@@ -31,17 +52,147 @@ class PyTableVersion:
         version: int,
         chunk_hashes: List[str],
     ) -> None: ...
+    def content_hash(self) -> str: ...
+
+class PyChainReuse:
+    """Chunk reuse across a table's version chain."""
+    versions: int
+    total_references: int
+    unique_chunks: int
+    avg_reuse_ratio: float
+    referenced_bytes: int
+    unique_bytes: int
+
+class PyPreviewReport:
+    """What writing a batch as a table's next version would change."""
+    new_chunks: int
+    reused_chunks: int
+    new_bytes: int
+    would_be_noop: bool
+
+class PySchemaCompat:
+    """How a new schema relates to a table's current one."""
+    kind: str  # "identical", "compatible" or "breaking"
+    added_columns: List[str]
+    changes: List[str]
+    is_breaking: bool
 
 class PyCatalog:
-    def __init__(self, path: str) -> None: ...
+    def __init__(self, path: str, strict_schemas: bool = False) -> None: ...
+    def store_schema(self, schema: pa.Schema) -> str:
+        """Store a schema and return its hash, for a version's schema_hash."""
+        ...
+    def load_schema(self, schema_hash: str) -> Optional[pa.Schema]: ...
+    def check_schema_compatibility(self, table_name: str, schema: pa.Schema) -> PySchemaCompat:
+        """Classify a schema against the table's latest stored schema."""
+        ...
     def commit(self, version: PyTableVersion) -> int: ...
+    def commit_validated(self, version: PyTableVersion) -> int: ...
     def get_version(
         self,
         table_name: str,
-        version: Optional[int] = None,
-    ) -> PyTableVersion: ...
+        version: Optional[Union[int, str]] = None,
+    ) -> PyTableVersion:
+        """Get a table version by number or tag (latest if omitted)."""
+        ...
     def list_versions(self, table_name: str) -> List[int]: ...
+    def list_versions_detailed(self, table_name: str) -> List[Tuple[int, int]]: ...
+    def get_version_as_of(self, table_name: str, timestamp: int) -> PyTableVersion:
+        """Get the newest version created at or before a Unix timestamp."""
+        ...
     def list_tables(self) -> List[str]: ...
+    def schema_history(self, table_name: str) -> List[Tuple[int, Optional[str]]]: ...
+    def schema_change_points(self, table_name: str) -> List[int]: ...
+    def version_content_hash(
+        self,
+        table_name: str,
+        version: Optional[int] = None,
+    ) -> str: ...
+    def chain_reuse(self, table_name: str, store: PyChunkStore) -> PyChainReuse: ...
+    def changed_chunks(
+        self,
+        table_name: str,
+        from_version: int,
+        to_version: int,
+    ) -> List[str]: ...
+    def tag_version(self, table_name: str, tag: str, version: int) -> None:
+        """Tag a table version, in the same namespace as create_tag.
+
+        Raises ValueError if the tag already names a different version.
+        """
+        ...
+    def list_all_tags(self) -> Dict[str, List[Tuple[str, int]]]:
+        """Every tag grouped by table, as (tag, version) sorted by tag."""
+        ...
+    def create_tag(self, tag: str, table_name: str, version: int, force: bool = False) -> None:
+        """Create an immutable catalog-wide tag for a table version.
+
+        Raises ValueError if the tag already exists, unless force is True.
+        """
+        ...
+    def resolve_tag(self, tag: str) -> Optional[Tuple[str, int]]: ...
+    def list_tags(self) -> List[Tuple[str, str, int]]: ...
+    def delete_tag(self, tag: str) -> bool: ...
+    def delete_version(self, table_name: str, version: int) -> None:
+        """Delete one version, repointing its children at its parent."""
+        ...
+    def prune_versions(
+        self,
+        table_name: str,
+        keep_last: int,
+        protected: Optional[List[int]] = None,
+    ) -> List[int]:
+        """Delete all but the newest keep_last versions of a table.
+
+        The latest version, tagged versions and versions in protected (e.g.
+        branch heads) are kept. Returns the deleted version numbers.
+        """
+        ...
+    def drop_table(self, table_name: str, remove_versions: bool = False) -> None: ...
+    def drop_table_with_orphans(
+        self,
+        table_name: str,
+        remove_versions: bool = False,
+    ) -> List[str]: ...
+
+def transfer_plan(
+    target_versions: Dict[str, PyTableVersion],
+    store: PyChunkStore,
+) -> List[str]: ...
+
+class PyTable:
+    """Handle for reading and writing one table's versions."""
+    name: str
+    def __init__(
+        self,
+        catalog: PyCatalog,
+        store: PyChunkStore,
+        name: str,
+        chunk_size_bytes: Optional[int] = None,
+        chunk_size_rows: Optional[int] = None,
+    ) -> None: ...
+    def rechunk(self, config: "PyMerkleConfig") -> int:
+        """Re-chunk the latest version into chunks of about config.chunk_size
+        encoded bytes and commit the same rows as a new version.
+
+        Returns:
+            The new version number
+
+        Raises:
+            IOError: If the table does not exist or a chunk is missing
+            ValueError: If the config is invalid or the table has no rows
+        """
+        ...
+    def preview_write(self, batch: pa.RecordBatch) -> PyPreviewReport:
+        """Preview writing a batch as the next version, storing nothing.
+
+        The batch is chunked and encoded as a write would, and the Merkle
+        tree over its chunks is diffed against the latest version's.
+
+        Raises:
+            ValueError: If the batch is invalid or has no rows
+        """
+        ...
 
 class PyBranch:
     """A branch represents a named pointer to table versions."""
@@ -63,7 +214,12 @@ class PyBranchDiff:
 
 class PyBranchManager:
     """Manages branches for Rhizo tables."""
-    def __init__(self, path: str) -> None: ...
+    def __init__(
+        self,
+        path: str,
+        default_branch: str = "main",
+        cache: bool = False,
+    ) -> None: ...
     def create(
         self,
         name: str,
@@ -74,10 +230,88 @@ class PyBranchManager:
     def list(self) -> List[str]: ...
     def delete(self, name: str) -> None: ...
     def update_head(self, branch_name: str, table_name: str, version: int) -> None: ...
+    def update_heads(self, branch_name: str, updates: List[Tuple[str, int]]) -> None: ...
     def get_table_version(self, branch_name: str, table_name: str) -> Optional[int]: ...
     def diff(self, source: str, target: str) -> PyBranchDiff: ...
+    def diff_many(self, source: str, targets: List[str]) -> List[PyBranchDiff]: ...
     def can_fast_forward(self, source: str, target: str) -> bool: ...
     def merge(self, source: str, into: str) -> None: ...
+    def merge_many(
+        self,
+        sources: List[str],
+        into: str,
+        catalog: PyCatalog,
+    ) -> PyMergeOutcome: ...
+    def merge_three_way_with_policy(
+        self,
+        source: str,
+        into: str,
+        catalog: PyCatalog,
+        policy_map: Dict[str, str],
+    ) -> PyMergeOutcome:
+        """Merge source into a target, settling diverged tables by policy.
+
+        Args:
+            policy_map: Table name -> "take_source", "take_target", "skip"
+                or "abort". Diverged tables without an entry are skipped.
+        """
+        ...
+    def merge_three_way(
+        self,
+        source: str,
+        into: str,
+        catalog: PyCatalog,
+        store: PyChunkStore,
+        registry: PyAlgebraicSchemaRegistry,
+    ) -> PyMergeOutcome:
+        """Merge source into a target, settling diverged tables algebraically.
+
+        The rows of each table both branches changed are combined under its
+        conflict-free schema, committed as the table's next version and set
+        as the target's head. If any diverged table cannot be combined, the
+        outcome lists the conflicts and nothing is written.
+        """
+        ...
+    def rebase(self, branch: str, onto: str, catalog: PyCatalog) -> List[str]:
+        """Replay branch's table updates on top of onto's current heads.
+
+        Tables only onto changed are inherited. Returns the tables whose
+        branch version was kept. Raises ValueError if both branches advanced
+        the same table.
+        """
+        ...
+    def log(
+        self,
+        branch: str,
+        table: str,
+        catalog: PyCatalog,
+        limit: Optional[int] = None,
+    ) -> List[PyTableVersion]:
+        """Versions a table has gone through on a branch, newest first.
+
+        Follows parent_version from the branch's head to the root.
+        """
+        ...
+    def get_version_as_of(
+        self,
+        branch: str,
+        table: str,
+        timestamp: int,
+        catalog: PyCatalog,
+    ) -> PyTableVersion:
+        """The version of a table a branch saw at a Unix timestamp."""
+        ...
+    def ancestry(self, branch: str) -> List[str]:
+        """Names of the branches a branch descends from, nearest first."""
+        ...
+    def exclusive_chunks(self, branch: str, other: str, catalog: PyCatalog) -> List[str]: ...
+    def branch_size(self, branch: str, catalog: PyCatalog, store: PyChunkStore) -> int: ...
+    def branches_referencing_chunk(self, hash: str, catalog: PyCatalog) -> List[str]:
+        """Names of the branches whose head versions include the chunk, sorted."""
+        ...
+    def live_chunks(self, catalog: PyCatalog) -> List[str]: ...
+    @staticmethod
+    def validate_name(name: str) -> bool: ...
     def get_default(self) -> Optional[str]: ...
     def set_default(self, name: str) -> None: ...
 
@@ -103,32 +337,82 @@ class PyRecoveryReport:
     errors: List[str]
     is_clean: bool
 
+class PyReconcileReport:
+    """Result of cross-checking the changelog against the catalog."""
+    transactions_checked: int
+    versions_checked: int
+    missing_in_catalog: List[Tuple[int, str, int]]
+    chunk_mismatches: List[Tuple[int, str, int]]
+    missing_in_changelog: List[Tuple[str, int]]
+    repaired: List[int]
+    is_consistent: bool
+
 class PyTransactionManager:
     """Manages cross-table ACID transactions."""
+    conflict_window: str
+
     def __init__(
         self,
         base_path: str,
         catalog_path: str,
         branch_path: Optional[str] = None,
         auto_recover: bool = False,
+        conflict_window: str = "all",
+        timeout_secs: Optional[float] = None,
     ) -> None: ...
+    def clone_handle(self) -> PyTransactionManager: ...
+    def reap_expired(self) -> List[int]:
+        """Abort active transactions older than timeout_secs; returns their IDs."""
+        ...
+    def advance_epoch(self) -> int: ...
     def begin(self, branch: Optional[str] = None) -> int: ...
+    def begin_speculative(self, branch: Optional[str] = None) -> int: ...
+    def begin_read_only(self, branch: Optional[str] = None) -> int:
+        """Begin a read-only transaction pinned to the current table versions."""
+        ...
+    def snapshot_version(self, tx_id: int, table_name: str) -> Optional[int]: ...
+    def is_speculative(self, tx_id: int) -> bool: ...
+    def promote(self, tx_id: int) -> int: ...
+    def check_conflicts(self, tx_id: int) -> None: ...
     def add_write(
         self,
         tx_id: int,
         table_name: str,
         new_version: int,
         chunk_hashes: List[str],
-    ) -> None: ...
+        key_range: Optional[Tuple[Any, Any]] = None,
+    ) -> None:
+        """Add a write; with key_range, only overlapping ranges conflict."""
+        ...
+    def savepoint(self, tx_id: int) -> int: ...
+    def rollback_to(self, tx_id: int, savepoint: int) -> None:
+        """Undo writes and reads recorded since the savepoint."""
+        ...
     def record_read(self, tx_id: int, table_name: str, version: int) -> None: ...
+    def latest_version_or_none(self, tx_id: int, table_name: str) -> Optional[int]: ...
     def commit(self, tx_id: int) -> None: ...
+    def commit_with_retry(
+        self,
+        tx_id: int,
+        max_attempts: int,
+        rebuild_callback: Callable[[Dict[str, int]], List[Tuple[str, int, List[str]]]],
+    ) -> int:
+        """Commit, rebuilding writes from a fresh snapshot after each conflict.
+
+        tx_id is aborted after its first conflict; retries commit from new
+        transactions, each checked only against commits made after it began.
+        """
+        ...
     def abort(self, tx_id: int, reason: str = "User requested") -> None: ...
     def get_transaction(self, tx_id: int) -> PyTransactionInfo: ...
     def active_transactions(self) -> List[PyTransactionInfo]: ...
+    def write_set_map(self) -> Dict[str, List[int]]: ...
     def active_count(self) -> int: ...
     def recover(self) -> PyRecoveryReport: ...
     def recover_and_apply(self) -> PyRecoveryReport: ...
     def verify_consistency(self) -> List[str]: ...
+    def reconcile_changelog(self, repair: bool = False) -> PyReconcileReport: ...
+    def metrics(self) -> Dict[str, int]: ...
     def get_changelog(
         self,
         since_tx_id: Optional[int] = None,
@@ -136,9 +420,27 @@ class PyTransactionManager:
         tables: Optional[List[str]] = None,
         branch: Optional[str] = None,
         limit: Optional[int] = None,
+        after_cursor: Optional[str] = None,
+        descending: bool = False,
+        until_tx_id: Optional[int] = None,
+        until_timestamp: Optional[int] = None,
+    ) -> Tuple[List["PyChangelogEntry"], Optional[str]]: ...
+    def merged_changelog(
+        self,
+        branches: List[str],
+        since_timestamp: int,
     ) -> List["PyChangelogEntry"]: ...
+    def recently_modified(self, since_timestamp: int) -> List[Tuple[str, str, int]]: ...
     def latest_tx_id(self) -> Optional[int]: ...
 
+class PyRepository:
+    path: str
+    chunks: PyChunkStore
+    catalog: PyCatalog
+    branches: PyBranchManager
+    transactions: PyTransactionManager
+    def __init__(self, path: str) -> None: ...
+
 class PyTableChange:
     """A single table change within a committed transaction."""
     table_name: str
@@ -152,6 +454,7 @@ class PyChangelogEntry:
     """Entry in the changelog representing a committed transaction."""
     tx_id: int
     epoch_id: int
+    commit_seq: int
     committed_at: int
     branch: str
     changes: List[PyTableChange]
@@ -191,6 +494,21 @@ class PyMerkleTree:
     def chunk_hashes(self) -> List[str]: ...
     def chunk_for_offset(self, offset: int) -> Optional[PyDataChunk]: ...
     def chunks_in_range(self, start: int, end: int) -> List[PyDataChunk]: ...
+    def chunks_for_byte_range(self, start: int, end: int) -> List[str]: ...
+    def generate_proof(self, index: int) -> PyMerkleProof: ...
+    def summary(self, max_level: int) -> List[PyMerkleNode]: ...
+    def to_bytes(self) -> bytes: ...
+    @staticmethod
+    def from_bytes(data: bytes) -> PyMerkleTree: ...
+
+class PyMerkleProof:
+    """Inclusion proof that a chunk belongs to a Merkle tree."""
+    chunk_index: int
+
+    def depth(self) -> int: ...
+    def to_json(self) -> str: ...
+    @staticmethod
+    def from_json(json: str) -> PyMerkleProof: ...
 
 class PyMerkleDiff:
     """Result of comparing two Merkle trees."""
@@ -208,12 +526,23 @@ class PyMerkleConfig:
     """Configuration for Merkle tree building."""
     chunk_size: int
     branching_factor: int
+    content_defined_chunking: bool
 
     def __init__(
         self,
         chunk_size: int = 65536,
         branching_factor: int = 2,
     ) -> None: ...
+    @staticmethod
+    def content_defined(
+        min_size: int,
+        avg_size: int,
+        max_size: int,
+        branching_factor: int = 2,
+    ) -> "PyMerkleConfig": ...
+    def validate(self) -> None: ...
+    def estimate_chunks(self, data_len: int) -> int: ...
+    def estimate_height(self, data_len: int) -> int: ...
 
 def merkle_build_tree(
     data: bytes,
@@ -230,6 +559,26 @@ def merkle_build_tree(
     """
     ...
 
+def merkle_rebuild_tree(
+    old_tree: PyMerkleTree,
+    new_data: bytes,
+    config: Optional[PyMerkleConfig] = None,
+) -> PyMerkleTree:
+    """Rebuild a Merkle tree for edited data, reusing the previous tree.
+
+    Chunks are matched to the old leaves by content, so only changed
+    leaves and their parents up to the root are hashed into the tree.
+
+    Args:
+        old_tree: Tree built from the data before the edit
+        new_data: The edited bytes
+        config: Optional MerkleConfig (uses defaults if not provided)
+
+    Returns:
+        PyMerkleTree equal to merkle_build_tree(new_data, config)
+    """
+    ...
+
 def merkle_diff_trees(
     old_tree: PyMerkleTree,
     new_tree: PyMerkleTree,
@@ -245,6 +594,23 @@ def merkle_diff_trees(
     """
     ...
 
+def merkle_verify_proof(
+    root: str,
+    chunk_hash: str,
+    proof: PyMerkleProof,
+) -> bool:
+    """Check that a chunk belongs to the tree with the given root.
+
+    Args:
+        root: Root hash of the tree
+        chunk_hash: Hash of the chunk being proven
+        proof: PyMerkleProof from PyMerkleTree.generate_proof
+
+    Returns:
+        True if the proof links chunk_hash to root
+    """
+    ...
+
 def merkle_verify_tree(
     tree: PyMerkleTree,
     chunk_store: PyChunkStore,
@@ -263,6 +629,28 @@ def merkle_verify_tree(
     """
     ...
 
+def merkle_read_range(
+    tree: PyMerkleTree,
+    store: PyChunkStore,
+    start: int,
+    end: int,
+) -> bytes:
+    """Read a byte range of a blob, fetching only the chunks that cover it.
+
+    Args:
+        tree: The Merkle tree describing the blob
+        store: A PyChunkStore holding the tree's chunks
+        start: Start byte offset (inclusive)
+        end: End byte offset (exclusive, clamped to the blob size)
+
+    Returns:
+        The exact bytes in [start, end)
+
+    Raises:
+        ValueError: If a needed chunk is missing
+    """
+    ...
+
 # =============================================================================
 # Phase 4: Native Parquet Encoder/Decoder
 # =============================================================================
@@ -274,12 +662,29 @@ class PyParquetEncoder:
     and parallel encoding of multiple batches using Rayon.
     """
 
-    def __init__(self, compression: Optional[str] = None) -> None:
+    def __init__(
+        self,
+        compression: Optional[str] = None,
+        max_row_group_size: Optional[int] = None,
+        enable_dictionary: bool = True,
+        write_statistics: bool = True,
+        data_page_size: Optional[int] = None,
+        preserve_field_metadata: bool = True,
+    ) -> None:
         """Create a new encoder.
 
         Args:
             compression: Compression type ("zstd", "snappy", "gzip", "lz4", "none")
                         Defaults to "zstd" for best compression/speed balance.
+            max_row_group_size: Maximum rows per row group. Smaller groups let
+                        filtered reads skip more data.
+            enable_dictionary: Whether to dictionary-encode columns
+            write_statistics: Whether to write column statistics
+            data_page_size: Target data page size in bytes
+            preserve_field_metadata: Whether to keep Arrow field metadata
+
+        Raises:
+            ValueError: If compression is unknown or max_row_group_size is 0
         """
         ...
 
@@ -306,6 +711,53 @@ class PyParquetEncoder:
         ...
 
 
+class PyParquetStreamWriter:
+    """Streaming Parquet writer that appends one row group per batch.
+
+    Writes go straight to the file or file object, so the full output is
+    never held in memory.
+    """
+
+    def __init__(
+        self,
+        path_or_fileobj: Union[str, os.PathLike, BinaryIO],
+        compression: Optional[str] = None,
+    ) -> None:
+        """Create a stream writer.
+
+        Args:
+            path_or_fileobj: File path, or a binary file object with `write`
+            compression: Compression type ("zstd", "snappy", "gzip", "lz4", "none")
+                        Defaults to "zstd".
+        """
+        ...
+
+    def write_batch(self, batch: pa.RecordBatch) -> None:
+        """Append a batch as a new row group.
+
+        Args:
+            batch: PyArrow RecordBatch; must match the first batch's schema
+        """
+        ...
+
+    def close(self) -> None:
+        """Write the Parquet footer and close the file.
+
+        File objects passed in are flushed but left open.
+
+        Raises:
+            ValueError: If already closed or no rows were written
+        """
+        ...
+
+
+class PyParquetBatchIterator:
+    """Iterator over the batches of a Parquet file, from PyParquetDecoder.decode_iter."""
+
+    def __iter__(self) -> "PyParquetBatchIterator": ...
+    def __next__(self) -> pa.RecordBatch: ...
+
+
 class PyParquetDecoder:
     """High-performance Parquet decoder using Rust's parquet crate.
 
@@ -328,6 +780,21 @@ class PyParquetDecoder:
         """
         ...
 
+    def decode_iter(self, data: bytes) -> PyParquetBatchIterator:
+        """Decode Parquet bytes lazily, one batch at a time.
+
+        Batches are not concatenated, so decoded data in memory is bounded
+        by the decoder's batch size rather than the file size. The encoded
+        bytes are copied once and kept until the iterator is dropped.
+
+        Args:
+            data: Parquet file bytes
+
+        Returns:
+            Iterator yielding PyArrow RecordBatches
+        """
+        ...
+
     def decode_batch(self, chunks: List[bytes]) -> List[pa.RecordBatch]:
         """Decode multiple Parquet chunks in parallel.
 
@@ -376,6 +843,86 @@ class PyParquetDecoder:
         """
         ...
 
+    def decode_cast(self, data: bytes, casts: List[Tuple[str, str]]) -> pa.RecordBatch:
+        """Decode and cast selected columns to target types.
+
+        Float to integer casts truncate toward zero; values that do not fit
+        the target type raise an error rather than becoming null.
+
+        Args:
+            data: Parquet file bytes
+            casts: List of (column_name, type) pairs, where type is an Arrow
+                type name such as "Int64", "Float64" or "Utf8"
+
+        Returns:
+            PyArrow RecordBatch with the listed columns cast
+
+        Raises:
+            ValueError: If a type name is unknown, a column is missing, or a
+                cast is not possible
+        """
+        ...
+
+    def decode_all_nullable(self, data: bytes) -> pa.RecordBatch:
+        """Decode with every top-level field marked nullable.
+
+        Column data is unchanged; use this before concatenating batches
+        from sources that disagree on nullability.
+        """
+        ...
+
+    def decode_rows(self, data: bytes, indices: List[int]) -> pa.RecordBatch:
+        """Decode only the rows at the given 0-based indices.
+
+        Indices are sorted and deduplicated, so rows are returned in
+        ascending index order.
+
+        Args:
+            data: Parquet file bytes
+            indices: Row indices to decode
+
+        Returns:
+            PyArrow RecordBatch with the selected rows
+
+        Raises:
+            IndexError: If an index is past the end of the file
+            ValueError: If indices is empty or decoding fails
+        """
+        ...
+
+    def repair(self, data: bytes) -> Tuple[bytes, PyRepairReport]:
+        """Salvage a file with corrupt row groups.
+
+        Decodes each row group on its own, drops those that fail, and
+        re-encodes the survivors with default encoder settings.
+
+        Args:
+            data: Parquet file bytes (the footer must be readable)
+
+        Returns:
+            Tuple of (repaired file bytes, PyRepairReport)
+
+        Raises:
+            ValueError: If the footer is unreadable or no row group survives
+        """
+        ...
+
+    def compare(self, data_a: bytes, data_b: bytes, float_tolerance: float = 1e-9) -> bool:
+        """Check whether two Parquet files decode to the same rows.
+
+        Schemas must match, nulls must be in the same positions, and float
+        columns may differ by up to float_tolerance.
+
+        Args:
+            data_a: First Parquet file bytes
+            data_b: Second Parquet file bytes
+            float_tolerance: Absolute tolerance for float values
+
+        Returns:
+            True if the files are row-equivalent
+        """
+        ...
+
     def decode_with_filter(
         self,
         data: bytes,
@@ -416,6 +963,48 @@ class PyParquetDecoder:
         """
         ...
 
+    def decode_with_expr(
+        self,
+        data: bytes,
+        expr: "PyPredicateExpr",
+        column_indices: Optional[List[int]] = None,
+    ) -> pa.RecordBatch:
+        """Decode with a predicate expression (AND / OR groups).
+
+        Row groups are skipped only when their statistics rule out the
+        whole expression.
+
+        Args:
+            data: Parquet file bytes
+            expr: PyPredicateExpr to apply
+            column_indices: Optional list of column indices to project
+
+        Returns:
+            PyArrow RecordBatch with the expression applied
+        """
+        ...
+
+    def decode_with_filter_masked(
+        self,
+        data: bytes,
+        filters: List["PyPredicateFilter"],
+        column_indices: Optional[List[int]] = None,
+    ) -> Tuple[pa.RecordBatch, pa.BooleanArray]:
+        """Decode all rows along with a mask of the rows that match the filters.
+
+        No rows are dropped, so positions line up with the file. Rows whose
+        filter column is null do not match.
+
+        Args:
+            data: Parquet file bytes
+            filters: List of PyPredicateFilter objects
+            column_indices: Optional list of column indices to project
+
+        Returns:
+            Tuple of (PyArrow RecordBatch with every row, PyArrow BooleanArray mask)
+        """
+        ...
+
     def get_pruning_stats(
         self,
         data: bytes,
@@ -442,6 +1031,140 @@ class PyParquetDecoder:
         """
         ...
 
+    def plan_filter(
+        self,
+        data: bytes,
+        filters: List["PyPredicateFilter"],
+    ) -> "PyFilterPlan":
+        """Plan a filtered decode using only footer statistics.
+
+        No data is decoded, so this is cheaper than `explain_filter` and
+        suited to cost estimation before running a query.
+
+        Args:
+            data: Parquet file bytes
+            filters: List of PyPredicateFilter objects
+
+        Returns:
+            PyFilterPlan with the surviving row group indices and their row count
+        """
+        ...
+
+    def explain_filter(
+        self,
+        data: bytes,
+        filters: List["PyPredicateFilter"],
+    ) -> "PyFilterExplain":
+        """Explain a filtered decode.
+
+        Reports row-group pruning (as in `get_pruning_stats`) together with
+        how many rows were scanned and returned by the actual decode.
+
+        Args:
+            data: Parquet file bytes
+            filters: List of PyPredicateFilter objects
+
+        Returns:
+            PyFilterExplain with row group and row counts
+        """
+        ...
+
+    def count_batch_with_filter(
+        self,
+        chunks: List[bytes],
+        filters: List["PyPredicateFilter"],
+    ) -> int:
+        """Count rows matching the filters across multiple chunks in parallel.
+
+        Equivalent to `COUNT(*) WHERE ...` over a table stored as many chunks.
+        Empty chunks and chunks with no matching rows contribute 0.
+
+        Args:
+            chunks: List of Parquet file bytes
+            filters: List of PyPredicateFilter objects
+
+        Returns:
+            Total number of matching rows across all chunks
+
+        Example:
+            >>> decoder = PyParquetDecoder()
+            >>> filter = PyPredicateFilter("age", "gt", 50)
+            >>> count = decoder.count_batch_with_filter(chunks, [filter])
+        """
+        ...
+
+    def percentile(
+        self,
+        data: bytes,
+        column: str,
+        q: float,
+        filters: List["PyPredicateFilter"] = [],
+    ) -> float:
+        """Compute the q-th quantile of a numeric column over matching rows.
+
+        Exact: matching values are sorted and linearly interpolated between
+        the two nearest ranks (NumPy's default). Nulls and NaNs are ignored.
+
+        Args:
+            data: Parquet file bytes
+            column: Name of a numeric column
+            q: Quantile between 0 and 1 (0.5 = median, 0.95 = p95)
+            filters: Optional list of PyPredicateFilter objects
+
+        Returns:
+            The quantile value as a float
+
+        Example:
+            >>> decoder = PyParquetDecoder()
+            >>> p95 = decoder.percentile(data, "latency_ms", 0.95)
+        """
+        ...
+
+    def count(self, data: bytes) -> int:
+        """Count rows using only the file footer (nulls included)."""
+        ...
+
+    def min_max(
+        self, data: bytes, column: str
+    ) -> Tuple[ScalarValueType, ScalarValueType]:
+        """Get the smallest and largest non-null values of a column.
+
+        Answered from row-group statistics when every row group has exact
+        ones; otherwise the column is decoded and scanned.
+
+        Args:
+            data: Parquet file bytes
+            column: Column name
+
+        Returns:
+            Tuple of (min, max); both None if the column has no non-null values
+
+        Raises:
+            ValueError: If the column is missing or its type is unsupported
+        """
+        ...
+
+
+class PyFilterExplain:
+    """Structured explanation of a filtered decode (for query debugging)."""
+    total_row_groups: int
+    pruned_row_groups: int
+    rows_scanned: int
+    rows_returned: int
+    filter_columns: List[str]
+
+class PyFilterPlan:
+    """Row groups a filtered decode would read, from footer statistics alone."""
+    row_groups_to_scan: List[int]
+    estimated_rows: int
+
+class PyRepairReport:
+    """Outcome of a Parquet repair."""
+    total_row_groups: int
+    dropped_row_groups: List[int]
+    rows_kept: int
+    rows_lost: int
+
 
 # =============================================================================
 # Phase R.2: Predicate Pushdown Types
@@ -459,13 +1182,21 @@ class PyFilterOp:
       - "le"  → column <= value
       - "gt"  → column > value
       - "ge"  → column >= value
+      - "in"  → column IN (values)
+      - "not_in" → column NOT IN (values)
+      - "is_null" → column IS NULL
+      - "is_not_null" → column IS NOT NULL
+      - "starts_with" → column LIKE 'value%'
+      - "ends_with" → column LIKE '%value'
+      - "contains" → column LIKE '%value%'
     """
 
     def __init__(self, op: str) -> None:
         """Create a filter operation from a string.
 
         Args:
-            op: One of "eq", "ne", "lt", "le", "gt", "ge"
+            op: One of "eq", "ne", "lt", "le", "gt", "ge", "in", "not_in",
+                "is_null", "is_not_null", "starts_with", "ends_with", "contains"
                 Also accepts symbols: "=", "==", "!=", "<>", "<", "<=", ">", ">="
         """
         ...
@@ -505,23 +1236,67 @@ class PyPredicateFilter:
         >>> filter = PyPredicateFilter("age", "gt", 50)
         >>> # status = 'active'
         >>> filter = PyPredicateFilter("status", "eq", "active")
+        >>> # status IN ('active', 'trial')
+        >>> filter = PyPredicateFilter("status", "in", ["active", "trial"])
     """
 
     column: str
     op: str
     value: str
 
-    def __init__(self, column: str, op: str, value: ScalarValueType) -> None:
+    def __init__(
+        self,
+        column: str,
+        op: str,
+        value: Union[ScalarValueType, List[ScalarValueType]] = None,
+    ) -> None:
         """Create a predicate filter.
 
         Args:
             column: Column name to filter on
-            op: Comparison operation (eq, ne, lt, le, gt, ge)
-            value: Value to compare against (int, float, str, bool, or None)
+            op: Comparison operation (eq, ne, lt, le, gt, ge, in, not_in,
+                is_null, is_not_null, starts_with, ends_with, contains)
+            value: Value to compare against (int, float, str, bool, or None);
+                a list of such values for "in" and "not_in". Ignored by
+                "is_null" and "is_not_null"
         """
         ...
 
 
+class PyPredicateExpr:
+    """A boolean combination of predicate filters (AND / OR groups).
+
+    An empty AND matches every row and an empty OR matches none.
+
+    Example:
+        >>> # age < 18 OR age > 65
+        >>> expr = PyPredicateExpr.or_([
+        ...     PyPredicateFilter("age", "lt", 18),
+        ...     PyPredicateFilter("age", "gt", 65),
+        ... ])
+        >>> result = decoder.decode_with_expr(data, expr)
+    """
+
+    @staticmethod
+    def leaf(filter: PyPredicateFilter) -> "PyPredicateExpr":
+        """Wrap a single filter."""
+        ...
+
+    @staticmethod
+    def and_(
+        items: List[Union["PyPredicateExpr", PyPredicateFilter]]
+    ) -> "PyPredicateExpr":
+        """Match rows that match every item."""
+        ...
+
+    @staticmethod
+    def or_(
+        items: List[Union["PyPredicateExpr", PyPredicateFilter]]
+    ) -> "PyPredicateExpr":
+        """Match rows that match at least one item."""
+        ...
+
+
 # =============================================================================
 # Phase AF: Algebraic Classification for Conflict-Free Merge
 # =============================================================================
@@ -530,7 +1305,8 @@ class PyOpType:
     """Algebraic operation type classification.
 
     Operations are classified by their algebraic properties:
-    - Semilattice: Associative, commutative, idempotent (MAX, MIN, UNION, INTERSECT)
+    - Semilattice: Associative, commutative, idempotent (MAX, MIN, UNION, INTERSECT, LWW,
+      ORSET_ADD, ORSET_REMOVE, MAP)
     - Abelian: Associative, commutative, has identity and inverse (ADD, MULTIPLY)
     - Generic: No special properties (OVERWRITE, CONDITIONAL, UNKNOWN)
 
@@ -541,7 +1317,8 @@ class PyOpType:
         """Create an operation type from string.
 
         Args:
-            op_type: One of "MAX", "MIN", "UNION", "INTERSECT", "ADD",
+            op_type: One of "MAX", "MIN", "UNION", "INTERSECT", "LWW",
+                    "ORSET_ADD", "ORSET_REMOVE", "MAP", "ADD",
                     "MULTIPLY", "OVERWRITE", "CONDITIONAL", "UNKNOWN"
         """
         ...
@@ -575,9 +1352,13 @@ class PyAlgebraicValue:
     - StringSet: For tags, permissions (set operations)
     - IntSet: For ID collections
     - Boolean: For flags
+    - Map: For nested fields merged key by key (built from a dict)
     """
 
-    def __init__(self, value: Optional[Union[int, float, bool, List[str], List[int]]]) -> None:
+    def __init__(
+        self,
+        value: Optional[Union[int, float, bool, List[str], List[int], Dict[str, Any]]],
+    ) -> None:
         """Create an algebraic value from a Python value (type inferred)."""
         ...
 
@@ -611,6 +1392,37 @@ class PyAlgebraicValue:
         """Create a null value."""
         ...
 
+    @staticmethod
+    def timestamped(ts: int, value: Any) -> "PyAlgebraicValue":
+        """Create a timestamped value for a last-writer-wins ("lww") register.
+
+        Args:
+            ts: Write timestamp; the larger timestamp wins on merge
+            value: A PyAlgebraicValue, or any value accepted by the constructor
+        """
+        ...
+
+    @staticmethod
+    def or_set(
+        adds: List[Tuple[str, str]] = [],
+        removed: List[str] = [],
+    ) -> "PyAlgebraicValue":
+        """Create an observed-remove set ("orset_add"/"orset_remove") state.
+
+        Args:
+            adds: (element, tag) pairs; every add needs a tag no other add uses
+            removed: Add-tags cancelled by removes
+        """
+        ...
+
+    def observed_remove(self, element: str) -> "PyAlgebraicValue":
+        """Build the remove for `element` as observed in this OR-Set."""
+        ...
+
+    def or_set_elements(self) -> Optional[List[str]]:
+        """Elements present in an OR-Set (sorted), or None for other types."""
+        ...
+
     def is_numeric(self) -> bool:
         """Check if this is a numeric type."""
         ...
@@ -655,8 +1467,20 @@ class PyTableAlgebraicSchema:
         """Add a column with the specified operation type."""
         ...
 
+    def add_custom_column(
+        self,
+        column: str,
+        merge: Callable[[PyAlgebraicValue, PyAlgebraicValue], PyAlgebraicValue],
+    ) -> None:
+        """Add a column merged by a Python callable.
+
+        Custom columns count as conflict-free, so ``merge(a, b)`` must equal
+        ``merge(b, a)``. A raised exception is reported as a merge conflict.
+        """
+        ...
+
     def get_op_type(self, column: str) -> PyOpType:
-        """Get the operation type for a column."""
+        """Get the operation type for a column (Unknown for a custom column)."""
         ...
 
     def is_fully_conflict_free(self) -> bool:
@@ -691,7 +1515,7 @@ class PyAlgebraicSchemaRegistry:
         ...
 
     def get_op_type(self, table: str, column: str) -> PyOpType:
-        """Get the operation type for a table/column."""
+        """Get the operation type for a table/column (Unknown for a custom column)."""
         ...
 
     def has_table(self, table: str) -> bool:
@@ -716,6 +1540,15 @@ class PyMergeAnalysis:
         """Check if merge can proceed without conflicts."""
         ...
 
+    def conflicting_columns(self, table: str) -> Optional[List[str]]:
+        """Conflicting columns of a conflicting table, sorted.
+
+        An empty list means the whole table conflicts (no schema, or a
+        conflicting default op type). Returns None if the table doesn't
+        conflict.
+        """
+        ...
+
     def tables_to_merge(self) -> List[str]:
         """Get all tables that need merging."""
         ...
@@ -729,8 +1562,10 @@ class PyMergeOutcome:
     fast_forwarded: List[str]
     algebraically_merged: List[str]
     conflicts: List[str]
+    resolved: List[str]
     success: bool
     description: Optional[str]
+    provenance: Dict[str, List[int]]
 
 
 def algebraic_merge(
@@ -754,6 +1589,28 @@ def algebraic_merge(
     ...
 
 
+def algebraic_merge_many(
+    branches_data: List[Dict[str, PyAlgebraicValue]],
+    schema: PyTableAlgebraicSchema,
+) -> Dict[str, PyAlgebraicValue]:
+    """Merge the algebraic column values of several branches.
+
+    Each column is folded across every branch that has it, using the
+    column's op type from the schema.
+
+    Args:
+        branches_data: One dict of column -> value per branch
+        schema: Table schema giving each column's op type
+
+    Returns:
+        Dict of column -> merged value
+
+    Raises:
+        ValueError: If a column's values conflict or have mismatched types
+    """
+    ...
+
+
 def analyze_merge(
     registry: PyAlgebraicSchemaRegistry,
     source_branch: PyBranch,
@@ -877,6 +1734,10 @@ class PyVectorClock:
         """
         ...
 
+    def tick_n(self, node_id: PyNodeId, n: int) -> None:
+        """Advance this node's logical time by n (same as n calls to tick)."""
+        ...
+
     def get(self, node_id: PyNodeId) -> int:
         """Get the logical time for a specific node."""
         ...
@@ -893,6 +1754,10 @@ class PyVectorClock:
         """
         ...
 
+    def merge_all(self, clocks: List["PyVectorClock"]) -> None:
+        """Merge several clocks into this one (same as merging each in turn)."""
+        ...
+
     def happened_before(self, other: "PyVectorClock") -> bool:
         """Check if this clock happened strictly before another clock."""
         ...
@@ -1048,6 +1913,17 @@ class PyAlgebraicTransaction:
         ...
 
 
+class PyRepairEvent:
+    """A key whose conflicting op types were repaired during a merge."""
+
+    key: str
+    registered: PyOpType
+    observed: List[PyOpType]
+
+    def __repr__(self) -> str:
+        ...
+
+
 class PyVersionedUpdate:
     """The result of a local commit: operations with their causal context.
 
@@ -1066,11 +1942,32 @@ class PyVersionedUpdate:
     clock: PyVectorClock
     origin_node: PyNodeId
     update_id: Optional[str]
+    metadata: Dict[str, str]
 
     def operations(self) -> List[PyAlgebraicOperation]:
         """Get all operations in this update."""
         ...
 
+    def metadata_conflicts(self, other: "PyVersionedUpdate") -> List[str]:
+        """Metadata keys both updates set to different values, sorted.
+
+        Merging keeps the greatest value for each such key.
+        """
+        ...
+
+    def to_bytes(self) -> bytes:
+        """Serialize to bytes for transport between nodes."""
+        ...
+
+    @staticmethod
+    def from_bytes(data: bytes) -> "PyVersionedUpdate":
+        """Deserialize an update produced by to_bytes.
+
+        Raises:
+            ValueError: If the data is not a valid update
+        """
+        ...
+
     def compare(self, other: "PyVersionedUpdate") -> PyCausalOrder:
         """Compare this update's causality with another."""
         ...
@@ -1140,6 +2037,9 @@ class PyLocalCommitProtocol:
         tx: PyAlgebraicTransaction,
         node_id: PyNodeId,
         clock: PyVectorClock,
+        registry: Optional[PyAlgebraicSchemaRegistry] = None,
+        table: Optional[str] = None,
+        strict: bool = False,
     ) -> PyVersionedUpdate:
         """Commit a transaction locally, returning a versioned update.
 
@@ -1152,12 +2052,18 @@ class PyLocalCommitProtocol:
             tx: The transaction to commit
             node_id: This node's ID
             clock: This node's vector clock (will be mutated)
+            registry: Optional schema registry; each key must use the op type
+                registered for that column of `table`
+            table: Table whose schema the keys are checked against
+                (required with `registry`)
+            strict: Also reject keys with no registered op type
 
         Returns:
             The committed update with causal context
 
         Raises:
-            ValueError: If the transaction cannot be committed locally
+            ValueError: If the transaction cannot be committed locally or an
+                op type conflicts with the registry
         """
         ...
 
@@ -1188,6 +2094,33 @@ class PyLocalCommitProtocol:
         """
         ...
 
+    @staticmethod
+    def merge_updates_repair(
+        update1: PyVersionedUpdate,
+        update2: PyVersionedUpdate,
+        registry: PyAlgebraicSchemaRegistry,
+        table: str,
+    ) -> Tuple[PyVersionedUpdate, List[PyRepairEvent]]:
+        """Merge two updates, repairing keys whose op types disagree.
+
+        Operations on a key with conflicting op types are re-typed to the
+        type registered for that column of `table` and merged again.
+
+        Args:
+            update1: First update
+            update2: Second update
+            registry: Schema registry giving the correct op types
+            table: Table whose schema the keys belong to
+
+        Returns:
+            Tuple of (merged update, list of PyRepairEvent)
+
+        Raises:
+            ValueError: If merge fails, including conflicts on keys with no
+                registered op type
+        """
+        ...
+
     @staticmethod
     def merge_all(updates: List[PyVersionedUpdate]) -> PyVersionedUpdate:
         """Merge multiple updates at once (more efficient than pairwise).
@@ -1203,6 +2136,29 @@ class PyLocalCommitProtocol:
         """
         ...
 
+    @staticmethod
+    def state_snapshot(
+        updates: List[PyVersionedUpdate],
+        clock: PyVectorClock,
+    ) -> PyVersionedUpdate:
+        """Fold a node's applied updates into a single snapshot update.
+
+        Each key gets one operation holding its merged value, and the
+        snapshot carries the node's clock, so a new node can bootstrap from
+        it instead of replaying every update.
+
+        Args:
+            updates: Updates the node has applied
+            clock: The node's current vector clock
+
+        Returns:
+            Snapshot update, with operations sorted by key
+
+        Raises:
+            ValueError: If a key's operations cannot be merged
+        """
+        ...
+
 
 # ============================================================================
 # Phase 4: Simulation Types (Multi-Node Convergence Testing)
@@ -1245,10 +2201,15 @@ class PySimulationConfig:
     Attributes:
         max_rounds: Maximum number of propagation rounds
         randomize_order: Whether to randomize message order
+        delivery_priority: Message delivery order within a round
+            ("fifo", "outbox_size" or "per_message")
+        delivery_budget: Maximum messages delivered per round (None = unlimited)
     """
 
     max_rounds: int
     randomize_order: bool
+    delivery_priority: str
+    delivery_budget: Optional[int]
 
     def __init__(self) -> None:
         """Create a default simulation configuration."""
@@ -1297,6 +2258,23 @@ class PySimulatedNode:
     def __repr__(self) -> str: ...
 
 
+class PyClusterSnapshot:
+    """A point-in-time copy of a simulated cluster.
+
+    Created by `PySimulatedCluster.snapshot()`; pass it to
+    `PySimulatedCluster.from_snapshot()` to fork an independent cluster.
+
+    Attributes:
+        num_nodes: Number of nodes captured
+        round: Simulation round at the time of the snapshot
+    """
+
+    num_nodes: int
+    round: int
+
+    def __repr__(self) -> str: ...
+
+
 class PySimulatedCluster:
     """A simulated cluster of nodes for testing coordination-free convergence.
 
@@ -1334,12 +2312,42 @@ class PySimulatedCluster:
         """Create a cluster with custom configuration."""
         ...
 
-    def commit_on_node(self, node_index: int, tx: PyAlgebraicTransaction) -> PyVersionedUpdate:
+    @staticmethod
+    def from_snapshot(snapshot: PyClusterSnapshot) -> "PySimulatedCluster":
+        """Create a cluster from a snapshot.
+
+        The new cluster is independent of the one the snapshot was taken from.
+        """
+        ...
+
+    def snapshot(self) -> PyClusterSnapshot:
+        """Capture the full cluster state (nodes, clocks, outboxes, in-flight
+        messages, round and stats)."""
+        ...
+
+    @staticmethod
+    def with_node_ids(ids: List[PyNodeId]) -> "PySimulatedCluster":
+        """Create a cluster whose nodes use the given ids, in order."""
+        ...
+
+    def node_id(self, node_index: int) -> PyNodeId:
+        """Get the id of a specific node.
+
+        Raises:
+            IndexError: If node_index is out of range
+        """
+        ...
+
+    def commit_on_node(
+        self, node_index: int, tx: PyAlgebraicTransaction, priority: int = 0
+    ) -> PyVersionedUpdate:
         """Commit a transaction on a specific node.
 
         Args:
             node_index: The index of the node to commit on
             tx: The algebraic transaction to commit
+            priority: Delivery priority of the update's messages (higher
+                first when delivery_priority is "per_message")
 
         Returns:
             The versioned update that was committed
@@ -1361,8 +2369,44 @@ class PySimulatedCluster:
         """
         ...
 
+    def partition_one_way(self, from_node: int, to_node: int) -> None:
+        """Block messages from one node to another, leaving the reverse
+        direction open."""
+        ...
+
+    def set_link_condition(
+        self, from_node: int, to_node: int, condition: PyNetworkCondition
+    ) -> None:
+        """Set the condition of the directed link from_node -> to_node.
+
+        The reverse link keeps its own condition.
+        """
+        ...
+
+    def link_condition(self, from_node: int, to_node: int) -> PyNetworkCondition:
+        """Get the effective condition of the directed link from_node -> to_node."""
+        ...
+
     def heal_partitions(self) -> None:
-        """Remove all network partitions (heal the network)."""
+        """Remove all network partitions (heal the network), including one-way ones."""
+        ...
+
+    def connectivity_report(self) -> Dict[str, List[Tuple[int, int]]]:
+        """List the links that are currently blocked.
+
+        Returns:
+            Dict with "blocked" (node pairs blocked both ways) and "one_way"
+            ((from, to) links whose reverse direction is blocked)
+        """
+        ...
+
+    def divergence_report(self) -> Dict[str, List[Optional[PyAlgebraicValue]]]:
+        """List the keys whose values differ between nodes.
+
+        Returns:
+            Dict mapping each diverging key to its value on every node
+            (None where the node lacks the key). Empty once converged.
+        """
         ...
 
     def requeue_all_updates(self) -> None:
@@ -1390,6 +2434,32 @@ class PySimulatedCluster:
         """Get all keys that exist across any node."""
         ...
 
+    def node_key_optypes(self, node_index: int) -> Dict[str, PyOpType]:
+        """Get the effective op type of each key on a specific node.
+
+        Returns a dict mapping key to PyOpType.
+        """
+        ...
+
+    def node_op_type_conflicts(self, node_index: int) -> List[str]:
+        """Get the keys on a node that were operated on with more than one op type."""
+        ...
+
+    def quorum_read(self, key: str, quorum: int) -> Optional[PyAlgebraicValue]:
+        """Read a key from a quorum of nodes.
+
+        Folds every distinct update applied by the first `quorum` nodes that
+        hold the key, so shared updates are counted once.
+
+        Args:
+            key: The key to read
+            quorum: Number of nodes that must hold the key
+
+        Returns:
+            The merged value, or None if fewer than `quorum` nodes hold the key
+        """
+        ...
+
     def get_stats(self) -> PySimulationStats:
         """Get simulation statistics."""
         ...
@@ -15,9 +15,11 @@ This module provides:
 Low-level types (from _rhizo):
 - PyChunkStore: Content-addressable chunk storage
 - PyCatalog: Table version catalog
+- PyTable: Handle over one table's versions (re-chunking)
 - PyBranchManager: Git-like branching
 - PyTransactionManager: Cross-table ACID transactions
-- PyMerkleConfig, merkle_build_tree, merkle_diff_trees, merkle_verify_tree: Merkle tree operations
+- PyRepository: Repository directory with all of the above wired together
+- PyMerkleConfig, merkle_build_tree, merkle_diff_trees, merkle_verify_tree, merkle_verify_proof: Merkle tree operations
 - PyParquetEncoder, PyParquetDecoder: High-performance Parquet I/O
 - PyPredicateFilter: Predicate pushdown filters
 - PyOpType, PyAlgebraicValue: Algebraic merge types
@@ -52,22 +54,31 @@ from .exceptions import (
 from _rhizo import (
     PyChunkStore,
     PyCatalog,
+    PyTable,
     PyBranchManager,
     PyTransactionManager,
+    PyRepository,
     PyTableVersion,
     PyBranch,
     PyBranchDiff,
     PyMerkleConfig,
     PyMerkleTree,
     PyMerkleDiff,
+    PyMerkleProof,
     PyDataChunk,
     PyMerkleNode,
     merkle_build_tree,
+    merkle_rebuild_tree,
     merkle_diff_trees,
     merkle_verify_tree,
+    merkle_verify_proof,
+    merkle_read_range,
+    transfer_plan,
     PyParquetEncoder,
+    PyParquetStreamWriter,
     PyParquetDecoder,
     PyPredicateFilter,
+    PyPredicateExpr,
     PyFilterOp,
     PyScalarValue,
     PyChangelogEntry,
@@ -82,6 +93,7 @@ from _rhizo import (
     PyMergeAnalysis,
     PyMergeOutcome,
     algebraic_merge,
+    algebraic_merge_many,
     # Distributed types (coordination-free transactions)
     PyNodeId,
     PyCausalOrder,
@@ -91,11 +103,13 @@ from _rhizo import (
     PyAlgebraicTransaction,
     PyVersionedUpdate,
     PyLocalCommitProtocol,
+    PyRepairEvent,
     # Simulation types (multi-node convergence testing)
     PyNetworkCondition,
     PySimulationConfig,
     PySimulationStats,
     PySimulatedNode,
+    PyClusterSnapshot,
     PySimulatedCluster,
     PySimulationBuilder,
 )
@@ -136,22 +150,31 @@ __all__ = [
     # Low-level types
     "PyChunkStore",
     "PyCatalog",
+    "PyTable",
     "PyBranchManager",
     "PyTransactionManager",
+    "PyRepository",
     "PyTableVersion",
     "PyBranch",
     "PyBranchDiff",
     "PyMerkleConfig",
     "PyMerkleTree",
     "PyMerkleDiff",
+    "PyMerkleProof",
     "PyDataChunk",
     "PyMerkleNode",
     "merkle_build_tree",
+    "merkle_rebuild_tree",
     "merkle_diff_trees",
     "merkle_verify_tree",
+    "merkle_verify_proof",
+    "merkle_read_range",
+    "transfer_plan",
     "PyParquetEncoder",
+    "PyParquetStreamWriter",
     "PyParquetDecoder",
     "PyPredicateFilter",
+    "PyPredicateExpr",
     "PyFilterOp",
     "PyScalarValue",
     "PyChangelogEntry",
@@ -166,6 +189,7 @@ __all__ = [
     "PyMergeAnalysis",
     "PyMergeOutcome",
     "algebraic_merge",
+    "algebraic_merge_many",
     # Distributed types
     "PyNodeId",
     "PyCausalOrder",
@@ -175,11 +199,13 @@ __all__ = [
     "PyAlgebraicTransaction",
     "PyVersionedUpdate",
     "PyLocalCommitProtocol",
+    "PyRepairEvent",
     # Simulation types
     "PyNetworkCondition",
     "PySimulationConfig",
     "PySimulationStats",
     "PySimulatedNode",
+    "PyClusterSnapshot",
     "PySimulatedCluster",
     "PySimulationBuilder",
 ]
@@ -15,22 +15,31 @@ from .olap_engine import OLAPEngine as OLAPEngine, is_datafusion_available as is
 from _rhizo import (
     PyChunkStore as PyChunkStore,
     PyCatalog as PyCatalog,
+    PyTable as PyTable,
     PyBranchManager as PyBranchManager,
     PyTransactionManager as PyTransactionManager,
+    PyRepository as PyRepository,
     PyTableVersion as PyTableVersion,
     PyBranch as PyBranch,
     PyBranchDiff as PyBranchDiff,
     PyMerkleConfig as PyMerkleConfig,
     PyMerkleTree as PyMerkleTree,
     PyMerkleDiff as PyMerkleDiff,
+    PyMerkleProof as PyMerkleProof,
     PyDataChunk as PyDataChunk,
     PyMerkleNode as PyMerkleNode,
     merkle_build_tree as merkle_build_tree,
+    merkle_rebuild_tree as merkle_rebuild_tree,
     merkle_diff_trees as merkle_diff_trees,
     merkle_verify_tree as merkle_verify_tree,
+    merkle_verify_proof as merkle_verify_proof,
+    merkle_read_range as merkle_read_range,
+    transfer_plan as transfer_plan,
     PyParquetEncoder as PyParquetEncoder,
+    PyParquetStreamWriter as PyParquetStreamWriter,
     PyParquetDecoder as PyParquetDecoder,
     PyPredicateFilter as PyPredicateFilter,
+    PyPredicateExpr as PyPredicateExpr,
     PyFilterOp as PyFilterOp,
     PyScalarValue as PyScalarValue,
     PyChangelogEntry as PyChangelogEntry,
@@ -45,6 +54,7 @@ from _rhizo import (
     PyMergeAnalysis as PyMergeAnalysis,
     PyMergeOutcome as PyMergeOutcome,
     algebraic_merge as algebraic_merge,
+    algebraic_merge_many as algebraic_merge_many,
 )
 
 __version__: str
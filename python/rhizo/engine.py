@@ -1372,7 +1372,7 @@ class QueryEngine:
                 "Initialize QueryEngine with a PyTransactionManager."
             )
 
-        entries = self.transaction_manager.get_changelog(
+        entries, _ = self.transaction_manager.get_changelog(
             since_tx_id=since_tx_id,
             since_timestamp=since_timestamp,
             tables=tables,
@@ -685,7 +685,7 @@ class OLAPEngine:
             >>> result = olap.query("SELECT * FROM __changelog WHERE table_name = 'orders'")
         """
         # Fetch changelog entries
-        entries = transaction_manager.get_changelog(
+        entries, _ = transaction_manager.get_changelog(
             since_tx_id=since_tx_id,
             since_timestamp=since_timestamp,
             tables=tables,
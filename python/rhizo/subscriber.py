@@ -247,7 +247,7 @@ class Subscriber:
 
         Updates the internal cursor (_last_tx_id) if new entries are found.
         """
-        entries = self._tx_manager.get_changelog(
+        entries, _ = self._tx_manager.get_changelog(
             since_tx_id=self._last_tx_id,
             tables=self._tables,
             branch=self._branch,
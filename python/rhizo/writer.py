@@ -13,7 +13,7 @@ from __future__ import annotations
 
 import io
 from dataclasses import dataclass
-from typing import TYPE_CHECKING, Optional, Union, List, Dict, Any
+from typing import TYPE_CHECKING, Optional, Union, List, Dict, Any, Tuple
 
 import pyarrow as pa
 import pyarrow.parquet as pq
@@ -67,6 +67,20 @@ class ChunkWriteResult:
     total_bytes: int
 
 
+@dataclass
+class PreviewResult:
+    """
+    What writing data as a table's next version would change.
+
+    Computed by TableWriter.preview_write without storing anything.
+    """
+    table_name: str
+    new_chunks: int
+    reused_chunks: int
+    new_bytes: int
+    would_be_noop: bool
+
+
 class TableWriter:
     """
     Writes data to Rhizo as versioned, content-addressable Parquet chunks.
@@ -144,37 +158,7 @@ class TableWriter:
         Raises:
             ValueError: If data is empty, invalid, or exceeds size limits
         """
-        # Convert to Arrow Table if needed
-        table = self._to_arrow(data)
-
-        if table.num_rows == 0:
-            raise ValueError("Cannot write empty table")
-
-        # Validate size limits (prevents OOM attacks)
-        if table.nbytes > self.max_table_size_bytes:
-            raise ValueError(
-                f"Table size ({table.nbytes:,} bytes) exceeds maximum "
-                f"({self.max_table_size_bytes:,} bytes). "
-                f"Increase max_table_size_bytes or chunk your data."
-            )
-
-        if len(table.column_names) > self.max_columns:
-            raise ValueError(
-                f"Column count ({len(table.column_names)}) exceeds maximum "
-                f"({self.max_columns}). Reduce columns or increase max_columns."
-            )
-
-        # Determine chunking strategy
-        chunks = self._chunk_table(table)
-
-        # Serialize all chunks to Parquet bytes
-        # Use parallel encoding for multiple chunks if native encoder available
-        if len(chunks) > 1 and self._native_encoder is not None:
-            # Convert all chunks to batches for parallel encoding via Rayon
-            batches = [chunk.combine_chunks().to_batches()[0] for chunk in chunks]
-            parquet_chunks = [bytes(b) for b in self._native_encoder.encode_batch(batches)]
-        else:
-            parquet_chunks = [self._to_parquet_bytes(chunk) for chunk in chunks]
+        table, parquet_chunks = self._encode(data)
         total_bytes = sum(len(p) for p in parquet_chunks)
 
         # Store all chunks in parallel using batch operation
@@ -186,8 +170,10 @@ class TableWriter:
         # Import here to avoid circular dependency
         import _rhizo
 
-        # Create and commit the version
+        # Create and commit the version, recording its schema so the
+        # catalog can check later writes for breaking changes
         table_version = _rhizo.PyTableVersion(table_name, version, chunk_hashes)
+        table_version.schema_hash = self.catalog.store_schema(table.schema)
         # Note: metadata handling would require extending PyTableVersion
 
         committed_version = self.catalog.commit(table_version)
@@ -220,6 +206,99 @@ class TableWriter:
         Returns:
             ChunkWriteResult with chunk info and the version that will be assigned
 
+        Raises:
+            ValueError: If data is empty, invalid, or exceeds size limits
+        """
+        table, parquet_chunks = self._encode(data)
+        total_bytes = sum(len(p) for p in parquet_chunks)
+
+        # Store all chunks in parallel using batch operation
+        chunk_hashes = self.store.put_batch(parquet_chunks)
+
+        # Determine what the next version WILL be (don't commit yet)
+        next_version = self._get_next_version(table_name)
+
+        return ChunkWriteResult(
+            table_name=table_name,
+            next_version=next_version,
+            chunk_count=len(chunk_hashes),
+            chunk_hashes=chunk_hashes,
+            total_rows=table.num_rows,
+            total_bytes=total_bytes,
+        )
+
+    def preview_write(
+        self,
+        table_name: str,
+        data: Union["pd.DataFrame", pa.Table],
+    ) -> PreviewResult:
+        """
+        Preview writing data as the next version, without storing anything.
+
+        The data is chunked and encoded as write() would with the native
+        Parquet encoder, and the Merkle tree over its chunks is diffed
+        against the tree over the table's latest version.
+
+        Args:
+            table_name: Name of the table to preview
+            data: DataFrame or Arrow Table to write
+
+        Returns:
+            PreviewResult; would_be_noop is True when the write would
+            reproduce the latest version exactly
+
+        Raises:
+            ValueError: If data is empty, invalid, or exceeds size limits
+        """
+        import _rhizo
+
+        table = self._validate(data)
+        handle = _rhizo.PyTable(
+            self.catalog,
+            self.store,
+            table_name,
+            chunk_size_bytes=self.chunk_size_bytes,
+            chunk_size_rows=self.chunk_size_rows,
+        )
+        report = handle.preview_write(table.combine_chunks().to_batches()[0])
+
+        return PreviewResult(
+            table_name=table_name,
+            new_chunks=report.new_chunks,
+            reused_chunks=report.reused_chunks,
+            new_bytes=report.new_bytes,
+            would_be_noop=report.would_be_noop,
+        )
+
+    def _encode(self, data: Union["pd.DataFrame", pa.Table]) -> Tuple[pa.Table, List[bytes]]:
+        """
+        Validate data and serialize it as Parquet chunks.
+
+        Returns the Arrow Table and the Parquet bytes of each chunk.
+
+        Raises:
+            ValueError: If data is empty, invalid, or exceeds size limits
+        """
+        table = self._validate(data)
+
+        # Determine chunking strategy
+        chunks = self._chunk_table(table)
+
+        # Serialize all chunks to Parquet bytes
+        # Use parallel encoding for multiple chunks if native encoder available
+        if len(chunks) > 1 and self._native_encoder is not None:
+            # Convert all chunks to batches for parallel encoding via Rayon
+            batches = [chunk.combine_chunks().to_batches()[0] for chunk in chunks]
+            parquet_chunks = [bytes(b) for b in self._native_encoder.encode_batch(batches)]
+        else:
+            parquet_chunks = [self._to_parquet_bytes(chunk) for chunk in chunks]
+
+        return table, parquet_chunks
+
+    def _validate(self, data: Union["pd.DataFrame", pa.Table]) -> pa.Table:
+        """
+        Convert data to an Arrow Table and check it against the size limits.
+
         Raises:
             ValueError: If data is empty, invalid, or exceeds size limits
         """
@@ -243,33 +322,7 @@ class TableWriter:
                 f"({self.max_columns}). Reduce columns or increase max_columns."
             )
 
-        # Determine chunking strategy
-        chunks = self._chunk_table(table)
-
-        # Serialize all chunks to Parquet bytes
-        # Use parallel encoding for multiple chunks if native encoder available
-        if len(chunks) > 1 and self._native_encoder is not None:
-            # Convert all chunks to batches for parallel encoding via Rayon
-            batches = [chunk.combine_chunks().to_batches()[0] for chunk in chunks]
-            parquet_chunks = [bytes(b) for b in self._native_encoder.encode_batch(batches)]
-        else:
-            parquet_chunks = [self._to_parquet_bytes(chunk) for chunk in chunks]
-        total_bytes = sum(len(p) for p in parquet_chunks)
-
-        # Store all chunks in parallel using batch operation
-        chunk_hashes = self.store.put_batch(parquet_chunks)
-
-        # Determine what the next version WILL be (don't commit yet)
-        next_version = self._get_next_version(table_name)
-
-        return ChunkWriteResult(
-            table_name=table_name,
-            next_version=next_version,
-            chunk_count=len(chunk_hashes),
-            chunk_hashes=chunk_hashes,
-            total_rows=table.num_rows,
-            total_bytes=total_bytes,
-        )
+        return table
 
     def _to_arrow(self, data: Union["pd.DataFrame", pa.Table]) -> pa.Table:
         """Convert input data to Arrow Table."""
@@ -12,6 +12,9 @@
 //! - MIN: Returns the smaller value
 //! - UNION: Returns the set union
 //! - INTERSECT: Returns the set intersection
+//! - LWW: Returns the `Timestamped` value with the larger timestamp
+//! - ORSET_ADD / ORSET_REMOVE: Unions the add-tags and the removed tags
+//! - MAP: Unions keys and merges shared keys recursively
 //!
 //! # Abelian Merges
 //!
@@ -21,7 +24,8 @@
 //! - MULTIPLY: Returns `a * b`
 
 use super::types::{AlgebraicValue, OpType};
-use std::collections::HashSet;
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, HashSet};
 
 /// Result of attempting an algebraic merge.
 #[derive(Debug, Clone, PartialEq)]
@@ -173,6 +177,9 @@ impl AlgebraicMerger {
             OpType::SemilatticeMin => Self::merge_min(value1, value2),
             OpType::SemilatticeUnion => Self::merge_union(value1, value2),
             OpType::SemilatticeIntersect => Self::merge_intersect(value1, value2),
+            OpType::LwwRegister => Self::merge_lww(value1, value2),
+            OpType::OrSetAdd | OpType::OrSetRemove => Self::merge_or_set(value1, value2, op_type),
+            OpType::MapMerge => Self::merge_map(value1, value2, OpType::SemilatticeMax),
             OpType::AbelianAdd => Self::merge_add(value1, value2),
             OpType::AbelianMultiply => Self::merge_multiply(value1, value2),
             _ => MergeResult::Conflict {
@@ -297,6 +304,127 @@ impl AlgebraicMerger {
         }
     }
 
+    /// Merge using LWW (last-writer-wins register).
+    ///
+    /// The value with the larger timestamp wins. Equal timestamps fall back
+    /// to `canonical_cmp` on the inner values, so concurrent writes with the
+    /// same timestamp still resolve identically on every node.
+    ///
+    /// Mathematical property: lww(a, b) = lww(b, a) and lww(a, a) = a
+    fn merge_lww(v1: &AlgebraicValue, v2: &AlgebraicValue) -> MergeResult {
+        match (v1, v2) {
+            (AlgebraicValue::Timestamped { .. }, AlgebraicValue::Timestamped { .. }) => {
+                let winner = if canonical_cmp(v1, v2) == Ordering::Less { v2 } else { v1 };
+                MergeResult::Merged(winner.clone())
+            }
+            _ => MergeResult::TypeMismatch {
+                type1: v1.type_name(),
+                type2: v2.type_name(),
+                operation: OpType::LwwRegister,
+            },
+        }
+    }
+
+    /// Merge two OR-Set states.
+    ///
+    /// Both the add-tags and the removed tags are unioned, so a remove only
+    /// cancels the adds it observed and a concurrent add (with a fresh tag)
+    /// survives it.
+    ///
+    /// Mathematical property: a semilattice on each component, so
+    /// or_set(a, b) = or_set(b, a) and or_set(a, a) = a
+    fn merge_or_set(v1: &AlgebraicValue, v2: &AlgebraicValue, op_type: OpType) -> MergeResult {
+        match (v1, v2) {
+            (
+                AlgebraicValue::OrSet { adds: a1, removed: r1 },
+                AlgebraicValue::OrSet { adds: a2, removed: r2 },
+            ) => {
+                let mut adds = a1.clone();
+                for (element, tags) in a2 {
+                    adds.entry(element.clone()).or_default().extend(tags.iter().cloned());
+                }
+                let removed = r1.union(r2).cloned().collect();
+                MergeResult::Merged(AlgebraicValue::OrSet { adds, removed })
+            }
+            _ => MergeResult::TypeMismatch {
+                type1: v1.type_name(),
+                type2: v2.type_name(),
+                operation: op_type,
+            },
+        }
+    }
+
+    /// Merge two maps field by field.
+    ///
+    /// Keys in only one map are kept as-is. A key in both merges with the
+    /// values' `implied_op_type` (nested maps recurse); scalars, which imply
+    /// nothing, merge with `scalar_op`. `merge` with `MapMerge` uses
+    /// `SemilatticeMax` for scalars.
+    ///
+    /// If a shared key fails to merge, the result is a `Conflict` whose
+    /// reason names the key, dotted for nested maps (e.g. `"owner.tags"`).
+    pub fn merge_map(v1: &AlgebraicValue, v2: &AlgebraicValue, scalar_op: OpType) -> MergeResult {
+        match (v1, v2) {
+            (AlgebraicValue::Map(m1), AlgebraicValue::Map(m2)) => {
+                match Self::merge_map_entries("", m1, m2, scalar_op) {
+                    Ok(merged) => MergeResult::Merged(AlgebraicValue::Map(merged)),
+                    Err((key, detail)) => MergeResult::Conflict {
+                        value1: v1.clone(),
+                        value2: v2.clone(),
+                        reason: format!("Cannot merge map key '{}': {}", key, detail),
+                    },
+                }
+            }
+            _ => MergeResult::TypeMismatch {
+                type1: v1.type_name(),
+                type2: v2.type_name(),
+                operation: OpType::MapMerge,
+            },
+        }
+    }
+
+    /// Recursive step of `merge_map`; errors carry the failing key path.
+    fn merge_map_entries(
+        prefix: &str,
+        m1: &BTreeMap<String, AlgebraicValue>,
+        m2: &BTreeMap<String, AlgebraicValue>,
+        scalar_op: OpType,
+    ) -> Result<BTreeMap<String, AlgebraicValue>, (String, String)> {
+        let mut merged = m1.clone();
+        for (key, v2) in m2 {
+            let Some(v1) = m1.get(key) else {
+                merged.insert(key.clone(), v2.clone());
+                continue;
+            };
+            let path = if prefix.is_empty() {
+                key.clone()
+            } else {
+                format!("{}.{}", prefix, key)
+            };
+
+            let value = if let (AlgebraicValue::Map(a), AlgebraicValue::Map(b)) = (v1, v2) {
+                AlgebraicValue::Map(Self::merge_map_entries(&path, a, b, scalar_op)?)
+            } else {
+                let op_type = v1
+                    .implied_op_type()
+                    .or_else(|| v2.implied_op_type())
+                    .unwrap_or(scalar_op);
+                match Self::merge(op_type, v1, v2) {
+                    MergeResult::Merged(v) => v,
+                    MergeResult::Conflict { reason, .. } => return Err((path, reason)),
+                    MergeResult::TypeMismatch { type1, type2, operation } => {
+                        return Err((
+                            path,
+                            format!("type mismatch, {} vs {} under {}", type1, type2, operation),
+                        ));
+                    }
+                }
+            };
+            merged.insert(key.clone(), value);
+        }
+        Ok(merged)
+    }
+
     /// Merge using ADD (Abelian group addition).
     ///
     /// Mathematical property: a + b = b + a
@@ -395,6 +523,66 @@ impl AlgebraicMerger {
     }
 }
 
+/// A fixed total order over values, used to break LWW timestamp ties.
+///
+/// Values of different types order by type; within a type they order by
+/// value, with floats compared by `total_cmp` and sets compared as sorted
+/// sequences (so `HashSet` iteration order never leaks into the result).
+/// `Timestamped` values order by timestamp first, then by inner value.
+fn canonical_cmp(a: &AlgebraicValue, b: &AlgebraicValue) -> Ordering {
+    fn rank(v: &AlgebraicValue) -> u8 {
+        match v {
+            AlgebraicValue::Null => 0,
+            AlgebraicValue::Boolean(_) => 1,
+            AlgebraicValue::Integer(_) => 2,
+            AlgebraicValue::Float(_) => 3,
+            AlgebraicValue::IntSet(_) => 4,
+            AlgebraicValue::StringSet(_) => 5,
+            AlgebraicValue::Timestamped { .. } => 6,
+            AlgebraicValue::OrSet { .. } => 7,
+            AlgebraicValue::Map(_) => 8,
+        }
+    }
+
+    match (a, b) {
+        (AlgebraicValue::Boolean(x), AlgebraicValue::Boolean(y)) => x.cmp(y),
+        (AlgebraicValue::Integer(x), AlgebraicValue::Integer(y)) => x.cmp(y),
+        (AlgebraicValue::Float(x), AlgebraicValue::Float(y)) => x.total_cmp(y),
+        (AlgebraicValue::IntSet(x), AlgebraicValue::IntSet(y)) => {
+            let mut x: Vec<_> = x.iter().collect();
+            let mut y: Vec<_> = y.iter().collect();
+            x.sort_unstable();
+            y.sort_unstable();
+            x.cmp(&y)
+        }
+        (AlgebraicValue::StringSet(x), AlgebraicValue::StringSet(y)) => {
+            let mut x: Vec<_> = x.iter().collect();
+            let mut y: Vec<_> = y.iter().collect();
+            x.sort_unstable();
+            y.sort_unstable();
+            x.cmp(&y)
+        }
+        (
+            AlgebraicValue::Timestamped { ts: ts1, value: v1 },
+            AlgebraicValue::Timestamped { ts: ts2, value: v2 },
+        ) => ts1.cmp(ts2).then_with(|| canonical_cmp(v1, v2)),
+        (
+            AlgebraicValue::OrSet { adds: a1, removed: r1 },
+            AlgebraicValue::OrSet { adds: a2, removed: r2 },
+        ) => a1.cmp(a2).then_with(|| r1.cmp(r2)),
+        (AlgebraicValue::Map(x), AlgebraicValue::Map(y)) => {
+            for ((k1, v1), (k2, v2)) in x.iter().zip(y.iter()) {
+                let ord = k1.cmp(k2).then_with(|| canonical_cmp(v1, v2));
+                if ord != Ordering::Equal {
+                    return ord;
+                }
+            }
+            x.len().cmp(&y.len())
+        }
+        _ => rank(a).cmp(&rank(b)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -730,6 +918,206 @@ mod tests {
         assert_eq!(result, MergeResult::Merged(AlgebraicValue::boolean(false)));
     }
 
+    // ============ LWW Tests ============
+
+    #[test]
+    fn test_merge_lww_larger_timestamp_wins() {
+        let old = AlgebraicValue::timestamped(100, 7);
+        let new = AlgebraicValue::timestamped(200, 3);
+
+        let result = AlgebraicMerger::merge(OpType::LwwRegister, &old, &new);
+        assert_eq!(result, MergeResult::Merged(new.clone()));
+        assert!(AlgebraicMerger::verify_commutativity(OpType::LwwRegister, &old, &new));
+        assert!(AlgebraicMerger::verify_idempotency(OpType::LwwRegister, &old));
+    }
+
+    #[test]
+    fn test_merge_lww_tie_is_deterministic() {
+        let a = AlgebraicValue::timestamped(100, AlgebraicValue::string_set(["x", "y"]));
+        let b = AlgebraicValue::timestamped(100, AlgebraicValue::string_set(["x", "z"]));
+
+        let ab = AlgebraicMerger::merge(OpType::LwwRegister, &a, &b);
+        let ba = AlgebraicMerger::merge(OpType::LwwRegister, &b, &a);
+        assert_eq!(ab, ba);
+        // Sorted ["x", "z"] > ["x", "y"]
+        assert_eq!(ab, MergeResult::Merged(b));
+    }
+
+    #[test]
+    fn test_merge_lww_converges_in_any_order() {
+        let writes = [
+            AlgebraicValue::timestamped(5, 1),
+            AlgebraicValue::timestamped(9, 2.5),
+            AlgebraicValue::timestamped(9, 2),
+            AlgebraicValue::timestamped(3, true),
+            AlgebraicValue::timestamped(9, AlgebraicValue::Null),
+        ];
+        let fold = |order: &[usize]| {
+            order.iter().skip(1).fold(writes[order[0]].clone(), |acc, &i| {
+                AlgebraicMerger::merge(OpType::LwwRegister, &acc, &writes[i]).unwrap()
+            })
+        };
+
+        let expected = fold(&[0, 1, 2, 3, 4]);
+        for order in [[4, 3, 2, 1, 0], [2, 0, 4, 1, 3], [1, 4, 0, 3, 2], [3, 2, 1, 4, 0]] {
+            assert_eq!(fold(&order), expected);
+        }
+        // Float ranks above Integer and Null at the tied timestamp
+        assert_eq!(expected, AlgebraicValue::timestamped(9, 2.5));
+    }
+
+    #[test]
+    fn test_merge_lww_requires_timestamps() {
+        let result = AlgebraicMerger::merge(
+            OpType::LwwRegister,
+            &AlgebraicValue::timestamped(1, 1),
+            &AlgebraicValue::integer(2),
+        );
+        assert!(result.is_type_mismatch());
+    }
+
+    // ============ OR-Set Tests ============
+
+    fn merge_or_set(values: &[&AlgebraicValue]) -> AlgebraicValue {
+        values.iter().skip(1).fold(values[0].clone(), |acc, v| {
+            AlgebraicMerger::merge(OpType::OrSetAdd, &acc, v).unwrap()
+        })
+    }
+
+    #[test]
+    fn test_merge_or_set_remove_cancels_observed_add() {
+        let add = AlgebraicValue::or_set_add("x", "n0:1");
+        let remove = add.observed_remove("x").unwrap();
+
+        let merged = merge_or_set(&[&add, &remove]);
+        assert_eq!(merged.or_set_elements(), Some(vec![]));
+        // Removing first and re-delivering the add still leaves it removed
+        assert_eq!(merge_or_set(&[&remove, &add, &add]), merged);
+        assert!(AlgebraicMerger::verify_idempotency(OpType::OrSetRemove, &merged));
+    }
+
+    #[test]
+    fn test_merge_or_set_concurrent_add_wins() {
+        let add1 = AlgebraicValue::or_set_add("x", "n0:1");
+        // Node 1 removes the add it observed while node 2 re-adds concurrently
+        let remove = add1.observed_remove("x").unwrap();
+        let add2 = AlgebraicValue::or_set_add("x", "n2:1");
+        let other = AlgebraicValue::or_set_add("y", "n2:2");
+
+        let expected = merge_or_set(&[&add1, &remove, &add2, &other]);
+        assert_eq!(expected.or_set_elements(), Some(vec!["x", "y"]));
+        for order in [
+            [&other, &add2, &remove, &add1],
+            [&remove, &add1, &other, &add2],
+            [&add2, &other, &add1, &remove],
+        ] {
+            assert_eq!(merge_or_set(&order), expected);
+        }
+        assert!(AlgebraicMerger::verify_commutativity(OpType::OrSetAdd, &remove, &add2));
+        assert_eq!(
+            merge_or_set(&[&merge_or_set(&[&add1, &remove]), &add2]),
+            merge_or_set(&[&add1, &merge_or_set(&[&remove, &add2])])
+        );
+    }
+
+    #[test]
+    fn test_merge_or_set_requires_or_sets() {
+        let result = AlgebraicMerger::merge(
+            OpType::OrSetRemove,
+            &AlgebraicValue::or_set_add("x", "t"),
+            &AlgebraicValue::string_set(["x"]),
+        );
+        assert!(result.is_type_mismatch());
+    }
+
+    // ============ Map Tests ============
+
+    #[test]
+    fn test_merge_map_unions_disjoint_fields() {
+        let a = AlgebraicValue::map([("name", AlgebraicValue::timestamped(1, 7))]);
+        let b = AlgebraicValue::map([("views", 4)]);
+
+        let result = AlgebraicMerger::merge(OpType::MapMerge, &a, &b);
+        assert_eq!(
+            result,
+            MergeResult::Merged(AlgebraicValue::map([
+                ("name", AlgebraicValue::timestamped(1, 7)),
+                ("views", AlgebraicValue::integer(4)),
+            ]))
+        );
+        assert!(AlgebraicMerger::verify_commutativity(OpType::MapMerge, &a, &b));
+        assert!(AlgebraicMerger::verify_idempotency(OpType::MapMerge, &a));
+    }
+
+    #[test]
+    fn test_merge_map_recurses_by_implied_type() {
+        let a = AlgebraicValue::map([
+            ("views", AlgebraicValue::integer(4)),
+            ("meta", AlgebraicValue::map([("tags", AlgebraicValue::string_set(["a"]))])),
+        ]);
+        let b = AlgebraicValue::map([
+            ("views", AlgebraicValue::integer(9)),
+            (
+                "meta",
+                AlgebraicValue::map([
+                    ("tags", AlgebraicValue::string_set(["b"])),
+                    ("owner", AlgebraicValue::timestamped(2, 1)),
+                ]),
+            ),
+        ]);
+
+        let expected = AlgebraicValue::map([
+            ("views", AlgebraicValue::integer(9)),
+            (
+                "meta",
+                AlgebraicValue::map([
+                    ("tags", AlgebraicValue::string_set(["a", "b"])),
+                    ("owner", AlgebraicValue::timestamped(2, 1)),
+                ]),
+            ),
+        ]);
+        assert_eq!(AlgebraicMerger::merge(OpType::MapMerge, &a, &b), MergeResult::Merged(expected));
+
+        // A per-map default changes how scalars merge
+        let summed = AlgebraicMerger::merge_map(&a, &b, OpType::AbelianAdd).unwrap();
+        assert_eq!(summed.as_map().unwrap()["views"], AlgebraicValue::integer(13));
+    }
+
+    #[test]
+    fn test_merge_map_reports_conflicting_key() {
+        let a = AlgebraicValue::map([
+            ("ok", AlgebraicValue::integer(1)),
+            ("meta", AlgebraicValue::map([("tags", AlgebraicValue::string_set(["a"]))])),
+        ]);
+        let b = AlgebraicValue::map([
+            ("ok", AlgebraicValue::integer(2)),
+            ("meta", AlgebraicValue::map([("tags", AlgebraicValue::integer(3))])),
+        ]);
+
+        for result in [
+            AlgebraicMerger::merge(OpType::MapMerge, &a, &b),
+            AlgebraicMerger::merge(OpType::MapMerge, &b, &a),
+        ] {
+            match result {
+                MergeResult::Conflict { reason, .. } => {
+                    assert!(reason.contains("'meta.tags'"), "{}", reason);
+                    assert!(reason.contains("type mismatch"), "{}", reason);
+                }
+                other => panic!("expected conflict, got {:?}", other),
+            }
+        }
+
+        let overwrite = AlgebraicMerger::merge_map(
+            &AlgebraicValue::map([("ok", 1)]),
+            &AlgebraicValue::map([("ok", 2)]),
+            OpType::GenericOverwrite,
+        );
+        assert!(matches!(overwrite, MergeResult::Conflict { reason, .. } if reason.contains("'ok'")));
+
+        let mismatch = AlgebraicMerger::merge(OpType::MapMerge, &a, &AlgebraicValue::integer(1));
+        assert!(mismatch.is_type_mismatch());
+    }
+
     // ============ Property Tests ============
 
     #[test]
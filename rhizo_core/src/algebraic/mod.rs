@@ -18,6 +18,10 @@
 //! - `SemilatticeMin`: min(a, b) — first-update-timestamp wins
 //! - `SemilatticeUnion`: A ∪ B — add-only sets (tags, permissions)
 //! - `SemilatticeIntersect`: A ∩ B — common elements only
+//! - `LwwRegister`: the `Timestamped` write with the larger timestamp
+//! - `OrSetAdd` / `OrSetRemove`: observed-remove sets; an add survives any
+//!   remove that had not seen it
+//! - `MapMerge`: field-by-field merge of nested `Map` values
 //!
 //! ## Abelian Group Operations
 //!
@@ -84,7 +88,9 @@ pub use types::{AlgebraicValue, OpType};
 pub use merge::{AlgebraicMerger, MergeResult};
 
 // Re-export schema types
-pub use schema::{AlgebraicSchemaRegistry, ColumnAlgebraic, TableAlgebraicSchema};
+pub use schema::{
+    AlgebraicSchemaRegistry, ColumnAlgebraic, CustomMerge, CustomMergeFn, TableAlgebraicSchema,
+};
 
 #[cfg(test)]
 mod integration_tests {
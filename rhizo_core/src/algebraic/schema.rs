@@ -24,9 +24,42 @@
 //! assert!(!schema.get_op_type("name").is_conflict_free());
 //! ```
 
+use super::merge::{AlgebraicMerger, MergeResult};
 use super::types::{AlgebraicValue, OpType};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+/// Signature of a user-supplied column merge function.
+pub type CustomMergeFn = dyn Fn(&AlgebraicValue, &AlgebraicValue) -> MergeResult + Send + Sync;
+
+/// A user-supplied merge function registered for a column.
+///
+/// The function must be commutative (and associative when more than two
+/// branches are merged) for merges to converge; this is not checked.
+/// Custom merges live only in memory and are skipped by serialization.
+#[derive(Clone)]
+pub struct CustomMerge(Arc<CustomMergeFn>);
+
+impl CustomMerge {
+    /// Apply the merge function to two values.
+    pub fn merge(&self, v1: &AlgebraicValue, v2: &AlgebraicValue) -> MergeResult {
+        (self.0)(v1, v2)
+    }
+}
+
+impl fmt::Debug for CustomMerge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CustomMerge(..)")
+    }
+}
+
+impl PartialEq for CustomMerge {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
 
 /// Algebraic annotation for a single column.
 ///
@@ -114,6 +147,10 @@ pub struct TableAlgebraicSchema {
 
     /// Optional description for documentation
     pub description: Option<String>,
+
+    /// Columns merged by a user-supplied function, keyed by column name
+    #[serde(skip)]
+    pub custom_columns: HashMap<String, CustomMerge>,
 }
 
 impl TableAlgebraicSchema {
@@ -126,6 +163,7 @@ impl TableAlgebraicSchema {
             columns: HashMap::new(),
             default_op_type: OpType::Unknown,
             description: None,
+            custom_columns: HashMap::new(),
         }
     }
 
@@ -138,6 +176,7 @@ impl TableAlgebraicSchema {
             columns: HashMap::new(),
             default_op_type: OpType::AbelianAdd,
             description: Some("All columns use additive merge".to_string()),
+            custom_columns: HashMap::new(),
         }
     }
 
@@ -150,6 +189,7 @@ impl TableAlgebraicSchema {
             columns: HashMap::new(),
             default_op_type: OpType::SemilatticeMax,
             description: Some("All columns use max merge".to_string()),
+            custom_columns: HashMap::new(),
         }
     }
 
@@ -168,19 +208,66 @@ impl TableAlgebraicSchema {
     pub fn add_column(&mut self, column: impl Into<String>, op_type: OpType) {
         let col_name = column.into();
         let annotation = ColumnAlgebraic::new(col_name.clone(), op_type);
+        self.custom_columns.remove(&col_name);
         self.columns.insert(col_name, annotation);
     }
 
     /// Add a column with full annotation.
     pub fn add_column_annotation(&mut self, annotation: ColumnAlgebraic) {
+        self.custom_columns.remove(&annotation.column);
         self.columns.insert(annotation.column.clone(), annotation);
     }
 
+    /// Add a column merged by a user-supplied function.
+    ///
+    /// Custom columns count as conflict-free, so the function must be
+    /// commutative: `merge(a, b)` and `merge(b, a)` must agree. Replaces any
+    /// op type annotation on the column.
+    pub fn add_custom_column(
+        &mut self,
+        column: impl Into<String>,
+        merge: Box<CustomMergeFn>,
+    ) {
+        let col_name = column.into();
+        self.columns.remove(&col_name);
+        self.custom_columns.insert(col_name, CustomMerge(Arc::from(merge)));
+    }
+
+    /// Get the custom merge function for a column, if one is registered.
+    pub fn get_custom(&self, column: &str) -> Option<&CustomMerge> {
+        self.custom_columns.get(column)
+    }
+
+    /// Merge two values of a column.
+    ///
+    /// Uses the column's custom merge function if registered, otherwise
+    /// the merger for its op type.
+    pub fn merge_column(
+        &self,
+        column: &str,
+        v1: &AlgebraicValue,
+        v2: &AlgebraicValue,
+    ) -> MergeResult {
+        match self.custom_columns.get(column) {
+            Some(custom) => custom.merge(v1, v2),
+            None => AlgebraicMerger::merge(self.get_op_type(column), v1, v2),
+        }
+    }
+
+    /// Check whether a column can be merged without conflicts.
+    fn is_column_conflict_free(&self, column: &str) -> bool {
+        self.custom_columns.contains_key(column) || self.get_op_type(column).is_conflict_free()
+    }
+
     /// Get the operation type for a column.
     ///
-    /// Returns the column's specific type if annotated,
-    /// otherwise returns the table's default.
+    /// Returns the column's specific type if annotated, `Unknown` for a
+    /// custom column (which has no op type; see `get_custom`), otherwise
+    /// the table's default.
     pub fn get_op_type(&self, column: &str) -> OpType {
+        if self.custom_columns.contains_key(column) {
+            return OpType::Unknown;
+        }
         self.columns
             .get(column)
             .map(|c| c.op_type)
@@ -197,6 +284,8 @@ impl TableAlgebraicSchema {
     /// Returns true if:
     /// - The default is conflict-free, AND
     /// - All explicit column annotations are conflict-free
+    ///
+    /// Custom columns are always treated as conflict-free.
     pub fn is_fully_conflict_free(&self) -> bool {
         self.default_op_type.is_conflict_free()
             && self.columns.values().all(|c| c.op_type.is_conflict_free())
@@ -204,15 +293,20 @@ impl TableAlgebraicSchema {
 
     /// Get list of all annotated columns.
     pub fn annotated_columns(&self) -> Vec<&str> {
-        self.columns.keys().map(|s| s.as_str()).collect()
+        self.columns
+            .keys()
+            .chain(self.custom_columns.keys())
+            .map(|s| s.as_str())
+            .collect()
     }
 
-    /// Get list of conflict-free columns.
+    /// Get list of conflict-free columns, including custom columns.
     pub fn conflict_free_columns(&self) -> Vec<&str> {
         self.columns
             .iter()
             .filter(|(_, c)| c.op_type.is_conflict_free())
             .map(|(k, _)| k.as_str())
+            .chain(self.custom_columns.keys().map(|k| k.as_str()))
             .collect()
     }
 
@@ -232,7 +326,7 @@ impl TableAlgebraicSchema {
     /// # Arguments
     /// * `columns` - List of column names being written
     pub fn can_auto_merge(&self, columns: &[&str]) -> bool {
-        columns.iter().all(|c| self.get_op_type(c).is_conflict_free())
+        columns.iter().all(|c| self.is_column_conflict_free(c))
     }
 }
 
@@ -264,7 +358,8 @@ impl AlgebraicSchemaRegistry {
 
     /// Get the operation type for a table/column.
     ///
-    /// Returns `Unknown` if table is not registered.
+    /// Returns `Unknown` if table is not registered, or if the column is
+    /// merged by a custom function (see `get_custom`).
     pub fn get_op_type(&self, table: &str, column: &str) -> OpType {
         self.schemas
             .get(table)
@@ -272,6 +367,11 @@ impl AlgebraicSchemaRegistry {
             .unwrap_or(OpType::Unknown)
     }
 
+    /// Get the custom merge function for a table/column, if one is registered.
+    pub fn get_custom(&self, table: &str, column: &str) -> Option<&CustomMerge> {
+        self.schemas.get(table).and_then(|s| s.get_custom(column))
+    }
+
     /// Check if a table is registered.
     pub fn has_table(&self, table: &str) -> bool {
         self.schemas.contains_key(table)
@@ -399,6 +499,44 @@ mod tests {
         assert!(conflicting.contains(&"c"));
     }
 
+    #[test]
+    fn test_table_schema_custom_column() {
+        let mut schema = TableAlgebraicSchema::new("test");
+        schema.set_default(OpType::AbelianAdd);
+        schema.add_column("c", OpType::GenericOverwrite);
+        assert!(!schema.is_fully_conflict_free());
+
+        schema.add_custom_column(
+            "c",
+            Box::new(|a, b| AlgebraicMerger::merge(OpType::SemilatticeMax, a, b)),
+        );
+        assert!(schema.get_column("c").is_none());
+        assert!(schema.get_custom("c").is_some());
+        // Custom columns don't fall back to the table default
+        assert_eq!(schema.get_op_type("c"), OpType::Unknown);
+        let mut registry = AlgebraicSchemaRegistry::new();
+        registry.register(schema.clone());
+        assert_eq!(registry.get_op_type("test", "c"), OpType::Unknown);
+        assert!(registry.get_custom("test", "c").is_some());
+        assert!(registry.get_custom("test", "d").is_none());
+        assert!(schema.is_fully_conflict_free());
+        assert!(schema.can_auto_merge(&["c"]));
+        assert_eq!(schema.conflict_free_columns(), vec!["c"]);
+        assert_eq!(
+            schema.merge_column("c", &AlgebraicValue::integer(2), &AlgebraicValue::integer(9)),
+            MergeResult::Merged(AlgebraicValue::integer(9))
+        );
+
+        // Custom functions are not serialized
+        let json = serde_json::to_string(&schema).unwrap();
+        let restored: TableAlgebraicSchema = serde_json::from_str(&json).unwrap();
+        assert!(restored.get_custom("c").is_none());
+
+        // Re-annotating the column drops the custom function
+        schema.add_column("c", OpType::SemilatticeMax);
+        assert!(schema.get_custom("c").is_none());
+    }
+
     #[test]
     fn test_registry_basic() {
         let mut registry = AlgebraicSchemaRegistry::new();
@@ -23,7 +23,7 @@
 //! Conflicts become mathematically impossible!
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
 
 /// Algebraic operation classification.
 ///
@@ -56,6 +56,33 @@ pub enum OpType {
     /// Guarantees: idempotent, commutative, associative
     SemilatticeIntersect,
 
+    /// Last-writer-wins register over `Timestamped` values
+    ///
+    /// Use for: profile fields, settings, any "latest write wins" cell
+    /// Guarantees: idempotent, commutative, associative (the larger
+    /// timestamp wins; equal timestamps are broken by a fixed value order)
+    LwwRegister,
+
+    /// Observed-remove set: add an element under a fresh tag
+    ///
+    /// Use for: membership lists, carts, any set that also needs removal
+    /// Guarantees: idempotent, commutative, associative (adds and removes
+    /// are both unions; a concurrent add survives a remove it was not
+    /// observed by)
+    OrSetAdd,
+
+    /// Observed-remove set: cancel the add-tags a node has observed
+    ///
+    /// Shares state with `OrSetAdd` on the same key and merges the same way.
+    OrSetRemove,
+
+    /// Field-by-field merge of `Map` values
+    ///
+    /// Use for: per-key metadata maps, nested documents
+    /// Guarantees: idempotent, commutative, associative (keys are unioned;
+    /// shared keys merge by their own `implied_op_type`, scalars by MAX)
+    MapMerge,
+
     // === Abelian Group Operations (conflict-free via combination) ===
     /// a + b - additive combination
     ///
@@ -113,6 +140,10 @@ impl OpType {
                 | Self::SemilatticeMin
                 | Self::SemilatticeUnion
                 | Self::SemilatticeIntersect
+                | Self::LwwRegister
+                | Self::OrSetAdd
+                | Self::OrSetRemove
+                | Self::MapMerge
                 | Self::AbelianAdd
                 | Self::AbelianMultiply
         )
@@ -127,6 +158,10 @@ impl OpType {
                 | Self::SemilatticeMin
                 | Self::SemilatticeUnion
                 | Self::SemilatticeIntersect
+                | Self::LwwRegister
+                | Self::OrSetAdd
+                | Self::OrSetRemove
+                | Self::MapMerge
         )
     }
 
@@ -136,10 +171,22 @@ impl OpType {
         matches!(self, Self::AbelianAdd | Self::AbelianMultiply)
     }
 
+    /// The op type a key written by this operation is tracked under.
+    ///
+    /// `OrSetRemove` maps to `OrSetAdd`, since both write the same OR-Set
+    /// state; every other type maps to itself.
+    #[inline]
+    pub fn merge_family(&self) -> Self {
+        match self {
+            Self::OrSetRemove => Self::OrSetAdd,
+            other => *other,
+        }
+    }
+
     /// Check if two operation types can be merged.
     ///
     /// Operations can be merged if:
-    /// 1. They are the same type, AND
+    /// 1. They are the same type (or share a `merge_family`), AND
     /// 2. The type is conflict-free
     ///
     /// # Example
@@ -152,7 +199,7 @@ impl OpType {
     /// ```
     #[inline]
     pub fn can_merge_with(&self, other: &Self) -> bool {
-        self == other && self.is_conflict_free()
+        self.merge_family() == other.merge_family() && self.is_conflict_free()
     }
 
     /// Get the identity element for this operation type, if applicable.
@@ -179,6 +226,10 @@ impl OpType {
             Self::SemilatticeMin => "Minimum value wins (first-writer-wins)",
             Self::SemilatticeUnion => "Set union (add-only collection)",
             Self::SemilatticeIntersect => "Set intersection (common elements only)",
+            Self::LwwRegister => "Last-writer-wins register (highest timestamp wins)",
+            Self::OrSetAdd => "Observed-remove set add (add wins over concurrent remove)",
+            Self::OrSetRemove => "Observed-remove set remove (cancels observed adds)",
+            Self::MapMerge => "Field-by-field map merge (keys unioned, values merged)",
             Self::AbelianAdd => "Additive delta (counters, accumulators)",
             Self::AbelianMultiply => "Multiplicative scaling",
             Self::GenericOverwrite => "Direct overwrite (may conflict)",
@@ -202,6 +253,10 @@ impl std::fmt::Display for OpType {
             Self::SemilatticeMin => write!(f, "MIN"),
             Self::SemilatticeUnion => write!(f, "UNION"),
             Self::SemilatticeIntersect => write!(f, "INTERSECT"),
+            Self::LwwRegister => write!(f, "LWW"),
+            Self::OrSetAdd => write!(f, "ORSET_ADD"),
+            Self::OrSetRemove => write!(f, "ORSET_REMOVE"),
+            Self::MapMerge => write!(f, "MAP"),
             Self::AbelianAdd => write!(f, "ADD"),
             Self::AbelianMultiply => write!(f, "MULTIPLY"),
             Self::GenericOverwrite => write!(f, "OVERWRITE"),
@@ -242,6 +297,33 @@ pub enum AlgebraicValue {
     /// Use for: flags with OR (union) or AND (intersect) semantics
     Boolean(bool),
 
+    /// A value tagged with the time it was written
+    ///
+    /// Use for: `LwwRegister` cells, where the larger `ts` wins
+    Timestamped {
+        /// Write timestamp (e.g. hybrid logical clock or epoch micros)
+        ts: u64,
+        /// The written value
+        value: Box<AlgebraicValue>,
+    },
+
+    /// Observed-remove set of strings
+    ///
+    /// Use for: `OrSetAdd`/`OrSetRemove` keys. An element is present while
+    /// at least one of its add-tags has not been removed.
+    OrSet {
+        /// Element -> the unique tags of the adds that inserted it
+        adds: BTreeMap<String, BTreeSet<String>>,
+        /// Add-tags cancelled by removes
+        removed: BTreeSet<String>,
+    },
+
+    /// String-keyed map of nested values
+    ///
+    /// Use for: `MapMerge` keys, where disjoint fields written on different
+    /// nodes both survive and shared fields merge recursively
+    Map(BTreeMap<String, AlgebraicValue>),
+
     /// Null/None value
     ///
     /// Represents absence of value
@@ -291,6 +373,97 @@ impl AlgebraicValue {
         Self::Null
     }
 
+    /// Create a timestamped value for a last-writer-wins register.
+    pub fn timestamped(ts: u64, value: impl Into<AlgebraicValue>) -> Self {
+        Self::Timestamped {
+            ts,
+            value: Box::new(value.into()),
+        }
+    }
+
+    /// Create an OR-Set add of `element` under a tag no other add uses.
+    pub fn or_set_add(element: impl Into<String>, tag: impl Into<String>) -> Self {
+        Self::OrSet {
+            adds: BTreeMap::from([(element.into(), BTreeSet::from([tag.into()]))]),
+            removed: BTreeSet::new(),
+        }
+    }
+
+    /// Create an OR-Set remove cancelling the given add-tags.
+    pub fn or_set_remove<I, S>(tags: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::OrSet {
+            adds: BTreeMap::new(),
+            removed: tags.into_iter().map(|t| t.into()).collect(),
+        }
+    }
+
+    /// Build the remove for `element` as observed in this OR-Set.
+    ///
+    /// Cancels every live add-tag of `element`, so adds this state has not
+    /// seen yet survive. Returns `None` if this is not an `OrSet`.
+    pub fn observed_remove(&self, element: &str) -> Option<Self> {
+        let Self::OrSet { adds, removed } = self else {
+            return None;
+        };
+        let tags = adds
+            .get(element)
+            .into_iter()
+            .flatten()
+            .filter(|t| !removed.contains(*t))
+            .cloned();
+        Some(Self::or_set_remove(tags))
+    }
+
+    /// Elements currently present in an OR-Set, in sorted order.
+    pub fn or_set_elements(&self) -> Option<Vec<&str>> {
+        match self {
+            Self::OrSet { adds, removed } => Some(
+                adds.iter()
+                    .filter(|(_, tags)| tags.iter().any(|t| !removed.contains(t)))
+                    .map(|(e, _)| e.as_str())
+                    .collect(),
+            ),
+            _ => None,
+        }
+    }
+
+    /// Create a map from `(key, value)` pairs.
+    pub fn map<I, K, V>(entries: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<AlgebraicValue>,
+    {
+        Self::Map(entries.into_iter().map(|(k, v)| (k.into(), v.into())).collect())
+    }
+
+    /// The op type this value's shape implies when nested in a `Map`.
+    ///
+    /// Maps, timestamped values, OR-Sets and sets carry their merge
+    /// semantics; scalars (numbers, booleans) and null do not, so they
+    /// return `None` and a map merge falls back to its default.
+    pub fn implied_op_type(&self) -> Option<OpType> {
+        match self {
+            Self::Map(_) => Some(OpType::MapMerge),
+            Self::Timestamped { .. } => Some(OpType::LwwRegister),
+            Self::OrSet { .. } => Some(OpType::OrSetAdd),
+            Self::StringSet(_) | Self::IntSet(_) => Some(OpType::SemilatticeUnion),
+            Self::Integer(_) | Self::Float(_) | Self::Boolean(_) | Self::Null => None,
+        }
+    }
+
+    /// Try to get the entries of a `Map`.
+    pub fn as_map(&self) -> Option<&BTreeMap<String, AlgebraicValue>> {
+        match self {
+            Self::Map(m) => Some(m),
+            _ => None,
+        }
+    }
+
     /// Get the type name for error messages.
     pub fn type_name(&self) -> &'static str {
         match self {
@@ -299,6 +472,9 @@ impl AlgebraicValue {
             Self::StringSet(_) => "StringSet",
             Self::IntSet(_) => "IntSet",
             Self::Boolean(_) => "Boolean",
+            Self::Timestamped { .. } => "Timestamped",
+            Self::OrSet { .. } => "OrSet",
+            Self::Map(_) => "Map",
             Self::Null => "Null",
         }
     }
@@ -338,6 +514,14 @@ impl AlgebraicValue {
             _ => None,
         }
     }
+
+    /// Try to get the timestamp and inner value of a `Timestamped` value.
+    pub fn as_timestamped(&self) -> Option<(u64, &AlgebraicValue)> {
+        match self {
+            Self::Timestamped { ts, value } => Some((*ts, value)),
+            _ => None,
+        }
+    }
 }
 
 impl std::fmt::Display for AlgebraicValue {
@@ -345,8 +529,11 @@ impl std::fmt::Display for AlgebraicValue {
         match self {
             Self::Integer(v) => write!(f, "{}", v),
             Self::Float(v) => write!(f, "{}", v),
+            // Sets are shown sorted so equal sets always print the same
             Self::StringSet(s) => {
-                let items: Vec<_> = s.iter().take(5).collect();
+                let mut items: Vec<_> = s.iter().collect();
+                items.sort();
+                items.truncate(5);
                 if s.len() > 5 {
                     write!(f, "{{{:?}... ({} total)}}", items, s.len())
                 } else {
@@ -354,7 +541,9 @@ impl std::fmt::Display for AlgebraicValue {
                 }
             }
             Self::IntSet(s) => {
-                let items: Vec<_> = s.iter().take(5).collect();
+                let mut items: Vec<_> = s.iter().collect();
+                items.sort();
+                items.truncate(5);
                 if s.len() > 5 {
                     write!(f, "{{{:?}... ({} total)}}", items, s.len())
                 } else {
@@ -362,6 +551,20 @@ impl std::fmt::Display for AlgebraicValue {
                 }
             }
             Self::Boolean(v) => write!(f, "{}", v),
+            Self::Timestamped { ts, value } => write!(f, "{}@{}", value, ts),
+            Self::OrSet { .. } => {
+                write!(f, "{:?}", self.or_set_elements().unwrap_or_default())
+            }
+            Self::Map(m) => {
+                write!(f, "{{")?;
+                for (i, (k, v)) in m.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{:?}: {}", k, v)?;
+                }
+                write!(f, "}}")
+            }
             Self::Null => write!(f, "null"),
         }
     }
@@ -432,6 +635,16 @@ mod tests {
         assert!(OpType::AbelianAdd.is_conflict_free());
         assert!(OpType::AbelianMultiply.is_conflict_free());
 
+        // Last-writer-wins registers are conflict-free
+        assert!(OpType::LwwRegister.is_conflict_free());
+
+        // OR-Set adds and removes are conflict-free and share state
+        assert!(OpType::OrSetAdd.is_conflict_free());
+        assert!(OpType::OrSetRemove.is_conflict_free());
+        assert!(OpType::OrSetAdd.can_merge_with(&OpType::OrSetRemove));
+        assert_eq!(OpType::OrSetRemove.merge_family(), OpType::OrSetAdd);
+        assert_eq!(OpType::AbelianAdd.merge_family(), OpType::AbelianAdd);
+
         // Generic operations are NOT conflict-free
         assert!(!OpType::GenericOverwrite.is_conflict_free());
         assert!(!OpType::GenericConditional.is_conflict_free());
@@ -445,6 +658,8 @@ mod tests {
         assert!(OpType::SemilatticeUnion.is_semilattice());
         assert!(OpType::SemilatticeIntersect.is_semilattice());
 
+        assert!(OpType::LwwRegister.is_semilattice());
+
         assert!(!OpType::AbelianAdd.is_semilattice());
         assert!(!OpType::GenericOverwrite.is_semilattice());
     }
@@ -544,6 +759,72 @@ mod tests {
         assert_eq!(v.type_name(), "Null");
     }
 
+    #[test]
+    fn test_algebraic_value_timestamped() {
+        let v = AlgebraicValue::timestamped(42, 7);
+        assert_eq!(v.type_name(), "Timestamped");
+        assert_eq!(v.as_timestamped(), Some((42, &AlgebraicValue::integer(7))));
+        assert_eq!(v.to_string(), "7@42");
+        assert!(!v.is_numeric());
+
+        let json = serde_json::to_string(&v).unwrap();
+        assert_eq!(serde_json::from_str::<AlgebraicValue>(&json).unwrap(), v);
+    }
+
+    #[test]
+    fn test_set_display_is_sorted() {
+        let v = AlgebraicValue::string_set(["c", "a", "b"]);
+        assert_eq!(v.to_string(), "[\"a\", \"b\", \"c\"]");
+
+        let v = AlgebraicValue::IntSet((1..=7).rev().collect());
+        assert_eq!(v.to_string(), "{[1, 2, 3, 4, 5]... (7 total)}");
+    }
+
+    #[test]
+    fn test_algebraic_value_or_set() {
+        let v = AlgebraicValue::OrSet {
+            adds: BTreeMap::from([
+                ("a".to_string(), BTreeSet::from(["t1".to_string(), "t2".to_string()])),
+                ("b".to_string(), BTreeSet::from(["t3".to_string()])),
+            ]),
+            removed: BTreeSet::from(["t1".to_string(), "t3".to_string()]),
+        };
+        assert_eq!(v.type_name(), "OrSet");
+        assert_eq!(v.or_set_elements(), Some(vec!["a"]));
+        assert_eq!(v.to_string(), "[\"a\"]");
+
+        // Only the live tag of "a" needs cancelling
+        assert_eq!(v.observed_remove("a"), Some(AlgebraicValue::or_set_remove(["t2"])));
+        assert_eq!(v.observed_remove("zzz"), Some(AlgebraicValue::or_set_remove(Vec::<String>::new())));
+        assert_eq!(AlgebraicValue::integer(1).observed_remove("a"), None);
+
+        let json = serde_json::to_string(&v).unwrap();
+        assert_eq!(serde_json::from_str::<AlgebraicValue>(&json).unwrap(), v);
+    }
+
+    #[test]
+    fn test_algebraic_value_map() {
+        let v = AlgebraicValue::map([
+            ("views", AlgebraicValue::integer(3)),
+            ("tags", AlgebraicValue::string_set(["a"])),
+            ("owner", AlgebraicValue::timestamped(5, 1)),
+        ]);
+        assert_eq!(v.type_name(), "Map");
+        assert_eq!(v.implied_op_type(), Some(OpType::MapMerge));
+        assert_eq!(v.as_map().unwrap().len(), 3);
+        assert_eq!(v.to_string(), "{\"owner\": 1@5, \"tags\": [\"a\"], \"views\": 3}");
+
+        assert_eq!(AlgebraicValue::integer(1).implied_op_type(), None);
+        assert_eq!(
+            AlgebraicValue::string_set(["x"]).implied_op_type(),
+            Some(OpType::SemilatticeUnion)
+        );
+        assert!(OpType::MapMerge.is_semilattice());
+
+        let json = serde_json::to_string(&v).unwrap();
+        assert_eq!(serde_json::from_str::<AlgebraicValue>(&json).unwrap(), v);
+    }
+
     #[test]
     fn test_algebraic_value_from_traits() {
         let v1: AlgebraicValue = 42i64.into();
@@ -82,7 +82,7 @@ impl Branch {
 }
 
 /// Result of comparing two branches.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct BranchDiff {
     /// Name of the source branch
     pub source_branch: String,
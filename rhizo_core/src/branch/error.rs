@@ -28,4 +28,25 @@ pub enum BranchError {
 
     #[error("Algebraic merge conflict on tables: {0:?}")]
     AlgebraicConflict(Vec<String>),
+
+    #[error("Cannot merge table '{table}': {reason}")]
+    TableMergeConflict { table: String, reason: String },
+
+    #[error("Cannot merge column '{column}': {reason}")]
+    ColumnMergeConflict { column: String, reason: String },
+
+    #[error("Merge aborted by resolver at table '{table}'")]
+    MergeAborted { table: String },
+
+    #[error("Invalid head for table '{table}': version {version}")]
+    InvalidHead { table: String, version: u64 },
+
+    #[error("Catalog error: {0}")]
+    CatalogError(String),
+
+    #[error("Chunk store error: {0}")]
+    ChunkStore(String),
+
+    #[error("Parquet error: {0}")]
+    Parquet(String),
 }
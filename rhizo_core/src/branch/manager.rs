@@ -1,14 +1,29 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::{RwLock, RwLockWriteGuard};
 
 use super::branch::{Branch, BranchDiff};
 use super::error::BranchError;
+use super::merge::{MergeAnalyzer, MergeOutcome, Resolution};
+use crate::algebraic::{AlgebraicSchemaRegistry, TableAlgebraicSchema};
+use crate::catalog::{CatalogError, FileCatalog, TableVersion};
+use crate::chunk_store::ChunkStore;
+use crate::parquet::{ParquetDecoder, ParquetEncoder};
+use arrow::record_batch::RecordBatch;
 
 const DEFAULT_BRANCH: &str = "main";
 const BRANCHES_DIR: &str = "_branches";
 const DEFAULT_FILE: &str = "_default.txt";
 
+/// Names that cannot be used for branches. Includes device names that
+/// cannot be used as file names on Windows.
+const RESERVED_NAMES: &[&str] = &[
+    "HEAD", "CON", "PRN", "AUX", "NUL",
+    "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9",
+    "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
 /// Manages branches for UDR tables.
 ///
 /// Branches are stored as JSON files in a `_branches` subdirectory.
@@ -16,8 +31,14 @@ const DEFAULT_FILE: &str = "_default.txt";
 ///
 /// Branch names with slashes (e.g., "feature/test") are stored with
 /// slashes converted to double underscores (e.g., "feature__test.json").
+///
+/// An optional in-memory cache (see `with_cache`) avoids re-reading branch
+/// files on every `get`/`diff`.
 pub struct BranchManager {
     base_path: PathBuf,
+    /// Branch state keyed by name (None = caching disabled). The lock is
+    /// taken either way, so it also serializes branch writes.
+    cache: RwLock<Option<HashMap<String, Branch>>>,
 }
 
 impl BranchManager {
@@ -26,22 +47,49 @@ impl BranchManager {
     /// Creates the branches directory if it doesn't exist.
     /// Creates a default "main" branch if no branches exist.
     pub fn new(base_path: impl AsRef<Path>) -> Result<Self, BranchError> {
+        Self::with_default(base_path, DEFAULT_BRANCH)
+    }
+
+    /// Create a new BranchManager with a custom default branch name.
+    ///
+    /// Like `new`, but if no branches exist the initial default branch is
+    /// created as `default_name` (e.g. "master" or "trunk") instead of "main".
+    /// An existing repository keeps its recorded default.
+    pub fn with_default(
+        base_path: impl AsRef<Path>,
+        default_name: &str,
+    ) -> Result<Self, BranchError> {
         let base_path = base_path.as_ref().to_path_buf();
-        let branches_dir = base_path.join(BRANCHES_DIR);
-        fs::create_dir_all(&branches_dir)?;
+        let manager = Self {
+            base_path,
+            cache: RwLock::new(None),
+        };
+        Self::validate_name(default_name)?;
 
-        let manager = Self { base_path };
+        let branches_dir = manager.base_path.join(BRANCHES_DIR);
+        fs::create_dir_all(&branches_dir)?;
 
-        // Create main branch if it doesn't exist
+        // Create the default branch if no branches exist
         if manager.list()?.is_empty() {
-            let main = Branch::new(DEFAULT_BRANCH, HashMap::new());
-            manager.save_branch(&main)?;
-            manager.set_default(DEFAULT_BRANCH)?;
+            let default = Branch::new(default_name, HashMap::new());
+            manager.save_branch(&default)?;
+            manager.set_default(default_name)?;
         }
 
         Ok(manager)
     }
 
+    /// Enable in-memory caching of branch state.
+    ///
+    /// Reads are served from the cache; writes (`create`, `update_head`,
+    /// merges) update disk first and then the cache, and `delete` evicts.
+    /// The cache only sees writes made through this manager, so don't
+    /// enable it when other processes modify the same branches.
+    pub fn with_cache(mut self) -> Self {
+        self.cache = RwLock::new(Some(HashMap::new()));
+        self
+    }
+
     /// Create a new branch from an existing branch.
     ///
     /// If `from_branch` is None, creates from the default branch.
@@ -53,7 +101,7 @@ impl BranchManager {
         description: Option<&str>,
     ) -> Result<Branch, BranchError> {
         // Validate branch name
-        self.validate_branch_name(name)?;
+        Self::validate_name(name)?;
 
         // Check if branch already exists
         if self.branch_exists(name) {
@@ -81,14 +129,17 @@ impl BranchManager {
 
     /// Get a branch by name.
     pub fn get(&self, name: &str) -> Result<Branch, BranchError> {
-        let path = self.branch_path(name);
-        if !path.exists() {
-            return Err(BranchError::BranchNotFound(name.to_string()));
+        {
+            let cache = self.cache.read().unwrap_or_else(|e| e.into_inner());
+            if let Some(branch) = cache.as_ref().and_then(|c| c.get(name)) {
+                return Ok(branch.clone());
+            }
         }
 
-        let json = fs::read_to_string(&path)?;
-        let branch: Branch = serde_json::from_str(&json)?;
-        Ok(branch)
+        // Read the file under the cache's write lock, which `save_branch`
+        // also holds, so a concurrent update can't land between the read
+        // and the insert and leave the older head cached
+        self.load_branch(&mut self.lock_cache(), name)
     }
 
     /// List all branch names.
@@ -130,26 +181,60 @@ impl BranchManager {
             }
         }
 
+        let mut cache = self.lock_cache();
         let path = self.branch_path(name);
         if !path.exists() {
             return Err(BranchError::BranchNotFound(name.to_string()));
         }
 
         fs::remove_file(&path)?;
+
+        if let Some(cache) = cache.as_mut() {
+            cache.remove(name);
+        }
+
         Ok(())
     }
 
     /// Update the head pointer for a table on a branch.
+    ///
+    /// The table name must be non-empty and the version at least 1. The
+    /// branch is read, updated and written under one lock, so
+    /// concurrent updates to other tables of the same branch all land.
     pub fn update_head(
         &self,
         branch_name: &str,
         table_name: &str,
         version: u64,
     ) -> Result<(), BranchError> {
-        let mut branch = self.get(branch_name)?;
+        Self::validate_head(table_name, version)?;
+
+        let mut cache = self.lock_cache();
+        let mut branch = self.load_branch(&mut cache, branch_name)?;
         branch.set_table_version(table_name, version);
-        self.save_branch(&branch)?;
-        Ok(())
+        self.write_branch(&mut cache, &branch)
+    }
+
+    /// Update the head pointers for several tables on a branch at once.
+    ///
+    /// All updates are validated before anything is written, and the branch
+    /// file is written once, so either every head moves or none do. Heads
+    /// are validated as in `update_head`.
+    pub fn update_heads(
+        &self,
+        branch_name: &str,
+        updates: &[(String, u64)],
+    ) -> Result<(), BranchError> {
+        for (table_name, version) in updates {
+            Self::validate_head(table_name, *version)?;
+        }
+
+        let mut cache = self.lock_cache();
+        let mut branch = self.load_branch(&mut cache, branch_name)?;
+        for (table_name, version) in updates {
+            branch.set_table_version(table_name, *version);
+        }
+        self.write_branch(&mut cache, &branch)
     }
 
     /// Get the version of a table on a branch.
@@ -171,6 +256,17 @@ impl BranchManager {
         Ok(BranchDiff::compute(&source_branch, &target_branch))
     }
 
+    /// Compare one source branch against several targets.
+    ///
+    /// The source is read once; diffs are returned in `targets` order.
+    pub fn diff_many(&self, source: &str, targets: &[&str]) -> Result<Vec<BranchDiff>, BranchError> {
+        let source_branch = self.get(source)?;
+        targets
+            .iter()
+            .map(|target| Ok(BranchDiff::compute(&source_branch, &self.get(target)?)))
+            .collect()
+    }
+
     /// Check if a fast-forward merge is possible.
     ///
     /// Fast-forward is possible when target branch has not diverged from source,
@@ -209,6 +305,566 @@ impl BranchManager {
         Ok(())
     }
 
+    /// Merge several source branches into `into` in a single pass.
+    ///
+    /// Each source proposes the tables whose version differs from the
+    /// target's; proposals the target has already moved past (an ancestor of
+    /// the target's version in the catalog, following `parent_version`) are
+    /// ignored. A proposal is taken when the target lacks the table or the
+    /// target's version is an ancestor of the proposed one. A table is a
+    /// conflict when sources propose different versions of it, or when a
+    /// proposal has diverged from the target.
+    ///
+    /// Conflict-free tables are applied with a single branch write, even if
+    /// other tables conflict; the outcome lists both, and `success` is true
+    /// only when there were no conflicts.
+    pub fn merge_many(
+        &self,
+        sources: &[&str],
+        into: &str,
+        catalog: &FileCatalog,
+    ) -> Result<MergeOutcome, BranchError> {
+        let mut target = self.get(into)?;
+
+        // table -> proposed version -> proposing sources
+        let mut proposals: BTreeMap<String, BTreeMap<u64, Vec<String>>> = BTreeMap::new();
+        for source in sources {
+            let branch = self.get(source)?;
+            for (table, version) in &branch.head {
+                if target.get_table_version(table) != Some(*version) {
+                    proposals
+                        .entry(table.clone())
+                        .or_default()
+                        .entry(*version)
+                        .or_default()
+                        .push(branch.name.clone());
+                }
+            }
+        }
+
+        let mut merged = Vec::new();
+        let mut conflicts = Vec::new();
+        let mut provenance = HashMap::new();
+
+        for (table, mut versions) in proposals {
+            if let Some(current) = target.get_table_version(&table) {
+                let mut stale = Vec::new();
+                for &version in versions.keys() {
+                    if Self::descends_from(catalog, &table, current, version)? {
+                        stale.push(version);
+                    }
+                }
+                for version in stale {
+                    versions.remove(&version);
+                }
+            }
+
+            if versions.is_empty() {
+                continue;
+            }
+            if versions.len() > 1 {
+                conflicts.push(table);
+                continue;
+            }
+
+            let version = *versions.keys().next().expect("non-empty proposals");
+            let fast_forward = match target.get_table_version(&table) {
+                None => true,
+                Some(current) => Self::descends_from(catalog, &table, version, current)?,
+            };
+
+            if fast_forward {
+                target.set_table_version(&table, version);
+                provenance.insert(table.clone(), vec![version]);
+                merged.push(table);
+            } else {
+                conflicts.push(table);
+            }
+        }
+
+        if !merged.is_empty() {
+            self.save_branch(&target)?;
+        }
+
+        let source_names = sources.join(",");
+        let mut outcome = if conflicts.is_empty() {
+            MergeOutcome::success(source_names, into, merged, Vec::new())
+        } else {
+            let mut outcome = MergeOutcome::conflict(source_names, into, conflicts);
+            outcome.fast_forwarded = merged;
+            outcome
+        };
+        outcome.provenance = provenance;
+        Ok(outcome.with_description(format!("Merged {} source branches", sources.len())))
+    }
+
+    /// Merge `source` into `into`, asking `resolve` about diverged tables.
+    ///
+    /// Tables the target lacks, or whose target version is an ancestor of
+    /// the source's, are fast-forwarded; tables the target has moved past
+    /// are left alone. For a table both branches changed, `resolve` gets
+    /// `(table, source_version, target_version, base_version)`, where the
+    /// base is the nearest common ancestor in the catalog (None if the
+    /// versions share no history), and its `Resolution` decides the table.
+    ///
+    /// Skipped tables are reported as conflicts, so `success` is false, but
+    /// everything else is still applied in a single branch write.
+    ///
+    /// # Errors
+    /// `MergeAborted` if `resolve` returns `Abort`; the target is unchanged.
+    pub fn merge_three_way<F>(
+        &self,
+        source: &str,
+        into: &str,
+        catalog: &FileCatalog,
+        resolve: F,
+    ) -> Result<MergeOutcome, BranchError>
+    where
+        F: FnMut(&str, u64, u64, Option<u64>) -> Resolution,
+    {
+        let (target, outcome) = self.plan_three_way(source, into, catalog, resolve)?;
+        if !outcome.fast_forwarded.is_empty() || !outcome.resolved.is_empty() {
+            self.save_branch(&target)?;
+        }
+        Ok(outcome)
+    }
+
+    /// `merge_three_way` without the branch write: the updated target and
+    /// the outcome of applying it.
+    fn plan_three_way<F>(
+        &self,
+        source: &str,
+        into: &str,
+        catalog: &FileCatalog,
+        mut resolve: F,
+    ) -> Result<(Branch, MergeOutcome), BranchError>
+    where
+        F: FnMut(&str, u64, u64, Option<u64>) -> Resolution,
+    {
+        let source_branch = self.get(source)?;
+        let mut target = self.get(into)?;
+
+        let mut tables: Vec<(&String, u64)> =
+            source_branch.head.iter().map(|(t, v)| (t, *v)).collect();
+        tables.sort();
+
+        let mut fast_forwarded = Vec::new();
+        let mut resolved = Vec::new();
+        let mut conflicts = Vec::new();
+        let mut provenance = HashMap::new();
+
+        for (table, source_version) in tables {
+            let target_version = match target.get_table_version(table) {
+                None => {
+                    target.set_table_version(table, source_version);
+                    provenance.insert(table.clone(), vec![source_version]);
+                    fast_forwarded.push(table.clone());
+                    continue;
+                }
+                Some(v) if v == source_version => continue,
+                Some(v) => v,
+            };
+
+            if Self::descends_from(catalog, table, target_version, source_version)? {
+                continue;
+            }
+            if Self::descends_from(catalog, table, source_version, target_version)? {
+                target.set_table_version(table, source_version);
+                provenance.insert(table.clone(), vec![source_version]);
+                fast_forwarded.push(table.clone());
+                continue;
+            }
+
+            let base = Self::common_ancestor(catalog, table, source_version, target_version)?;
+            match resolve(table, source_version, target_version, base) {
+                Resolution::TakeSource => {
+                    target.set_table_version(table, source_version);
+                    provenance.insert(table.clone(), vec![source_version]);
+                    resolved.push(table.clone());
+                }
+                Resolution::TakeTarget => {
+                    provenance.insert(table.clone(), vec![target_version]);
+                    resolved.push(table.clone());
+                }
+                Resolution::Skip => conflicts.push(table.clone()),
+                Resolution::Abort => {
+                    return Err(BranchError::MergeAborted { table: table.clone() });
+                }
+            }
+        }
+
+        let mut outcome = if conflicts.is_empty() {
+            MergeOutcome::success(source, into, fast_forwarded, Vec::new())
+        } else {
+            let mut outcome = MergeOutcome::conflict(source, into, conflicts);
+            outcome.fast_forwarded = fast_forwarded;
+            outcome
+        };
+        outcome.resolved = resolved;
+        outcome.provenance = provenance;
+        Ok((target, outcome))
+    }
+
+    /// Three-way merge of `source` into `into` that settles diverged tables
+    /// algebraically.
+    ///
+    /// Fast-forwards work as in `merge_three_way`. For a table both branches
+    /// changed whose schema in `registry` is fully conflict-free, the rows of
+    /// the source, target and common versions are decoded from `store` and
+    /// combined with `MergeAnalyzer::algebraic_merge_batches`; the result is
+    /// committed to `catalog` as the table's next version (parent: the
+    /// target's version) and becomes the target's head. Its provenance is
+    /// `[source_version, target_version]`.
+    ///
+    /// The merge is all or nothing: if any table has no conflict-free schema
+    /// or its rows cannot be combined, the outcome lists the conflicts and
+    /// nothing is written, not even the fast-forwards.
+    ///
+    /// # Errors
+    /// Catalog, chunk store or Parquet errors while reading or writing
+    /// table data. The target head is only updated after every merged
+    /// version is committed.
+    pub fn merge_three_way_algebraic(
+        &self,
+        source: &str,
+        into: &str,
+        catalog: &FileCatalog,
+        store: &ChunkStore,
+        registry: &AlgebraicSchemaRegistry,
+    ) -> Result<MergeOutcome, BranchError> {
+        let (mut target, mut outcome) = self.plan_three_way(source, into, catalog, |table, _, _, _| {
+            match registry.get(table) {
+                Some(schema) if schema.is_fully_conflict_free() => Resolution::TakeTarget,
+                _ => Resolution::Skip,
+            }
+        })?;
+        let source_branch = self.get(source)?;
+
+        // Combine every table the resolver accepted before writing any of them
+        let mut merged = Vec::new();
+        let mut reasons = Vec::new();
+        if outcome.conflicts.is_empty() {
+            for table in &outcome.resolved {
+                let (source_version, target_version) = match (
+                    source_branch.get_table_version(table),
+                    target.get_table_version(table),
+                ) {
+                    (Some(s), Some(t)) => (s, t),
+                    _ => continue,
+                };
+                let base = Self::common_ancestor(catalog, table, source_version, target_version)?;
+                let Some(schema) = registry.get(table) else { continue };
+                match Self::merge_table_rows(catalog, store, table, source_version, target_version, base, schema) {
+                    Ok(batch) => merged.push((table.clone(), source_version, target_version, batch)),
+                    Err(e @ (BranchError::ColumnMergeConflict { .. } | BranchError::TableMergeConflict { .. })) => {
+                        outcome.conflicts.push(table.clone());
+                        reasons.push(format!("{}: {}", table, e));
+                    }
+                    Err(e) => return Err(e),
+                }
+            }
+        }
+
+        if !outcome.conflicts.is_empty() {
+            outcome.success = false;
+            outcome.fast_forwarded.clear();
+            outcome.resolved.clear();
+            outcome.provenance.clear();
+            outcome.description = Some(if reasons.is_empty() {
+                "Merge refused: tables without a conflict-free schema diverged".to_string()
+            } else {
+                format!("Merge refused: {}", reasons.join("; "))
+            });
+            return Ok(outcome);
+        }
+
+        let encoder = ParquetEncoder::new();
+        for (table, source_version, target_version, batch) in merged {
+            let chunk_hashes = match batch {
+                Some(batch) => {
+                    let chunk = encoder
+                        .encode(&batch)
+                        .map_err(|e| BranchError::Parquet(e.to_string()))?;
+                    vec![store.put(&chunk).map_err(|e| BranchError::ChunkStore(e.to_string()))?]
+                }
+                None => Vec::new(),
+            };
+            let latest = catalog
+                .get_version(&table, None)
+                .map_err(|e| BranchError::CatalogError(e.to_string()))?;
+            let target_table_version = catalog
+                .get_version(&table, Some(target_version))
+                .map_err(|e| BranchError::CatalogError(e.to_string()))?;
+            let mut version = TableVersion::new(&table, latest.version + 1, chunk_hashes)
+                .with_metadata("merged_from", format!("{},{}", source_version, target_version));
+            version.parent_version = Some(target_version);
+            version.schema_hash = target_table_version.schema_hash;
+            let version = catalog
+                .commit(version)
+                .map_err(|e| BranchError::CatalogError(e.to_string()))?;
+
+            target.set_table_version(&table, version);
+            outcome.provenance.insert(table.clone(), vec![source_version, target_version]);
+            outcome.algebraically_merged.push(table);
+        }
+        outcome.resolved.clear();
+
+        if !outcome.fast_forwarded.is_empty() || !outcome.algebraically_merged.is_empty() {
+            self.save_branch(&target)?;
+        }
+        Ok(outcome)
+    }
+
+    /// Rows of `table` merged from its source, target and base versions
+    /// (None if none of them holds any rows)
+    fn merge_table_rows(
+        catalog: &FileCatalog,
+        store: &ChunkStore,
+        table: &str,
+        source_version: u64,
+        target_version: u64,
+        base: Option<u64>,
+        schema: &TableAlgebraicSchema,
+    ) -> Result<Option<RecordBatch>, BranchError> {
+        let read = |version: u64| Self::read_rows(catalog, store, table, version);
+        let base_rows = match base {
+            Some(version) => read(version)?,
+            None => None,
+        };
+        match (read(source_version)?, read(target_version)?) {
+            (Some(source_rows), Some(target_rows)) => Ok(Some(MergeAnalyzer::algebraic_merge_batches(
+                &source_rows,
+                &target_rows,
+                base_rows.as_ref(),
+                schema,
+            )?)),
+            (None, None) if base_rows.is_none() => Ok(None),
+            _ => Err(BranchError::TableMergeConflict {
+                table: table.to_string(),
+                reason: "rows are matched by position, but only some versions hold rows".to_string(),
+            }),
+        }
+    }
+
+    /// Every chunk of a version decoded into one batch (None if it has none)
+    fn read_rows(
+        catalog: &FileCatalog,
+        store: &ChunkStore,
+        table: &str,
+        version: u64,
+    ) -> Result<Option<RecordBatch>, BranchError> {
+        let table_version = catalog
+            .get_version(table, Some(version))
+            .map_err(|e| BranchError::CatalogError(e.to_string()))?;
+        let decoder = ParquetDecoder::new();
+        let mut batches = Vec::new();
+        for hash in &table_version.chunk_hashes {
+            let data = store.get(hash).map_err(|e| BranchError::ChunkStore(e.to_string()))?;
+            batches.push(decoder.decode(&data).map_err(|e| BranchError::Parquet(e.to_string()))?);
+        }
+        match batches.first() {
+            Some(first) => arrow::compute::concat_batches(&first.schema(), &batches)
+                .map(Some)
+                .map_err(|e| BranchError::Parquet(e.to_string())),
+            None => Ok(None),
+        }
+    }
+
+    /// Replay `branch`'s table updates on top of `onto`'s current heads.
+    ///
+    /// The rebased branch starts from `onto`'s heads, so tables only `onto`
+    /// changed (or only `onto` has) are inherited. A table `branch` added,
+    /// or advanced past `onto`'s version in the catalog lineage, keeps
+    /// `branch`'s version and is returned (sorted). Tables where `onto` is
+    /// ahead simply take `onto`'s version. `parent_branch` becomes `onto`.
+    ///
+    /// # Errors
+    /// `MergeConflict` listing every table where both branches advanced
+    /// from a common version; the branch is left unchanged.
+    pub fn rebase(
+        &self,
+        branch: &str,
+        onto: &str,
+        catalog: &FileCatalog,
+    ) -> Result<Vec<String>, BranchError> {
+        let mut rebased_branch = self.get(branch)?;
+        let onto_branch = self.get(onto)?;
+
+        let mut tables: Vec<(&String, u64)> =
+            rebased_branch.head.iter().map(|(t, v)| (t, *v)).collect();
+        tables.sort();
+
+        let mut head = onto_branch.head.clone();
+        let mut rebased = Vec::new();
+        let mut conflicts = Vec::new();
+        for (table, version) in tables {
+            match onto_branch.get_table_version(table) {
+                Some(base) if base == version => {}
+                Some(base) if Self::descends_from(catalog, table, base, version)? => {}
+                Some(base) if !Self::descends_from(catalog, table, version, base)? => {
+                    conflicts.push(table.clone());
+                }
+                _ => {
+                    head.insert(table.clone(), version);
+                    rebased.push(table.clone());
+                }
+            }
+        }
+
+        if !conflicts.is_empty() {
+            return Err(BranchError::MergeConflict(conflicts));
+        }
+
+        rebased_branch.head = head;
+        rebased_branch.parent_branch = Some(onto.to_string());
+        self.save_branch(&rebased_branch)?;
+        Ok(rebased)
+    }
+
+    /// Versions `table` has gone through on `branch`, newest first.
+    ///
+    /// Starts at the branch's head for the table and follows
+    /// `parent_version` in the catalog until the root, so history shared
+    /// with other branches before a fork is included. At most `limit`
+    /// versions are returned. Empty if the branch has no such table.
+    pub fn log(
+        &self,
+        branch: &str,
+        table: &str,
+        catalog: &FileCatalog,
+        limit: Option<usize>,
+    ) -> Result<Vec<TableVersion>, BranchError> {
+        let limit = limit.unwrap_or(usize::MAX);
+        let mut history = Vec::new();
+        let mut next = self.get(branch)?.get_table_version(table);
+
+        while let Some(version) = next {
+            if history.len() >= limit {
+                break;
+            }
+            let table_version = catalog
+                .get_version(table, Some(version))
+                .map_err(|e| BranchError::CatalogError(e.to_string()))?;
+            // Parents always precede their children; stop on anything else
+            next = table_version.parent_version.filter(|parent| *parent < version);
+            history.push(table_version);
+        }
+        Ok(history)
+    }
+
+    /// The version of `table` that `branch` saw at `timestamp` (Unix seconds).
+    ///
+    /// Walks the branch head's history (see [`log`](Self::log)) and returns
+    /// the newest version created at or before `timestamp`. Unlike
+    /// `FileCatalog::get_version_as_of`, versions committed only on other
+    /// branches are never returned.
+    pub fn get_version_as_of(
+        &self,
+        branch: &str,
+        table: &str,
+        timestamp: i64,
+        catalog: &FileCatalog,
+    ) -> Result<TableVersion, BranchError> {
+        self.log(branch, table, catalog, None)?
+            .into_iter()
+            .find(|v| v.created_at <= timestamp)
+            .ok_or_else(|| {
+                BranchError::CatalogError(
+                    CatalogError::NoVersionAsOf {
+                        table: table.to_string(),
+                        timestamp,
+                    }
+                    .to_string(),
+                )
+            })
+    }
+
+    /// Names of the branches `branch` descends from, nearest first.
+    ///
+    /// Follows `parent_branch` until a root, a deleted parent, or a branch
+    /// already visited (rebases can make parents point back at each other).
+    /// `branch` itself is not included.
+    pub fn ancestry(&self, branch: &str) -> Result<Vec<String>, BranchError> {
+        let mut seen = HashSet::from([branch.to_string()]);
+        let mut ancestors = Vec::new();
+        let mut next = self.get(branch)?.parent_branch;
+
+        while let Some(parent) = next {
+            if !seen.insert(parent.clone()) || !self.branch_exists(&parent) {
+                break;
+            }
+            next = self.get(&parent)?.parent_branch;
+            ancestors.push(parent);
+        }
+        Ok(ancestors)
+    }
+
+    /// Chunk hashes referenced by `branch`'s heads but not by `other`'s.
+    ///
+    /// These are the chunks that would become unreferenced (from the point
+    /// of view of these two branches) if `branch` were dropped. Sorted.
+    pub fn exclusive_chunks(
+        &self,
+        branch: &str,
+        other: &str,
+        catalog: &FileCatalog,
+    ) -> Result<Vec<String>, BranchError> {
+        let ours = self.head_chunks(branch, catalog)?;
+        let theirs = self.head_chunks(other, catalog)?;
+        Ok(ours.difference(&theirs).cloned().collect())
+    }
+
+    /// Total bytes of the chunks referenced by `branch`'s heads.
+    ///
+    /// A chunk shared by several tables (or listed twice) is counted once.
+    /// Chunk sizes come from `store`.
+    pub fn branch_size(
+        &self,
+        branch: &str,
+        catalog: &FileCatalog,
+        store: &ChunkStore,
+    ) -> Result<u64, BranchError> {
+        let mut total = 0u64;
+        for hash in self.head_chunks(branch, catalog)? {
+            total += store
+                .size(&hash)
+                .map_err(|e| BranchError::ChunkStore(e.to_string()))?;
+        }
+        Ok(total)
+    }
+
+    /// Every chunk hash still reachable, for `ChunkStore::garbage_collect`.
+    ///
+    /// Marks the chunks of every version file in the catalog, including
+    /// the versions a dropped table kept, since a later commit brings them
+    /// back. Branch heads only point at catalog versions, so every head
+    /// whose version file still exists is covered too; a head whose
+    /// versions were removed with the table marks nothing.
+    pub fn live_chunks(&self, catalog: &FileCatalog) -> Result<HashSet<String>, BranchError> {
+        let chunks = catalog
+            .all_referenced_chunks()
+            .map_err(|e| BranchError::CatalogError(e.to_string()))?;
+        Ok(chunks.into_iter().collect())
+    }
+
+    /// Names of the branches whose head versions include chunk `hash`.
+    ///
+    /// Useful for impact analysis: these are the branches that break if the
+    /// chunk is lost or corrupt. Sorted by branch name.
+    pub fn branches_referencing_chunk(
+        &self,
+        hash: &str,
+        catalog: &FileCatalog,
+    ) -> Result<Vec<String>, BranchError> {
+        let mut branches = Vec::new();
+        for branch in self.list()? {
+            if self.head_chunks(&branch, catalog)?.contains(hash) {
+                branches.push(branch);
+            }
+        }
+        Ok(branches)
+    }
+
     /// Get the default branch name.
     pub fn get_default(&self) -> Result<Option<String>, BranchError> {
         let path = self.base_path.join(BRANCHES_DIR).join(DEFAULT_FILE);
@@ -235,32 +891,13 @@ impl BranchManager {
         Ok(())
     }
 
-    // --- Private helpers ---
-
-    fn branch_path(&self, name: &str) -> PathBuf {
-        // Convert slashes to double underscores for filesystem safety
-        let safe_name = name.replace("/", "__");
-        self.base_path
-            .join(BRANCHES_DIR)
-            .join(format!("{}.json", safe_name))
-    }
-
-    fn branch_exists(&self, name: &str) -> bool {
-        self.branch_path(name).exists()
-    }
-
-    fn save_branch(&self, branch: &Branch) -> Result<(), BranchError> {
-        let path = self.branch_path(&branch.name);
-        let temp_path = path.with_extension("json.tmp");
-
-        let json = serde_json::to_string_pretty(branch)?;
-        fs::write(&temp_path, &json)?;
-        fs::rename(&temp_path, &path)?;
-
-        Ok(())
-    }
-
-    fn validate_branch_name(&self, name: &str) -> Result<(), BranchError> {
+    /// Check that a name is usable as a branch name, without creating it.
+    ///
+    /// Names may contain alphanumerics, `-`, `_` and `/` (for namespacing,
+    /// e.g. `feature/login`). They may not be empty, start with `_`,
+    /// start or end with `/`, contain `//`, or be a reserved name such as
+    /// `HEAD` (case-insensitive).
+    pub fn validate_name(name: &str) -> Result<(), BranchError> {
         if name.is_empty() {
             return Err(BranchError::InvalidBranchName(
                 "Branch name cannot be empty".to_string(),
@@ -292,53 +929,312 @@ impl BranchManager {
             ));
         }
 
-        Ok(())
-    }
-}
+        if name.starts_with('/') || name.ends_with('/') {
+            return Err(BranchError::InvalidBranchName(
+                "Branch name cannot start or end with a slash".to_string(),
+            ));
+        }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::env;
+        if RESERVED_NAMES.iter().any(|r| r.eq_ignore_ascii_case(name)) {
+            return Err(BranchError::InvalidBranchName(format!(
+                "Branch name is reserved: {}",
+                name
+            )));
+        }
 
-    fn temp_dir() -> PathBuf {
-        env::temp_dir().join(format!("udr_branch_test_{}", uuid::Uuid::new_v4()))
+        Ok(())
     }
 
-    #[test]
-    fn test_new_creates_main_branch() {
-        let dir = temp_dir();
-        let manager = BranchManager::new(&dir).unwrap();
+    // --- Private helpers ---
 
-        let branches = manager.list().unwrap();
-        assert!(branches.contains(&"main".to_string()));
+    /// A head must name a table and a committed version (versions start at 1)
+    fn validate_head(table_name: &str, version: u64) -> Result<(), BranchError> {
+        if table_name.is_empty() || version == 0 {
+            return Err(BranchError::InvalidHead {
+                table: table_name.to_string(),
+                version,
+            });
+        }
+        Ok(())
+    }
 
-        let main = manager.get("main").unwrap();
-        assert_eq!(main.name, "main");
-        assert!(main.head.is_empty());
+    fn branch_path(&self, name: &str) -> PathBuf {
+        // Convert slashes to double underscores for filesystem safety
+        let safe_name = name.replace("/", "__");
+        self.base_path
+            .join(BRANCHES_DIR)
+            .join(format!("{}.json", safe_name))
+    }
 
-        fs::remove_dir_all(&dir).ok();
+    fn branch_exists(&self, name: &str) -> bool {
+        self.branch_path(name).exists()
     }
 
-    #[test]
-    fn test_create_branch() {
-        let dir = temp_dir();
-        let manager = BranchManager::new(&dir).unwrap();
+    /// Whether `ancestor` is reachable from `version` via `parent_version`.
+    fn descends_from(
+        catalog: &FileCatalog,
+        table: &str,
+        version: u64,
+        ancestor: u64,
+    ) -> Result<bool, BranchError> {
+        let mut current = version;
+        while current > ancestor {
+            let table_version = catalog
+                .get_version(table, Some(current))
+                .map_err(|e| BranchError::CatalogError(e.to_string()))?;
+            match table_version.parent_version {
+                // Parents always precede their children; anything else is corrupt
+                Some(parent) if parent < current => current = parent,
+                _ => return Ok(false),
+            }
+        }
+        Ok(current == ancestor)
+    }
 
-        // Add a table to main
-        manager.update_head("main", "users", 1).unwrap();
+    /// Nearest version that both `a` and `b` descend from, following
+    /// `parent_version` in the catalog.
+    fn common_ancestor(
+        catalog: &FileCatalog,
+        table: &str,
+        a: u64,
+        b: u64,
+    ) -> Result<Option<u64>, BranchError> {
+        let lineage = |start: u64| -> Result<Vec<u64>, BranchError> {
+            let mut chain = vec![start];
+            let mut current = start;
+            loop {
+                let table_version = catalog
+                    .get_version(table, Some(current))
+                    .map_err(|e| BranchError::CatalogError(e.to_string()))?;
+                match table_version.parent_version {
+                    Some(parent) if parent < current => {
+                        chain.push(parent);
+                        current = parent;
+                    }
+                    _ => return Ok(chain),
+                }
+            }
+        };
 
-        // Create feature branch
-        let feature = manager
-            .create("feature/test", Some("main"), Some("Test branch"))
-            .unwrap();
+        let ours: HashSet<u64> = lineage(a)?.into_iter().collect();
+        Ok(lineage(b)?.into_iter().find(|v| ours.contains(v)))
+    }
 
-        assert_eq!(feature.name, "feature/test");
-        assert_eq!(feature.get_table_version("users"), Some(1));
-        assert_eq!(feature.parent_branch, Some("main".to_string()));
-        assert_eq!(feature.description, Some("Test branch".to_string()));
+    /// All chunk hashes referenced by a branch's head versions.
+    fn head_chunks(&self, name: &str, catalog: &FileCatalog) -> Result<BTreeSet<String>, BranchError> {
+        let branch = self.get(name)?;
+        let mut chunks = BTreeSet::new();
+        for (table, version) in &branch.head {
+            let table_version = catalog
+                .get_version(table, Some(*version))
+                .map_err(|e| BranchError::CatalogError(e.to_string()))?;
+            chunks.extend(table_version.chunk_hashes);
+        }
+        Ok(chunks)
+    }
 
-        fs::remove_dir_all(&dir).ok();
+    fn save_branch(&self, branch: &Branch) -> Result<(), BranchError> {
+        self.write_branch(&mut self.lock_cache(), branch)
+    }
+
+    /// Read a branch from the cache, or from disk into the cache. The
+    /// caller holds the `lock_cache` guard.
+    fn load_branch(
+        &self,
+        cache: &mut Option<HashMap<String, Branch>>,
+        name: &str,
+    ) -> Result<Branch, BranchError> {
+        if let Some(branch) = cache.as_ref().and_then(|c| c.get(name)) {
+            return Ok(branch.clone());
+        }
+
+        let path = self.branch_path(name);
+        if !path.exists() {
+            return Err(BranchError::BranchNotFound(name.to_string()));
+        }
+
+        let json = fs::read_to_string(&path)?;
+        let branch: Branch = serde_json::from_str(&json)?;
+        if let Some(cache) = cache.as_mut() {
+            cache.insert(branch.name.clone(), branch.clone());
+        }
+        Ok(branch)
+    }
+
+    /// Write a branch to disk and the cache. The caller holds the
+    /// `lock_cache` guard.
+    fn write_branch(
+        &self,
+        cache: &mut Option<HashMap<String, Branch>>,
+        branch: &Branch,
+    ) -> Result<(), BranchError> {
+        let path = self.branch_path(&branch.name);
+        let temp_path = path.with_extension("json.tmp");
+
+        let json = serde_json::to_string_pretty(branch)?;
+        fs::write(&temp_path, &json)?;
+        fs::rename(&temp_path, &path)?;
+
+        if let Some(cache) = cache.as_mut() {
+            cache.insert(branch.name.clone(), branch.clone());
+        }
+        Ok(())
+    }
+
+    /// Write access to the cache, taken even when caching is disabled.
+    /// Held across the disk access of every write and of cache fills so
+    /// the two stay in step, and across read-modify-write head updates.
+    fn lock_cache(&self) -> RwLockWriteGuard<'_, Option<HashMap<String, Branch>>> {
+        // A poisoned lock still holds a consistent map (writers only insert/remove)
+        self.cache.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    fn temp_dir() -> PathBuf {
+        env::temp_dir().join(format!("udr_branch_test_{}", uuid::Uuid::new_v4()))
+    }
+
+    #[test]
+    fn test_new_creates_main_branch() {
+        let dir = temp_dir();
+        let manager = BranchManager::new(&dir).unwrap();
+
+        let branches = manager.list().unwrap();
+        assert!(branches.contains(&"main".to_string()));
+
+        let main = manager.get("main").unwrap();
+        assert_eq!(main.name, "main");
+        assert!(main.head.is_empty());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_with_default_branch_name() {
+        let dir = temp_dir();
+        let manager = BranchManager::with_default(&dir, "trunk").unwrap();
+
+        assert_eq!(manager.get_default().unwrap(), Some("trunk".to_string()));
+        assert_eq!(manager.list().unwrap(), vec!["trunk"]);
+
+        // create() without from_branch forks from the configured default
+        manager.update_head("trunk", "users", 3).unwrap();
+        let feature = manager.create("feature", None, None).unwrap();
+        assert_eq!(feature.parent_branch, Some("trunk".to_string()));
+        assert_eq!(feature.get_table_version("users"), Some(3));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_with_default_invalid_name() {
+        let dir = temp_dir();
+
+        let result = BranchManager::with_default(&dir, "_bad");
+        assert!(matches!(result, Err(BranchError::InvalidBranchName(_))));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_cache_fill_racing_update_keeps_newest_head() {
+        let dir = temp_dir();
+        BranchManager::new(&dir).unwrap().update_head("main", "users", 1).unwrap();
+
+        for version in 2..=50 {
+            // A fresh cache, so the reader's first get fills it from disk
+            let manager = BranchManager::new(&dir).unwrap().with_cache();
+            std::thread::scope(|s| {
+                s.spawn(|| manager.get("main").unwrap());
+                manager.update_head("main", "users", version).unwrap();
+            });
+            assert_eq!(manager.get_table_version("main", "users").unwrap(), Some(version));
+        }
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_concurrent_update_head_keeps_every_table() {
+        for cached in [false, true] {
+            let dir = temp_dir();
+            let manager = BranchManager::new(&dir).unwrap();
+            let manager = if cached { manager.with_cache() } else { manager };
+
+            std::thread::scope(|s| {
+                for t in 0..8 {
+                    let manager = &manager;
+                    s.spawn(move || {
+                        for v in 1..=10 {
+                            manager.update_head("main", &format!("t{}", t), v).unwrap();
+                        }
+                        manager
+                            .update_heads("main", &[(format!("u{}", t), 1), (format!("t{}", t), 11)])
+                            .unwrap();
+                    });
+                }
+            });
+
+            let main = BranchManager::new(&dir).unwrap().get("main").unwrap();
+            assert_eq!(main.head.len(), 16, "cached: {}", cached);
+            for t in 0..8 {
+                assert_eq!(main.get_table_version(&format!("t{}", t)), Some(11));
+                assert_eq!(main.get_table_version(&format!("u{}", t)), Some(1));
+            }
+
+            fs::remove_dir_all(&dir).ok();
+        }
+    }
+
+    #[test]
+    fn test_cached_reads_reflect_writes() {
+        let dir = temp_dir();
+        let manager = BranchManager::new(&dir).unwrap().with_cache();
+
+        manager.update_head("main", "users", 1).unwrap();
+        assert_eq!(manager.get_table_version("main", "users").unwrap(), Some(1));
+
+        // Cached read after a write sees the new head, not the stale one
+        manager.update_head("main", "users", 2).unwrap();
+        assert_eq!(manager.get("main").unwrap().get_table_version("users"), Some(2));
+
+        // Created branches are visible, deleted branches are evicted
+        manager.create("feature", None, None).unwrap();
+        assert_eq!(manager.get_table_version("feature", "users").unwrap(), Some(2));
+        manager.delete("feature").unwrap();
+        assert!(matches!(manager.get("feature"), Err(BranchError::BranchNotFound(_))));
+
+        // Disk matches what the cache served
+        let uncached = BranchManager::new(&dir).unwrap();
+        assert_eq!(uncached.get_table_version("main", "users").unwrap(), Some(2));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_create_branch() {
+        let dir = temp_dir();
+        let manager = BranchManager::new(&dir).unwrap();
+
+        // Add a table to main
+        manager.update_head("main", "users", 1).unwrap();
+
+        // Create feature branch
+        let feature = manager
+            .create("feature/test", Some("main"), Some("Test branch"))
+            .unwrap();
+
+        assert_eq!(feature.name, "feature/test");
+        assert_eq!(feature.get_table_version("users"), Some(1));
+        assert_eq!(feature.parent_branch, Some("main".to_string()));
+        assert_eq!(feature.description, Some("Test branch".to_string()));
+
+        fs::remove_dir_all(&dir).ok();
     }
 
     #[test]
@@ -424,6 +1320,615 @@ mod tests {
         fs::remove_dir_all(&dir).ok();
     }
 
+    #[test]
+    fn test_update_heads_all_or_nothing() {
+        let dir = temp_dir();
+        let manager = BranchManager::new(&dir).unwrap();
+
+        manager.update_head("main", "users", 1).unwrap();
+
+        let updates = vec![
+            ("users".to_string(), 2),
+            ("orders".to_string(), 0),
+            ("items".to_string(), 3),
+        ];
+        let result = manager.update_heads("main", &updates);
+        assert!(matches!(result, Err(BranchError::InvalidHead { version: 0, .. })));
+        let result = manager.update_head("main", "orders", 0);
+        assert!(matches!(result, Err(BranchError::InvalidHead { version: 0, .. })));
+        let result = manager.update_head("main", "", 1);
+        assert!(matches!(result, Err(BranchError::InvalidHead { version: 1, .. })));
+
+        let main = manager.get("main").unwrap();
+        assert_eq!(main.get_table_version("users"), Some(1));
+        assert_eq!(main.get_table_version("orders"), None);
+        assert_eq!(main.get_table_version("items"), None);
+
+        let updates = vec![("users".to_string(), 2), ("orders".to_string(), 1)];
+        manager.update_heads("main", &updates).unwrap();
+
+        let main = manager.get("main").unwrap();
+        assert_eq!(main.get_table_version("users"), Some(2));
+        assert_eq!(main.get_table_version("orders"), Some(1));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_exclusive_chunks() {
+        use crate::catalog::TableVersion;
+
+        let dir = temp_dir();
+        let manager = BranchManager::new(&dir).unwrap();
+        let catalog = FileCatalog::new(dir.join("catalog")).unwrap();
+
+        let v1 = vec!["c1".to_string(), "c2".to_string()];
+        let v2 = vec!["c1".to_string(), "c2".to_string(), "c3".to_string()];
+        catalog.commit(TableVersion::new("users", 1, v1)).unwrap();
+        catalog.commit(TableVersion::new("users", 2, v2)).unwrap();
+
+        manager.update_head("main", "users", 1).unwrap();
+        manager.create("feature", None, None).unwrap();
+        manager.update_head("feature", "users", 2).unwrap();
+
+        let exclusive = manager.exclusive_chunks("feature", "main", &catalog).unwrap();
+        assert_eq!(exclusive, vec!["c3".to_string()]);
+        assert!(manager.exclusive_chunks("main", "feature", &catalog).unwrap().is_empty());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_branches_referencing_chunk() {
+        use crate::catalog::TableVersion;
+
+        let dir = temp_dir();
+        let manager = BranchManager::new(&dir).unwrap();
+        let catalog = FileCatalog::new(dir.join("catalog")).unwrap();
+
+        catalog.commit(TableVersion::new("users", 1, vec!["c1".to_string()])).unwrap();
+        catalog
+            .commit(TableVersion::new("users", 2, vec!["c1".to_string(), "c2".to_string()]))
+            .unwrap();
+        catalog.commit(TableVersion::new("orders", 1, vec!["c3".to_string()])).unwrap();
+
+        manager.update_head("main", "users", 1).unwrap();
+        manager.create("feature", None, None).unwrap();
+        manager.update_head("feature", "users", 2).unwrap();
+        manager.create("reports", None, None).unwrap();
+        manager.update_heads("reports", &[("users".to_string(), 2), ("orders".to_string(), 1)]).unwrap();
+
+        let branches = |hash: &str| manager.branches_referencing_chunk(hash, &catalog).unwrap();
+        assert_eq!(branches("c1"), vec!["feature", "main", "reports"]);
+        assert_eq!(branches("c2"), vec!["feature", "reports"]);
+        assert_eq!(branches("c3"), vec!["reports"]);
+        assert!(branches("missing").is_empty());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_branch_size_counts_shared_chunk_once() {
+        use crate::catalog::TableVersion;
+
+        let dir = temp_dir();
+        let manager = BranchManager::new(&dir).unwrap();
+        let catalog = FileCatalog::new(dir.join("catalog")).unwrap();
+        let store = ChunkStore::new(dir.join("chunks")).unwrap();
+
+        let shared = store.put(&[0u8; 100]).unwrap();
+        let users_only = store.put(&[1u8; 10]).unwrap();
+        let orders_only = store.put(&[2u8; 1]).unwrap();
+        catalog
+            .commit(TableVersion::new("users", 1, vec![shared.clone(), users_only]))
+            .unwrap();
+        catalog
+            .commit(TableVersion::new("orders", 1, vec![shared, orders_only]))
+            .unwrap();
+
+        manager.update_head("main", "users", 1).unwrap();
+        manager.update_head("main", "orders", 1).unwrap();
+
+        assert_eq!(manager.branch_size("main", &catalog, &store).unwrap(), 111);
+        assert!(matches!(
+            manager.branch_size("missing", &catalog, &store),
+            Err(BranchError::BranchNotFound(_))
+        ));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_live_chunks_feeds_garbage_collect() {
+        use crate::catalog::TableVersion;
+
+        let dir = temp_dir();
+        let manager = BranchManager::new(&dir).unwrap();
+        let catalog = FileCatalog::new(dir.join("catalog")).unwrap();
+        let store = ChunkStore::new(dir.join("chunks")).unwrap();
+
+        let old = store.put(b"users v1").unwrap();
+        let new = store.put(b"users v2").unwrap();
+        let scratch = store.put(b"scratch v1").unwrap();
+        let orphan = store.put(b"never committed").unwrap();
+        catalog.commit(TableVersion::new("users", 1, vec![old.clone()])).unwrap();
+        catalog.commit(TableVersion::new("users", 2, vec![new.clone()])).unwrap();
+        catalog.commit(TableVersion::new("scratch", 1, vec![scratch.clone()])).unwrap();
+        manager.update_head("main", "users", 2).unwrap();
+        manager.create("feature", Some("main"), None).unwrap();
+        manager.update_head("feature", "scratch", 1).unwrap();
+
+        // Superseded versions are still live
+        let live = manager.live_chunks(&catalog).unwrap();
+        assert_eq!(live, HashSet::from([old, new, scratch.clone()]));
+
+        let report = store.garbage_collect(&live).unwrap();
+        assert_eq!(report.removed_count, 1);
+        assert!(!store.exists(&orphan).unwrap());
+
+        // A dropped table that keeps its versions keeps its chunks, so
+        // recommitting it brings back readable versions
+        catalog.drop_table("scratch", false).unwrap();
+        let live = manager.live_chunks(&catalog).unwrap();
+        assert!(live.contains(&scratch));
+        assert_eq!(store.garbage_collect(&live).unwrap().removed_count, 0);
+        let scratch_v2 = store.put(b"scratch v2").unwrap();
+        catalog.commit(TableVersion::new("scratch", 2, vec![scratch_v2.clone()])).unwrap();
+        let restored = catalog.get_version("scratch", Some(1)).unwrap();
+        assert_eq!(store.get(&restored.chunk_hashes[0]).unwrap(), b"scratch v1");
+
+        // A head on a table dropped with its versions is skipped rather
+        // than failing GC
+        catalog.drop_table("scratch", true).unwrap();
+        let live = manager.live_chunks(&catalog).unwrap();
+        assert!(!live.contains(&scratch));
+        assert_eq!(store.garbage_collect(&live).unwrap().removed_count, 2);
+        assert!(!store.exists(&scratch).unwrap());
+        assert!(!store.exists(&scratch_v2).unwrap());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_merge_many_disjoint_tables() {
+        use crate::catalog::TableVersion;
+
+        let dir = temp_dir();
+        let manager = BranchManager::new(&dir).unwrap();
+        let catalog = FileCatalog::new(dir.join("catalog")).unwrap();
+
+        for (table, version) in [("users", 1), ("users", 2), ("orders", 1), ("items", 1)] {
+            catalog.commit(TableVersion::new(table, version, vec![])).unwrap();
+        }
+        manager.update_head("main", "users", 1).unwrap();
+
+        manager.create("feature/a", None, None).unwrap();
+        manager.update_head("feature/a", "users", 2).unwrap();
+        manager.create("feature/b", None, None).unwrap();
+        manager.update_head("feature/b", "orders", 1).unwrap();
+        manager.create("feature/c", None, None).unwrap();
+        manager.update_head("feature/c", "items", 1).unwrap();
+
+        let outcome = manager
+            .merge_many(&["feature/a", "feature/b", "feature/c"], "main", &catalog)
+            .unwrap();
+
+        assert!(outcome.success);
+        assert!(outcome.conflicts.is_empty());
+        assert_eq!(outcome.fast_forwarded, vec!["items", "orders", "users"]);
+        assert_eq!(outcome.provenance["users"], vec![2]);
+
+        let main = manager.get("main").unwrap();
+        assert_eq!(main.get_table_version("users"), Some(2));
+        assert_eq!(main.get_table_version("orders"), Some(1));
+        assert_eq!(main.get_table_version("items"), Some(1));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_merge_many_reports_conflicts() {
+        use crate::catalog::TableVersion;
+
+        let dir = temp_dir();
+        let manager = BranchManager::new(&dir).unwrap();
+        let catalog = FileCatalog::new(dir.join("catalog")).unwrap();
+
+        for (table, version) in [("users", 1), ("users", 2), ("users", 3), ("orders", 1)] {
+            catalog.commit(TableVersion::new(table, version, vec![])).unwrap();
+        }
+        manager.update_head("main", "users", 1).unwrap();
+
+        manager.create("a", None, None).unwrap();
+        manager.update_head("a", "users", 2).unwrap();
+        manager.create("b", None, None).unwrap();
+        manager.update_heads("b", &[("users".to_string(), 3), ("orders".to_string(), 1)]).unwrap();
+
+        let outcome = manager.merge_many(&["a", "b"], "main", &catalog).unwrap();
+
+        assert!(!outcome.success);
+        assert_eq!(outcome.conflicts, vec!["users"]);
+        assert_eq!(outcome.fast_forwarded, vec!["orders"]);
+
+        // Conflict-free tables are still applied
+        let main = manager.get("main").unwrap();
+        assert_eq!(main.get_table_version("users"), Some(1));
+        assert_eq!(main.get_table_version("orders"), Some(1));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    /// main and "feature" both fork `users` from v1: feature to v2, main to v3.
+    /// `orders` v1 exists only on feature.
+    fn diverged_setup(dir: &Path) -> (BranchManager, FileCatalog) {
+        use crate::catalog::TableVersion;
+
+        let manager = BranchManager::new(dir).unwrap();
+        let catalog = FileCatalog::new(dir.join("catalog")).unwrap();
+        catalog.commit(TableVersion::new("users", 1, vec![])).unwrap();
+        catalog.commit(TableVersion::new("users", 2, vec![])).unwrap();
+        catalog
+            .commit(TableVersion {
+                parent_version: Some(1),
+                ..TableVersion::new("users", 3, vec![])
+            })
+            .unwrap();
+        catalog.commit(TableVersion::new("orders", 1, vec![])).unwrap();
+
+        manager.update_head("main", "users", 1).unwrap();
+        manager.create("feature", None, None).unwrap();
+        manager.update_heads("feature", &[("users".to_string(), 2), ("orders".to_string(), 1)]).unwrap();
+        manager.update_head("main", "users", 3).unwrap();
+        (manager, catalog)
+    }
+
+    #[test]
+    fn test_merge_three_way_resolver_settles_divergence() {
+        let dir = temp_dir();
+        let (manager, catalog) = diverged_setup(&dir);
+
+        // Without a resolver opinion the table stays a conflict
+        assert!(manager.merge_fast_forward("feature", "main").is_err());
+
+        let mut calls = Vec::new();
+        let outcome = manager
+            .merge_three_way("feature", "main", &catalog, |table, src, tgt, base| {
+                calls.push((table.to_string(), src, tgt, base));
+                Resolution::TakeSource
+            })
+            .unwrap();
+
+        assert_eq!(calls, vec![("users".to_string(), 2, 3, Some(1))]);
+        assert!(outcome.success);
+        assert_eq!(outcome.fast_forwarded, vec!["orders"]);
+        assert_eq!(outcome.resolved, vec!["users"]);
+        assert_eq!(outcome.provenance["users"], vec![2]);
+
+        let main = manager.get("main").unwrap();
+        assert_eq!(main.get_table_version("users"), Some(2));
+        assert_eq!(main.get_table_version("orders"), Some(1));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_merge_three_way_skip_and_abort() {
+        let dir = temp_dir();
+        let (manager, catalog) = diverged_setup(&dir);
+
+        let err = manager
+            .merge_three_way("feature", "main", &catalog, |_, _, _, _| Resolution::Abort)
+            .unwrap_err();
+        assert!(matches!(err, BranchError::MergeAborted { ref table } if table == "users"));
+        // Nothing was written, not even the fast-forwardable table
+        assert_eq!(manager.get("main").unwrap().get_table_version("orders"), None);
+
+        let outcome = manager
+            .merge_three_way("feature", "main", &catalog, |_, _, _, _| Resolution::Skip)
+            .unwrap();
+        assert!(!outcome.success);
+        assert_eq!(outcome.conflicts, vec!["users"]);
+        assert_eq!(outcome.fast_forwarded, vec!["orders"]);
+
+        let main = manager.get("main").unwrap();
+        assert_eq!(main.get_table_version("users"), Some(3));
+        assert_eq!(main.get_table_version("orders"), Some(1));
+
+        // Target already ahead of source: nothing to resolve
+        manager.update_head("feature", "users", 1).unwrap();
+        let outcome = manager
+            .merge_three_way("feature", "main", &catalog, |_, _, _, _| unreachable!())
+            .unwrap();
+        assert!(outcome.success);
+        assert!(outcome.resolved.is_empty());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_merge_three_way_algebraic_refuses_conflicts() {
+        use crate::algebraic::{AlgebraicSchemaRegistry, OpType, TableAlgebraicSchema};
+
+        let dir = temp_dir();
+        let (manager, catalog) = diverged_setup(&dir);
+        let store = ChunkStore::new(dir.join("chunks")).unwrap();
+
+        // No schema for users: the divergence is a conflict
+        let mut registry = AlgebraicSchemaRegistry::new();
+        let outcome = manager
+            .merge_three_way_algebraic("feature", "main", &catalog, &store, &registry)
+            .unwrap();
+        assert!(!outcome.success);
+        assert_eq!(outcome.conflicts, vec!["users"]);
+        assert!(outcome.fast_forwarded.is_empty());
+
+        // A conflicting column still blocks the merge
+        let mut users = TableAlgebraicSchema::all_additive("users");
+        users.add_column("name", OpType::GenericOverwrite);
+        registry.register(users);
+        let outcome = manager
+            .merge_three_way_algebraic("feature", "main", &catalog, &store, &registry)
+            .unwrap();
+        assert_eq!(outcome.conflicts, vec!["users"]);
+
+        // Nothing was written, not even the fast-forwardable table
+        let main = manager.get("main").unwrap();
+        assert_eq!(main.get_table_version("users"), Some(3));
+        assert_eq!(main.get_table_version("orders"), None);
+        assert_eq!(catalog.list_versions("users").unwrap(), vec![1, 2, 3]);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    /// Commit `columns` as `version` of `table`, with `parent` as its parent
+    fn commit_rows(
+        catalog: &FileCatalog,
+        store: &ChunkStore,
+        table: &str,
+        version: u64,
+        parent: Option<u64>,
+        columns: Vec<(&str, arrow::array::ArrayRef)>,
+    ) {
+        let batch = RecordBatch::try_from_iter(columns).unwrap();
+        let hash = store.put(&ParquetEncoder::new().encode(&batch).unwrap()).unwrap();
+        let mut table_version = TableVersion::new(table, version, vec![hash]);
+        table_version.parent_version = parent;
+        catalog.commit(table_version).unwrap();
+    }
+
+    #[test]
+    fn test_merge_three_way_algebraic_combines_rows() {
+        use crate::algebraic::{AlgebraicSchemaRegistry, OpType, TableAlgebraicSchema};
+        use arrow::array::{ArrayRef, Int64Array, StringArray};
+        use std::sync::Arc;
+
+        let dir = temp_dir();
+        let manager = BranchManager::new(&dir).unwrap();
+        let catalog = FileCatalog::new(dir.join("catalog")).unwrap();
+        let store = ChunkStore::new(dir.join("chunks")).unwrap();
+        let rows = |clicks: [i64; 2], names: [&str; 2], peak: [i64; 2]| -> Vec<(&str, ArrayRef)> {
+            vec![
+                ("id", Arc::new(Int64Array::from(vec![1, 2])) as ArrayRef),
+                ("clicks", Arc::new(Int64Array::from(clicks.to_vec())) as ArrayRef),
+                ("name", Arc::new(StringArray::from(names.to_vec())) as ArrayRef),
+                ("peak", Arc::new(Int64Array::from(peak.to_vec())) as ArrayRef),
+            ]
+        };
+
+        // feature and main both fork counters v1
+        commit_rows(&catalog, &store, "counters", 1, None, rows([10, 20], ["a", "b"], [5, 5]));
+        commit_rows(&catalog, &store, "counters", 2, Some(1), rows([13, 20], ["a", "b"], [7, 5]));
+        commit_rows(&catalog, &store, "counters", 3, Some(1), rows([12, 25], ["a", "b2"], [6, 9]));
+        catalog.commit(TableVersion::new("orders", 1, vec![])).unwrap();
+
+        manager.create("feature", None, None).unwrap();
+        manager
+            .update_heads("feature", &[("counters".to_string(), 2), ("orders".to_string(), 1)])
+            .unwrap();
+        manager.update_head("main", "counters", 3).unwrap();
+
+        let mut registry = AlgebraicSchemaRegistry::new();
+        let mut counters = TableAlgebraicSchema::all_additive("counters");
+        counters.add_column("peak", OpType::SemilatticeMax);
+        registry.register(counters);
+
+        let outcome = manager
+            .merge_three_way_algebraic("feature", "main", &catalog, &store, &registry)
+            .unwrap();
+        assert!(outcome.success);
+        assert_eq!(outcome.algebraically_merged, vec!["counters"]);
+        assert_eq!(outcome.fast_forwarded, vec!["orders"]);
+        assert!(outcome.conflicts.is_empty());
+        assert_eq!(outcome.provenance["counters"], vec![2, 3]);
+        assert_eq!(outcome.provenance["orders"], vec![1]);
+
+        // The merged version is committed on top of main's and becomes its head
+        let main = manager.get("main").unwrap();
+        assert_eq!(main.get_table_version("counters"), Some(4));
+        assert_eq!(main.get_table_version("orders"), Some(1));
+        let merged = catalog.get_version("counters", Some(4)).unwrap();
+        assert_eq!(merged.parent_version, Some(3));
+
+        // clicks: 10 -> 13 and 10 -> 12 add up to 15; 20 -> 25 only on main.
+        // name: only main renamed row 2. peak: max of both sides.
+        let batch = BranchManager::read_rows(&catalog, &store, "counters", 4).unwrap().unwrap();
+        let expected = RecordBatch::try_from_iter(rows([15, 25], ["a", "b2"], [7, 9])).unwrap();
+        assert_eq!(batch, expected);
+
+        // Both branches renaming the same row cannot be merged
+        commit_rows(&catalog, &store, "counters", 5, Some(4), rows([15, 25], ["a", "x"], [7, 9]));
+        commit_rows(&catalog, &store, "counters", 6, Some(4), rows([15, 25], ["a", "y"], [7, 9]));
+        manager.update_head("feature", "counters", 5).unwrap();
+        manager.update_head("main", "counters", 6).unwrap();
+        let outcome = manager
+            .merge_three_way_algebraic("feature", "main", &catalog, &store, &registry)
+            .unwrap();
+        assert!(!outcome.success);
+        assert_eq!(outcome.conflicts, vec!["counters"]);
+        assert!(outcome.description.unwrap().contains("name"));
+        assert_eq!(manager.get("main").unwrap().get_table_version("counters"), Some(6));
+        assert_eq!(catalog.list_versions("counters").unwrap(), vec![1, 2, 3, 4, 5, 6]);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_merge_three_way_algebraic_records_provenance() {
+        use crate::algebraic::{AlgebraicSchemaRegistry, TableAlgebraicSchema};
+        use arrow::array::{ArrayRef, Int64Array};
+        use std::sync::Arc;
+
+        let dir = temp_dir();
+        let manager = BranchManager::new(&dir).unwrap();
+        let catalog = FileCatalog::new(dir.join("catalog")).unwrap();
+        let store = ChunkStore::new(dir.join("chunks")).unwrap();
+        let hits = |n: i64| vec![("hits", Arc::new(Int64Array::from(vec![n])) as ArrayRef)];
+        commit_rows(&catalog, &store, "stats", 1, None, hits(1));
+        commit_rows(&catalog, &store, "stats", 2, Some(1), hits(2));
+        commit_rows(&catalog, &store, "stats", 3, Some(1), hits(4));
+
+        manager.create("feature", None, None).unwrap();
+        manager.update_head("feature", "stats", 2).unwrap();
+        manager.update_head("main", "stats", 3).unwrap();
+        let mut registry = AlgebraicSchemaRegistry::new();
+        registry.register(TableAlgebraicSchema::all_additive("stats"));
+
+        let outcome = manager
+            .merge_three_way_algebraic("feature", "main", &catalog, &store, &registry)
+            .unwrap();
+
+        // Both contributing versions are listed, source first
+        assert_eq!(outcome.provenance.len(), 1);
+        assert_eq!(outcome.provenance["stats"], vec![2, 3]);
+        let merged = catalog.get_version("stats", Some(4)).unwrap();
+        assert_eq!(merged.metadata.get("merged_from").map(String::as_str), Some("2,3"));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_rebase() {
+        use crate::catalog::TableVersion;
+
+        let dir = temp_dir();
+        let manager = BranchManager::new(&dir).unwrap();
+        let catalog = FileCatalog::new(dir.join("catalog")).unwrap();
+        for (table, version) in [("users", 1), ("users", 2), ("orders", 1), ("orders", 2), ("audit", 1)] {
+            catalog.commit(TableVersion::new(table, version, vec![])).unwrap();
+        }
+
+        manager
+            .update_heads("main", &[("users".to_string(), 1), ("orders".to_string(), 1)])
+            .unwrap();
+        manager.create("feature", None, None).unwrap();
+        manager.update_head("feature", "users", 2).unwrap();
+        manager.update_head("main", "orders", 2).unwrap();
+        manager.update_head("main", "audit", 1).unwrap();
+
+        let rebased = manager.rebase("feature", "main", &catalog).unwrap();
+        assert_eq!(rebased, vec!["users"]);
+
+        // Branch's own update replayed; onto-only changes inherited
+        let feature = manager.get("feature").unwrap();
+        assert_eq!(feature.get_table_version("users"), Some(2));
+        assert_eq!(feature.get_table_version("orders"), Some(2));
+        assert_eq!(feature.get_table_version("audit"), Some(1));
+        assert_eq!(feature.parent_branch, Some("main".to_string()));
+        let feature_head = feature.head.clone();
+
+        // Both sides advance users from v2
+        catalog
+            .commit(TableVersion { parent_version: Some(2), ..TableVersion::new("users", 3, vec![]) })
+            .unwrap();
+        catalog
+            .commit(TableVersion { parent_version: Some(2), ..TableVersion::new("users", 4, vec![]) })
+            .unwrap();
+        manager.update_head("feature", "users", 3).unwrap();
+        manager.update_heads("main", &[("users".to_string(), 4)]).unwrap();
+
+        let err = manager.rebase("feature", "main", &catalog).unwrap_err();
+        assert!(matches!(err, BranchError::MergeConflict(ref t) if t == &vec!["users".to_string()]));
+        let feature = manager.get("feature").unwrap();
+        assert_eq!(feature.get_table_version("users"), Some(3));
+        assert_eq!(feature.head.len(), feature_head.len());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_log_and_ancestry() {
+        let dir = temp_dir();
+        let (manager, catalog) = diverged_setup(&dir);
+
+        // users v3 on main forks from v1, skipping feature's v2
+        let versions = |branch: &str, limit| -> Vec<u64> {
+            manager
+                .log(branch, "users", &catalog, limit)
+                .unwrap()
+                .iter()
+                .map(|v| v.version)
+                .collect()
+        };
+        assert_eq!(versions("main", None), vec![3, 1]);
+        assert_eq!(versions("feature", None), vec![2, 1]);
+        assert_eq!(versions("main", Some(1)), vec![3]);
+        assert!(manager.log("main", "orders", &catalog, None).unwrap().is_empty());
+
+        manager.create("feature/sub", Some("feature"), None).unwrap();
+        assert_eq!(manager.ancestry("feature/sub").unwrap(), vec!["feature", "main"]);
+        assert!(manager.ancestry("main").unwrap().is_empty());
+        // A parent cycle stops at the first repeat
+        let mut main = manager.get("main").unwrap();
+        main.parent_branch = Some("feature/sub".to_string());
+        manager.save_branch(&main).unwrap();
+        assert_eq!(manager.ancestry("feature").unwrap(), vec!["main", "feature/sub"]);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_get_version_as_of_follows_branch_history() {
+        let dir = temp_dir();
+        let manager = BranchManager::new(&dir).unwrap();
+        let catalog = FileCatalog::new(dir.join("catalog")).unwrap();
+
+        // v1 shared, v2 on feature, v3 on main (forked from v1)
+        for (v, parent, created_at) in [(1, None, 1_000), (2, Some(1), 2_000), (3, Some(1), 3_000)] {
+            catalog
+                .commit(TableVersion {
+                    parent_version: parent,
+                    created_at,
+                    ..TableVersion::new("users", v, vec![])
+                })
+                .unwrap();
+        }
+        manager.update_head("main", "users", 1).unwrap();
+        manager.create("feature", None, None).unwrap();
+        manager.update_head("feature", "users", 2).unwrap();
+        manager.update_head("main", "users", 3).unwrap();
+
+        let as_of = |branch: &str, ts| manager.get_version_as_of(branch, "users", ts, &catalog).unwrap().version;
+        assert_eq!(as_of("main", 1_000), 1);
+        assert_eq!(as_of("main", 2_999), 1);
+        assert_eq!(as_of("main", 3_000), 3);
+        assert_eq!(as_of("feature", 1_999), 1);
+        assert_eq!(as_of("feature", 2_000), 2);
+        // v3 is newer but not on feature's history
+        assert_eq!(as_of("feature", 3_000), 2);
+
+        assert!(matches!(
+            manager.get_version_as_of("main", "users", 999, &catalog),
+            Err(BranchError::CatalogError(_))
+        ));
+        assert!(manager.get_version_as_of("main", "orders", 3_000, &catalog).is_err());
+        assert!(matches!(
+            manager.get_version_as_of("missing", "users", 3_000, &catalog),
+            Err(BranchError::BranchNotFound(_))
+        ));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
     #[test]
     fn test_diff_branches() {
         let dir = temp_dir();
@@ -446,6 +1951,31 @@ mod tests {
         fs::remove_dir_all(&dir).ok();
     }
 
+    #[test]
+    fn test_diff_many() {
+        let dir = temp_dir();
+        let manager = BranchManager::new(&dir).unwrap();
+
+        manager.update_head("main", "users", 1).unwrap();
+        manager.create("release-1", Some("main"), None).unwrap();
+        manager.update_head("main", "orders", 1).unwrap();
+        manager.create("release-2", Some("main"), None).unwrap();
+        manager.create("feature", Some("main"), None).unwrap();
+        manager.update_head("feature", "users", 2).unwrap();
+
+        let targets = ["release-1", "release-2", "main"];
+        let diffs = manager.diff_many("feature", &targets).unwrap();
+        assert_eq!(diffs.len(), 3);
+        for (diff, target) in diffs.iter().zip(targets) {
+            assert_eq!(diff, &manager.diff("feature", target).unwrap());
+        }
+
+        let result = manager.diff_many("feature", &["release-1", "missing"]);
+        assert!(matches!(result, Err(BranchError::BranchNotFound(_))));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
     #[test]
     fn test_fast_forward_merge() {
         let dir = temp_dir();
@@ -522,6 +2052,43 @@ mod tests {
         assert!(manager.create("feature/test-1", None, None).is_ok());
         assert!(manager.create("experiment_v2", None, None).is_ok());
 
+        // Reserved names are rejected by create too
+        assert!(matches!(
+            manager.create("HEAD", None, None),
+            Err(BranchError::InvalidBranchName(_))
+        ));
+
         fs::remove_dir_all(&dir).ok();
     }
+
+    #[test]
+    fn test_validate_name() {
+        // Valid names
+        for name in ["main", "experiment_v2", "release-1", "feature/login", "team/alice/wip"] {
+            assert!(BranchManager::validate_name(name).is_ok(), "{} should be valid", name);
+        }
+
+        // Slashes only as separators between non-empty segments
+        for name in ["/feature", "feature/", "feature//x", "a\\b"] {
+            assert!(
+                matches!(BranchManager::validate_name(name), Err(BranchError::InvalidBranchName(_))),
+                "{} should be invalid",
+                name
+            );
+        }
+
+        // Reserved names, case-insensitive
+        for name in ["HEAD", "head", "con", "NUL", "lpt1"] {
+            assert!(
+                matches!(BranchManager::validate_name(name), Err(BranchError::InvalidBranchName(_))),
+                "{} should be reserved",
+                name
+            );
+        }
+
+        // Other invalid names
+        assert!(BranchManager::validate_name("").is_err());
+        assert!(BranchManager::validate_name("_hidden").is_err());
+        assert!(BranchManager::validate_name("has space").is_err());
+    }
 }
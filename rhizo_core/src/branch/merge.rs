@@ -4,13 +4,16 @@
 //! enabling automatic conflict resolution for tables with algebraic schemas.
 
 use super::branch::BranchDiff;
-#[cfg(test)]
-use crate::algebraic::TableAlgebraicSchema;
-use crate::algebraic::AlgebraicSchemaRegistry;
-#[cfg(test)]
-use crate::algebraic::OpType;
+use super::error::BranchError;
+use crate::algebraic::{
+    AlgebraicSchemaRegistry, AlgebraicValue, MergeResult, OpType, TableAlgebraicSchema,
+};
+use arrow::array::{Array, ArrayRef, BooleanArray, Float64Array, Int64Array};
+use arrow::datatypes::DataType;
+use arrow::record_batch::RecordBatch;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
 
 /// Result of analyzing merge compatibility.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -29,6 +32,14 @@ pub struct MergeAnalysis {
 
     /// Tables with same version in both (no merge needed)
     pub unchanged: Vec<String>,
+
+    /// Conflicting columns of each conflicting table, sorted
+    ///
+    /// Lists the columns annotated with a non-conflict-free op type. An
+    /// empty list means the conflict comes from the table itself: it has no
+    /// registered schema, or its default op type conflicts.
+    #[serde(default)]
+    pub conflicting_detail: HashMap<String, Vec<String>>,
 }
 
 impl MergeAnalysis {
@@ -66,11 +77,22 @@ pub struct MergeOutcome {
     /// Tables that conflicted (if any)
     pub conflicts: Vec<String>,
 
+    /// Diverged tables settled by a resolver (see `BranchManager::merge_three_way`)
+    #[serde(default)]
+    pub resolved: Vec<String>,
+
     /// Whether the merge was successful
     pub success: bool,
 
     /// Optional description of what happened
     pub description: Option<String>,
+
+    /// Source versions combined into each merged table (for audit)
+    ///
+    /// Algebraically merged tables list `[source_version, target_version]`;
+    /// fast-forwarded tables list the single source version taken.
+    #[serde(default)]
+    pub provenance: HashMap<String, Vec<u64>>,
 }
 
 impl MergeOutcome {
@@ -87,8 +109,10 @@ impl MergeOutcome {
             fast_forwarded,
             algebraically_merged,
             conflicts: Vec::new(),
+            resolved: Vec::new(),
             success: true,
             description: None,
+            provenance: HashMap::new(),
         }
     }
 
@@ -104,8 +128,10 @@ impl MergeOutcome {
             fast_forwarded: Vec::new(),
             algebraically_merged: Vec::new(),
             conflicts,
+            resolved: Vec::new(),
             success: false,
             description: Some("Merge failed due to conflicts".to_string()),
+            provenance: HashMap::new(),
         }
     }
 
@@ -116,6 +142,21 @@ impl MergeOutcome {
     }
 }
 
+/// How a resolver settles a table both branches changed.
+///
+/// Returned by the callback passed to `BranchManager::merge_three_way`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Resolution {
+    /// Point the target at the source's version
+    TakeSource,
+    /// Keep the target's version
+    TakeTarget,
+    /// Leave the table unmerged and report it as a conflict
+    Skip,
+    /// Stop the merge without writing anything
+    Abort,
+}
+
 /// Analyzer for algebraic merge compatibility.
 ///
 /// This determines which tables can be auto-merged based on their
@@ -140,12 +181,16 @@ impl<'a> MergeAnalyzer<'a> {
     pub fn analyze(&self, diff: &BranchDiff) -> MergeAnalysis {
         let mut auto_mergeable = Vec::new();
         let mut conflicting = Vec::new();
+        let mut conflicting_detail = HashMap::new();
 
         // Analyze modified tables (potential conflicts)
         for (table, _src_ver, _tgt_ver) in &diff.modified {
             if self.can_auto_merge_table(table) {
                 auto_mergeable.push(table.clone());
             } else {
+                let (_, mut columns) = self.analyze_table_columns(table);
+                columns.sort();
+                conflicting_detail.insert(table.clone(), columns);
                 conflicting.push(table.clone());
             }
         }
@@ -156,6 +201,7 @@ impl<'a> MergeAnalyzer<'a> {
             source_only: diff.added_in_source.iter().map(|(t, _)| t.clone()).collect(),
             target_only: diff.added_in_target.iter().map(|(t, _)| t.clone()).collect(),
             unchanged: diff.unchanged.clone(),
+            conflicting_detail,
         }
     }
 
@@ -189,6 +235,173 @@ impl<'a> MergeAnalyzer<'a> {
         result
     }
 
+    /// Merge the algebraic column values of several branches.
+    ///
+    /// Each column present in any branch is folded across every branch that
+    /// has it, using the column's custom merge function or op type from
+    /// `schema`. A column held by a single branch keeps that branch's value.
+    ///
+    /// # Errors
+    /// Returns `BranchError::ColumnMergeConflict` for the first column (in
+    /// name order) whose values conflict or have mismatched types.
+    pub fn algebraic_merge_many(
+        branches_data: &[HashMap<String, AlgebraicValue>],
+        schema: &TableAlgebraicSchema,
+    ) -> Result<HashMap<String, AlgebraicValue>, BranchError> {
+        let mut columns: Vec<&String> = branches_data.iter().flat_map(|data| data.keys()).collect();
+        columns.sort();
+        columns.dedup();
+
+        let mut merged = HashMap::new();
+        for column in columns {
+            let mut values = branches_data.iter().filter_map(|data| data.get(column));
+            let Some(first) = values.next() else { continue };
+            let mut acc = first.clone();
+            for value in values {
+                acc = match schema.merge_column(column, &acc, value) {
+                    MergeResult::Merged(v) => v,
+                    MergeResult::Conflict { reason, .. } => {
+                        return Err(BranchError::ColumnMergeConflict {
+                            column: column.clone(),
+                            reason,
+                        })
+                    }
+                    MergeResult::TypeMismatch { type1, type2, operation } => {
+                        return Err(BranchError::ColumnMergeConflict {
+                            column: column.clone(),
+                            reason: format!(
+                                "type mismatch: {} vs {} for {:?}",
+                                type1, type2, operation
+                            ),
+                        })
+                    }
+                };
+            }
+            merged.insert(column.clone(), acc);
+        }
+        Ok(merged)
+    }
+
+    /// Merge the rows of two diverged versions of a table.
+    ///
+    /// Rows are matched by position, so `source`, `target` and `base` (their
+    /// common version, if known) must have the same schema and row count.
+    /// Each cell of an Int64, Float64 or Boolean column that only one side
+    /// changed since `base` takes that side's value; the cells of a row that
+    /// both sides changed are combined with `algebraic_merge_many`. Additive
+    /// cells also fold in the negated base value, so the common starting
+    /// value is counted once. Columns of any other type cannot be combined
+    /// and must not have changed on both sides.
+    ///
+    /// # Errors
+    /// `TableMergeConflict` if the shapes differ; `ColumnMergeConflict` if a
+    /// column of another type or a multiplicative cell changed on both sides,
+    /// or if values fail to merge or no longer fit the column's type.
+    pub fn algebraic_merge_batches(
+        source: &RecordBatch,
+        target: &RecordBatch,
+        base: Option<&RecordBatch>,
+        schema: &TableAlgebraicSchema,
+    ) -> Result<RecordBatch, BranchError> {
+        for other in std::iter::once(target).chain(base) {
+            if other.schema() != source.schema() || other.num_rows() != source.num_rows() {
+                return Err(BranchError::TableMergeConflict {
+                    table: schema.table.clone(),
+                    reason: "rows are matched by position, but the versions differ in schema or row count"
+                        .to_string(),
+                });
+            }
+        }
+
+        let fields = source.schema().fields().clone();
+        let algebraic: Vec<usize> = (0..fields.len())
+            .filter(|&i| is_algebraic_type(fields[i].data_type()))
+            .collect();
+
+        let mut merged_cells: Vec<Vec<AlgebraicValue>> =
+            vec![Vec::with_capacity(source.num_rows()); algebraic.len()];
+        for row in 0..source.num_rows() {
+            let mut sides: [HashMap<String, AlgebraicValue>; 3] = Default::default();
+            let mut kept = HashMap::new();
+            for &i in &algebraic {
+                let column = fields[i].name();
+                let s = cell_value(source.column(i), row);
+                let t = cell_value(target.column(i), row);
+                let b = base.map(|b| cell_value(b.column(i), row));
+                let op_type = match schema.get_custom(column) {
+                    Some(_) => None,
+                    None => Some(schema.get_op_type(column)),
+                };
+
+                let abelian = op_type.is_some_and(|op| op.is_abelian());
+                if b.as_ref() == Some(&s) || (s == t && (b.is_none() || !abelian)) {
+                    kept.insert(column.clone(), t);
+                    continue;
+                }
+                if b.as_ref() == Some(&t) {
+                    kept.insert(column.clone(), s);
+                    continue;
+                }
+                if let Some(b) = b {
+                    match op_type {
+                        Some(OpType::AbelianAdd) => {
+                            sides[2].insert(column.clone(), negate(column, &b)?);
+                        }
+                        Some(OpType::AbelianMultiply) => {
+                            return Err(BranchError::ColumnMergeConflict {
+                                column: column.clone(),
+                                reason: format!(
+                                    "row {} changed on both branches, and the common version's \
+                                     factor cannot be divided out",
+                                    row
+                                ),
+                            });
+                        }
+                        _ => {}
+                    }
+                }
+                sides[0].insert(column.clone(), s);
+                sides[1].insert(column.clone(), t);
+            }
+
+            kept.extend(Self::algebraic_merge_many(&sides, schema)?);
+            for (cells, &i) in merged_cells.iter_mut().zip(&algebraic) {
+                cells.push(kept.remove(fields[i].name()).unwrap_or_default());
+            }
+        }
+
+        let mut merged_cells = merged_cells.into_iter();
+        let mut columns: Vec<ArrayRef> = Vec::with_capacity(fields.len());
+        for (i, field) in fields.iter().enumerate() {
+            let column = if is_algebraic_type(field.data_type()) {
+                let cells = merged_cells.next().unwrap_or_default();
+                build_array(field.name(), field.data_type(), cells)?
+            } else {
+                let (s, t) = (source.column(i), target.column(i));
+                match base.map(|b| b.column(i)) {
+                    _ if s == t => Arc::clone(t),
+                    Some(b) if b == s => Arc::clone(t),
+                    Some(b) if b == t => Arc::clone(s),
+                    _ => {
+                        return Err(BranchError::ColumnMergeConflict {
+                            column: field.name().clone(),
+                            reason: format!(
+                                "{} values changed on both branches cannot be merged",
+                                field.data_type()
+                            ),
+                        })
+                    }
+                }
+            };
+            columns.push(column);
+        }
+
+        RecordBatch::try_new(source.schema(), columns).map_err(|e| BranchError::TableMergeConflict {
+            table: schema.table.clone(),
+            reason: e.to_string(),
+        })
+    }
+
     /// Analyze columns within a single table.
     fn analyze_table_columns(&self, table: &str) -> (Vec<String>, Vec<String>) {
         match self.registry.get(table) {
@@ -208,6 +421,86 @@ impl<'a> MergeAnalyzer<'a> {
     }
 }
 
+/// Whether cells of `data_type` map to an `AlgebraicValue`
+fn is_algebraic_type(data_type: &DataType) -> bool {
+    matches!(data_type, DataType::Int64 | DataType::Float64 | DataType::Boolean)
+}
+
+/// A cell of an Int64, Float64 or Boolean column (nulls become `Null`)
+fn cell_value(array: &ArrayRef, row: usize) -> AlgebraicValue {
+    if array.is_null(row) {
+        return AlgebraicValue::Null;
+    }
+    if let Some(values) = array.as_any().downcast_ref::<Int64Array>() {
+        AlgebraicValue::Integer(values.value(row))
+    } else if let Some(values) = array.as_any().downcast_ref::<Float64Array>() {
+        AlgebraicValue::Float(values.value(row))
+    } else if let Some(values) = array.as_any().downcast_ref::<BooleanArray>() {
+        AlgebraicValue::Boolean(values.value(row))
+    } else {
+        AlgebraicValue::Null
+    }
+}
+
+/// Additive inverse of a base cell, folded in so it is not counted twice
+fn negate(column: &str, value: &AlgebraicValue) -> Result<AlgebraicValue, BranchError> {
+    match value {
+        AlgebraicValue::Integer(v) => v.checked_neg().map(AlgebraicValue::Integer),
+        AlgebraicValue::Float(v) => Some(AlgebraicValue::Float(-v)),
+        _ => None,
+    }
+    .ok_or_else(|| BranchError::ColumnMergeConflict {
+        column: column.to_string(),
+        reason: format!("cannot subtract the common version's {} value", value.type_name()),
+    })
+}
+
+/// Column of `data_type` holding merged cells
+fn build_array(
+    column: &str,
+    data_type: &DataType,
+    cells: Vec<AlgebraicValue>,
+) -> Result<ArrayRef, BranchError> {
+    let mismatch = |value: &AlgebraicValue| BranchError::ColumnMergeConflict {
+        column: column.to_string(),
+        reason: format!("merged {} value does not fit a {} column", value.type_name(), data_type),
+    };
+    let array: ArrayRef = match data_type {
+        DataType::Int64 => Arc::new(
+            cells
+                .iter()
+                .map(|v| match v {
+                    AlgebraicValue::Integer(i) => Ok(Some(*i)),
+                    AlgebraicValue::Null => Ok(None),
+                    other => Err(mismatch(other)),
+                })
+                .collect::<Result<Int64Array, _>>()?,
+        ),
+        DataType::Float64 => Arc::new(
+            cells
+                .iter()
+                .map(|v| match v {
+                    AlgebraicValue::Float(f) => Ok(Some(*f)),
+                    AlgebraicValue::Integer(i) => Ok(Some(*i as f64)),
+                    AlgebraicValue::Null => Ok(None),
+                    other => Err(mismatch(other)),
+                })
+                .collect::<Result<Float64Array, _>>()?,
+        ),
+        _ => Arc::new(
+            cells
+                .iter()
+                .map(|v| match v {
+                    AlgebraicValue::Boolean(b) => Ok(Some(*b)),
+                    AlgebraicValue::Null => Ok(None),
+                    other => Err(mismatch(other)),
+                })
+                .collect::<Result<BooleanArray, _>>()?,
+        ),
+    };
+    Ok(array)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -341,6 +634,21 @@ mod tests {
         assert!(conflict_cols.contains(&"name".to_string()));
     }
 
+    #[test]
+    fn test_merge_analysis_conflicting_detail() {
+        let registry = create_test_registry();
+        let analyzer = MergeAnalyzer::new(&registry);
+        let diff = create_test_diff();
+
+        let analysis = analyzer.analyze(&diff);
+
+        // users: login_count and last_login auto-merge, name conflicts
+        assert_eq!(analysis.conflicting_detail.get("users"), Some(&vec!["name".to_string()]));
+        // settings conflicts through its default op type, not a column
+        assert_eq!(analysis.conflicting_detail.get("settings"), Some(&vec![]));
+        assert!(!analysis.conflicting_detail.contains_key("counters"));
+    }
+
     #[test]
     fn test_merge_outcome_success() {
         let outcome = MergeOutcome::success(
@@ -368,6 +676,77 @@ mod tests {
         assert_eq!(outcome.conflicts, vec!["users"]);
     }
 
+    #[test]
+    fn test_algebraic_merge_many_sums_counters() {
+        let registry = create_test_registry();
+        let schema = registry.get("users").unwrap();
+
+        let branch = |logins: i64, last_login: i64| {
+            HashMap::from([
+                ("login_count".to_string(), AlgebraicValue::integer(logins)),
+                ("last_login".to_string(), AlgebraicValue::integer(last_login)),
+            ])
+        };
+        let mut third = branch(7, 150);
+        third.remove("last_login");
+
+        let merged =
+            MergeAnalyzer::algebraic_merge_many(&[branch(3, 100), branch(5, 200), third], schema).unwrap();
+        assert_eq!(merged["login_count"], AlgebraicValue::integer(15));
+        assert_eq!(merged["last_login"], AlgebraicValue::integer(200));
+        assert!(MergeAnalyzer::algebraic_merge_many(&[], schema).unwrap().is_empty());
+
+        // Differing overwrite values cannot be combined
+        let names = |id: i64| HashMap::from([("name".to_string(), AlgebraicValue::integer(id))]);
+        let err = MergeAnalyzer::algebraic_merge_many(&[names(1), names(2)], schema).unwrap_err();
+        assert!(matches!(err, BranchError::ColumnMergeConflict { ref column, .. } if column == "name"));
+    }
+
+    fn max_by_abs() -> Box<crate::algebraic::CustomMergeFn> {
+        Box::new(|a, b| match (a.as_integer(), b.as_integer()) {
+            (Some(x), Some(y)) => {
+                // Ties on magnitude go to the larger value so the merge commutes
+                let winner = if (x.abs(), x) >= (y.abs(), y) { x } else { y };
+                MergeResult::Merged(AlgebraicValue::integer(winner))
+            }
+            _ => MergeResult::TypeMismatch {
+                type1: a.type_name(),
+                type2: b.type_name(),
+                operation: OpType::Unknown,
+            },
+        })
+    }
+
+    #[test]
+    fn test_custom_column_is_auto_mergeable() {
+        let mut registry = AlgebraicSchemaRegistry::new();
+        let mut counters = TableAlgebraicSchema::new("counters");
+        counters.set_default(OpType::AbelianAdd);
+        counters.add_column("peak_delta", OpType::GenericOverwrite);
+        registry.register(counters.clone());
+
+        let diff = create_test_diff();
+        let analysis = MergeAnalyzer::new(&registry).analyze(&diff);
+        assert!(analysis.conflicting.contains(&"counters".to_string()));
+
+        counters.add_custom_column("peak_delta", max_by_abs());
+        registry.register(counters);
+        let analyzer = MergeAnalyzer::new(&registry);
+        let analysis = analyzer.analyze(&diff);
+        assert!(analysis.auto_mergeable.contains(&"counters".to_string()));
+        assert!(!analysis.conflicting_detail.contains_key("counters"));
+        let columns = analyzer.analyze_columns(&diff);
+        assert_eq!(columns["counters"], (vec!["peak_delta".to_string()], vec![]));
+
+        let schema = registry.get("counters").unwrap();
+        let branch = |v: i64| HashMap::from([("peak_delta".to_string(), AlgebraicValue::integer(v))]);
+        for order in [[3, -7, 5], [5, 3, -7], [-7, 5, 3]] {
+            let data: Vec<_> = order.iter().map(|&v| branch(v)).collect();
+            let merged = MergeAnalyzer::algebraic_merge_many(&data, schema).unwrap();
+            assert_eq!(merged["peak_delta"], AlgebraicValue::integer(-7));
+        }
+    }
+
     #[test]
     fn test_unregistered_table_cannot_merge() {
         let registry = AlgebraicSchemaRegistry::new(); // Empty registry
@@ -375,4 +754,44 @@ mod tests {
 
         assert!(!analyzer.can_auto_merge_table("any_table"));
     }
+
+    #[test]
+    fn test_algebraic_merge_batches_multiply_and_shape() {
+        let mut schema = TableAlgebraicSchema::new("rates");
+        schema.add_column("factor", OpType::AbelianMultiply);
+        let batch = |factors: Vec<i64>| {
+            let factors: ArrayRef = Arc::new(Int64Array::from(factors));
+            RecordBatch::try_from_iter(vec![("factor", factors)]).unwrap()
+        };
+
+        // One side unchanged since the base: take the other side's value
+        let merged = MergeAnalyzer::algebraic_merge_batches(
+            &batch(vec![2, 3]),
+            &batch(vec![2, 5]),
+            Some(&batch(vec![2, 3])),
+            &schema,
+        )
+        .unwrap();
+        assert_eq!(merged, batch(vec![2, 5]));
+
+        // Both changed: the base factor cannot be divided out
+        let err = MergeAnalyzer::algebraic_merge_batches(
+            &batch(vec![4]),
+            &batch(vec![6]),
+            Some(&batch(vec![2])),
+            &schema,
+        )
+        .unwrap_err();
+        assert!(matches!(err, BranchError::ColumnMergeConflict { ref column, .. } if column == "factor"));
+
+        // Without a base the values simply multiply
+        let merged =
+            MergeAnalyzer::algebraic_merge_batches(&batch(vec![4]), &batch(vec![6]), None, &schema).unwrap();
+        assert_eq!(merged, batch(vec![24]));
+
+        // Rows are matched by position
+        let err =
+            MergeAnalyzer::algebraic_merge_batches(&batch(vec![1]), &batch(vec![1, 2]), None, &schema).unwrap_err();
+        assert!(matches!(err, BranchError::TableMergeConflict { .. }));
+    }
 }
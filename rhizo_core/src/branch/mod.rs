@@ -8,4 +8,4 @@ pub mod merge;
 pub use branch::{Branch, BranchDiff};
 pub use error::BranchError;
 pub use manager::BranchManager;
-pub use merge::{MergeAnalysis, MergeAnalyzer, MergeOutcome};
+pub use merge::{MergeAnalysis, MergeAnalyzer, MergeOutcome, Resolution};
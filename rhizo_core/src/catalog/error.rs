@@ -19,4 +19,34 @@ pub enum CatalogError {
 
     #[error("Latest pointer corrupted for table: {0}")]
     LatestPointerCorrupted(String),
+
+    #[error("Chunk store error: {0}")]
+    ChunkStore(String),
+
+    #[error("Duplicate chunk hash in {table} v{version}: {hash}")]
+    DuplicateChunk { table: String, version: u64, hash: String },
+
+    #[error("Version has no chunks: {0} v{1}")]
+    EmptyVersion(String, u64),
+
+    #[error("Tag already exists: {0}")]
+    TagExists(String),
+
+    #[error("Tag not found: {0}")]
+    TagNotFound(String),
+
+    #[error("Cannot delete {table} v{version}: {reason}")]
+    VersionProtected { table: String, version: u64, reason: String },
+
+    #[error("No version of {table} exists as of {timestamp}")]
+    NoVersionAsOf { table: String, timestamp: i64 },
+
+    #[error("Schema error: {0}")]
+    Schema(String),
+
+    #[error("No stored schema for {table} v{version}")]
+    SchemaNotTracked { table: String, version: u64 },
+
+    #[error("Breaking schema change for {table}: {}", changes.join("; "))]
+    BreakingSchemaChange { table: String, changes: Vec<String> },
 }
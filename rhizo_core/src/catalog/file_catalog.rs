@@ -1,17 +1,63 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
+use arrow::datatypes::Schema;
 use super::error::CatalogError;
+use super::schema::{self, SchemaCompat};
 use super::version::TableVersion;
+use crate::chunk_store::ChunkStore;
+
+/// File holding every tag in the catalog, next to the table directories.
+const TAGS_FILE: &str = "_tags.json";
+const TAGS_LOCK_FILE: &str = "_tags.lock";
+
+/// Directory holding stored Arrow schemas, one `<schema_hash>.arrow` each.
+const SCHEMAS_DIR: &str = "_schemas";
+
+/// Chunk reuse across a table's version chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChainReuse {
+    /// Number of versions in the chain
+    pub versions: usize,
+    /// Chunk references summed over all versions
+    pub total_references: usize,
+    /// Distinct chunks referenced by any version
+    pub unique_chunks: usize,
+    /// Mean fraction of each version's references already present in the
+    /// previous version (versions after the first; 0.0 if there is only one)
+    pub avg_reuse_ratio: f64,
+    /// Bytes summed over all references
+    pub referenced_bytes: u64,
+    /// Bytes actually stored for the distinct chunks
+    pub unique_bytes: u64,
+}
 
 pub struct FileCatalog {
     base_path: PathBuf,
+    strict_schemas: bool,
 }
 
 impl FileCatalog {
     pub fn new(base_path: impl AsRef<Path>) -> Result<Self, CatalogError> {
         let base_path = base_path.as_ref().to_path_buf();
         fs::create_dir_all(&base_path)?;
-        Ok(Self { base_path })
+        Ok(Self { base_path, strict_schemas: false })
+    }
+
+    /// Reject commits whose schema is a breaking change from the latest
+    /// version's (see [`check_schema_compatibility`](Self::check_schema_compatibility)).
+    ///
+    /// Only applies when both versions carry a `schema_hash` stored with
+    /// [`store_schema`](Self::store_schema); other commits are not checked.
+    pub fn with_strict_schemas(mut self, strict: bool) -> Self {
+        self.strict_schemas = strict;
+        self
+    }
+
+    /// Commit after checking the version with `TableVersion::validate`.
+    pub fn commit_validated(&self, version: TableVersion) -> Result<u64, CatalogError> {
+        version.validate()?;
+        self.commit(version)
     }
 
     pub fn commit(&self, version: TableVersion) -> Result<u64, CatalogError> {
@@ -27,6 +73,10 @@ impl FileCatalog {
             });
         }
 
+        if self.strict_schemas && expected_version > 1 {
+            self.check_strict_schema(&version)?;
+        }
+
         // Write version file atomically (write to temp, then rename)
         let version_path = table_dir.join(format!("{}.json", version.version));
         let temp_version_path = version_path.with_extension("json.tmp");
@@ -40,13 +90,19 @@ impl FileCatalog {
         fs::write(&temp_latest_path, version.version.to_string())?;
         fs::rename(&temp_latest_path, &latest_path)?;
 
+        // Committing to a dropped table re-creates it
+        let dropped_path = table_dir.join("dropped");
+        if dropped_path.exists() {
+            fs::remove_file(&dropped_path)?;
+        }
+
         Ok(version.version)
     }
 
     pub fn get_version(&self, table_name: &str, version: Option<u64>) -> Result<TableVersion, CatalogError> {
         let table_dir = self.base_path.join(table_name);
         
-        if !table_dir.exists() {
+        if !self.table_exists(table_name) {
             return Err(CatalogError::TableNotFound(table_name.to_string()));
         }
 
@@ -70,7 +126,7 @@ impl FileCatalog {
     pub fn list_versions(&self, table_name: &str) -> Result<Vec<u64>, CatalogError> {
         let table_dir = self.base_path.join(table_name);
         
-        if !table_dir.exists() {
+        if !self.table_exists(table_name) {
             return Err(CatalogError::TableNotFound(table_name.to_string()));
         }
 
@@ -102,7 +158,9 @@ impl FileCatalog {
             let entry = entry?;
             if entry.file_type()?.is_dir() {
                 if let Some(name) = entry.file_name().to_str() {
-                    tables.push(name.to_string());
+                    if name != SCHEMAS_DIR && !self.is_dropped(name) {
+                        tables.push(name.to_string());
+                    }
                 }
             }
         }
@@ -111,10 +169,581 @@ impl FileCatalog {
         Ok(tables)
     }
 
+    /// Each version's schema fingerprint, in version order.
+    pub fn schema_history(&self, table_name: &str) -> Result<Vec<(u64, Option<String>)>, CatalogError> {
+        self.list_versions(table_name)?
+            .into_iter()
+            .map(|v| Ok((v, self.get_version(table_name, Some(v))?.schema_hash)))
+            .collect()
+    }
+
+    /// Each version's `created_at` timestamp, in version order.
+    ///
+    /// Reads the version files in a single pass over the table directory,
+    /// parsing only their timestamps.
+    pub fn list_versions_detailed(&self, table_name: &str) -> Result<Vec<(u64, i64)>, CatalogError> {
+        #[derive(serde::Deserialize)]
+        struct CreatedAt {
+            created_at: i64,
+        }
+
+        if !self.table_exists(table_name) {
+            return Err(CatalogError::TableNotFound(table_name.to_string()));
+        }
+
+        let mut versions = Vec::new();
+        for entry in fs::read_dir(self.base_path.join(table_name))? {
+            let path = entry?.path();
+            if path.extension().is_none_or(|ext| ext != "json") {
+                continue;
+            }
+            let Some(v) = path.file_stem().and_then(|s| s.to_string_lossy().parse::<u64>().ok()) else {
+                continue;
+            };
+            let parsed: CreatedAt = serde_json::from_str(&fs::read_to_string(&path)?)?;
+            versions.push((v, parsed.created_at));
+        }
+
+        versions.sort();
+        Ok(versions)
+    }
+
+    /// The table as it was at `timestamp` (Unix seconds): the newest version
+    /// whose `created_at` is at or before it.
+    ///
+    /// Versions are scanned from the latest down, so the result is the
+    /// highest version number that qualifies even if clocks went backwards
+    /// between commits. Fails with `NoVersionAsOf` if the table had no
+    /// version yet at `timestamp`.
+    pub fn get_version_as_of(&self, table_name: &str, timestamp: i64) -> Result<TableVersion, CatalogError> {
+        for v in self.list_versions(table_name)?.into_iter().rev() {
+            let version = self.get_version(table_name, Some(v))?;
+            if version.created_at <= timestamp {
+                return Ok(version);
+            }
+        }
+        Err(CatalogError::NoVersionAsOf {
+            table: table_name.to_string(),
+            timestamp,
+        })
+    }
+
+    /// Store an Arrow schema and return its hash, for use as a version's
+    /// `schema_hash`.
+    ///
+    /// Storing the same fields again is a no-op. Schema-level metadata is
+    /// not stored and does not affect the hash.
+    pub fn store_schema(&self, schema: &Schema) -> Result<String, CatalogError> {
+        let bytes = schema::encode_schema(schema)?;
+        let hash = blake3::hash(&bytes).to_hex().to_string();
+
+        let dir = self.base_path.join(SCHEMAS_DIR);
+        let path = dir.join(format!("{}.arrow", hash));
+        if !path.exists() {
+            fs::create_dir_all(&dir)?;
+            let temp_path = path.with_extension("arrow.tmp");
+            fs::write(&temp_path, &bytes)?;
+            fs::rename(&temp_path, &path)?;
+        }
+        Ok(hash)
+    }
+
+    /// The schema stored under `hash`, if any.
+    pub fn load_schema(&self, hash: &str) -> Result<Option<Schema>, CatalogError> {
+        let path = self.base_path.join(SCHEMAS_DIR).join(format!("{}.arrow", hash));
+        if !path.exists() {
+            return Ok(None);
+        }
+        schema::decode_schema(&fs::read(path)?).map(Some)
+    }
+
+    /// Classify `new_schema` against the stored schema of the table's
+    /// latest version.
+    ///
+    /// Fails with `SchemaNotTracked` if the latest version has no
+    /// `schema_hash` or its schema was never stored.
+    pub fn check_schema_compatibility(
+        &self,
+        table_name: &str,
+        new_schema: &Schema,
+    ) -> Result<SchemaCompat, CatalogError> {
+        let latest = self.get_version(table_name, None)?;
+        let current = self
+            .version_schema(&latest)?
+            .ok_or_else(|| CatalogError::SchemaNotTracked {
+                table: table_name.to_string(),
+                version: latest.version,
+            })?;
+        Ok(schema::check_compatibility(&current, new_schema))
+    }
+
+    fn version_schema(&self, version: &TableVersion) -> Result<Option<Schema>, CatalogError> {
+        match &version.schema_hash {
+            Some(hash) => self.load_schema(hash),
+            None => Ok(None),
+        }
+    }
+
+    fn check_strict_schema(&self, version: &TableVersion) -> Result<(), CatalogError> {
+        let Some(new_schema) = self.version_schema(version)? else {
+            return Ok(());
+        };
+        let latest = self.get_version(&version.table_name, None)?;
+        let Some(current) = self.version_schema(&latest)? else {
+            return Ok(());
+        };
+        match schema::check_compatibility(&current, &new_schema) {
+            SchemaCompat::Breaking { changes } => Err(CatalogError::BreakingSchemaChange {
+                table: version.table_name.clone(),
+                changes,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Content hash of a table version; see `TableVersion::content_hash`.
+    pub fn version_content_hash(&self, table_name: &str, version: Option<u64>) -> Result<String, CatalogError> {
+        Ok(self.get_version(table_name, version)?.content_hash())
+    }
+
+    /// Chunk reuse across every version of a table.
+    ///
+    /// A version's reuse ratio is the fraction of its chunk references that
+    /// also appear in the previous version; an empty version counts as fully
+    /// reused. Chunk sizes come from `store`.
+    pub fn chain_reuse(&self, table_name: &str, store: &ChunkStore) -> Result<ChainReuse, CatalogError> {
+        let versions = self.list_versions(table_name)?;
+        if versions.is_empty() {
+            return Err(CatalogError::TableNotFound(table_name.to_string()));
+        }
+
+        let mut sizes: HashMap<String, u64> = HashMap::new();
+        let mut total_references = 0;
+        let mut referenced_bytes = 0u64;
+        let mut ratio_sum = 0.0;
+        let mut previous: Option<BTreeSet<String>> = None;
+
+        for v in &versions {
+            let chunks = self.get_version(table_name, Some(*v))?.chunk_hashes;
+
+            for hash in &chunks {
+                let size = match sizes.get(hash) {
+                    Some(size) => *size,
+                    None => {
+                        let mmap = store
+                            .get_mmap(hash)
+                            .map_err(|e| CatalogError::ChunkStore(e.to_string()))?;
+                        sizes.insert(hash.clone(), mmap.len() as u64);
+                        mmap.len() as u64
+                    }
+                };
+                referenced_bytes += size;
+            }
+            total_references += chunks.len();
+
+            if let Some(prev) = &previous {
+                ratio_sum += if chunks.is_empty() {
+                    1.0
+                } else {
+                    let reused = chunks.iter().filter(|h| prev.contains(*h)).count();
+                    reused as f64 / chunks.len() as f64
+                };
+            }
+            previous = Some(chunks.into_iter().collect());
+        }
+
+        let transitions = versions.len() - 1;
+        Ok(ChainReuse {
+            versions: versions.len(),
+            total_references,
+            unique_chunks: sizes.len(),
+            avg_reuse_ratio: if transitions == 0 { 0.0 } else { ratio_sum / transitions as f64 },
+            referenced_bytes,
+            unique_bytes: sizes.values().sum(),
+        })
+    }
+
+    /// Chunks referenced by `to_version` but not by `from_version`.
+    ///
+    /// Hashes are returned in `to_version` order without duplicates, so a
+    /// consumer can fetch and decode only what changed. Either version may
+    /// be the older one.
+    pub fn changed_chunks(
+        &self,
+        table_name: &str,
+        from_version: u64,
+        to_version: u64,
+    ) -> Result<Vec<String>, CatalogError> {
+        let from: HashSet<String> = self
+            .get_version(table_name, Some(from_version))?
+            .chunk_hashes
+            .into_iter()
+            .collect();
+        let to = self.get_version(table_name, Some(to_version))?.chunk_hashes;
+
+        let mut seen = HashSet::new();
+        Ok(to
+            .into_iter()
+            .filter(|h| !from.contains(h) && seen.insert(h.clone()))
+            .collect())
+    }
+
+    /// Versions whose schema fingerprint differs from the prior version.
+    ///
+    /// The first version is never a change point.
+    pub fn schema_change_points(&self, table_name: &str) -> Result<Vec<u64>, CatalogError> {
+        let history = self.schema_history(table_name)?;
+        Ok(history
+            .windows(2)
+            .filter(|pair| pair[0].1 != pair[1].1)
+            .map(|pair| pair[1].0)
+            .collect())
+    }
+
+    /// Label a version of a table with `tag`.
+    ///
+    /// Shorthand for `create_tag` without `force`: tags share one
+    /// catalog-wide namespace, so this fails with `TagExists` if the tag
+    /// already names a different version. Tagging the same version again
+    /// is a no-op.
+    pub fn tag_version(&self, table_name: &str, tag: &str, version: u64) -> Result<(), CatalogError> {
+        // Ensure the version exists
+        self.get_version(table_name, Some(version))?;
+
+        self.update_tags(|tags| {
+            let target = (table_name.to_string(), version);
+            match tags.get(tag) {
+                Some(existing) if *existing != target => Err(CatalogError::TagExists(tag.to_string())),
+                _ => {
+                    tags.insert(tag.to_string(), target);
+                    Ok(())
+                }
+            }
+        })
+    }
+
+    /// Every table's (tag, version) pairs, sorted by tag.
+    ///
+    /// Tables without tags are omitted.
+    pub fn list_all_tags(&self) -> Result<HashMap<String, Vec<(String, u64)>>, CatalogError> {
+        let mut all: HashMap<String, Vec<(String, u64)>> = HashMap::new();
+        for (tag, (table, version)) in self.read_tags()? {
+            all.entry(table).or_default().push((tag, version));
+        }
+        Ok(all)
+    }
+
+    /// Create a catalog-wide tag pointing at `table` `version`.
+    ///
+    /// Tags are immutable: they keep naming the same version however many
+    /// commits land afterwards, and creating a tag that already exists
+    /// fails with `TagExists` unless `force` is set. Tags live in
+    /// `_tags.json`.
+    pub fn create_tag(
+        &self,
+        tag: &str,
+        table_name: &str,
+        version: u64,
+        force: bool,
+    ) -> Result<(), CatalogError> {
+        // Ensure the version exists
+        self.get_version(table_name, Some(version))?;
+
+        self.update_tags(|tags| {
+            if !force && tags.contains_key(tag) {
+                return Err(CatalogError::TagExists(tag.to_string()));
+            }
+            tags.insert(tag.to_string(), (table_name.to_string(), version));
+            Ok(())
+        })
+    }
+
+    /// The (table, version) a tag points at, if the tag exists.
+    pub fn resolve_tag(&self, tag: &str) -> Result<Option<(String, u64)>, CatalogError> {
+        Ok(self.read_tags()?.remove(tag))
+    }
+
+    /// Every tag as (tag, table, version), sorted by tag.
+    pub fn list_tags(&self) -> Result<Vec<(String, String, u64)>, CatalogError> {
+        Ok(self
+            .read_tags()?
+            .into_iter()
+            .map(|(tag, (table, version))| (tag, table, version))
+            .collect())
+    }
+
+    /// Delete a tag. Returns false if it did not exist.
+    pub fn delete_tag(&self, tag: &str) -> Result<bool, CatalogError> {
+        self.update_tags(|tags| Ok(tags.remove(tag).is_some()))
+    }
+
+    /// The table version a tag points at.
+    ///
+    /// # Errors
+    /// `TagNotFound` if the tag does not exist.
+    pub fn get_tagged_version(&self, tag: &str) -> Result<TableVersion, CatalogError> {
+        let (table_name, version) = self
+            .resolve_tag(tag)?
+            .ok_or_else(|| CatalogError::TagNotFound(tag.to_string()))?;
+        self.get_version(&table_name, Some(version))
+    }
+
+    /// Delete a single version of a table.
+    ///
+    /// Versions whose `parent_version` was the deleted one are repointed at
+    /// its parent, so the remaining history stays walkable.
+    ///
+    /// # Errors
+    /// `VersionProtected` if the version is the table's latest or is named
+    /// by a tag; `VersionNotFound` if it is absent.
+    pub fn delete_version(&self, table_name: &str, version: u64) -> Result<(), CatalogError> {
+        self.get_version(table_name, Some(version))?;
+
+        let protect = |reason: String| CatalogError::VersionProtected {
+            table: table_name.to_string(),
+            version,
+            reason,
+        };
+        if version == self.get_latest_version_num(table_name)? {
+            return Err(protect("it is the latest version".to_string()));
+        }
+        if let Some(tag) = self.tags_for(table_name)?.get(&version) {
+            return Err(protect(format!("it is tagged '{}'", tag)));
+        }
+
+        self.remove_versions(table_name, &BTreeSet::from([version]))
+    }
+
+    /// Delete all but the newest `keep_last` versions of a table.
+    ///
+    /// The latest version, tagged versions, and any version in `protected`
+    /// (for example the versions branch heads point at) are always kept.
+    /// `parent_version` chains are repointed past deleted versions as in
+    /// `delete_version`. Returns the deleted version numbers, ascending.
+    pub fn prune_versions(
+        &self,
+        table_name: &str,
+        keep_last: usize,
+        protected: Option<&HashSet<u64>>,
+    ) -> Result<Vec<u64>, CatalogError> {
+        let versions = self.list_versions(table_name)?;
+        let latest = self.get_latest_version_num(table_name)?;
+        let tagged = self.tags_for(table_name)?;
+
+        let cutoff = versions.len().saturating_sub(keep_last);
+        let doomed: BTreeSet<u64> = versions[..cutoff]
+            .iter()
+            .copied()
+            .filter(|v| {
+                *v != latest
+                    && !tagged.contains_key(v)
+                    && !protected.is_some_and(|p| p.contains(v))
+            })
+            .collect();
+
+        self.remove_versions(table_name, &doomed)?;
+        Ok(doomed.into_iter().collect())
+    }
+
+    /// Drop a table so it no longer appears in the catalog.
+    ///
+    /// Removes the `latest` pointer and records the drop, so `list_tables`
+    /// excludes the table and `get_version` returns `TableNotFound`. If
+    /// `remove_versions` is true, all version files are deleted as well;
+    /// otherwise they are kept on disk and a later commit resumes the
+    /// version sequence after the last dropped version.
+    ///
+    /// Chunks are never touched; use `drop_table_with_orphans` to find
+    /// chunks that can be garbage collected afterwards.
+    pub fn drop_table(&self, table_name: &str, remove_versions: bool) -> Result<(), CatalogError> {
+        if !self.table_exists(table_name) {
+            return Err(CatalogError::TableNotFound(table_name.to_string()));
+        }
+
+        let table_dir = self.base_path.join(table_name);
+
+        if remove_versions {
+            fs::remove_dir_all(&table_dir)?;
+            return Ok(());
+        }
+
+        // Record the drop (with the last version) before removing the pointer
+        let last_version = self.get_latest_version_num(table_name)?;
+        let dropped_path = table_dir.join("dropped");
+        let temp_dropped_path = table_dir.join("dropped.tmp");
+        fs::write(&temp_dropped_path, last_version.to_string())?;
+        fs::rename(&temp_dropped_path, &dropped_path)?;
+
+        fs::remove_file(table_dir.join("latest"))?;
+
+        Ok(())
+    }
+
+    /// Drop a table and return the chunk hashes it leaves orphaned.
+    ///
+    /// A chunk is orphaned if some version of the dropped table references
+    /// it and no version file left on disk does. The result is sorted and
+    /// deduplicated, ready to hand to chunk garbage collection.
+    ///
+    /// With `remove_versions` false nothing is orphaned: the retained
+    /// versions come back if the table is committed to again, so their
+    /// chunks must stay.
+    pub fn drop_table_with_orphans(
+        &self,
+        table_name: &str,
+        remove_versions: bool,
+    ) -> Result<Vec<String>, CatalogError> {
+        let dropped_chunks = self.referenced_chunks(table_name)?;
+
+        self.drop_table(table_name, remove_versions)?;
+        if !remove_versions {
+            return Ok(Vec::new());
+        }
+
+        let live_chunks = self.all_referenced_chunks()?;
+        Ok(dropped_chunks.difference(&live_chunks).cloned().collect())
+    }
+
+    /// All chunk hashes referenced by any version file on disk.
+    ///
+    /// Unlike walking `list_tables`, this includes the versions a dropped
+    /// table kept.
+    pub fn all_referenced_chunks(&self) -> Result<BTreeSet<String>, CatalogError> {
+        let mut chunks = BTreeSet::new();
+        for entry in fs::read_dir(&self.base_path)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_dir() || entry.file_name() == SCHEMAS_DIR {
+                continue;
+            }
+            for file in fs::read_dir(entry.path())? {
+                let path = file?.path();
+                let is_version = path.extension().is_some_and(|ext| ext == "json")
+                    && path.file_stem()
+                        .is_some_and(|stem| stem.to_string_lossy().parse::<u64>().is_ok());
+                if is_version {
+                    let table_version: TableVersion = serde_json::from_str(&fs::read_to_string(&path)?)?;
+                    chunks.extend(table_version.chunk_hashes);
+                }
+            }
+        }
+        Ok(chunks)
+    }
+
+    /// All chunk hashes referenced by any version of a table.
+    fn referenced_chunks(&self, table_name: &str) -> Result<BTreeSet<String>, CatalogError> {
+        let mut chunks = BTreeSet::new();
+        for version in self.list_versions(table_name)? {
+            chunks.extend(self.get_version(table_name, Some(version))?.chunk_hashes);
+        }
+        Ok(chunks)
+    }
+
+    /// Delete version files, first repointing surviving children past them.
+    fn remove_versions(&self, table_name: &str, doomed: &BTreeSet<u64>) -> Result<(), CatalogError> {
+        if doomed.is_empty() {
+            return Ok(());
+        }
+
+        let mut parents = HashMap::new();
+        for version in self.list_versions(table_name)? {
+            let parent = self.get_version(table_name, Some(version))?.parent_version;
+            parents.insert(version, parent);
+        }
+
+        let table_dir = self.base_path.join(table_name);
+        for (&version, &parent) in &parents {
+            if doomed.contains(&version) {
+                continue;
+            }
+            // Skip over deleted ancestors to the nearest surviving one
+            let mut new_parent = parent;
+            while let Some(p) = new_parent.filter(|p| doomed.contains(p)) {
+                new_parent = parents.get(&p).copied().flatten().filter(|pp| *pp < p);
+            }
+            if new_parent != parent {
+                let mut table_version = self.get_version(table_name, Some(version))?;
+                table_version.parent_version = new_parent;
+                let version_path = table_dir.join(format!("{}.json", version));
+                let temp_version_path = version_path.with_extension("json.tmp");
+                fs::write(&temp_version_path, serde_json::to_string_pretty(&table_version)?)?;
+                fs::rename(&temp_version_path, &version_path)?;
+            }
+        }
+
+        for version in doomed {
+            fs::remove_file(table_dir.join(format!("{}.json", version)))?;
+        }
+        Ok(())
+    }
+
+    /// Tag names per version of a table.
+    fn tags_for(&self, table_name: &str) -> Result<HashMap<u64, String>, CatalogError> {
+        Ok(self
+            .read_tags()?
+            .into_iter()
+            .filter(|(_, (table, _))| table == table_name)
+            .map(|(tag, (_, version))| (version, tag))
+            .collect())
+    }
+
+    fn read_tags(&self) -> Result<BTreeMap<String, (String, u64)>, CatalogError> {
+        let tags_path = self.base_path.join(TAGS_FILE);
+        if !tags_path.exists() {
+            return Ok(BTreeMap::new());
+        }
+        Ok(serde_json::from_str(&fs::read_to_string(&tags_path)?)?)
+    }
+
+    /// Read-modify-write the tags under an exclusive lock on
+    /// `_tags.lock`, so concurrent writers (threads or processes) cannot
+    /// drop each other's tags. Nothing is written if `update` fails.
+    fn update_tags<T>(
+        &self,
+        update: impl FnOnce(&mut BTreeMap<String, (String, u64)>) -> Result<T, CatalogError>,
+    ) -> Result<T, CatalogError> {
+        let lock = fs::OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(self.base_path.join(TAGS_LOCK_FILE))?;
+        lock.lock()?;
+
+        let mut tags = self.read_tags()?;
+        let result = update(&mut tags)?;
+        self.write_tags(&tags)?;
+        Ok(result)
+    }
+
+    fn write_tags(&self, tags: &BTreeMap<String, (String, u64)>) -> Result<(), CatalogError> {
+        let tags_path = self.base_path.join(TAGS_FILE);
+        let temp_tags_path = tags_path.with_extension("json.tmp");
+        fs::write(&temp_tags_path, serde_json::to_string_pretty(tags)?)?;
+        fs::rename(&temp_tags_path, &tags_path)?;
+        Ok(())
+    }
+
+    fn table_exists(&self, table_name: &str) -> bool {
+        self.base_path.join(table_name).exists() && !self.is_dropped(table_name)
+    }
+
+    fn is_dropped(&self, table_name: &str) -> bool {
+        self.base_path.join(table_name).join("dropped").exists()
+    }
+
     fn get_latest_version_num(&self, table_name: &str) -> Result<u64, CatalogError> {
-        let latest_path = self.base_path.join(table_name).join("latest");
+        let table_dir = self.base_path.join(table_name);
+        let latest_path = table_dir.join("latest");
 
         if !latest_path.exists() {
+            // A dropped table keeps its sequence so retained versions aren't overwritten
+            let dropped_path = table_dir.join("dropped");
+            if dropped_path.exists() {
+                let content = fs::read_to_string(&dropped_path)?;
+                return content
+                    .trim()
+                    .parse::<u64>()
+                    .map_err(|_| CatalogError::LatestPointerCorrupted(table_name.to_string()));
+            }
             return Ok(0);
         }
 
@@ -126,6 +755,32 @@ impl FileCatalog {
     }
 }
 
+/// Chunks referenced by `target` that `have` is missing.
+///
+/// Each hash appears once, in table-name order and then in the order the
+/// version references it. Copying these chunks into `have` is enough to
+/// make every target version readable from it.
+pub fn transfer_plan(
+    target: &HashMap<String, TableVersion>,
+    have: &ChunkStore,
+) -> Result<Vec<String>, CatalogError> {
+    let mut tables: Vec<&String> = target.keys().collect();
+    tables.sort();
+
+    let mut seen = BTreeSet::new();
+    let mut wanted: Vec<&str> = Vec::new();
+    for table in tables {
+        for hash in &target[table].chunk_hashes {
+            if seen.insert(hash.as_str()) {
+                wanted.push(hash);
+            }
+        }
+    }
+
+    have.missing_from(&wanted)
+        .map_err(|e| CatalogError::ChunkStore(e.to_string()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -278,4 +933,511 @@ mod tests {
 
         fs::remove_dir_all(&dir).ok();
     }
+
+    #[test]
+    fn test_schema_change_points() {
+        let dir = temp_dir();
+        let catalog = FileCatalog::new(&dir).unwrap();
+
+        for (v, schema) in [(1, "schema_a"), (2, "schema_a"), (3, "schema_b"), (4, "schema_b")] {
+            let version = TableVersion::new("events", v, vec![]).with_schema_hash(schema);
+            catalog.commit(version).unwrap();
+        }
+
+        let history = catalog.schema_history("events").unwrap();
+        assert_eq!(history.len(), 4);
+        assert_eq!(history[0], (1, Some("schema_a".to_string())));
+        assert_eq!(history[3], (4, Some("schema_b".to_string())));
+
+        assert_eq!(catalog.schema_change_points("events").unwrap(), vec![3]);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_schema_compatibility_and_strict_commits() {
+        use arrow::datatypes::{DataType, Field};
+
+        let dir = temp_dir();
+        let catalog = FileCatalog::new(&dir).unwrap();
+
+        let v1_schema = Schema::new(vec![Field::new("id", DataType::Int64, false)]);
+        let added = Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("note", DataType::Utf8, true),
+        ]);
+        let retyped = Schema::new(vec![Field::new("id", DataType::Utf8, false)]);
+
+        let v1_hash = catalog.store_schema(&v1_schema).unwrap();
+        assert_eq!(catalog.store_schema(&v1_schema).unwrap(), v1_hash);
+        assert_eq!(catalog.load_schema(&v1_hash).unwrap(), Some(v1_schema.clone()));
+        assert_eq!(catalog.load_schema("unknown").unwrap(), None);
+        catalog
+            .commit(TableVersion::new("events", 1, vec![]).with_schema_hash(&v1_hash))
+            .unwrap();
+        assert_eq!(catalog.list_tables().unwrap(), vec!["events"]);
+
+        assert_eq!(
+            catalog.check_schema_compatibility("events", &v1_schema).unwrap(),
+            SchemaCompat::Identical
+        );
+        assert_eq!(
+            catalog.check_schema_compatibility("events", &added).unwrap(),
+            SchemaCompat::Compatible { added_columns: vec!["note".to_string()] }
+        );
+        assert!(catalog.check_schema_compatibility("events", &retyped).unwrap().is_breaking());
+
+        // Strict catalogs refuse the breaking commit and keep the compatible one
+        let strict = FileCatalog::new(&dir).unwrap().with_strict_schemas(true);
+        let retyped_hash = strict.store_schema(&retyped).unwrap();
+        let result = strict.commit(TableVersion::new("events", 2, vec![]).with_schema_hash(&retyped_hash));
+        assert!(matches!(result, Err(CatalogError::BreakingSchemaChange { .. })));
+        assert_eq!(strict.list_versions("events").unwrap(), vec![1]);
+
+        let added_hash = strict.store_schema(&added).unwrap();
+        strict
+            .commit(TableVersion::new("events", 2, vec![]).with_schema_hash(&added_hash))
+            .unwrap();
+        // Versions without a stored schema are not checked
+        strict.commit(TableVersion::new("events", 3, vec![])).unwrap();
+        assert!(matches!(
+            strict.check_schema_compatibility("events", &added),
+            Err(CatalogError::SchemaNotTracked { version: 3, .. })
+        ));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_version_content_hash() {
+        let dir = temp_dir();
+        let catalog = FileCatalog::new(&dir).unwrap();
+
+        let chunks = |hs: &[&str]| hs.iter().map(|h| h.to_string()).collect::<Vec<_>>();
+        catalog.commit(TableVersion::new("a", 1, chunks(&["h1", "h2", "h3"]))).unwrap();
+        catalog.commit(TableVersion::new("b", 1, chunks(&["h3", "h1", "h2"]))).unwrap();
+        catalog.commit(TableVersion::new("b", 2, chunks(&["h1", "h2", "h4"]))).unwrap();
+
+        let a1 = catalog.version_content_hash("a", Some(1)).unwrap();
+        let b1 = catalog.version_content_hash("b", Some(1)).unwrap();
+        let b2 = catalog.version_content_hash("b", None).unwrap();
+        assert_eq!(a1, b1);
+        assert_ne!(b1, b2);
+
+        let with_schema = TableVersion::new("a", 1, chunks(&["h1", "h2", "h3"])).with_schema_hash("s");
+        assert_ne!(with_schema.content_hash(), a1);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_commit_validated_rejects_duplicate_chunk() {
+        let dir = temp_dir();
+        let catalog = FileCatalog::new(&dir).unwrap();
+
+        let dup = TableVersion::new("t", 1, vec!["h1".into(), "h2".into(), "h1".into()]);
+        let err = catalog.commit_validated(dup).unwrap_err();
+        assert!(matches!(
+            &err,
+            CatalogError::DuplicateChunk { table, version: 1, hash } if table == "t" && hash == "h1"
+        ));
+        assert_eq!(err.to_string(), "Duplicate chunk hash in t v1: h1");
+        assert!(matches!(catalog.list_versions("t"), Err(CatalogError::TableNotFound(_))));
+
+        let empty = TableVersion::new("t", 1, vec![]);
+        assert!(matches!(
+            catalog.commit_validated(empty),
+            Err(CatalogError::EmptyVersion(_, 1))
+        ));
+
+        let ok = TableVersion::new("t", 1, vec!["h1".into(), "h2".into()]);
+        assert_eq!(catalog.commit_validated(ok).unwrap(), 1);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_chain_reuse() {
+        let dir = temp_dir();
+        let catalog = FileCatalog::new(dir.join("catalog")).unwrap();
+        let store = ChunkStore::new(dir.join("chunks")).unwrap();
+
+        let hashes: Vec<String> = (0..12)
+            .map(|i| store.put(format!("chunk-{:02}", i).as_bytes()).unwrap())
+            .collect();
+
+        // Each version replaces one of ten chunks
+        catalog.commit(TableVersion::new("t", 1, hashes[0..10].to_vec())).unwrap();
+        let mut v2 = hashes[0..9].to_vec();
+        v2.push(hashes[10].clone());
+        catalog.commit(TableVersion::new("t", 2, v2)).unwrap();
+        let mut v3 = hashes[0..8].to_vec();
+        v3.extend([hashes[10].clone(), hashes[11].clone()]);
+        catalog.commit(TableVersion::new("t", 3, v3)).unwrap();
+
+        let reuse = catalog.chain_reuse("t", &store).unwrap();
+        assert_eq!(reuse.versions, 3);
+        assert_eq!(reuse.total_references, 30);
+        assert_eq!(reuse.unique_chunks, 12);
+        assert!((reuse.avg_reuse_ratio - 0.9).abs() < 1e-9);
+        assert_eq!(reuse.referenced_bytes, 30 * 8);
+        assert_eq!(reuse.unique_bytes, 12 * 8);
+
+        let result = catalog.chain_reuse("missing", &store);
+        assert!(matches!(result, Err(CatalogError::TableNotFound(_))));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_transfer_plan() {
+        let dir = temp_dir();
+        let source = ChunkStore::new(dir.join("source")).unwrap();
+        let local = ChunkStore::new(dir.join("local")).unwrap();
+
+        let hashes: Vec<String> = (0..10)
+            .map(|i| source.put(format!("chunk-{}", i).as_bytes()).unwrap())
+            .collect();
+        for i in 0..7 {
+            local.put(format!("chunk-{}", i).as_bytes()).unwrap();
+        }
+
+        let mut target = HashMap::new();
+        target.insert("a".to_string(), TableVersion::new("a", 1, hashes[0..6].to_vec()));
+        target.insert("b".to_string(), TableVersion::new("b", 3, hashes[4..10].to_vec()));
+
+        let plan = transfer_plan(&target, &local).unwrap();
+        assert_eq!(plan, hashes[7..10].to_vec());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_list_all_tags() {
+        let dir = temp_dir();
+        let catalog = FileCatalog::new(&dir).unwrap();
+
+        for v in 1..=3 {
+            catalog.commit(TableVersion::new("users", v, vec![])).unwrap();
+        }
+        catalog.commit(TableVersion::new("orders", 1, vec![])).unwrap();
+        catalog.commit(TableVersion::new("untagged", 1, vec![])).unwrap();
+
+        catalog.tag_version("users", "release", 2).unwrap();
+        catalog.tag_version("users", "baseline", 1).unwrap();
+        catalog.create_tag("orders-launch", "orders", 1, false).unwrap();
+
+        let all = catalog.list_all_tags().unwrap();
+        assert_eq!(all.len(), 2);
+        assert_eq!(
+            all["users"],
+            vec![("baseline".to_string(), 1), ("release".to_string(), 2)]
+        );
+        assert_eq!(all["orders"], vec![("orders-launch".to_string(), 1)]);
+
+        // Both kinds of tag live in one namespace, seen by every tag API
+        assert_eq!(catalog.resolve_tag("release").unwrap(), Some(("users".to_string(), 2)));
+        assert_eq!(catalog.get_tagged_version("release").unwrap().version, 2);
+        assert_eq!(catalog.list_tags().unwrap().len(), 3);
+
+        // A tag is never silently moved, even to another table
+        catalog.tag_version("users", "release", 2).unwrap();
+        let result = catalog.tag_version("users", "release", 3);
+        assert!(matches!(result, Err(CatalogError::TagExists(ref t)) if t == "release"));
+        let result = catalog.tag_version("orders", "release", 1);
+        assert!(matches!(result, Err(CatalogError::TagExists(_))));
+        assert_eq!(catalog.resolve_tag("release").unwrap(), Some(("users".to_string(), 2)));
+        catalog.create_tag("release", "users", 3, true).unwrap();
+        assert_eq!(catalog.list_all_tags().unwrap()["users"][1], ("release".to_string(), 3));
+        assert_eq!(catalog.list_versions("users").unwrap(), vec![1, 2, 3]);
+
+        let result = catalog.tag_version("users", "future", 9);
+        assert!(matches!(result, Err(CatalogError::VersionNotFound(_, 9))));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_release_tags_are_immutable() {
+        let dir = temp_dir();
+        let catalog = FileCatalog::new(&dir).unwrap();
+        catalog.commit(TableVersion::new("users", 1, vec!["a".to_string()])).unwrap();
+        catalog.commit(TableVersion::new("orders", 1, vec![])).unwrap();
+
+        catalog.create_tag("v1.0-release", "users", 1, false).unwrap();
+        catalog.create_tag("orders-launch", "orders", 1, false).unwrap();
+
+        // Newer commits do not move the tag
+        catalog.commit(TableVersion::new("users", 2, vec!["b".to_string()])).unwrap();
+        assert_eq!(
+            catalog.resolve_tag("v1.0-release").unwrap(),
+            Some(("users".to_string(), 1))
+        );
+        let tagged = catalog.get_tagged_version("v1.0-release").unwrap();
+        assert_eq!(tagged.chunk_hashes, vec!["a".to_string()]);
+
+        let result = catalog.create_tag("v1.0-release", "users", 2, false);
+        assert!(matches!(result, Err(CatalogError::TagExists(ref t)) if t == "v1.0-release"));
+        assert_eq!(catalog.resolve_tag("v1.0-release").unwrap().unwrap().1, 1);
+        catalog.create_tag("v1.0-release", "users", 2, true).unwrap();
+        assert_eq!(catalog.resolve_tag("v1.0-release").unwrap().unwrap().1, 2);
+
+        let result = catalog.create_tag("bad", "users", 9, false);
+        assert!(matches!(result, Err(CatalogError::VersionNotFound(_, 9))));
+
+        assert_eq!(
+            catalog.list_tags().unwrap(),
+            vec![
+                ("orders-launch".to_string(), "orders".to_string(), 1),
+                ("v1.0-release".to_string(), "users".to_string(), 2),
+            ]
+        );
+        // The tags file is not mistaken for a table
+        assert_eq!(catalog.list_tables().unwrap(), vec!["orders", "users"]);
+
+        assert!(catalog.delete_tag("orders-launch").unwrap());
+        assert!(!catalog.delete_tag("orders-launch").unwrap());
+        assert_eq!(catalog.resolve_tag("orders-launch").unwrap(), None);
+        assert!(matches!(
+            catalog.get_tagged_version("orders-launch"),
+            Err(CatalogError::TagNotFound(_))
+        ));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_concurrent_create_tag_keeps_every_tag() {
+        let dir = temp_dir();
+        let catalog = FileCatalog::new(&dir).unwrap();
+        catalog.commit(TableVersion::new("users", 1, vec![])).unwrap();
+
+        std::thread::scope(|s| {
+            for t in 0..8 {
+                let catalog = &catalog;
+                s.spawn(move || {
+                    for i in 0..10 {
+                        catalog.create_tag(&format!("t{}-{}", t, i), "users", 1, false).unwrap();
+                    }
+                });
+            }
+        });
+
+        assert_eq!(catalog.list_tags().unwrap().len(), 80);
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_prune_versions_keeps_history_walkable() {
+        let dir = temp_dir();
+        let catalog = FileCatalog::new(&dir).unwrap();
+        for v in 1..=8 {
+            catalog.commit(TableVersion::new("events", v, vec![format!("c{}", v)])).unwrap();
+        }
+        catalog.tag_version("events", "baseline", 2).unwrap();
+
+        let result = catalog.delete_version("events", 8);
+        assert!(matches!(result, Err(CatalogError::VersionProtected { version: 8, .. })));
+        let result = catalog.delete_version("events", 2);
+        assert!(matches!(result, Err(CatalogError::VersionProtected { version: 2, .. })));
+
+        catalog.delete_version("events", 5).unwrap();
+        assert_eq!(catalog.get_version("events", Some(6)).unwrap().parent_version, Some(4));
+        assert!(matches!(
+            catalog.delete_version("events", 5),
+            Err(CatalogError::VersionNotFound(_, 5))
+        ));
+
+        // v3 is held by a branch head, v2 by its tag
+        let protected = HashSet::from([3]);
+        let deleted = catalog.prune_versions("events", 2, Some(&protected)).unwrap();
+        assert_eq!(deleted, vec![1, 4, 6]);
+        assert_eq!(catalog.list_versions("events").unwrap(), vec![2, 3, 7, 8]);
+
+        // Walking back from the latest reaches every surviving version
+        let mut chain = Vec::new();
+        let mut next = Some(8);
+        while let Some(v) = next {
+            chain.push(v);
+            next = catalog.get_version("events", Some(v)).unwrap().parent_version;
+        }
+        assert_eq!(chain, vec![8, 7, 3, 2]);
+
+        // Pruning never touches the latest, and commits continue the sequence
+        assert_eq!(catalog.prune_versions("events", 0, None).unwrap(), vec![3, 7]);
+        catalog.commit(TableVersion::new("events", 9, vec![])).unwrap();
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_drop_table() {
+        let dir = temp_dir();
+        let catalog = FileCatalog::new(&dir).unwrap();
+
+        catalog.commit(TableVersion::new("keep", 1, vec![])).unwrap();
+        catalog.commit(TableVersion::new("gone", 1, vec![])).unwrap();
+        catalog.commit(TableVersion::new("gone", 2, vec![])).unwrap();
+
+        catalog.drop_table("gone", false).unwrap();
+
+        assert_eq!(catalog.list_tables().unwrap(), vec!["keep"]);
+        let result = catalog.get_version("gone", None);
+        assert!(matches!(result, Err(CatalogError::TableNotFound(_))));
+        let result = catalog.get_version("gone", Some(1));
+        assert!(matches!(result, Err(CatalogError::TableNotFound(_))));
+
+        // Version files are retained, and re-creating resumes the sequence
+        assert!(dir.join("gone").join("2.json").exists());
+        let result = catalog.commit(TableVersion::new("gone", 1, vec![]));
+        assert!(matches!(result, Err(CatalogError::InvalidVersion { expected: 3, .. })));
+        catalog.commit(TableVersion::new("gone", 3, vec![])).unwrap();
+        assert_eq!(catalog.list_tables().unwrap(), vec!["gone", "keep"]);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_drop_table_remove_versions() {
+        let dir = temp_dir();
+        let catalog = FileCatalog::new(&dir).unwrap();
+
+        catalog.commit(TableVersion::new("gone", 1, vec![])).unwrap();
+        catalog.drop_table("gone", true).unwrap();
+
+        assert!(catalog.list_tables().unwrap().is_empty());
+        assert!(!dir.join("gone").exists());
+        let result = catalog.get_version("gone", None);
+        assert!(matches!(result, Err(CatalogError::TableNotFound(_))));
+
+        // Dropping again is an error
+        let result = catalog.drop_table("gone", true);
+        assert!(matches!(result, Err(CatalogError::TableNotFound(_))));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_drop_table_with_orphans() {
+        let dir = temp_dir();
+        let catalog = FileCatalog::new(&dir).unwrap();
+
+        catalog.commit(TableVersion::new("a", 1, vec!["shared".to_string(), "a1".to_string()])).unwrap();
+        catalog.commit(TableVersion::new("a", 2, vec!["a2".to_string()])).unwrap();
+        catalog.commit(TableVersion::new("b", 1, vec!["shared".to_string()])).unwrap();
+
+        let orphans = catalog.drop_table_with_orphans("a", true).unwrap();
+
+        assert_eq!(orphans, vec!["a1", "a2"]);
+        assert_eq!(catalog.list_tables().unwrap(), vec!["b"]);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_drop_table_keeping_versions_orphans_nothing() {
+        let dir = temp_dir();
+        let catalog = FileCatalog::new(&dir).unwrap();
+
+        catalog.commit(TableVersion::new("a", 1, vec!["a1".to_string()])).unwrap();
+        catalog.commit(TableVersion::new("b", 1, vec!["b1".to_string()])).unwrap();
+        catalog.drop_table("b", false).unwrap();
+
+        // Retained versions come back on recommit, so their chunks stay live
+        assert!(catalog.drop_table_with_orphans("a", false).unwrap().is_empty());
+        assert_eq!(
+            catalog.all_referenced_chunks().unwrap(),
+            BTreeSet::from(["a1".to_string(), "b1".to_string()])
+        );
+
+        catalog.commit(TableVersion::new("a", 2, vec!["a2".to_string()])).unwrap();
+        assert_eq!(catalog.get_version("a", Some(1)).unwrap().chunk_hashes, vec!["a1"]);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_changed_chunks_after_incremental_write() {
+        let dir = temp_dir();
+        let catalog = FileCatalog::new(&dir).unwrap();
+        let store = ChunkStore::new(dir.join("chunks")).unwrap();
+
+        let first = store.put(b"rows 0-999").unwrap();
+        let second = store.put(b"rows 1000-1999").unwrap();
+        let third = store.put(b"rows 2000-2999").unwrap();
+        catalog
+            .commit(TableVersion::new("events", 1, vec![first.clone(), second.clone(), third.clone()]))
+            .unwrap();
+
+        // Only the middle block is rewritten
+        let rewritten = store.put(b"rows 1000-1999 (corrected)").unwrap();
+        catalog
+            .commit(TableVersion::new("events", 2, vec![first.clone(), rewritten.clone(), third.clone()]))
+            .unwrap();
+
+        assert_eq!(catalog.changed_chunks("events", 1, 2).unwrap(), vec![rewritten]);
+        assert_eq!(catalog.changed_chunks("events", 2, 1).unwrap(), vec![second]);
+        assert!(catalog.changed_chunks("events", 2, 2).unwrap().is_empty());
+        assert!(matches!(
+            catalog.changed_chunks("events", 1, 3),
+            Err(CatalogError::VersionNotFound(..))
+        ));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_list_versions_detailed() {
+        let dir = temp_dir();
+        let catalog = FileCatalog::new(&dir).unwrap();
+
+        for (v, created_at) in [(1, 1_700_000_000), (2, 1_700_000_500), (3, 1_700_003_600)] {
+            let mut version = TableVersion::new("events", v, vec![format!("h{}", v)]);
+            version.created_at = created_at;
+            catalog.commit(version).unwrap();
+        }
+
+        let detailed = catalog.list_versions_detailed("events").unwrap();
+        assert_eq!(detailed, vec![(1, 1_700_000_000), (2, 1_700_000_500), (3, 1_700_003_600)]);
+        for (v, created_at) in detailed {
+            assert_eq!(catalog.get_version("events", Some(v)).unwrap().created_at, created_at);
+        }
+        assert!(matches!(
+            catalog.list_versions_detailed("missing"),
+            Err(CatalogError::TableNotFound(_))
+        ));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_get_version_as_of() {
+        let dir = temp_dir();
+        let catalog = FileCatalog::new(&dir).unwrap();
+
+        for (v, created_at) in [(1, 1_700_000_000), (2, 1_700_000_500), (3, 1_700_003_600)] {
+            let mut version = TableVersion::new("events", v, vec![format!("h{}", v)]);
+            version.created_at = created_at;
+            catalog.commit(version).unwrap();
+        }
+
+        let as_of = |ts| catalog.get_version_as_of("events", ts).unwrap().version;
+        assert_eq!(as_of(1_700_000_000), 1);
+        assert_eq!(as_of(1_700_000_499), 1);
+        assert_eq!(as_of(1_700_000_500), 2);
+        assert_eq!(as_of(1_700_003_599), 2);
+        assert_eq!(as_of(1_700_003_600), 3);
+        assert_eq!(as_of(i64::MAX), 3);
+
+        assert!(matches!(
+            catalog.get_version_as_of("events", 1_699_999_999),
+            Err(CatalogError::NoVersionAsOf { timestamp: 1_699_999_999, .. })
+        ));
+        assert!(matches!(
+            catalog.get_version_as_of("missing", 1_700_000_000),
+            Err(CatalogError::TableNotFound(_))
+        ));
+
+        fs::remove_dir_all(&dir).ok();
+    }
 }
@@ -1,7 +1,9 @@
 pub mod error;
 pub mod version;
 pub mod file_catalog;
+pub mod schema;
 
 pub use error::CatalogError;
 pub use version::TableVersion;
-pub use file_catalog::FileCatalog;
+pub use file_catalog::{transfer_plan, ChainReuse, FileCatalog};
+pub use schema::SchemaCompat;
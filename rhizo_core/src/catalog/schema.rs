@@ -0,0 +1,179 @@
+//! Arrow schema fingerprints and compatibility between table versions.
+
+use arrow::datatypes::Schema;
+use arrow::ipc::reader::StreamReader;
+use arrow::ipc::writer::StreamWriter;
+use std::io::Cursor;
+
+use super::error::CatalogError;
+
+/// How a new schema relates to a table's current one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaCompat {
+    /// Same fields, in the same order
+    Identical,
+    /// Existing data still reads correctly: the only changes are added
+    /// nullable columns, reordering, or columns becoming nullable
+    Compatible {
+        /// Columns the new schema adds, in the new schema's order
+        added_columns: Vec<String>,
+    },
+    /// Existing data no longer fits the schema
+    Breaking {
+        /// One human-readable line per incompatible change
+        changes: Vec<String>,
+    },
+}
+
+impl SchemaCompat {
+    pub fn is_breaking(&self) -> bool {
+        matches!(self, SchemaCompat::Breaking { .. })
+    }
+}
+
+/// Classify `new` against `old`.
+///
+/// Dropping a column, changing its type, making it non-nullable, or adding
+/// a non-nullable column is breaking. Schema-level metadata is ignored.
+pub fn check_compatibility(old: &Schema, new: &Schema) -> SchemaCompat {
+    if old.fields() == new.fields() {
+        return SchemaCompat::Identical;
+    }
+
+    let mut changes = Vec::new();
+    for old_field in old.fields() {
+        match new.field_with_name(old_field.name()) {
+            Err(_) => changes.push(format!("column '{}' was dropped", old_field.name())),
+            Ok(new_field) => {
+                if new_field.data_type() != old_field.data_type() {
+                    changes.push(format!(
+                        "column '{}' changed type from {} to {}",
+                        old_field.name(),
+                        old_field.data_type(),
+                        new_field.data_type()
+                    ));
+                }
+                if old_field.is_nullable() && !new_field.is_nullable() {
+                    changes.push(format!("column '{}' became non-nullable", old_field.name()));
+                }
+            }
+        }
+    }
+
+    let mut added_columns = Vec::new();
+    for new_field in new.fields() {
+        if old.field_with_name(new_field.name()).is_ok() {
+            continue;
+        }
+        if new_field.is_nullable() {
+            added_columns.push(new_field.name().clone());
+        } else {
+            changes.push(format!("non-nullable column '{}' was added", new_field.name()));
+        }
+    }
+
+    if changes.is_empty() {
+        SchemaCompat::Compatible { added_columns }
+    } else {
+        SchemaCompat::Breaking { changes }
+    }
+}
+
+/// Arrow IPC encoding of `schema` without its schema-level metadata.
+///
+/// Metadata is dropped so that the encoding (and the hash derived from it)
+/// depends only on the fields.
+pub fn encode_schema(schema: &Schema) -> Result<Vec<u8>, CatalogError> {
+    let fields_only = Schema::new(schema.fields().clone());
+    let mut writer = StreamWriter::try_new(Vec::new(), &fields_only).map_err(schema_err)?;
+    writer.finish().map_err(schema_err)?;
+    writer.into_inner().map_err(schema_err)
+}
+
+/// Decode a schema written by [`encode_schema`].
+pub fn decode_schema(bytes: &[u8]) -> Result<Schema, CatalogError> {
+    let reader = StreamReader::try_new(Cursor::new(bytes), None).map_err(schema_err)?;
+    Ok(reader.schema().as_ref().clone())
+}
+
+/// Fingerprint of a schema's fields, used as `TableVersion::schema_hash`.
+pub fn schema_hash(schema: &Schema) -> Result<String, CatalogError> {
+    Ok(blake3::hash(&encode_schema(schema)?).to_hex().to_string())
+}
+
+fn schema_err(e: arrow::error::ArrowError) -> CatalogError {
+    CatalogError::Schema(e.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::datatypes::{DataType, Field};
+    use std::collections::HashMap;
+
+    fn schema(fields: &[(&str, DataType, bool)]) -> Schema {
+        Schema::new(
+            fields
+                .iter()
+                .map(|(name, ty, nullable)| Field::new(*name, ty.clone(), *nullable))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    #[test]
+    fn test_check_compatibility() {
+        let base = schema(&[("id", DataType::Int64, false), ("name", DataType::Utf8, true)]);
+
+        assert_eq!(check_compatibility(&base, &base), SchemaCompat::Identical);
+
+        let added = schema(&[
+            ("id", DataType::Int64, false),
+            ("name", DataType::Utf8, true),
+            ("email", DataType::Utf8, true),
+        ]);
+        assert_eq!(
+            check_compatibility(&base, &added),
+            SchemaCompat::Compatible { added_columns: vec!["email".to_string()] }
+        );
+
+        let reordered = schema(&[("name", DataType::Utf8, true), ("id", DataType::Int64, true)]);
+        assert_eq!(
+            check_compatibility(&base, &reordered),
+            SchemaCompat::Compatible { added_columns: vec![] }
+        );
+
+        let retyped = schema(&[("id", DataType::Utf8, false), ("name", DataType::Utf8, true)]);
+        let dropped = schema(&[("id", DataType::Int64, false)]);
+        let tightened = schema(&[("id", DataType::Int64, false), ("name", DataType::Utf8, false)]);
+        let required = schema(&[
+            ("id", DataType::Int64, false),
+            ("name", DataType::Utf8, true),
+            ("age", DataType::Int32, false),
+        ]);
+        for new in [&retyped, &dropped, &tightened, &required] {
+            assert!(check_compatibility(&base, new).is_breaking());
+        }
+
+        match check_compatibility(&base, &retyped) {
+            SchemaCompat::Breaking { changes } => {
+                assert_eq!(changes, vec!["column 'id' changed type from Int64 to Utf8"]);
+            }
+            other => panic!("expected breaking, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_encode_roundtrip_ignores_metadata() {
+        let base = schema(&[("id", DataType::Int64, false), ("name", DataType::Utf8, true)]);
+        let with_metadata = base
+            .clone()
+            .with_metadata(HashMap::from([("pandas".to_string(), "{}".to_string())]));
+
+        let decoded = decode_schema(&encode_schema(&with_metadata).unwrap()).unwrap();
+        assert_eq!(decoded, base);
+        assert_eq!(schema_hash(&base).unwrap(), schema_hash(&with_metadata).unwrap());
+
+        let other = schema(&[("id", DataType::Int32, false)]);
+        assert_ne!(schema_hash(&base).unwrap(), schema_hash(&other).unwrap());
+    }
+}
@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use super::error::CatalogError;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TableVersion {
@@ -37,4 +38,42 @@ impl TableVersion {
         self.schema_hash = Some(hash.into());
         self
     }
+
+    /// Check that the version lists at least one chunk and no chunk twice.
+    pub fn validate(&self) -> Result<(), CatalogError> {
+        if self.chunk_hashes.is_empty() {
+            return Err(CatalogError::EmptyVersion(self.table_name.clone(), self.version));
+        }
+        let mut seen = HashSet::new();
+        for hash in &self.chunk_hashes {
+            if !seen.insert(hash.as_str()) {
+                return Err(CatalogError::DuplicateChunk {
+                    table: self.table_name.clone(),
+                    version: self.version,
+                    hash: hash.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// BLAKE3 hash over the sorted chunk hashes and the schema hash.
+    ///
+    /// Independent of chunk order, table name, version number and
+    /// timestamps, so two versions holding the same data compare equal.
+    pub fn content_hash(&self) -> String {
+        let mut chunks: Vec<&str> = self.chunk_hashes.iter().map(String::as_str).collect();
+        chunks.sort_unstable();
+
+        let mut hasher = blake3::Hasher::new();
+        for chunk in chunks {
+            hasher.update(chunk.as_bytes());
+            hasher.update(b"\n");
+        }
+        hasher.update(b"schema:");
+        if let Some(schema) = &self.schema_hash {
+            hasher.update(schema.as_bytes());
+        }
+        hasher.finalize().to_hex().to_string()
+    }
 }
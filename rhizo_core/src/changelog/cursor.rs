@@ -0,0 +1,121 @@
+//! Continuation tokens for paging through the changelog.
+
+use crate::transaction::TransactionError;
+
+/// Position in the changelog after the last entry a caller has seen.
+///
+/// Entries are returned in commit order, so resuming strictly after the
+/// last entry's `(commit_seq, tx_id)` position never repeats or skips an
+/// entry, even when transactions commit out of the order they began in.
+/// `offset` counts the entries handed out across all pages so far.
+///
+/// A cursor remembers whether it came from a descending query; passing it
+/// to a query in the other direction is rejected rather than silently
+/// reading the wrong side of the position.
+///
+/// Cursors travel as opaque strings via [`encode`](Self::encode) and
+/// [`decode`](Self::decode).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChangelogCursor {
+    last_commit_seq: u64,
+    last_tx_id: u64,
+    offset: u64,
+    descending: bool,
+}
+
+impl ChangelogCursor {
+    /// Create a cursor positioned after the entry at `last_commit_seq`
+    /// and `last_tx_id`
+    pub fn new(last_commit_seq: u64, last_tx_id: u64, offset: u64, descending: bool) -> Self {
+        Self { last_commit_seq, last_tx_id, offset, descending }
+    }
+
+    /// Commit sequence number of the last entry returned
+    pub fn last_commit_seq(&self) -> u64 {
+        self.last_commit_seq
+    }
+
+    /// Transaction ID of the last entry returned
+    pub fn last_tx_id(&self) -> u64 {
+        self.last_tx_id
+    }
+
+    /// Position of the last entry returned in commit order
+    pub fn position(&self) -> (u64, u64) {
+        (self.last_commit_seq, self.last_tx_id)
+    }
+
+    /// Number of entries returned before this cursor
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    /// Whether this cursor continues a descending query
+    pub fn is_descending(&self) -> bool {
+        self.descending
+    }
+
+    /// Encode as an opaque token.
+    pub fn encode(&self) -> String {
+        let direction = if self.descending { 'd' } else { 'a' };
+        format!(
+            "{}{:016x}{:016x}{:016x}",
+            direction, self.last_commit_seq, self.last_tx_id, self.offset
+        )
+    }
+
+    /// Decode a token produced by [`encode`](Self::encode).
+    pub fn decode(token: &str) -> Result<Self, TransactionError> {
+        let invalid = || TransactionError::InvalidCursor(token.to_string());
+        if token.len() != 49 || !token.is_ascii() {
+            return Err(invalid());
+        }
+        let (direction, rest) = token.split_at(1);
+        let descending = match direction {
+            "a" => false,
+            "d" => true,
+            _ => return Err(invalid()),
+        };
+        let (seq, rest) = rest.split_at(16);
+        let (tx, offset) = rest.split_at(16);
+        Ok(Self {
+            last_commit_seq: u64::from_str_radix(seq, 16).map_err(|_| invalid())?,
+            last_tx_id: u64::from_str_radix(tx, 16).map_err(|_| invalid())?,
+            offset: u64::from_str_radix(offset, 16).map_err(|_| invalid())?,
+            descending,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cursor_roundtrip() {
+        let cursor = ChangelogCursor::new(3, 42, 7, false);
+        let token = cursor.encode();
+        assert_eq!(ChangelogCursor::decode(&token).unwrap(), cursor);
+
+        let descending = ChangelogCursor::new(3, 42, 7, true);
+        assert_ne!(descending.encode(), token);
+        assert_eq!(ChangelogCursor::decode(&descending.encode()).unwrap(), descending);
+
+        let max = ChangelogCursor::new(u64::MAX, u64::MAX, u64::MAX, true);
+        assert_eq!(ChangelogCursor::decode(&max.encode()).unwrap(), max);
+    }
+
+    #[test]
+    fn test_cursor_rejects_garbage() {
+        let old_format = "000000000000002a0000000000000007";
+        let no_direction = "0".repeat(49);
+        let not_hex = format!("azz{}z", "0".repeat(45));
+        let not_ascii = format!("é{}", "0".repeat(47));
+        for token in ["", "42", old_format, &no_direction, &not_hex, &not_ascii] {
+            assert!(matches!(
+                ChangelogCursor::decode(token),
+                Err(TransactionError::InvalidCursor(_))
+            ));
+        }
+    }
+}
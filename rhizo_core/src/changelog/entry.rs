@@ -78,6 +78,11 @@ pub struct ChangelogEntry {
     /// Epoch this transaction was committed in
     pub epoch_id: u64,
 
+    /// Position in commit order (0 for commits logged before commit
+    /// sequence numbers existed)
+    #[serde(default)]
+    pub commit_seq: u64,
+
     /// Unix timestamp when committed
     pub committed_at: i64,
 
@@ -102,6 +107,7 @@ impl ChangelogEntry {
         Self {
             tx_id,
             epoch_id,
+            commit_seq: 0,
             committed_at,
             branch: branch.into(),
             changes: Vec::new(),
@@ -133,6 +139,7 @@ impl ChangelogEntry {
         Self {
             tx_id: tx.tx_id,
             epoch_id: tx.epoch_id,
+            commit_seq: tx.commit_seq.unwrap_or(0),
             committed_at: tx.committed_at.unwrap_or(0),
             branch: tx.branch.clone(),
             changes,
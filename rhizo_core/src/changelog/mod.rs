@@ -4,6 +4,7 @@
 //! - `ChangelogEntry` - A committed transaction viewed as changelog entry
 //! - `TableChange` - A single table modification within a commit
 //! - `ChangelogQuery` - Builder for filtering changelog queries
+//! - `ChangelogCursor` - Continuation token for paging through results
 //!
 //! The changelog is built on top of the TransactionLog, providing a
 //! streaming-friendly view of committed transactions. This enables
@@ -11,9 +12,11 @@
 //! - Batch: "What is the state at version V?" (via QueryEngine.query())
 //! - Stream: "What changed since version V?" (via changelog)
 
+mod cursor;
 mod entry;
 mod query;
 
+pub use cursor::ChangelogCursor;
 pub use entry::{ChangelogEntry, TableChange};
 pub use query::ChangelogQuery;
 
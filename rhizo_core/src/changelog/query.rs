@@ -1,5 +1,7 @@
 //! Query builder for changelog filtering.
 
+use super::cursor::ChangelogCursor;
+
 /// Query parameters for filtering changelog entries.
 ///
 /// Uses a builder pattern for ergonomic query construction:
@@ -10,6 +12,9 @@
 ///     .for_tables(vec!["users".to_string(), "orders".to_string()])
 ///     .on_branch("main")
 ///     .with_limit(50);
+///
+/// // The 10 most recent commits
+/// let latest = ChangelogQuery::new().descending().with_limit(10);
 /// ```
 #[derive(Debug, Clone, Default)]
 pub struct ChangelogQuery {
@@ -19,6 +24,12 @@ pub struct ChangelogQuery {
     /// Start from this timestamp (inclusive, Unix seconds)
     pub since_timestamp: Option<i64>,
 
+    /// Stop at this transaction ID (inclusive)
+    pub until_tx_id: Option<u64>,
+
+    /// Stop before this timestamp (exclusive, Unix seconds)
+    pub until_timestamp: Option<i64>,
+
     /// Filter to specific tables (None = all tables)
     pub tables: Option<Vec<String>>,
 
@@ -27,6 +38,12 @@ pub struct ChangelogQuery {
 
     /// Maximum entries to return (None = unlimited)
     pub limit: Option<usize>,
+
+    /// Resume after the position returned with a previous page
+    pub after_cursor: Option<ChangelogCursor>,
+
+    /// Return newest entries first
+    pub descending: bool,
 }
 
 impl ChangelogQuery {
@@ -51,6 +68,21 @@ impl ChangelogQuery {
         self
     }
 
+    /// Filter to entries up to and including this transaction ID.
+    pub fn until_tx(mut self, tx_id: u64) -> Self {
+        self.until_tx_id = Some(tx_id);
+        self
+    }
+
+    /// Filter to entries committed before this timestamp.
+    ///
+    /// Only entries with committed_at < timestamp will be returned, so
+    /// `since_time(a).until_time(b)` covers the half-open window `[a, b)`.
+    pub fn until_time(mut self, timestamp: i64) -> Self {
+        self.until_timestamp = Some(timestamp);
+        self
+    }
+
     /// Filter to entries that changed any of these tables.
     ///
     /// An entry is included if it changed at least one of the specified tables.
@@ -66,11 +98,49 @@ impl ChangelogQuery {
     }
 
     /// Limit the number of entries returned.
+    ///
+    /// When descending, the limit keeps the newest entries.
     pub fn with_limit(mut self, limit: usize) -> Self {
         self.limit = Some(limit);
         self
     }
 
+    /// Return entries newest first (descending commit order).
+    pub fn descending(mut self) -> Self {
+        self.descending = true;
+        self
+    }
+
+    /// Resume after a cursor returned with a previous page.
+    ///
+    /// The cursor bounds by commit order, while `since_tx` and `until_tx`
+    /// still filter by transaction ID. On a descending query the cursor
+    /// bounds from above instead, so the next page continues towards older
+    /// entries. The cursor must come from a query in the same direction.
+    pub fn with_cursor(mut self, cursor: ChangelogCursor) -> Self {
+        self.after_cursor = Some(cursor);
+        self
+    }
+
+    /// Whether `tx_id` is within `since_tx_id` (exclusive) and
+    /// `until_tx_id` (inclusive).
+    pub fn tx_in_range(&self, tx_id: u64) -> bool {
+        self.since_tx_id.is_none_or(|since| tx_id > since)
+            && self.until_tx_id.is_none_or(|until| tx_id <= until)
+    }
+
+    /// Whether an entry at `commit_seq` and `tx_id` comes after the cursor
+    /// in this query's direction (always true without a cursor).
+    pub fn is_past_cursor(&self, commit_seq: u64, tx_id: u64) -> bool {
+        self.after_cursor.is_none_or(|cursor| {
+            if self.descending {
+                (commit_seq, tx_id) < cursor.position()
+            } else {
+                (commit_seq, tx_id) > cursor.position()
+            }
+        })
+    }
+
     /// Check if an entry matches this query's filters.
     ///
     /// Note: This does NOT check since_tx_id or since_timestamp,
@@ -123,6 +193,36 @@ mod tests {
         assert_eq!(query.limit, Some(10));
     }
 
+    #[test]
+    fn test_tx_in_range() {
+        assert!(ChangelogQuery::new().tx_in_range(1));
+
+        let query = ChangelogQuery::new().since_tx(5).until_tx(8);
+        assert!(!query.tx_in_range(5));
+        assert!(query.tx_in_range(6));
+        assert!(query.tx_in_range(8));
+        assert!(!query.tx_in_range(9));
+    }
+
+    #[test]
+    fn test_is_past_cursor() {
+        assert!(ChangelogQuery::new().is_past_cursor(0, 0));
+
+        // Positions compare by commit sequence first, then tx_id
+        let cursor = ChangelogCursor::new(4, 7, 2, false);
+        let ascending = ChangelogQuery::new().with_cursor(cursor);
+        assert!(ascending.is_past_cursor(5, 1));
+        assert!(ascending.is_past_cursor(4, 8));
+        assert!(!ascending.is_past_cursor(4, 7));
+        assert!(!ascending.is_past_cursor(3, 9));
+
+        let cursor = ChangelogCursor::new(4, 7, 2, true);
+        let descending = ChangelogQuery::new().with_cursor(cursor).descending();
+        assert!(descending.is_past_cursor(3, 9));
+        assert!(!descending.is_past_cursor(4, 7));
+        assert!(!descending.is_past_cursor(5, 1));
+    }
+
     #[test]
     fn test_matches_no_filter() {
         let query = ChangelogQuery::new();
@@ -13,4 +13,10 @@ pub enum ChunkStoreError {
 
     #[error("Hash mismatch: expected {expected}, got {actual}")]
     HashMismatch { expected: String, actual: String },
+
+    #[error("Ambiguous hash prefix {prefix}: matches {matches} chunks")]
+    AmbiguousPrefix { prefix: String, matches: usize },
+
+    #[error("Invalid metadata for chunk {hash}: {reason}")]
+    InvalidMeta { hash: String, reason: String },
 }
@@ -2,4 +2,4 @@ pub mod error;
 pub mod store;
 
 pub use error::ChunkStoreError;
-pub use store::{ChunkMmap, ChunkStore};
+pub use store::{ChunkMmap, ChunkStore, GcReport, DEFAULT_SHORT_HASH_LEN};
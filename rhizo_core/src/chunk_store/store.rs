@@ -1,6 +1,9 @@
+use std::collections::{HashMap, HashSet};
 use std::fs::{self, File};
+use std::io::{self, Read, Write};
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
+use std::time::Duration;
 use rayon::prelude::*;
 use memmap2::Mmap;
 use tracing::warn;
@@ -9,6 +12,15 @@ use super::error::ChunkStoreError;
 /// BLAKE3 hashes are 64 hex characters (256 bits)
 const EXPECTED_HASH_LEN: usize = 64;
 
+/// Default length of short hashes for display and prefix lookup
+pub const DEFAULT_SHORT_HASH_LEN: usize = 12;
+
+/// Shortest prefix `resolve_prefix` accepts (the two directory levels)
+const MIN_PREFIX_LEN: usize = 4;
+
+/// Block size used by `put_reader`
+const STREAM_BLOCK_SIZE: usize = 64 * 1024;
+
 /// A memory-mapped chunk that keeps the underlying file handle alive.
 ///
 /// On Windows, the file handle must remain open while the memory mapping is in use.
@@ -54,15 +66,120 @@ impl AsRef<[u8]> for ChunkMmap {
     }
 }
 
+/// Result of a `ChunkStore::garbage_collect` sweep.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GcReport {
+    /// Chunks deleted because they were not in the live set
+    pub removed_count: usize,
+    /// Bytes freed by the deleted chunks (sidecars not included)
+    pub removed_bytes: u64,
+    /// Chunks examined
+    pub scanned_count: usize,
+}
+
 pub struct ChunkStore {
     base_path: PathBuf,
+    /// Total attempts for retryable filesystem operations (1 = no retry)
+    max_attempts: u32,
+    /// Delay before the first retry; grows linearly with each attempt
+    backoff: Duration,
+    /// Characters kept by `short_hash`
+    short_hash_len: usize,
 }
 
 impl ChunkStore {
     pub fn new(base_path: impl AsRef<Path>) -> Result<Self, ChunkStoreError> {
         let base_path = base_path.as_ref().to_path_buf();
         fs::create_dir_all(&base_path)?;
-        Ok(Self { base_path })
+        Ok(Self {
+            base_path,
+            max_attempts: 1,
+            backoff: Duration::ZERO,
+            short_hash_len: DEFAULT_SHORT_HASH_LEN,
+        })
+    }
+
+    /// Set the length of hashes returned by `short_hash`.
+    ///
+    /// Clamped to 4..=64 so short hashes can always be passed to
+    /// `resolve_prefix`.
+    pub fn with_short_hash_len(mut self, len: usize) -> Self {
+        self.short_hash_len = len.clamp(MIN_PREFIX_LEN, EXPECTED_HASH_LEN);
+        self
+    }
+
+    /// Configured short hash length.
+    pub fn short_hash_len(&self) -> usize {
+        self.short_hash_len
+    }
+
+    /// Shorten a hash to the configured length for display.
+    pub fn short_hash(&self, full: &str) -> String {
+        full.chars().take(self.short_hash_len).collect()
+    }
+
+    /// Resolve a hash prefix to the full hash of a stored chunk.
+    ///
+    /// # Errors
+    /// - `ChunkStoreError::InvalidHash` if the prefix is shorter than 4
+    ///   characters, longer than a hash, or not hexadecimal
+    /// - `ChunkStoreError::NotFound` if no chunk matches
+    /// - `ChunkStoreError::AmbiguousPrefix` if more than one chunk matches
+    pub fn resolve_prefix(&self, prefix: &str) -> Result<String, ChunkStoreError> {
+        if prefix.len() < MIN_PREFIX_LEN || prefix.len() > EXPECTED_HASH_LEN {
+            return Err(ChunkStoreError::InvalidHash(format!(
+                "prefix must be {} to {} characters, got {}",
+                MIN_PREFIX_LEN,
+                EXPECTED_HASH_LEN,
+                prefix.len()
+            )));
+        }
+        if !prefix.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(ChunkStoreError::InvalidHash(
+                "hash must contain only hexadecimal characters".to_string()
+            ));
+        }
+
+        let prefix = prefix.to_ascii_lowercase();
+        let dir = self.base_path.join(&prefix[0..2]).join(&prefix[2..4]);
+        let entries = match fs::read_dir(&dir) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                return Err(ChunkStoreError::NotFound(prefix));
+            }
+            Err(e) => return Err(ChunkStoreError::Io(e)),
+        };
+
+        let mut matches = Vec::new();
+        for entry in entries {
+            let name = entry?.file_name().to_string_lossy().into_owned();
+            // Skip in-flight temp files
+            if name.len() == EXPECTED_HASH_LEN && name.starts_with(&prefix) {
+                matches.push(name);
+            }
+        }
+
+        match matches.len() {
+            0 => Err(ChunkStoreError::NotFound(prefix)),
+            1 => Ok(matches.remove(0)),
+            n => Err(ChunkStoreError::AmbiguousPrefix { prefix, matches: n }),
+        }
+    }
+
+    /// Retry transient filesystem errors in `put` and `get`.
+    ///
+    /// Only the write/rename in `put` and the read in `get` are retried, and
+    /// only for transient error kinds (`Interrupted`, `WouldBlock`,
+    /// `TimedOut`). `NotFound`, hash errors and other I/O errors fail
+    /// immediately.
+    ///
+    /// # Arguments
+    /// * `max_attempts` - Total attempts per operation (values below 1 are treated as 1)
+    /// * `backoff` - Delay before the first retry; attempt `n` waits `n * backoff`
+    pub fn with_retry(mut self, max_attempts: u32, backoff: Duration) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self.backoff = backoff;
+        self
     }
 
     pub fn put(&self, data: &[u8]) -> Result<String, ChunkStoreError> {
@@ -77,40 +194,171 @@ impl ChunkStore {
             // Use unique temp file name to avoid collisions in parallel writes
             let temp_name = format!("{}.{}.tmp", hash, uuid::Uuid::new_v4());
             let temp_path = chunk_path.with_file_name(&temp_name);
-            fs::write(&temp_path, data)?;
-
-            // Rename may fail if another thread created the chunk first - that's OK
-            // because content-addressed storage means both wrote the same data
-            match fs::rename(&temp_path, &chunk_path) {
-                Ok(()) => {}
-                Err(_) if chunk_path.exists() => {
-                    // Another thread beat us - clean up our temp file
-                    if let Err(e) = fs::remove_file(&temp_path) {
-                        warn!(
-                            path = %temp_path.display(),
-                            error = %e,
-                            "Failed to remove orphaned temp file after concurrent write"
-                        );
-                    }
-                    // This is not an error - the chunk exists with correct content
+            self.retry_io(|| fs::write(&temp_path, data))?;
+            self.rename_into_place(&temp_path, &chunk_path)?;
+        }
+
+        Ok(hash)
+    }
+
+    /// Store a chunk read from `reader`, returning its hash.
+    ///
+    /// Data is hashed and written in 64 KB blocks, so memory use stays
+    /// constant regardless of chunk size. Blocks go to a uniquely named temp
+    /// file in the store root, which is renamed to the chunk path once the
+    /// hash is known. The result is identical to `put` on the same bytes.
+    ///
+    /// Reads are not retried (the reader can't be rewound); a failed read
+    /// removes the temp file and returns `ChunkStoreError::Io`.
+    pub fn put_reader<R: Read>(&self, mut reader: R) -> Result<String, ChunkStoreError> {
+        let temp_path = self.base_path.join(format!(".stream.{}.tmp", uuid::Uuid::new_v4()));
+
+        let hash = match Self::write_hashed(&mut reader, &temp_path) {
+            Ok(hash) => hash,
+            Err(e) => {
+                fs::remove_file(&temp_path).ok();
+                return Err(ChunkStoreError::Io(e));
+            }
+        };
+
+        let chunk_path = self.hash_to_path(&hash)?;
+        if chunk_path.exists() {
+            fs::remove_file(&temp_path)?;
+            return Ok(hash);
+        }
+        if let Some(parent) = chunk_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        self.rename_into_place(&temp_path, &chunk_path)?;
+
+        Ok(hash)
+    }
+
+    /// Open a chunk for streaming reads.
+    ///
+    /// The data is not verified against the hash; use `get_verified` for that.
+    pub fn get_reader(&self, hash: &str) -> Result<impl Read, ChunkStoreError> {
+        self.validate_hash(hash)?;
+        let chunk_path = self.hash_to_path(hash)?;
+
+        match self.retry_io(|| File::open(&chunk_path)) {
+            Ok(file) => Ok(io::BufReader::with_capacity(STREAM_BLOCK_SIZE, file)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                Err(ChunkStoreError::NotFound(hash.to_string()))
+            }
+            Err(e) => Err(ChunkStoreError::Io(e)),
+        }
+    }
+
+    /// Copy `reader` to `path` block by block, returning the BLAKE3 hash.
+    fn write_hashed<R: Read>(reader: &mut R, path: &Path) -> io::Result<String> {
+        let mut file = File::create(path)?;
+        let mut hasher = blake3::Hasher::new();
+        let mut block = vec![0u8; STREAM_BLOCK_SIZE];
+        loop {
+            let n = match reader.read(&mut block) {
+                Ok(0) => break,
+                Ok(n) => n,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            };
+            hasher.update(&block[..n]);
+            file.write_all(&block[..n])?;
+        }
+        file.flush()?;
+        Ok(hasher.finalize().to_hex().to_string())
+    }
+
+    /// Rename a fully written temp file to its chunk path.
+    fn rename_into_place(&self, temp_path: &Path, chunk_path: &Path) -> Result<(), ChunkStoreError> {
+        // Rename may fail if another thread created the chunk first - that's OK
+        // because content-addressed storage means both wrote the same data
+        match self.retry_io(|| fs::rename(temp_path, chunk_path)) {
+            Ok(()) => Ok(()),
+            Err(_) if chunk_path.exists() => {
+                // Another thread beat us - clean up our temp file
+                if let Err(e) = fs::remove_file(temp_path) {
+                    warn!(
+                        path = %temp_path.display(),
+                        error = %e,
+                        "Failed to remove orphaned temp file after concurrent write"
+                    );
                 }
-                Err(e) => {
-                    // Actual error - clean up and return
-                    if let Err(cleanup_err) = fs::remove_file(&temp_path) {
-                        warn!(
-                            path = %temp_path.display(),
-                            error = %cleanup_err,
-                            "Failed to remove temp file after write error"
-                        );
-                    }
-                    return Err(ChunkStoreError::Io(e));
+                // This is not an error - the chunk exists with correct content
+                Ok(())
+            }
+            Err(e) => {
+                // Actual error - clean up and return
+                if let Err(cleanup_err) = fs::remove_file(temp_path) {
+                    warn!(
+                        path = %temp_path.display(),
+                        error = %cleanup_err,
+                        "Failed to remove temp file after write error"
+                    );
                 }
+                Err(ChunkStoreError::Io(e))
             }
         }
+    }
+
+    /// Store a chunk together with a metadata sidecar (`<hash>.meta.json`).
+    ///
+    /// The content hash covers `data` only, so identical data stored with
+    /// different metadata shares one chunk; the latest metadata replaces any
+    /// earlier sidecar.
+    pub fn put_with_meta(
+        &self,
+        data: &[u8],
+        meta: HashMap<String, String>,
+    ) -> Result<String, ChunkStoreError> {
+        let hash = self.put(data)?;
+        let meta_path = self.meta_path(&hash)?;
+        let json = serde_json::to_vec_pretty(&meta).map_err(|e| ChunkStoreError::InvalidMeta {
+            hash: hash.clone(),
+            reason: e.to_string(),
+        })?;
+
+        let temp_path = meta_path.with_file_name(format!("{}.meta.{}.tmp", hash, uuid::Uuid::new_v4()));
+        self.retry_io(|| fs::write(&temp_path, &json))?;
+        if let Err(e) = self.retry_io(|| fs::rename(&temp_path, &meta_path)) {
+            if let Err(cleanup_err) = fs::remove_file(&temp_path) {
+                warn!(
+                    path = %temp_path.display(),
+                    error = %cleanup_err,
+                    "Failed to remove temp file after metadata write error"
+                );
+            }
+            return Err(ChunkStoreError::Io(e));
+        }
 
         Ok(hash)
     }
 
+    /// Get the metadata stored for a chunk by `put_with_meta`.
+    ///
+    /// Returns an empty map for chunks stored without metadata.
+    ///
+    /// # Errors
+    /// - `ChunkStoreError::NotFound` if the chunk doesn't exist
+    /// - `ChunkStoreError::InvalidMeta` if the sidecar is not a string map
+    pub fn get_meta(&self, hash: &str) -> Result<HashMap<String, String>, ChunkStoreError> {
+        self.validate_hash(hash)?;
+        if !self.hash_to_path(hash)?.exists() {
+            return Err(ChunkStoreError::NotFound(hash.to_string()));
+        }
+
+        let meta_path = self.meta_path(hash)?;
+        let json = match self.retry_io(|| fs::read(&meta_path)) {
+            Ok(json) => json,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(HashMap::new()),
+            Err(e) => return Err(ChunkStoreError::Io(e)),
+        };
+        serde_json::from_slice(&json).map_err(|e| ChunkStoreError::InvalidMeta {
+            hash: hash.to_string(),
+            reason: e.to_string(),
+        })
+    }
+
     pub fn get(&self, hash: &str) -> Result<Vec<u8>, ChunkStoreError> {
         self.validate_hash(hash)?;
         let chunk_path = self.hash_to_path(hash)?;
@@ -119,7 +367,7 @@ impl ChunkStore {
             return Err(ChunkStoreError::NotFound(hash.to_string()));
         }
 
-        Ok(fs::read(&chunk_path)?)
+        Ok(self.retry_io(|| fs::read(&chunk_path))?)
     }
 
     /// Get chunk data with integrity verification.
@@ -192,10 +440,7 @@ impl ChunkStore {
     /// # Returns
     /// Vector of memory-mapped views in the same order as input hashes
     pub fn get_mmap_batch(&self, hashes: &[&str]) -> Result<Vec<ChunkMmap>, ChunkStoreError> {
-        hashes
-            .par_iter()
-            .map(|hash| self.get_mmap(hash))
-            .collect()
+        crate::parallel::install(|| hashes.par_iter().map(|hash| self.get_mmap(hash)).collect())
     }
 
     pub fn exists(&self, hash: &str) -> Result<bool, ChunkStoreError> {
@@ -203,6 +448,23 @@ impl ChunkStore {
         Ok(self.hash_to_path(hash)?.exists())
     }
 
+    /// Size of a chunk in bytes, read from file metadata without opening it.
+    ///
+    /// # Errors
+    /// - `ChunkStoreError::NotFound` if the chunk doesn't exist
+    /// - `ChunkStoreError::InvalidHash` if the hash format is invalid
+    pub fn size(&self, hash: &str) -> Result<u64, ChunkStoreError> {
+        self.validate_hash(hash)?;
+        let metadata = fs::metadata(self.hash_to_path(hash)?).map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                ChunkStoreError::NotFound(hash.to_string())
+            } else {
+                ChunkStoreError::Io(e)
+            }
+        })?;
+        Ok(metadata.len())
+    }
+
     pub fn delete(&self, hash: &str) -> Result<(), ChunkStoreError> {
         self.validate_hash(hash)?;
         let chunk_path = self.hash_to_path(hash)?;
@@ -211,6 +473,12 @@ impl ChunkStore {
             fs::remove_file(&chunk_path)?;
         }
 
+        // Drop the metadata sidecar with its chunk
+        let meta_path = self.meta_path(hash)?;
+        if meta_path.exists() {
+            fs::remove_file(&meta_path)?;
+        }
+
         Ok(())
     }
 
@@ -263,6 +531,100 @@ impl ChunkStore {
         (removed, failed)
     }
 
+    /// Delete every chunk whose hash is not in `live_hashes`.
+    ///
+    /// Equivalent to `garbage_collect_with_options(live_hashes, false)`.
+    pub fn garbage_collect(&self, live_hashes: &HashSet<String>) -> Result<GcReport, ChunkStoreError> {
+        self.garbage_collect_with_options(live_hashes, false)
+    }
+
+    /// Delete every chunk whose hash is not in `live_hashes`.
+    ///
+    /// Walks the `ab/cd/<hash>` layout and removes unreferenced chunks along
+    /// with their metadata sidecars, including sidecars whose chunk is
+    /// already gone (these are not counted in the report). Temp files and
+    /// anything that is not a chunk are left alone (see
+    /// `cleanup_orphaned_temp_files`). With `prune_empty_dirs`, shard
+    /// directories left empty are removed too.
+    ///
+    /// The live set must be complete: a chunk written after it was computed
+    /// is deleted, so don't run this alongside writers.
+    pub fn garbage_collect_with_options(
+        &self,
+        live_hashes: &HashSet<String>,
+        prune_empty_dirs: bool,
+    ) -> Result<GcReport, ChunkStoreError> {
+        let mut report = GcReport::default();
+
+        for path in self.walk_directory(&self.base_path)? {
+            let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+                continue;
+            };
+            let is_hash = |s: &str| s.len() == EXPECTED_HASH_LEN && s.chars().all(|c| c.is_ascii_hexdigit());
+
+            if let Some(hash) = name.strip_suffix(".meta.json") {
+                // Sidecars normally go with their chunk below; reap any left
+                // behind by a chunk removed some other way
+                if is_hash(hash) && !live_hashes.contains(hash) && !self.hash_to_path(hash)?.exists() {
+                    match fs::remove_file(&path) {
+                        Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e.into()),
+                        _ => {}
+                    }
+                }
+                continue;
+            }
+            let hash = name;
+            if !is_hash(hash) {
+                continue;
+            }
+
+            report.scanned_count += 1;
+            if live_hashes.contains(hash) {
+                continue;
+            }
+
+            let size = match fs::metadata(&path) {
+                Ok(meta) => meta.len(),
+                // Deleted since the walk; still drop its sidecar
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                    self.delete(hash)?;
+                    continue;
+                }
+                Err(e) => return Err(e.into()),
+            };
+            self.delete(hash)?;
+            report.removed_count += 1;
+            report.removed_bytes += size;
+        }
+
+        if prune_empty_dirs {
+            self.prune_empty_dirs()?;
+        }
+
+        Ok(report)
+    }
+
+    /// Remove empty `ab/` and `ab/cd/` shard directories.
+    fn prune_empty_dirs(&self) -> Result<(), ChunkStoreError> {
+        for outer in fs::read_dir(&self.base_path)? {
+            let outer = outer?.path();
+            if !outer.is_dir() {
+                continue;
+            }
+            for inner in fs::read_dir(&outer)? {
+                let inner = inner?.path();
+                if inner.is_dir() && fs::read_dir(&inner)?.next().is_none() {
+                    // A concurrent put may have just written here
+                    fs::remove_dir(&inner).ok();
+                }
+            }
+            if fs::read_dir(&outer)?.next().is_none() {
+                fs::remove_dir(&outer).ok();
+            }
+        }
+        Ok(())
+    }
+
     /// Recursively walk a directory and collect all file paths.
     fn walk_directory(&self, dir: &Path) -> Result<Vec<PathBuf>, std::io::Error> {
         let mut files = Vec::new();
@@ -311,10 +673,7 @@ impl ChunkStore {
     /// # std::fs::remove_dir_all(&dir).ok();
     /// ```
     pub fn put_batch(&self, chunks: &[&[u8]]) -> Result<Vec<String>, ChunkStoreError> {
-        chunks
-            .par_iter()
-            .map(|data| self.put(data))
-            .collect()
+        crate::parallel::install(|| chunks.par_iter().map(|data| self.put(data)).collect())
     }
 
     /// Retrieve multiple chunks in parallel by their hashes.
@@ -342,10 +701,16 @@ impl ChunkStore {
     /// # std::fs::remove_dir_all(&dir).ok();
     /// ```
     pub fn get_batch(&self, hashes: &[&str]) -> Result<Vec<Vec<u8>>, ChunkStoreError> {
-        hashes
-            .par_iter()
-            .map(|hash| self.get(hash))
-            .collect()
+        crate::parallel::install(|| hashes.par_iter().map(|hash| self.get(hash)).collect())
+    }
+
+    /// Retrieve multiple chunks in parallel, keeping every outcome.
+    ///
+    /// Unlike `get_batch`, which stops at the first failure, this returns
+    /// one result per hash (in input order), so callers can use the chunks
+    /// that are available and see exactly which ones are not.
+    pub fn get_batch_collect(&self, hashes: &[&str]) -> Vec<Result<Vec<u8>, ChunkStoreError>> {
+        crate::parallel::install(|| hashes.par_iter().map(|hash| self.get(hash)).collect())
     }
 
     /// Retrieve multiple chunks with verification in parallel.
@@ -362,10 +727,34 @@ impl ChunkStore {
     /// # Errors
     /// Returns `ChunkStoreError::HashMismatch` if any chunk fails verification
     pub fn get_batch_verified(&self, hashes: &[&str]) -> Result<Vec<Vec<u8>>, ChunkStoreError> {
-        hashes
-            .par_iter()
-            .map(|hash| self.get_verified(hash))
-            .collect()
+        crate::parallel::install(|| hashes.par_iter().map(|hash| self.get_verified(hash)).collect())
+    }
+
+    /// Check existence of multiple chunks in parallel.
+    ///
+    /// Returns results in the same order as input hashes.
+    pub fn exists_batch(&self, hashes: &[&str]) -> Result<Vec<bool>, ChunkStoreError> {
+        crate::parallel::install(|| hashes.par_iter().map(|hash| self.exists(hash)).collect())
+    }
+
+    /// Hashes from `hashes` that are not stored locally.
+    ///
+    /// Existence is checked in parallel. Each missing hash appears once, in
+    /// first-seen order, so the result can be sent as-is when pushing to a
+    /// store that lacks these chunks.
+    ///
+    /// # Errors
+    /// Returns `ChunkStoreError::InvalidHash` if any hash is malformed
+    pub fn missing_from(&self, hashes: &[&str]) -> Result<Vec<String>, ChunkStoreError> {
+        let mut seen = HashSet::new();
+        let unique: Vec<&str> = hashes.iter().copied().filter(|h| seen.insert(*h)).collect();
+        let present = self.exists_batch(&unique)?;
+        Ok(unique
+            .into_iter()
+            .zip(present)
+            .filter(|(_, exists)| !exists)
+            .map(|(hash, _)| hash.to_string())
+            .collect())
     }
 
     /// Validate that a hash string is properly formatted.
@@ -385,6 +774,25 @@ impl ChunkStore {
         Ok(())
     }
 
+    /// Run a filesystem operation, retrying transient errors per the retry policy.
+    fn retry_io<T>(&self, mut op: impl FnMut() -> io::Result<T>) -> io::Result<T> {
+        let mut attempt = 1;
+        loop {
+            match op() {
+                Err(e) if attempt < self.max_attempts && is_transient(&e) => {
+                    warn!(attempt, error = %e, "Transient filesystem error, retrying");
+                    std::thread::sleep(self.backoff * attempt);
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+
+    fn meta_path(&self, hash: &str) -> Result<PathBuf, ChunkStoreError> {
+        Ok(self.hash_to_path(hash)?.with_file_name(format!("{}.meta.json", hash)))
+    }
+
     fn hash_to_path(&self, hash: &str) -> Result<PathBuf, ChunkStoreError> {
         // For internal use after put(), we trust the hash is valid
         // For external use, validate_hash should be called first
@@ -401,6 +809,14 @@ impl ChunkStore {
     }
 }
 
+/// Error kinds worth retrying on networked filesystems (EINTR, EAGAIN, timeouts).
+fn is_transient(e: &io::Error) -> bool {
+    matches!(
+        e.kind(),
+        io::ErrorKind::Interrupted | io::ErrorKind::WouldBlock | io::ErrorKind::TimedOut
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -466,6 +882,17 @@ mod tests {
         fs::remove_dir_all(&dir).ok();
     }
 
+    #[test]
+    fn test_size() {
+        let dir = temp_dir();
+        let store = ChunkStore::new(&dir).unwrap();
+
+        let hash = store.put(b"test data").unwrap();
+        assert_eq!(store.size(&hash).unwrap(), 9);
+        assert!(matches!(store.size(&fake_valid_hash()), Err(ChunkStoreError::NotFound(_))));
+        fs::remove_dir_all(&dir).ok();
+    }
+
     #[test]
     fn test_delete() {
         let dir = temp_dir();
@@ -764,6 +1191,24 @@ mod tests {
         fs::remove_dir_all(&dir).ok();
     }
 
+    #[test]
+    fn test_get_batch_collect() {
+        let dir = temp_dir();
+        let store = ChunkStore::new(&dir).unwrap();
+
+        let hash1 = store.put(b"data one").unwrap();
+        let hash2 = store.put(b"data two").unwrap();
+        let fake_hash = fake_valid_hash();
+
+        let results = store.get_batch_collect(&[&hash1, &fake_hash, &hash2]);
+
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0].as_ref().unwrap(), b"data one");
+        assert!(matches!(&results[1], Err(ChunkStoreError::NotFound(h)) if *h == fake_hash));
+        assert_eq!(results[2].as_ref().unwrap(), b"data two");
+        fs::remove_dir_all(&dir).ok();
+    }
+
     #[test]
     fn test_get_batch_verified() {
         let dir = temp_dir();
@@ -987,4 +1432,281 @@ mod tests {
 
         fs::remove_dir_all(&dir).ok();
     }
+
+    #[test]
+    fn test_put_with_meta_roundtrip_and_dedup() {
+        let dir = temp_dir();
+        let store = ChunkStore::new(&dir).unwrap();
+
+        let meta = |name: &str| {
+            HashMap::from([
+                ("content-type".to_string(), "image/png".to_string()),
+                ("name".to_string(), name.to_string()),
+            ])
+        };
+
+        let hash1 = store.put_with_meta(b"pixels", meta("a.png")).unwrap();
+        assert_eq!(hash1, blake3::hash(b"pixels").to_hex().to_string());
+        assert_eq!(store.get_meta(&hash1).unwrap(), meta("a.png"));
+
+        // Same data, different metadata: one content chunk, latest sidecar wins
+        let hash2 = store.put_with_meta(b"pixels", meta("b.png")).unwrap();
+        assert_eq!(hash1, hash2);
+        assert_eq!(store.get(&hash1).unwrap(), b"pixels");
+        assert_eq!(store.get_meta(&hash1).unwrap(), meta("b.png"));
+        let chunk_dir = store.hash_to_path(&hash1).unwrap().parent().unwrap().to_path_buf();
+        assert_eq!(fs::read_dir(&chunk_dir).unwrap().count(), 2);
+
+        // Sidecars are neither temp files nor prefix matches
+        assert_eq!(store.cleanup_orphaned_temp_files(), (0, 0));
+        assert_eq!(store.resolve_prefix(&hash1[..8]).unwrap(), hash1);
+
+        // Plain chunks have no metadata; deleting a chunk drops its sidecar
+        let plain = store.put(b"plain").unwrap();
+        assert!(store.get_meta(&plain).unwrap().is_empty());
+        store.delete(&hash1).unwrap();
+        assert!(!store.meta_path(&hash1).unwrap().exists());
+        assert!(matches!(store.get_meta(&hash1), Err(ChunkStoreError::NotFound(_))));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_retry_transient_error_then_success() {
+        let dir = temp_dir();
+        let store = ChunkStore::new(&dir).unwrap().with_retry(3, Duration::ZERO);
+
+        // Fault injection: fail once with EINTR, then succeed
+        let mut calls = 0;
+        let result = store.retry_io(|| {
+            calls += 1;
+            if calls == 1 {
+                Err(io::Error::from(io::ErrorKind::Interrupted))
+            } else {
+                Ok(42)
+            }
+        });
+
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(calls, 2);
+
+        // Retry-enabled store still round-trips normally
+        let hash = store.put(b"retry data").unwrap();
+        assert_eq!(store.get(&hash).unwrap(), b"retry data");
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_retry_does_not_retry_permanent_errors() {
+        let dir = temp_dir();
+        let store = ChunkStore::new(&dir).unwrap().with_retry(5, Duration::ZERO);
+
+        let mut calls = 0;
+        let result: io::Result<()> = store.retry_io(|| {
+            calls += 1;
+            Err(io::Error::from(io::ErrorKind::NotFound))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+
+        // Gives up after max_attempts on persistent transient errors
+        let mut calls = 0;
+        let result: io::Result<()> = store.retry_io(|| {
+            calls += 1;
+            Err(io::Error::from(io::ErrorKind::WouldBlock))
+        });
+
+        assert!(result.is_err());
+        assert_eq!(calls, 5);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_short_hash_and_resolve_prefix() {
+        let dir = temp_dir();
+        let store = ChunkStore::new(&dir).unwrap();
+        assert_eq!(store.short_hash_len(), DEFAULT_SHORT_HASH_LEN);
+
+        let store = store.with_short_hash_len(10);
+        let hashes: Vec<String> = (0..20)
+            .map(|i| store.put(format!("chunk {}", i).as_bytes()).unwrap())
+            .collect();
+
+        for hash in &hashes {
+            let short = store.short_hash(hash);
+            assert_eq!(short.len(), 10);
+            assert!(hash.starts_with(&short));
+            assert_eq!(&store.resolve_prefix(&short).unwrap(), hash);
+        }
+
+        // Clamped to the resolvable range
+        assert_eq!(ChunkStore::new(&dir).unwrap().with_short_hash_len(1).short_hash_len(), 4);
+
+        assert!(matches!(store.resolve_prefix("ab"), Err(ChunkStoreError::InvalidHash(_))));
+        assert!(matches!(store.resolve_prefix("zzzz"), Err(ChunkStoreError::InvalidHash(_))));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_resolve_prefix_ambiguous_and_missing() {
+        let dir = temp_dir();
+        let store = ChunkStore::new(&dir).unwrap();
+
+        // Two chunks sharing a 4-char prefix
+        let chunk_dir = dir.join("ab").join("cd");
+        fs::create_dir_all(&chunk_dir).unwrap();
+        let first = format!("abcd{}", "0".repeat(60));
+        let second = format!("abcd{}", "1".repeat(60));
+        fs::write(chunk_dir.join(&first), b"x").unwrap();
+        fs::write(chunk_dir.join(&second), b"y").unwrap();
+
+        assert!(matches!(
+            store.resolve_prefix("abcd"),
+            Err(ChunkStoreError::AmbiguousPrefix { matches: 2, .. })
+        ));
+        assert_eq!(store.resolve_prefix("abcd1").unwrap(), second);
+        assert!(matches!(store.resolve_prefix("abcd2"), Err(ChunkStoreError::NotFound(_))));
+        assert!(matches!(store.resolve_prefix("ffff"), Err(ChunkStoreError::NotFound(_))));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_garbage_collect_removes_unreferenced_chunks() {
+        let dir = temp_dir();
+        let store = ChunkStore::new(&dir).unwrap();
+
+        let live = store.put(b"keep me").unwrap();
+        let dead = store.put(b"delete me").unwrap();
+        let dead_with_meta = store
+            .put_with_meta(b"me too", HashMap::from([("k".to_string(), "v".to_string())]))
+            .unwrap();
+        let tmp = dir.join("in-flight.tmp");
+        fs::write(&tmp, b"partial").unwrap();
+
+        let report = store.garbage_collect(&HashSet::from([live.clone()])).unwrap();
+        assert_eq!(
+            report,
+            GcReport { removed_count: 2, removed_bytes: 15, scanned_count: 3 }
+        );
+        assert_eq!(store.get(&live).unwrap(), b"keep me");
+        assert!(!store.exists(&dead).unwrap());
+        assert!(!store.exists(&dead_with_meta).unwrap());
+        assert!(!store.meta_path(&dead_with_meta).unwrap().exists());
+        assert!(tmp.exists());
+
+        // Nothing left to collect
+        let again = store.garbage_collect(&HashSet::from([live.clone()])).unwrap();
+        assert_eq!(again, GcReport { removed_count: 0, removed_bytes: 0, scanned_count: 1 });
+
+        // A sidecar whose chunk was removed behind the store's back is reaped
+        let stray = store
+            .put_with_meta(b"stray", HashMap::from([("k".to_string(), "v".to_string())]))
+            .unwrap();
+        fs::remove_file(store.hash_to_path(&stray).unwrap()).unwrap();
+        store.garbage_collect(&HashSet::from([live])).unwrap();
+        assert!(!store.meta_path(&stray).unwrap().exists());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_garbage_collect_prunes_empty_dirs() {
+        let dir = temp_dir();
+        let store = ChunkStore::new(&dir).unwrap();
+
+        let hash = store.put(b"short lived").unwrap();
+        let shard = store.hash_to_path(&hash).unwrap().parent().unwrap().to_path_buf();
+
+        store.garbage_collect(&HashSet::new()).unwrap();
+        assert!(shard.exists());
+
+        store
+            .garbage_collect_with_options(&HashSet::new(), true)
+            .unwrap();
+        assert!(!shard.exists());
+        assert!(!dir.join(&hash[0..2]).exists());
+        assert!(dir.exists());
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_missing_from() {
+        let dir = temp_dir();
+        let store = ChunkStore::new(&dir).unwrap();
+
+        let present = store.put(b"present").unwrap();
+        let absent_a = "a".repeat(EXPECTED_HASH_LEN);
+        let absent_b = "b".repeat(EXPECTED_HASH_LEN);
+
+        let missing = store
+            .missing_from(&[&absent_b, &present, &absent_a, &absent_b])
+            .unwrap();
+        assert_eq!(missing, vec![absent_b, absent_a]);
+        assert!(store.missing_from(&[&present]).unwrap().is_empty());
+        assert!(matches!(
+            store.missing_from(&["not-a-hash"]),
+            Err(ChunkStoreError::InvalidHash(_))
+        ));
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_put_reader_matches_put() {
+        let dir = temp_dir();
+        let store = ChunkStore::new(&dir).unwrap();
+
+        // Spans several blocks with a partial last block
+        let data: Vec<u8> = (0..STREAM_BLOCK_SIZE * 3 + 123).map(|i| (i % 251) as u8).collect();
+        let streamed = store.put_reader(std::io::Cursor::new(&data)).unwrap();
+        assert_eq!(streamed, blake3::hash(&data).to_hex().to_string());
+        assert_eq!(store.get_verified(&streamed).unwrap(), data);
+
+        // Same content again is deduplicated, and put agrees on the hash
+        assert_eq!(store.put_reader(&data[..]).unwrap(), streamed);
+        assert_eq!(store.put(&data).unwrap(), streamed);
+        assert_eq!(store.put_reader(std::io::empty()).unwrap(), store.put(b"").unwrap());
+
+        // No temp files left behind
+        assert_eq!(store.cleanup_orphaned_temp_files(), (0, 0));
+
+        let mut read_back = Vec::new();
+        store.get_reader(&streamed).unwrap().read_to_end(&mut read_back).unwrap();
+        assert_eq!(read_back, data);
+
+        fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_put_reader_failure_leaves_no_temp_file() {
+        struct FailingReader(usize);
+        impl Read for FailingReader {
+            fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+                if self.0 == 0 {
+                    return Err(io::Error::other("source went away"));
+                }
+                self.0 -= 1;
+                buf[0] = 1;
+                Ok(1)
+            }
+        }
+
+        let dir = temp_dir();
+        let store = ChunkStore::new(&dir).unwrap();
+
+        assert!(matches!(store.put_reader(FailingReader(3)), Err(ChunkStoreError::Io(_))));
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 0);
+        assert!(matches!(
+            store.get_reader(&fake_valid_hash()),
+            Err(ChunkStoreError::NotFound(_))
+        ));
+
+        fs::remove_dir_all(&dir).ok();
+    }
 }
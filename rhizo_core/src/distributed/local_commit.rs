@@ -93,9 +93,10 @@
 //! ```
 
 use super::vector_clock::{CausalOrder, NodeId, VectorClock};
-use crate::algebraic::{AlgebraicMerger, AlgebraicValue, MergeResult, OpType};
+use crate::algebraic::{AlgebraicMerger, AlgebraicSchemaRegistry, AlgebraicValue, MergeResult, OpType};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use tracing::warn;
 
 /// A single algebraic operation on a key.
 ///
@@ -242,6 +243,9 @@ pub struct VersionedUpdate {
     origin_node: NodeId,
     /// Optional update ID for deduplication
     update_id: Option<String>,
+    /// Application metadata carried from the source transaction
+    #[serde(default)]
+    metadata: HashMap<String, String>,
 }
 
 impl VersionedUpdate {
@@ -256,6 +260,7 @@ impl VersionedUpdate {
             clock,
             origin_node,
             update_id: None,
+            metadata: HashMap::new(),
         }
     }
 
@@ -271,9 +276,16 @@ impl VersionedUpdate {
             clock,
             origin_node,
             update_id: Some(update_id.into()),
+            metadata: HashMap::new(),
         }
     }
 
+    /// Attach application metadata to this update.
+    pub fn with_metadata(mut self, metadata: HashMap<String, String>) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
     /// Get the operations.
     #[inline]
     pub fn operations(&self) -> &[AlgebraicOperation] {
@@ -298,6 +310,42 @@ impl VersionedUpdate {
         self.update_id.as_deref()
     }
 
+    /// Get the application metadata.
+    #[inline]
+    pub fn metadata(&self) -> &HashMap<String, String> {
+        &self.metadata
+    }
+
+    /// Get a single metadata value.
+    pub fn get_metadata(&self, key: &str) -> Option<&str> {
+        self.metadata.get(key).map(|s| s.as_str())
+    }
+
+    /// Metadata keys present in both updates with different values, sorted.
+    ///
+    /// Merging keeps the greatest value for such keys; call this first if
+    /// the caller needs to know a value was dropped.
+    pub fn metadata_conflicts(&self, other: &VersionedUpdate) -> Vec<String> {
+        let mut keys: Vec<String> = self
+            .metadata
+            .iter()
+            .filter(|(k, v)| other.metadata.get(*k).is_some_and(|o| o != *v))
+            .map(|(k, _)| k.clone())
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    /// Serialize to bytes for transport between nodes.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, LocalCommitError> {
+        serde_json::to_vec(self).map_err(|e| LocalCommitError::Serialization(e.to_string()))
+    }
+
+    /// Deserialize from bytes produced by `to_bytes`.
+    pub fn from_bytes(data: &[u8]) -> Result<Self, LocalCommitError> {
+        serde_json::from_slice(data).map_err(|e| LocalCommitError::Serialization(e.to_string()))
+    }
+
     /// Compare this update's causality with another.
     pub fn compare(&self, other: &VersionedUpdate) -> CausalOrder {
         self.clock.compare(&other.clock)
@@ -309,6 +357,17 @@ impl VersionedUpdate {
     }
 }
 
+/// A key whose conflicting op types were repaired during a merge.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RepairEvent {
+    /// The repaired key
+    pub key: String,
+    /// Op type from the schema registry, used for the re-merge
+    pub registered: OpType,
+    /// Distinct op types found on the incoming operations
+    pub observed: Vec<OpType>,
+}
+
 /// Error type for local commit operations.
 #[derive(Debug, Clone, PartialEq)]
 pub enum LocalCommitError {
@@ -330,6 +389,13 @@ pub enum LocalCommitError {
         type1: &'static str,
         type2: &'static str,
     },
+    /// Operation types disagree with the registered schema.
+    SchemaMismatch {
+        table: String,
+        keys: Vec<String>,
+    },
+    /// Update could not be serialized or deserialized.
+    Serialization(String),
 }
 
 impl std::fmt::Display for LocalCommitError {
@@ -345,6 +411,10 @@ impl std::fmt::Display for LocalCommitError {
             Self::TypeMismatch { key, type1, type2 } => {
                 write!(f, "Type mismatch for key '{}': {} vs {}", key, type1, type2)
             }
+            Self::SchemaMismatch { table, keys } => {
+                write!(f, "Operation types on keys {:?} do not match schema for table '{}'", keys, table)
+            }
+            Self::Serialization(msg) => write!(f, "Serialization error: {}", msg),
         }
     }
 }
@@ -464,7 +534,57 @@ impl LocalCommitProtocol {
             tx.operations.clone(),
             clock.clone(),
             node_id.clone(),
-        ))
+        )
+        .with_metadata(tx.metadata.clone()))
+    }
+
+    /// Commit locally after checking op types against a schema registry.
+    ///
+    /// Each operation's key is treated as a column of `table`, and its op
+    /// type must equal the type registered for that column. Mixing, say,
+    /// `AbelianAdd` and `SemilatticeMax` on one key across transactions
+    /// breaks convergence, so such commits are rejected up front.
+    ///
+    /// Keys whose registered type is `Unknown` (unregistered table, or an
+    /// unannotated column with an `Unknown` default) pass unless `strict`
+    /// is set, in which case they are rejected too. Keys naming a custom
+    /// column always pass: its merge function, not an op type, is its rule.
+    ///
+    /// # Errors
+    /// `SchemaMismatch` naming the offending keys, or any error from
+    /// `commit_local`. The clock is not advanced on failure.
+    pub fn commit_local_with_schema(
+        tx: &AlgebraicTransaction,
+        node_id: &NodeId,
+        clock: &mut VectorClock,
+        registry: &AlgebraicSchemaRegistry,
+        table: &str,
+        strict: bool,
+    ) -> Result<VersionedUpdate, LocalCommitError> {
+        let mut keys: Vec<String> = Vec::new();
+        for op in &tx.operations {
+            if registry.get_custom(table, &op.key).is_some() {
+                continue;
+            }
+            let expected = registry.get_op_type(table, &op.key);
+            let mismatch = if expected == OpType::Unknown {
+                strict
+            } else {
+                op.op_type.merge_family() != expected.merge_family()
+            };
+            if mismatch && !keys.contains(&op.key) {
+                keys.push(op.key.clone());
+            }
+        }
+
+        if !keys.is_empty() {
+            return Err(LocalCommitError::SchemaMismatch {
+                table: table.to_string(),
+                keys,
+            });
+        }
+
+        Self::commit_local(tx, node_id, clock)
     }
 
     /// Merge two versioned updates into one.
@@ -474,6 +594,11 @@ impl LocalCommitProtocol {
     /// 1. Combines operations by key
     /// 2. Merges values using algebraic operations
     /// 3. Computes the merged vector clock
+    /// 4. Takes the union of both updates' metadata
+    ///
+    /// A metadata key set to different values on each side keeps the
+    /// greatest value, so the merge stays commutative; such keys are
+    /// logged and can be listed with `VersionedUpdate::metadata_conflicts`.
     ///
     /// # Mathematical Guarantees
     ///
@@ -528,20 +653,10 @@ impl LocalCommitProtocol {
         update1: &VersionedUpdate,
         update2: &VersionedUpdate,
     ) -> Result<VersionedUpdate, LocalCommitError> {
-        // Group operations by key
-        let mut by_key: HashMap<String, Vec<&AlgebraicOperation>> = HashMap::new();
-
-        for op in update1.operations() {
-            by_key.entry(op.key().to_string()).or_default().push(op);
-        }
-        for op in update2.operations() {
-            by_key.entry(op.key().to_string()).or_default().push(op);
-        }
-
         // Merge operations for each key
         let mut merged_ops = Vec::new();
 
-        for (key, ops) in by_key {
+        for (key, ops) in Self::group_by_key(update1, update2) {
             if ops.len() == 1 {
                 // No merge needed, just include the operation
                 merged_ops.push(ops[0].clone());
@@ -552,6 +667,94 @@ impl LocalCommitProtocol {
             }
         }
 
+        Ok(Self::merged_update(merged_ops, update1, update2))
+    }
+
+    /// Merge two updates, repairing keys whose op types disagree.
+    ///
+    /// Where `merge_updates` would fail because one node used a different
+    /// op type for a key, this looks up the type registered for that key as
+    /// a column of `table`, re-types every operation on the key to it, and
+    /// merges again. Each repair is logged and returned as a `RepairEvent`
+    /// (sorted by key), so one misconfigured node doesn't halt convergence.
+    ///
+    /// Keys with no registered op type, including custom columns, cannot be
+    /// repaired and still fail with `MergeFailed`.
+    pub fn merge_updates_repair(
+        update1: &VersionedUpdate,
+        update2: &VersionedUpdate,
+        registry: &AlgebraicSchemaRegistry,
+        table: &str,
+    ) -> Result<(VersionedUpdate, Vec<RepairEvent>), LocalCommitError> {
+        let mut merged_ops = Vec::new();
+        let mut repairs = Vec::new();
+
+        for (key, ops) in Self::group_by_key(update1, update2) {
+            if ops.len() == 1 {
+                merged_ops.push(ops[0].clone());
+                continue;
+            }
+
+            let mut observed: Vec<OpType> = Vec::new();
+            for op in &ops {
+                if !observed.contains(&op.op_type()) {
+                    observed.push(op.op_type());
+                }
+            }
+
+            let registered = registry.get_op_type(table, &key);
+            if observed.len() == 1 || registered == OpType::Unknown {
+                merged_ops.push(Self::merge_operations_for_key(&key, &ops)?);
+                continue;
+            }
+
+            warn!(
+                key = %key,
+                registered = %registered,
+                observed = ?observed,
+                "repairing conflicting op types with registered type"
+            );
+            let retyped: Vec<AlgebraicOperation> = ops
+                .iter()
+                .map(|op| AlgebraicOperation::new(key.as_str(), registered, op.value().clone()))
+                .collect();
+            let retyped_refs: Vec<&AlgebraicOperation> = retyped.iter().collect();
+            merged_ops.push(Self::merge_operations_for_key(&key, &retyped_refs)?);
+
+            repairs.push(RepairEvent {
+                key,
+                registered,
+                observed,
+            });
+        }
+
+        repairs.sort_by(|a, b| a.key.cmp(&b.key));
+        Ok((Self::merged_update(merged_ops, update1, update2), repairs))
+    }
+
+    /// Group both updates' operations by key.
+    fn group_by_key<'a>(
+        update1: &'a VersionedUpdate,
+        update2: &'a VersionedUpdate,
+    ) -> HashMap<String, Vec<&'a AlgebraicOperation>> {
+        let mut by_key: HashMap<String, Vec<&AlgebraicOperation>> = HashMap::new();
+
+        for op in update1.operations() {
+            by_key.entry(op.key().to_string()).or_default().push(op);
+        }
+        for op in update2.operations() {
+            by_key.entry(op.key().to_string()).or_default().push(op);
+        }
+
+        by_key
+    }
+
+    /// Wrap merged operations with the combined clock and a synthetic origin.
+    fn merged_update(
+        merged_ops: Vec<AlgebraicOperation>,
+        update1: &VersionedUpdate,
+        update2: &VersionedUpdate,
+    ) -> VersionedUpdate {
         // Merge the vector clocks
         let merged_clock = VectorClock::max(update1.clock(), update2.clock());
 
@@ -563,7 +766,34 @@ impl LocalCommitProtocol {
             update2.origin_node()
         ));
 
-        Ok(VersionedUpdate::new(merged_ops, merged_clock, origin))
+        VersionedUpdate::new(merged_ops, merged_clock, origin)
+            .with_metadata(Self::merge_metadata(&[update1, update2]))
+    }
+
+    /// Union the updates' metadata, keeping the greatest value on conflict.
+    fn merge_metadata(updates: &[&VersionedUpdate]) -> HashMap<String, String> {
+        let mut merged: HashMap<String, String> = HashMap::new();
+        for update in updates {
+            for (key, value) in update.metadata() {
+                match merged.get_mut(key) {
+                    Some(existing) if existing != value => {
+                        warn!(
+                            key = %key,
+                            kept = %existing.as_str().max(value.as_str()),
+                            "conflicting metadata values in merge"
+                        );
+                        if value > existing {
+                            *existing = value.clone();
+                        }
+                    }
+                    Some(_) => {}
+                    None => {
+                        merged.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        merged
     }
 
     /// Merge multiple operations on the same key.
@@ -584,7 +814,7 @@ impl LocalCommitProtocol {
 
         // Verify all operations have the same op_type
         for op in ops.iter().skip(1) {
-            if op.op_type() != op_type {
+            if op.op_type().merge_family() != op_type.merge_family() {
                 return Err(LocalCommitError::MergeFailed {
                     key: key.to_string(),
                     reason: format!(
@@ -671,7 +901,40 @@ impl LocalCommitProtocol {
         let origins: Vec<String> = updates.iter().map(|u| u.origin_node().to_string()).collect();
         let origin = NodeId::new(format!("merged:{}", origins.join("+")));
 
-        Ok(VersionedUpdate::new(merged_ops, merged_clock, origin))
+        let all: Vec<&VersionedUpdate> = updates.iter().collect();
+        Ok(VersionedUpdate::new(merged_ops, merged_clock, origin)
+            .with_metadata(Self::merge_metadata(&all)))
+    }
+
+    /// Fold a node's applied updates into one snapshot update.
+    ///
+    /// Every key gets a single operation holding its merged value, and the
+    /// snapshot carries `node_clock`, so a new node can bootstrap by applying
+    /// it instead of replaying history. Operations are sorted by key; with no
+    /// updates the snapshot is empty.
+    ///
+    /// # Errors
+    /// Fails like `merge_all` if a key's operations cannot be merged.
+    pub fn state_snapshot(
+        applied: &[VersionedUpdate],
+        node_clock: &VectorClock,
+    ) -> Result<VersionedUpdate, LocalCommitError> {
+        let mut by_key: HashMap<&str, Vec<&AlgebraicOperation>> = HashMap::new();
+        for update in applied {
+            for op in update.operations() {
+                by_key.entry(op.key()).or_default().push(op);
+            }
+        }
+
+        let mut keys: Vec<&str> = by_key.keys().copied().collect();
+        keys.sort_unstable();
+
+        let mut ops = Vec::with_capacity(keys.len());
+        for key in keys {
+            ops.push(Self::merge_operations_for_key(key, &by_key[key])?);
+        }
+
+        Ok(VersionedUpdate::new(ops, node_clock.clone(), NodeId::new("snapshot")))
     }
 }
 
@@ -852,6 +1115,55 @@ mod tests {
         assert!(matches!(result, Err(LocalCommitError::EmptyTransaction)));
     }
 
+    #[test]
+    fn test_commit_local_with_schema() {
+        use crate::algebraic::TableAlgebraicSchema;
+
+        let node = NodeId::new("node-1");
+        let mut clock = VectorClock::new();
+
+        let mut schema = TableAlgebraicSchema::new("stats");
+        schema.add_column("views", OpType::AbelianAdd);
+        let mut registry = AlgebraicSchemaRegistry::new();
+        registry.register(schema);
+
+        let mut tx = AlgebraicTransaction::new();
+        tx.add_operation(add_op("views", 1));
+        LocalCommitProtocol::commit_local_with_schema(&tx, &node, &mut clock, &registry, "stats", false)
+            .unwrap();
+        assert_eq!(clock.get(&node), 1);
+
+        let mut tx = AlgebraicTransaction::new();
+        tx.add_operation(max_op("views", 7));
+        let result =
+            LocalCommitProtocol::commit_local_with_schema(&tx, &node, &mut clock, &registry, "stats", false);
+        assert_eq!(
+            result.unwrap_err(),
+            LocalCommitError::SchemaMismatch {
+                table: "stats".to_string(),
+                keys: vec!["views".to_string()],
+            }
+        );
+        assert_eq!(clock.get(&node), 1);
+
+        // Unannotated key passes unless strict
+        let mut tx = AlgebraicTransaction::new();
+        tx.add_operation(max_op("last_seen", 7));
+        assert!(LocalCommitProtocol::commit_local_with_schema(&tx, &node, &mut clock, &registry, "stats", false)
+            .is_ok());
+        let result =
+            LocalCommitProtocol::commit_local_with_schema(&tx, &node, &mut clock, &registry, "stats", true);
+        assert!(matches!(result, Err(LocalCommitError::SchemaMismatch { .. })));
+
+        // A custom column passes whatever op type it is written with, even
+        // in a table whose default would reject it
+        let mut schema = TableAlgebraicSchema::all_additive("stats");
+        schema.add_custom_column("last_seen", Box::new(|a, b| AlgebraicMerger::merge(OpType::SemilatticeMax, a, b)));
+        registry.register(schema);
+        assert!(LocalCommitProtocol::commit_local_with_schema(&tx, &node, &mut clock, &registry, "stats", true)
+            .is_ok());
+    }
+
     // ============ merge_updates Tests ============
 
     #[test]
@@ -960,6 +1272,66 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_merge_updates_repair() {
+        use crate::algebraic::TableAlgebraicSchema;
+
+        let node_a = NodeId::new("a");
+        let node_b = NodeId::new("b");
+        let mut clock_a = VectorClock::new();
+        let mut clock_b = VectorClock::new();
+
+        let mut registry = AlgebraicSchemaRegistry::new();
+        registry.register(TableAlgebraicSchema::all_additive("stats"));
+
+        // Node b misclassifies "views" as max
+        let mut tx_a = AlgebraicTransaction::new();
+        tx_a.add_operation(add_op("views", 5));
+        tx_a.add_operation(add_op("clicks", 1));
+        let mut tx_b = AlgebraicTransaction::new();
+        tx_b.add_operation(max_op("views", 3));
+        let update_a = LocalCommitProtocol::commit_local(&tx_a, &node_a, &mut clock_a).unwrap();
+        let update_b = LocalCommitProtocol::commit_local(&tx_b, &node_b, &mut clock_b).unwrap();
+
+        assert!(LocalCommitProtocol::merge_updates(&update_a, &update_b).is_err());
+
+        let (merged, repairs) =
+            LocalCommitProtocol::merge_updates_repair(&update_a, &update_b, &registry, "stats").unwrap();
+        assert_eq!(
+            repairs,
+            vec![RepairEvent {
+                key: "views".to_string(),
+                registered: OpType::AbelianAdd,
+                observed: vec![OpType::AbelianAdd, OpType::SemilatticeMax],
+            }]
+        );
+
+        // Same result as if node b had used the registered type
+        let mut tx_b_correct = AlgebraicTransaction::new();
+        tx_b_correct.add_operation(add_op("views", 3));
+        let update_b_correct =
+            LocalCommitProtocol::commit_local(&tx_b_correct, &node_b, &mut VectorClock::new()).unwrap();
+        let expected = LocalCommitProtocol::merge_updates(&update_a, &update_b_correct).unwrap();
+
+        let value_of = |u: &VersionedUpdate, key: &str| {
+            u.operations().iter().find(|op| op.key() == key).map(|op| (op.op_type(), op.value().clone()))
+        };
+        assert_eq!(value_of(&merged, "views"), value_of(&expected, "views"));
+        assert_eq!(value_of(&merged, "views").unwrap().1, AlgebraicValue::integer(8));
+        assert_eq!(value_of(&merged, "clicks"), value_of(&expected, "clicks"));
+
+        // Unregistered table: nothing to repair with
+        let result = LocalCommitProtocol::merge_updates_repair(&update_a, &update_b, &registry, "other");
+        assert!(matches!(result, Err(LocalCommitError::MergeFailed { .. })));
+
+        // A custom column is not re-typed to the table default
+        let mut stats = TableAlgebraicSchema::all_additive("stats");
+        stats.add_custom_column("views", Box::new(|a, b| AlgebraicMerger::merge(OpType::SemilatticeMax, a, b)));
+        registry.register(stats);
+        let result = LocalCommitProtocol::merge_updates_repair(&update_a, &update_b, &registry, "stats");
+        assert!(matches!(result, Err(LocalCommitError::MergeFailed { .. })));
+    }
+
     // ============ CRITICAL: Commutativity Tests ============
 
     #[test]
@@ -1273,6 +1645,55 @@ mod tests {
         assert_eq!(update.operations().len(), parsed.operations().len());
     }
 
+    #[test]
+    fn test_versioned_update_metadata() {
+        let node_a = NodeId::new("a");
+        let node_b = NodeId::new("b");
+        let mut clock_a = VectorClock::new();
+        let mut clock_b = VectorClock::new();
+
+        let mut tx_a = AlgebraicTransaction::new();
+        tx_a.add_operation(add_op("counter", 1));
+        tx_a.set_metadata("trace", "t-1");
+        tx_a.set_metadata("region", "us");
+
+        let mut tx_b = AlgebraicTransaction::new();
+        tx_b.add_operation(add_op("counter", 2));
+        tx_b.set_metadata("user", "bob");
+        tx_b.set_metadata("region", "eu");
+
+        let update_a = LocalCommitProtocol::commit_local(&tx_a, &node_a, &mut clock_a).unwrap();
+        let update_b = LocalCommitProtocol::commit_local(&tx_b, &node_b, &mut clock_b).unwrap();
+        assert_eq!(update_a.get_metadata("trace"), Some("t-1"));
+
+        // Metadata survives a byte round trip
+        let parsed = VersionedUpdate::from_bytes(&update_a.to_bytes().unwrap()).unwrap();
+        assert_eq!(parsed.metadata(), update_a.metadata());
+        assert!(matches!(
+            VersionedUpdate::from_bytes(b"not json"),
+            Err(LocalCommitError::Serialization(_))
+        ));
+
+        assert_eq!(update_a.metadata_conflicts(&update_b), vec!["region".to_string()]);
+
+        let merged_ab = LocalCommitProtocol::merge_updates(&update_a, &update_b).unwrap();
+        let merged_ba = LocalCommitProtocol::merge_updates(&update_b, &update_a).unwrap();
+        assert_eq!(merged_ab.get_metadata("trace"), Some("t-1"));
+        assert_eq!(merged_ab.get_metadata("user"), Some("bob"));
+        assert_eq!(merged_ab.get_metadata("region"), Some("us"));
+        assert_eq!(merged_ab.metadata(), merged_ba.metadata());
+
+        let merged_all = LocalCommitProtocol::merge_all(&[update_a, update_b]).unwrap();
+        assert_eq!(merged_all.metadata(), merged_ab.metadata());
+    }
+
+    #[test]
+    fn test_versioned_update_without_metadata_deserializes() {
+        let json = r#"{"operations":[],"clock":{"clocks":{}},"origin_node":"n","update_id":null}"#;
+        let parsed: VersionedUpdate = serde_json::from_str(json).unwrap();
+        assert!(parsed.metadata().is_empty());
+    }
+
     // ============ Complex Scenario Tests ============
 
     #[test]
@@ -1380,4 +1801,46 @@ mod tests {
         assert_eq!(get_counter(&merge_reverse), Some(150));
         assert_eq!(get_counter(&merge_all_result), Some(150));
     }
+
+    #[test]
+    fn test_state_snapshot_bootstraps_new_node() {
+        use crate::distributed::simulation::SimulatedNode;
+
+        let nodes: Vec<NodeId> = (0..3).map(|i| NodeId::new(format!("node-{}", i))).collect();
+        let mut clocks: Vec<VectorClock> = (0..3).map(|_| VectorClock::new()).collect();
+
+        let mut updates = Vec::new();
+        for round in 0..4i64 {
+            for (i, (node, clock)) in nodes.iter().zip(clocks.iter_mut()).enumerate() {
+                let mut tx = AlgebraicTransaction::new();
+                tx.add_operation(add_op("total", round * 10 + i as i64));
+                tx.add_operation(max_op("high_water", (round * 7 + i as i64 * 3) % 11));
+                tx.add_operation(union_op("seen", &[node.as_str()]));
+                updates.push(LocalCommitProtocol::commit_local(&tx, node, clock).unwrap());
+            }
+        }
+
+        // Peer that replayed every update
+        let mut peer = SimulatedNode::new(0);
+        for update in &updates {
+            peer.apply_update(update);
+        }
+        let mut peer_clock = VectorClock::new();
+        peer_clock.merge_all(&clocks.iter().collect::<Vec<_>>());
+
+        let snapshot = LocalCommitProtocol::state_snapshot(&updates, &peer_clock).unwrap();
+        assert_eq!(snapshot.operations().len(), 3);
+        assert_eq!(snapshot.clock(), &peer_clock);
+
+        // A new node applies only the snapshot
+        let mut fresh = SimulatedNode::new(1);
+        fresh.apply_update(&snapshot);
+        for key in ["total", "high_water", "seen"] {
+            assert_eq!(fresh.get_state(key), peer.get_state(key), "key {}", key);
+        }
+        assert_eq!(fresh.get_state("total").unwrap().as_integer(), Some(192));
+
+        let empty = LocalCommitProtocol::state_snapshot(&[], &VectorClock::new()).unwrap();
+        assert!(empty.operations().is_empty());
+    }
 }
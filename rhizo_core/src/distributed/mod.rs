@@ -112,10 +112,10 @@ mod vector_clock;
 
 pub use local_commit::{
     AlgebraicOperation, AlgebraicTransaction, LocalCommitError, LocalCommitProtocol,
-    VersionedUpdate,
+    RepairEvent, VersionedUpdate,
 };
 pub use simulation::{
-    Message, NetworkCondition, SimulatedCluster, SimulatedNode, SimulationBuilder,
+    ClusterSnapshot, ConnectivityReport, DeliveryPriority, KeyDivergence, Message, NetworkCondition, SimulatedCluster, SimulatedNode, SimulationBuilder,
     SimulationConfig, SimulationStats,
 };
 pub use vector_clock::{CausalOrder, NodeId, VectorClock};
@@ -66,6 +66,14 @@ pub struct SimulationConfig {
     pub randomize_order: bool,
     /// Simulated network partition (node pairs that can't communicate)
     pub partitions: Vec<(usize, usize)>,
+    /// Directed link conditions keyed by `(from, to)`; unlisted links are
+    /// `NetworkCondition::Perfect`
+    pub link_conditions: HashMap<(usize, usize), NetworkCondition>,
+    /// Order in which pending messages are delivered within a round
+    pub delivery_priority: DeliveryPriority,
+    /// Maximum messages delivered per round (None = unlimited).
+    /// Undelivered messages carry over to the next round.
+    pub delivery_budget: Option<usize>,
 }
 
 impl Default for SimulationConfig {
@@ -74,10 +82,32 @@ impl Default for SimulationConfig {
             max_rounds: 100,
             randomize_order: false,
             partitions: Vec::new(),
+            link_conditions: HashMap::new(),
+            delivery_priority: DeliveryPriority::Fifo,
+            delivery_budget: None,
         }
     }
 }
 
+/// Delivery ordering for messages within a round.
+///
+/// Ordering only changes outcomes when a `delivery_budget` limits how many
+/// messages get through per round; otherwise every message is delivered
+/// in the same round regardless of order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DeliveryPriority {
+    /// Deliver in send order
+    #[default]
+    Fifo,
+    /// Messages from nodes with larger outboxes (more recent activity)
+    /// deliver first
+    OutboxSize,
+    /// Deliver by each message's `priority` field, highest first. The
+    /// priority is set per update with `commit_on_node_with_priority`
+    /// (default 0) and kept when the update is gossiped onwards.
+    PerMessage,
+}
+
 /// Network condition for message delivery.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NetworkCondition {
@@ -91,6 +121,24 @@ pub enum NetworkCondition {
     Partitioned,
 }
 
+/// Links that cannot carry messages in one or both directions.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConnectivityReport {
+    /// Node pairs `(a, b)`, `a < b`, blocked in both directions
+    pub blocked: Vec<(usize, usize)>,
+    /// Directed links `(from, to)` that deliver while `to -> from` is blocked
+    pub one_way: Vec<(usize, usize)>,
+}
+
+/// A key whose value differs between nodes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyDivergence {
+    /// The diverging key
+    pub key: String,
+    /// Value on each node by index (None if the node lacks the key)
+    pub values: Vec<Option<AlgebraicValue>>,
+}
+
 /// A message being sent between nodes.
 #[derive(Debug, Clone)]
 pub struct Message {
@@ -102,6 +150,8 @@ pub struct Message {
     pub update: VersionedUpdate,
     /// Delivery delay (rounds remaining)
     pub delay: usize,
+    /// Delivery priority (higher delivers first under `DeliveryPriority::PerMessage`)
+    pub priority: usize,
 }
 
 /// A simulated node in the cluster.
@@ -121,12 +171,22 @@ pub struct SimulatedNode {
     pub applied_updates: HashSet<String>,
     /// Pending updates to send to other nodes
     pub outbox: VecDeque<VersionedUpdate>,
+    /// Keys that received an operation whose type differs from the key's
+    /// established op type (the operation is not applied)
+    pub op_type_conflicts: HashSet<String>,
+    /// Delivery priority of updates this node sends, by update id
+    /// (updates not listed have priority 0)
+    pub update_priorities: HashMap<String, usize>,
 }
 
 impl SimulatedNode {
     /// Create a new simulated node.
     pub fn new(index: usize) -> Self {
-        let node_id = NodeId::new(format!("node-{}", index));
+        Self::with_id(index, NodeId::new(format!("node-{}", index)))
+    }
+
+    /// Create a new simulated node with an explicit id.
+    pub fn with_id(index: usize, node_id: NodeId) -> Self {
         Self {
             node_id,
             index,
@@ -135,6 +195,8 @@ impl SimulatedNode {
             local_updates: Vec::new(),
             applied_updates: HashSet::new(),
             outbox: VecDeque::new(),
+            op_type_conflicts: HashSet::new(),
+            update_priorities: HashMap::new(),
         }
     }
 
@@ -163,16 +225,18 @@ impl SimulatedNode {
 
             if let Some((existing_op_type, existing_value)) = self.state.get(&key) {
                 // Merge with existing value
-                if *existing_op_type == op.op_type() {
+                if *existing_op_type == op.op_type().merge_family() {
                     let merge_result =
                         AlgebraicMerger::merge(op.op_type(), existing_value, op.value());
                     if let MergeResult::Merged(merged_value) = merge_result {
-                        self.state.insert(key, (op.op_type(), merged_value));
+                        self.state.insert(key, (op.op_type().merge_family(), merged_value));
                     }
+                } else {
+                    self.op_type_conflicts.insert(key);
                 }
             } else {
                 // First value for this key
-                self.state.insert(key, (op.op_type(), op.value().clone()));
+                self.state.insert(key, (op.op_type().merge_family(), op.value().clone()));
             }
         }
 
@@ -199,6 +263,14 @@ impl SimulatedNode {
         true
     }
 
+    /// Delivery priority this node sends `update` with.
+    pub fn priority_of(&self, update: &VersionedUpdate) -> usize {
+        self.update_priorities
+            .get(&self.generate_update_id(update))
+            .copied()
+            .unwrap_or(0)
+    }
+
     /// Generate a unique ID for an update (for deduplication).
     fn generate_update_id(&self, update: &VersionedUpdate) -> String {
         format!("{}:{}", update.origin_node(), update.clock().sum())
@@ -213,6 +285,14 @@ impl SimulatedNode {
     pub fn keys(&self) -> Vec<String> {
         self.state.keys().cloned().collect()
     }
+
+    /// Get the effective op type of each key in state.
+    pub fn key_optypes(&self) -> HashMap<String, OpType> {
+        self.state
+            .iter()
+            .map(|(k, (op_type, _))| (k.clone(), *op_type))
+            .collect()
+    }
 }
 
 /// A simulated cluster of nodes.
@@ -230,6 +310,32 @@ pub struct SimulatedCluster {
     pub stats: SimulationStats,
 }
 
+/// A point-in-time copy of a cluster.
+///
+/// Holds node states, clocks, outboxes, in-flight messages, configuration,
+/// round and stats, so a restored cluster continues exactly where the
+/// original was when the snapshot was taken.
+#[derive(Debug, Clone)]
+pub struct ClusterSnapshot {
+    nodes: Vec<SimulatedNode>,
+    messages: VecDeque<Message>,
+    config: SimulationConfig,
+    round: usize,
+    stats: SimulationStats,
+}
+
+impl ClusterSnapshot {
+    /// Number of nodes captured.
+    pub fn num_nodes(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Simulation round at the time of the snapshot.
+    pub fn round(&self) -> usize {
+        self.round
+    }
+}
+
 /// Statistics from the simulation.
 #[derive(Debug, Default, Clone)]
 pub struct SimulationStats {
@@ -270,11 +376,54 @@ impl SimulatedCluster {
         }
     }
 
+    /// Create a cluster whose nodes use the given ids, in order.
+    pub fn with_node_ids(ids: Vec<NodeId>) -> Self {
+        let nodes = ids
+            .into_iter()
+            .enumerate()
+            .map(|(index, id)| SimulatedNode::with_id(index, id))
+            .collect();
+        Self {
+            nodes,
+            messages: VecDeque::new(),
+            config: SimulationConfig::default(),
+            round: 0,
+            stats: SimulationStats::default(),
+        }
+    }
+
+    /// Capture the full cluster state.
+    pub fn snapshot(&self) -> ClusterSnapshot {
+        ClusterSnapshot {
+            nodes: self.nodes.clone(),
+            messages: self.messages.clone(),
+            config: self.config.clone(),
+            round: self.round,
+            stats: self.stats.clone(),
+        }
+    }
+
+    /// Rebuild a cluster from a snapshot.
+    pub fn restore(snapshot: ClusterSnapshot) -> Self {
+        Self {
+            nodes: snapshot.nodes,
+            messages: snapshot.messages,
+            config: snapshot.config,
+            round: snapshot.round,
+            stats: snapshot.stats,
+        }
+    }
+
     /// Get the number of nodes.
     pub fn num_nodes(&self) -> usize {
         self.nodes.len()
     }
 
+    /// Get the id of a specific node (None if the index is out of range).
+    pub fn node_id(&self, node_index: usize) -> Option<&NodeId> {
+        self.nodes.get(node_index).map(|node| &node.node_id)
+    }
+
     /// Commit a transaction on a specific node.
     pub fn commit_on_node(
         &mut self,
@@ -286,19 +435,48 @@ impl SimulatedCluster {
         Ok(update)
     }
 
+    /// Commit a transaction whose messages carry `priority`.
+    ///
+    /// Under `DeliveryPriority::PerMessage`, higher-priority messages are
+    /// delivered first; nodes that gossip the update on keep its priority.
+    pub fn commit_on_node_with_priority(
+        &mut self,
+        node_index: usize,
+        tx: AlgebraicTransaction,
+        priority: usize,
+    ) -> Result<VersionedUpdate, LocalCommitError> {
+        let update = self.commit_on_node(node_index, tx)?;
+        let node = &mut self.nodes[node_index];
+        let update_id = node.generate_update_id(&update);
+        node.update_priorities.insert(update_id, priority);
+        Ok(update)
+    }
+
     /// Broadcast updates from all nodes to all other nodes.
     pub fn broadcast_all(&mut self) {
         let num_nodes = self.nodes.len();
 
         for from in 0..num_nodes {
+            let outbox_size = self.nodes[from].outbox.len();
             while let Some(update) = self.nodes[from].outbox.pop_front() {
+                let priority = match self.config.delivery_priority {
+                    DeliveryPriority::OutboxSize => outbox_size,
+                    DeliveryPriority::Fifo | DeliveryPriority::PerMessage => {
+                        self.nodes[from].priority_of(&update)
+                    }
+                };
                 for to in 0..num_nodes {
                     if from != to && !self.is_partitioned(from, to) {
+                        let delay = match self.link_condition(from, to) {
+                            NetworkCondition::Delayed(rounds) => rounds,
+                            _ => 0,
+                        };
                         self.messages.push_back(Message {
                             from,
                             to,
                             update: update.clone(),
-                            delay: 0,
+                            delay,
+                            priority,
                         });
                         self.stats.messages_sent += 1;
                     }
@@ -307,13 +485,37 @@ impl SimulatedCluster {
         }
     }
 
-    /// Deliver all pending messages.
+    /// Deliver pending messages.
+    ///
+    /// Messages still delayed wait another round. The rest are ordered by
+    /// `config.delivery_priority` and at most `config.delivery_budget` are
+    /// delivered; the others stay queued.
     pub fn deliver_messages(&mut self) {
-        let messages: Vec<Message> = self.messages.drain(..).collect();
+        let (mut messages, delayed): (Vec<Message>, Vec<Message>) =
+            self.messages.drain(..).partition(|m| m.delay == 0);
+        for mut msg in delayed {
+            msg.delay -= 1;
+            self.messages.push_back(msg);
+        }
+
+        if self.config.delivery_priority != DeliveryPriority::Fifo {
+            // Stable sort keeps send order among equal priorities
+            messages.sort_by_key(|m| std::cmp::Reverse(m.priority));
+        }
+
+        if let Some(budget) = self.config.delivery_budget {
+            if messages.len() > budget {
+                self.messages.extend(messages.drain(budget..));
+            }
+        }
 
         for msg in messages {
             if !self.is_partitioned(msg.from, msg.to) {
-                self.nodes[msg.to].receive_update(&msg.update);
+                let node = &mut self.nodes[msg.to];
+                if node.receive_update(&msg.update) && msg.priority > 0 {
+                    let update_id = node.generate_update_id(&msg.update);
+                    node.update_priorities.insert(update_id, msg.priority);
+                }
                 self.stats.messages_delivered += 1;
             } else {
                 self.stats.messages_dropped += 1;
@@ -331,7 +533,8 @@ impl SimulatedCluster {
     /// Propagate until convergence or max rounds.
     pub fn propagate_all(&mut self) {
         for _ in 0..self.config.max_rounds {
-            let had_messages = !self.nodes.iter().all(|n| n.outbox.is_empty());
+            let had_messages = !self.messages.is_empty()
+                || !self.nodes.iter().all(|n| n.outbox.is_empty());
 
             self.propagate_round();
 
@@ -342,12 +545,43 @@ impl SimulatedCluster {
         }
     }
 
-    /// Check if two nodes are partitioned.
+    /// Check if messages from `from` to `to` are dropped.
     fn is_partitioned(&self, from: usize, to: usize) -> bool {
-        self.config
+        self.link_condition(from, to) == NetworkCondition::Partitioned
+    }
+
+    /// Get the effective condition of the directed link `from -> to`.
+    ///
+    /// A two-way partition between the nodes overrides the link's own
+    /// condition.
+    pub fn link_condition(&self, from: usize, to: usize) -> NetworkCondition {
+        let partitioned = self
+            .config
             .partitions
             .iter()
-            .any(|&(a, b)| (a == from && b == to) || (a == to && b == from))
+            .any(|&(a, b)| (a == from && b == to) || (a == to && b == from));
+        if partitioned {
+            return NetworkCondition::Partitioned;
+        }
+        self.config
+            .link_conditions
+            .get(&(from, to))
+            .copied()
+            .unwrap_or(NetworkCondition::Perfect)
+    }
+
+    /// Set the condition of the directed link `from -> to`.
+    ///
+    /// The reverse link keeps its own condition. `Partitioned` drops
+    /// messages and `Delayed(n)` holds them for `n` extra rounds;
+    /// `Reordered` delivers normally, since merges are order-independent.
+    /// `Perfect` clears the link.
+    pub fn set_link_condition(&mut self, from: usize, to: usize, condition: NetworkCondition) {
+        if condition == NetworkCondition::Perfect {
+            self.config.link_conditions.remove(&(from, to));
+        } else {
+            self.config.link_conditions.insert((from, to), condition);
+        }
     }
 
     /// Add a network partition between two nodes.
@@ -355,9 +589,37 @@ impl SimulatedCluster {
         self.config.partitions.push((node_a, node_b));
     }
 
-    /// Remove all partitions (heal network).
+    /// Block messages from `from` to `to` while `to -> from` still delivers.
+    pub fn partition_one_way(&mut self, from: usize, to: usize) {
+        self.set_link_condition(from, to, NetworkCondition::Partitioned);
+    }
+
+    /// Remove all partitions (heal network), including one-way ones.
+    ///
+    /// Delayed and reordered links are left as they are.
     pub fn heal_partitions(&mut self) {
         self.config.partitions.clear();
+        self.config
+            .link_conditions
+            .retain(|_, condition| *condition != NetworkCondition::Partitioned);
+    }
+
+    /// List the links that are currently blocked both ways or one way.
+    pub fn connectivity_report(&self) -> ConnectivityReport {
+        let mut report = ConnectivityReport::default();
+        let n = self.nodes.len();
+        for a in 0..n {
+            for b in (a + 1)..n {
+                match (self.is_partitioned(a, b), self.is_partitioned(b, a)) {
+                    (true, true) => report.blocked.push((a, b)),
+                    (false, true) => report.one_way.push((a, b)),
+                    (true, false) => report.one_way.push((b, a)),
+                    (false, false) => {}
+                }
+            }
+        }
+        report.one_way.sort();
+        report
     }
 
     /// Re-queue all local updates for propagation.
@@ -400,11 +662,103 @@ impl SimulatedCluster {
         true
     }
 
+    /// List the keys whose values differ between nodes, sorted by key.
+    ///
+    /// Empty exactly when `verify_convergence` returns true.
+    pub fn divergence_report(&self) -> Vec<KeyDivergence> {
+        let mut keys = self.all_keys();
+        keys.sort();
+        keys.into_iter()
+            .filter_map(|key| {
+                let values: Vec<Option<AlgebraicValue>> =
+                    self.nodes.iter().map(|n| n.get_state(&key).cloned()).collect();
+                if values.iter().all(|v| *v == values[0]) {
+                    return None;
+                }
+                Some(KeyDivergence { key, values })
+            })
+            .collect()
+    }
+
     /// Get the state of a key on a specific node.
     pub fn get_node_state(&self, node_index: usize, key: &str) -> Option<&AlgebraicValue> {
         self.nodes[node_index].get_state(key)
     }
 
+    /// Get the effective op type of each key on a specific node.
+    ///
+    /// The op type is the one established by the first operation the node
+    /// applied for the key. Later operations with a different type are
+    /// reported by `node_op_type_conflicts`.
+    pub fn node_key_optypes(&self, node_index: usize) -> HashMap<String, OpType> {
+        self.nodes[node_index].key_optypes()
+    }
+
+    /// Get the keys on a specific node that were operated on with more than
+    /// one op type, sorted.
+    pub fn node_op_type_conflicts(&self, node_index: usize) -> Vec<String> {
+        let mut keys: Vec<String> = self.nodes[node_index]
+            .op_type_conflicts
+            .iter()
+            .cloned()
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    /// Read a key from a quorum of nodes.
+    ///
+    /// Takes the first `quorum` nodes (by index) that hold the key and folds
+    /// every distinct update any of them has applied, using the key's op
+    /// type on the first of those nodes. Replaying updates rather than
+    /// merging node states keeps non-idempotent types such as `AbelianAdd`
+    /// from counting a shared update twice. Returns `None` if fewer than
+    /// `quorum` nodes hold the key.
+    pub fn quorum_read(&self, key: &str, quorum: usize) -> Option<AlgebraicValue> {
+        let readers: Vec<&SimulatedNode> = self
+            .nodes
+            .iter()
+            .filter(|n| n.state.contains_key(key))
+            .take(quorum)
+            .collect();
+        if readers.is_empty() || readers.len() < quorum {
+            return None;
+        }
+        let op_type = readers[0].state[key].0;
+
+        let mut seen: Vec<&String> = readers
+            .iter()
+            .flat_map(|n| n.applied_updates.iter())
+            .collect::<HashSet<_>>()
+            .into_iter()
+            .collect();
+        seen.sort();
+
+        let updates: HashMap<String, &VersionedUpdate> = self
+            .nodes
+            .iter()
+            .flat_map(|n| n.local_updates.iter().map(move |u| (n.generate_update_id(u), u)))
+            .collect();
+
+        let mut value: Option<AlgebraicValue> = None;
+        for id in seen {
+            let Some(update) = updates.get(id) else { continue };
+            for op in update.operations() {
+                if op.key() != key || op.op_type().merge_family() != op_type {
+                    continue;
+                }
+                value = match value {
+                    None => Some(op.value().clone()),
+                    Some(current) => match AlgebraicMerger::merge(op_type, &current, op.value()) {
+                        MergeResult::Merged(merged) => Some(merged),
+                        _ => Some(current),
+                    },
+                };
+            }
+        }
+        value
+    }
+
     /// Get statistics from the simulation.
     pub fn get_stats(&self) -> &SimulationStats {
         &self.stats
@@ -535,6 +889,56 @@ mod tests {
         assert_eq!(value.as_integer(), Some(10));
     }
 
+    #[test]
+    fn test_snapshot_restore_is_independent() {
+        let mut cluster = SimulatedCluster::new(3);
+        for i in 0..3 {
+            let mut tx = AlgebraicTransaction::new();
+            tx.add_operation(add_op("counter", ((i + 1) * 10) as i64));
+            cluster.commit_on_node(i, tx).unwrap();
+        }
+        cluster.broadcast_all();
+
+        let snapshot = cluster.snapshot();
+        assert_eq!(snapshot.num_nodes(), 3);
+        assert_eq!(snapshot.round(), cluster.round);
+
+        // Advance the fork to convergence
+        let mut fork = SimulatedCluster::restore(snapshot.clone());
+        fork.propagate_all();
+        assert!(fork.verify_convergence());
+        assert_eq!(fork.get_node_state(1, "counter").unwrap().as_integer(), Some(60));
+
+        // The original and a second restore are still mid-run
+        for c in [&cluster, &SimulatedCluster::restore(snapshot)] {
+            assert!(!c.verify_convergence());
+            assert_eq!(c.get_node_state(1, "counter").unwrap().as_integer(), Some(20));
+            assert_eq!(c.messages.len(), cluster.messages.len());
+            assert_eq!(c.round, cluster.round);
+        }
+
+        // Advancing the original reaches the same end state as the fork
+        cluster.propagate_all();
+        assert_eq!(cluster.get_node_state(1, "counter"), fork.get_node_state(1, "counter"));
+    }
+
+    #[test]
+    fn test_with_node_ids() {
+        let mut cluster = SimulatedCluster::with_node_ids(vec![
+            NodeId::new("us-east"),
+            NodeId::new("eu-west"),
+        ]);
+        assert_eq!(cluster.num_nodes(), 2);
+        assert_eq!(cluster.node_id(1).unwrap().as_str(), "eu-west");
+        assert!(cluster.node_id(2).is_none());
+
+        let mut tx = AlgebraicTransaction::new();
+        tx.add_operation(add_op("counter", 1));
+        let update = cluster.commit_on_node(1, tx).unwrap();
+
+        assert_eq!(update.origin_node(), &NodeId::new("eu-west"));
+    }
+
     // ============ Two Node Convergence ============
 
     #[test]
@@ -694,6 +1098,99 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_or_set_concurrent_add_remove_converges() {
+        let mut cluster = SimulatedCluster::new(3);
+
+        let mut tx = AlgebraicTransaction::new();
+        tx.add_operation(AlgebraicOperation::new(
+            "members",
+            OpType::OrSetAdd,
+            AlgebraicValue::or_set_add("alice", "node-0:1"),
+        ));
+        tx.add_operation(AlgebraicOperation::new(
+            "members",
+            OpType::OrSetAdd,
+            AlgebraicValue::or_set_add("bob", "node-0:2"),
+        ));
+        cluster.commit_on_node(0, tx).unwrap();
+        cluster.propagate_all();
+
+        // Node 1 removes both members it has seen; node 2 concurrently re-adds alice
+        let observed = cluster.get_node_state(1, "members").unwrap().clone();
+        let mut tx1 = AlgebraicTransaction::new();
+        for member in ["alice", "bob"] {
+            tx1.add_operation(AlgebraicOperation::new(
+                "members",
+                OpType::OrSetRemove,
+                observed.observed_remove(member).unwrap(),
+            ));
+        }
+        cluster.commit_on_node(1, tx1).unwrap();
+
+        let mut tx2 = AlgebraicTransaction::new();
+        tx2.add_operation(AlgebraicOperation::new(
+            "members",
+            OpType::OrSetAdd,
+            AlgebraicValue::or_set_add("alice", "node-2:1"),
+        ));
+        cluster.commit_on_node(2, tx2).unwrap();
+
+        assert_eq!(
+            cluster.get_node_state(1, "members").unwrap().or_set_elements(),
+            Some(vec![])
+        );
+
+        cluster.propagate_all();
+
+        // The unobserved add wins; bob's only add was observed and removed
+        assert!(cluster.verify_convergence());
+        for i in 0..3 {
+            assert_eq!(
+                cluster.get_node_state(i, "members").unwrap().or_set_elements(),
+                Some(vec!["alice"])
+            );
+            assert!(cluster.node_op_type_conflicts(i).is_empty());
+            assert_eq!(cluster.node_key_optypes(i)["members"], OpType::OrSetAdd);
+        }
+    }
+
+    #[test]
+    fn test_map_disjoint_fields_converge() {
+        let mut cluster = SimulatedCluster::new(2);
+
+        let mut tx0 = AlgebraicTransaction::new();
+        tx0.add_operation(AlgebraicOperation::new(
+            "profile",
+            OpType::MapMerge,
+            AlgebraicValue::map([
+                ("name", AlgebraicValue::timestamped(1, 10)),
+                ("logins", AlgebraicValue::integer(3)),
+            ]),
+        ));
+        cluster.commit_on_node(0, tx0).unwrap();
+
+        let mut tx1 = AlgebraicTransaction::new();
+        tx1.add_operation(AlgebraicOperation::new(
+            "profile",
+            OpType::MapMerge,
+            AlgebraicValue::map([
+                ("roles", AlgebraicValue::string_set(["admin"])),
+                ("logins", AlgebraicValue::integer(5)),
+            ]),
+        ));
+        cluster.commit_on_node(1, tx1).unwrap();
+
+        cluster.propagate_all();
+
+        assert!(cluster.verify_convergence());
+        let profile = cluster.get_node_state(0, "profile").unwrap().as_map().unwrap().clone();
+        assert_eq!(profile.len(), 3);
+        assert_eq!(profile["logins"], AlgebraicValue::integer(5));
+        assert_eq!(profile["roles"], AlgebraicValue::string_set(["admin"]));
+        assert_eq!(profile["name"], AlgebraicValue::timestamped(1, 10));
+    }
+
     // ============ Network Partition Tests ============
 
     #[test]
@@ -762,6 +1259,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_quorum_read_under_partial_propagation() {
+        // Nodes 3 and 4 are cut off from the majority
+        let mut cluster = SimulatedCluster::new(5);
+        for a in 0..3 {
+            for b in 3..5 {
+                cluster.partition(a, b);
+            }
+        }
+
+        for i in 0..3 {
+            let mut tx = AlgebraicTransaction::new();
+            tx.add_operation(add_op("counter", 10));
+            cluster.commit_on_node(i, tx).unwrap();
+        }
+
+        // Before any propagation each majority node only sees its own write,
+        // but a quorum of them covers all three
+        assert_eq!(cluster.get_node_state(0, "counter").unwrap().as_integer(), Some(10));
+        assert_eq!(cluster.quorum_read("counter", 3).unwrap().as_integer(), Some(30));
+
+        // After propagation within the majority, shared updates count once
+        cluster.propagate_all();
+        assert!(!cluster.verify_convergence());
+        assert_eq!(cluster.get_node_state(0, "counter").unwrap().as_integer(), Some(30));
+        assert_eq!(cluster.quorum_read("counter", 3).unwrap().as_integer(), Some(30));
+        assert_eq!(cluster.quorum_read("counter", 1).unwrap().as_integer(), Some(30));
+
+        // The minority never saw the key
+        assert!(cluster.get_node_state(3, "counter").is_none());
+        assert!(cluster.quorum_read("counter", 4).is_none());
+    }
+
     #[test]
     fn test_partial_partition() {
         // 3 nodes: 0 <-> 1 (ok), 1 <-> 2 (ok), 0 <-> 2 (partitioned)
@@ -786,6 +1316,69 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_one_way_partition() {
+        let mut cluster = SimulatedCluster::new(3);
+
+        // Updates flow 0 -> 1 but not 1 -> 0
+        cluster.partition_one_way(1, 0);
+        cluster.partition(0, 2);
+        assert_eq!(cluster.link_condition(0, 1), NetworkCondition::Perfect);
+        assert_eq!(cluster.link_condition(1, 0), NetworkCondition::Partitioned);
+        assert_eq!(
+            cluster.connectivity_report(),
+            ConnectivityReport {
+                blocked: vec![(0, 2)],
+                one_way: vec![(0, 1)],
+            }
+        );
+        cluster.heal_partitions();
+        assert_eq!(cluster.connectivity_report(), ConnectivityReport::default());
+
+        let mut cluster = SimulatedCluster::new(2);
+        cluster.partition_one_way(1, 0);
+
+        let mut tx0 = AlgebraicTransaction::new();
+        tx0.add_operation(add_op("counter", 10));
+        cluster.commit_on_node(0, tx0).unwrap();
+
+        let mut tx1 = AlgebraicTransaction::new();
+        tx1.add_operation(add_op("counter", 20));
+        cluster.commit_on_node(1, tx1).unwrap();
+
+        cluster.propagate_all();
+
+        // Node 1 applied node 0's update; node 0 never saw node 1's
+        assert!(!cluster.verify_convergence());
+        assert_eq!(cluster.get_node_state(1, "counter").unwrap().as_integer(), Some(30));
+        assert_eq!(cluster.get_node_state(0, "counter").unwrap().as_integer(), Some(10));
+        assert_eq!(
+            cluster.divergence_report(),
+            vec![KeyDivergence {
+                key: "counter".to_string(),
+                values: vec![Some(AlgebraicValue::integer(10)), Some(AlgebraicValue::integer(30))],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_delayed_link() {
+        let mut cluster = SimulatedCluster::new(2);
+        cluster.set_link_condition(0, 1, NetworkCondition::Delayed(2));
+
+        let mut tx = AlgebraicTransaction::new();
+        tx.add_operation(add_op("counter", 10));
+        cluster.commit_on_node(0, tx).unwrap();
+
+        cluster.propagate_round();
+        cluster.propagate_round();
+        assert!(cluster.get_node_state(1, "counter").is_none());
+
+        cluster.propagate_round();
+        assert_eq!(cluster.get_node_state(1, "counter").unwrap().as_integer(), Some(10));
+        assert!(cluster.divergence_report().is_empty());
+    }
+
     // ============ Commutativity Tests ============
 
     #[test]
@@ -944,6 +1537,124 @@ mod tests {
         assert!(stats.messages_delivered > 0);
     }
 
+    // ============ Delivery Priority Tests ============
+
+    fn run_budgeted_workload(priority: DeliveryPriority) -> SimulatedCluster {
+        let config = SimulationConfig {
+            delivery_priority: priority,
+            delivery_budget: Some(4),
+            ..Default::default()
+        };
+        let mut cluster = SimulatedCluster::with_config(4, config);
+
+        // Node 0 is busy; the others commit once each
+        for i in 0..6 {
+            let mut tx = AlgebraicTransaction::new();
+            tx.add_operation(add_op("hot", i + 1));
+            cluster.commit_on_node(0, tx).unwrap();
+        }
+        for node in 1..4 {
+            let mut tx = AlgebraicTransaction::new();
+            tx.add_operation(max_op("cold", node as i64));
+            cluster.commit_on_node(node, tx).unwrap();
+        }
+
+        cluster.propagate_all();
+        cluster
+    }
+
+    #[test]
+    fn test_priority_delivery_converges_no_slower() {
+        let fifo = run_budgeted_workload(DeliveryPriority::Fifo);
+        let prioritized = run_budgeted_workload(DeliveryPriority::OutboxSize);
+
+        assert!(fifo.verify_convergence());
+        assert!(prioritized.verify_convergence());
+        assert_eq!(prioritized.get_node_state(3, "hot").unwrap().as_integer(), Some(21));
+
+        let fifo_rounds = fifo.get_stats().rounds_to_converge.unwrap();
+        let prioritized_rounds = prioritized.get_stats().rounds_to_converge.unwrap();
+        assert!(prioritized_rounds <= fifo_rounds);
+    }
+
+    #[test]
+    fn test_per_message_priority_delivers_urgent_first() {
+        let config = SimulationConfig {
+            delivery_priority: DeliveryPriority::PerMessage,
+            delivery_budget: Some(1),
+            ..Default::default()
+        };
+        let mut cluster = SimulatedCluster::with_config(2, config);
+
+        let mut routine = AlgebraicTransaction::new();
+        routine.add_operation(add_op("routine", 1));
+        cluster.commit_on_node(0, routine).unwrap();
+
+        // Sent after `routine`, but delivered ahead of it
+        let mut urgent = AlgebraicTransaction::new();
+        urgent.add_operation(add_op("urgent", 1));
+        cluster.commit_on_node_with_priority(0, urgent, 5).unwrap();
+
+        cluster.propagate_round();
+        assert!(cluster.get_node_state(1, "urgent").is_some());
+        assert!(cluster.get_node_state(1, "routine").is_none());
+
+        cluster.propagate_all();
+        assert!(cluster.verify_convergence());
+    }
+
+    #[test]
+    fn test_delivery_budget_carries_over() {
+        let config = SimulationConfig {
+            delivery_budget: Some(1),
+            ..Default::default()
+        };
+        let mut cluster = SimulatedCluster::with_config(3, config);
+
+        let mut tx = AlgebraicTransaction::new();
+        tx.add_operation(add_op("x", 1));
+        cluster.commit_on_node(0, tx).unwrap();
+
+        cluster.propagate_round();
+        assert_eq!(cluster.get_stats().messages_delivered, 1);
+        assert_eq!(cluster.messages.len(), 1);
+
+        cluster.propagate_all();
+        assert!(cluster.verify_convergence());
+    }
+
+    // ============ Introspection Tests ============
+
+    #[test]
+    fn test_node_key_optypes() {
+        let mut cluster = SimulatedCluster::new(2);
+
+        let mut tx0 = AlgebraicTransaction::new();
+        tx0.add_operation(add_op("counter", 5));
+        cluster.commit_on_node(0, tx0).unwrap();
+
+        let mut tx1 = AlgebraicTransaction::new();
+        tx1.add_operation(union_op("tags", &["a"]));
+        cluster.commit_on_node(1, tx1).unwrap();
+
+        cluster.propagate_all();
+
+        let optypes = cluster.node_key_optypes(0);
+        assert_eq!(optypes.len(), 2);
+        assert_eq!(optypes["counter"], OpType::AbelianAdd);
+        assert_eq!(optypes["tags"], OpType::SemilatticeUnion);
+        assert!(cluster.node_op_type_conflicts(0).is_empty());
+
+        // Operating on "counter" with a different op type is a conflict
+        let mut tx2 = AlgebraicTransaction::new();
+        tx2.add_operation(max_op("counter", 100));
+        cluster.commit_on_node(1, tx2).unwrap();
+
+        assert_eq!(cluster.node_key_optypes(1)["counter"], OpType::AbelianAdd);
+        assert_eq!(cluster.node_op_type_conflicts(1), vec!["counter".to_string()]);
+        assert_eq!(cluster.get_node_state(1, "counter").unwrap().as_integer(), Some(5));
+    }
+
     // ============ Edge Cases ============
 
     #[test]
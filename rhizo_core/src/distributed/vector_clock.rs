@@ -133,6 +133,18 @@ impl VectorClock {
         *self.clocks.entry(node_id.clone()).or_insert(0) += 1;
     }
 
+    /// Advance a node's logical time by `n` in one call.
+    ///
+    /// Equivalent to calling `tick` `n` times; saturates at `u64::MAX`.
+    /// `n == 0` leaves the clock unchanged.
+    pub fn tick_n(&mut self, node_id: &NodeId, n: u64) {
+        if n == 0 {
+            return;
+        }
+        let entry = self.clocks.entry(node_id.clone()).or_insert(0);
+        *entry = entry.saturating_add(n);
+    }
+
     /// Get the logical time for a specific node.
     ///
     /// Returns 0 if the node has no entry (never observed).
@@ -179,6 +191,15 @@ impl VectorClock {
         }
     }
 
+    /// Merge several clocks into this one.
+    ///
+    /// Equivalent to calling `merge` with each clock in turn.
+    pub fn merge_all(&mut self, others: &[&VectorClock]) {
+        for other in others {
+            self.merge(other);
+        }
+    }
+
     /// Check if this clock happened strictly before another clock.
     ///
     /// Returns true iff all components of self are ≤ other, and at least one is strictly <.
@@ -422,6 +443,55 @@ mod tests {
         assert_eq!(clock1.get(&n3), 5); // max(0, 5)
     }
 
+    #[test]
+    fn test_tick_n_equals_repeated_tick() {
+        let n1 = node("a");
+        let n2 = node("b");
+
+        let mut batched = VectorClock::with_node(n2.clone(), 2);
+        batched.tick_n(&n1, 5);
+
+        let mut stepped = VectorClock::with_node(n2.clone(), 2);
+        for _ in 0..5 {
+            stepped.tick(&n1);
+        }
+
+        assert_eq!(batched, stepped);
+        assert_eq!(batched.get(&n1), 5);
+
+        batched.tick_n(&n1, 0);
+        assert_eq!(batched, stepped);
+
+        batched.tick_n(&n2, u64::MAX);
+        assert_eq!(batched.get(&n2), u64::MAX);
+    }
+
+    #[test]
+    fn test_merge_all_equals_sequential_merges() {
+        let (a, b, c) = (node("a"), node("b"), node("c"));
+
+        let mut clock1 = VectorClock::new();
+        clock1.set(&a, 4);
+        clock1.set(&b, 1);
+        let mut clock2 = VectorClock::new();
+        clock2.set(&b, 7);
+        clock2.set(&c, 2);
+        let clock3 = VectorClock::with_node(c.clone(), 9);
+
+        let mut batched = VectorClock::with_node(a.clone(), 6);
+        batched.merge_all(&[&clock1, &clock2, &clock3]);
+
+        let mut sequential = VectorClock::with_node(a.clone(), 6);
+        sequential.merge(&clock1);
+        sequential.merge(&clock2);
+        sequential.merge(&clock3);
+
+        assert_eq!(batched, sequential);
+        assert_eq!(batched.get(&a), 6); // max(6, 4)
+        assert_eq!(batched.get(&b), 7); // max(1, 7)
+        assert_eq!(batched.get(&c), 9); // max(2, 9)
+    }
+
     #[test]
     fn test_happened_before_simple() {
         let n = node("node-1");
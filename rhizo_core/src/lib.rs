@@ -5,39 +5,50 @@ pub mod changelog;
 pub mod chunk_store;
 pub mod distributed;
 pub mod merkle;
+pub mod parallel;
 pub mod parquet;
+pub mod repository;
+pub mod table;
 pub mod transaction;
 
 pub use algebraic::{
-    AlgebraicMerger, AlgebraicSchemaRegistry, AlgebraicValue, ColumnAlgebraic, MergeResult,
-    OpType, TableAlgebraicSchema,
+    AlgebraicMerger, AlgebraicSchemaRegistry, AlgebraicValue, ColumnAlgebraic, CustomMerge,
+    MergeResult, OpType, TableAlgebraicSchema,
 };
 pub use branch::{
     Branch, BranchDiff, BranchError, BranchManager, MergeAnalysis, MergeAnalyzer, MergeOutcome,
+    Resolution,
 };
-pub use catalog::{CatalogError, FileCatalog, TableVersion};
-pub use changelog::{ChangelogEntry, ChangelogQuery, TableChange};
-pub use chunk_store::{ChunkMmap, ChunkStore, ChunkStoreError};
+pub use catalog::{
+    transfer_plan, CatalogError, ChainReuse, FileCatalog, SchemaCompat,
+    TableVersion,
+};
+pub use changelog::{ChangelogCursor, ChangelogEntry, ChangelogQuery, TableChange};
+pub use chunk_store::{ChunkMmap, ChunkStore, ChunkStoreError, GcReport};
 pub use merkle::{
-    build_tree, diff_trees, verify_tree, DataChunk, MerkleConfig, MerkleDiff, MerkleError,
-    MerkleNode, MerkleTree,
+    build_tree, build_tree_from_chunks, diff_trees, read_range, rebuild_tree, verify_proof, verify_tree, Chunking, DataChunk, MerkleConfig, MerkleDiff,
+    MerkleError, MerkleNode, MerkleProof, MerkleTree, ProofStep, TreeSummary,
 };
+pub use parallel::{configure_parallelism, current_parallelism};
 pub use parquet::{
-    FilterOp, ParquetCompression, ParquetDecoder, ParquetEncoder, ParquetError, PredicateFilter,
-    ScalarValue,
+    batches_equivalent, batches_equivalent_with_tolerance,
+    AggregateResult, AggregateSpec, FilterExplain, FilterOp, FilterPlan, ParquetCompression, ParquetDecoder, ParquetEncoder, ParquetError, ParquetStreamWriter, ParquetWriterConfig,
+    PredicateExpr, PredicateFilter, RepairReport, ScalarValue,
 };
+pub use repository::{Repository, RepositoryError};
+pub use table::{PreviewReport, Table, TableError};
 pub use transaction::{
-    Conflict, ConflictDetector, EpochConfig, EpochId, EpochMetadata, EpochStatus, RecoveryManager,
-    RecoveryReport, TableLevelConflictDetector, TableWrite, TransactionError, TransactionLog,
-    TransactionManager, TransactionRecord, TransactionStatus, TxId, WriteGranularity,
+    Conflict, ConflictDetector, ConflictWindow, EpochConfig, EpochId, EpochMetadata, EpochStatus, RecoveryManager, SavepointId,
+    ReconcileReport, RecoveryReport, RowRangeConflictDetector, TableLevelConflictDetector, TableWrite, TransactionError, TransactionLog,
+    TransactionManager, TransactionRecord, TransactionStatus, TxId, TxMetrics, WriteGranularity,
     // Coordination-free mode (Phase 5)
     TransactionMode, CoordinationFreeConfig, CoordinationFreeError, CoordinationFreeManager,
 };
 
 pub use distributed::{
     AlgebraicOperation, AlgebraicTransaction, CausalOrder, LocalCommitError, LocalCommitProtocol,
-    NodeId, VectorClock, VersionedUpdate,
+    NodeId, RepairEvent, VectorClock, VersionedUpdate,
     // Simulation types (Phase 4)
-    Message, NetworkCondition, SimulatedCluster, SimulatedNode, SimulationBuilder,
+    ClusterSnapshot, ConnectivityReport, DeliveryPriority, KeyDivergence, Message, NetworkCondition, SimulatedCluster, SimulatedNode, SimulationBuilder,
     SimulationConfig, SimulationStats,
 };
@@ -0,0 +1,129 @@
+//! Content-defined chunking with a Gear rolling hash (FastCDC).
+//!
+//! Boundaries are placed where the hash of the last 64 bytes matches a
+//! mask, so they move with the content: inserting or deleting bytes only
+//! changes the chunks around the edit. Normalized chunking uses a stricter
+//! mask before the average size and a looser one after it, which keeps
+//! sizes close to the average.
+
+/// Gear table: one pseudo-random value per byte.
+///
+/// Generated from a fixed seed, so boundaries are stable across builds and
+/// platforms (changing it would break dedup against existing trees).
+const GEAR: [u64; 256] = gear_table();
+
+const fn gear_table() -> [u64; 256] {
+    let mut table = [0u64; 256];
+    let mut state = 0u64;
+    let mut i = 0;
+    while i < 256 {
+        // splitmix64
+        state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        table[i] = z ^ (z >> 31);
+        i += 1;
+    }
+    table
+}
+
+/// Mask of the top `bits` bits (the Gear hash mixes into high bits).
+fn top_bits(bits: u32) -> u64 {
+    match bits {
+        0 => 0,
+        b if b >= 64 => u64::MAX,
+        b => u64::MAX << (64 - b),
+    }
+}
+
+/// Length of the next chunk at the start of `data`.
+///
+/// Chunks are at least `min` and at most `max` bytes, except that the last
+/// chunk may be shorter than `min`.
+fn cut_point(data: &[u8], min: usize, avg: usize, max: usize) -> usize {
+    let len = data.len();
+    if len <= min {
+        return len;
+    }
+
+    let bits = avg.max(1).ilog2();
+    let strict = top_bits(bits + 1);
+    let loose = top_bits(bits.saturating_sub(1));
+    let end = len.min(max);
+    let normal = end.min(avg);
+
+    let mut hash = 0u64;
+    let mut i = min;
+    while i < normal {
+        hash = (hash << 1).wrapping_add(GEAR[data[i] as usize]);
+        if hash & strict == 0 {
+            return i + 1;
+        }
+        i += 1;
+    }
+    while i < end {
+        hash = (hash << 1).wrapping_add(GEAR[data[i] as usize]);
+        if hash & loose == 0 {
+            return i + 1;
+        }
+        i += 1;
+    }
+    end
+}
+
+/// Split data at content-defined boundaries, returning `[start, end)` ranges.
+///
+/// Bounds must satisfy `0 < min <= avg <= max` (checked by `build_tree`).
+pub(super) fn content_defined_ranges(data: &[u8], min: usize, avg: usize, max: usize) -> Vec<(usize, usize)> {
+    let mut ranges = Vec::new();
+    let mut offset = 0usize;
+
+    while offset < data.len() {
+        let end = offset + cut_point(&data[offset..], min, avg, max);
+        ranges.push((offset, end));
+        offset = end;
+    }
+
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pseudo_random(len: usize) -> Vec<u8> {
+        let mut state = 0x2545_F491_4F6C_DD1Du64;
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                (state >> 56) as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_chunks_respect_bounds_and_cover_data() {
+        let data = pseudo_random(256 * 1024);
+        let ranges = content_defined_ranges(&data, 1024, 4096, 16384);
+
+        let mut expected_start = 0;
+        for (i, &(start, end)) in ranges.iter().enumerate() {
+            assert_eq!(start, expected_start);
+            let last = i == ranges.len() - 1;
+            assert!(end - start <= 16384);
+            assert!(last || end - start >= 1024);
+            expected_start = end;
+        }
+        assert_eq!(expected_start, data.len());
+
+        // Sizes vary with content and average out near the target
+        let avg = data.len() / ranges.len();
+        assert!((2048..=8192).contains(&avg), "average chunk size {}", avg);
+        let first = ranges[0].1 - ranges[0].0;
+        assert!(ranges.iter().any(|(start, end)| end - start != first));
+    }
+
+}
@@ -12,6 +12,15 @@ pub enum MerkleError {
     #[error("Invalid chunk size: must be > 0, got {0}")]
     InvalidChunkSize(usize),
 
+    #[error("Invalid branching factor: must be >= 2, got {0}")]
+    InvalidBranchingFactor(usize),
+
+    #[error("Invalid content-defined chunk bounds: need 0 < min <= avg <= max, got {min}/{avg}/{max}")]
+    InvalidChunkBounds { min: usize, avg: usize, max: usize },
+
+    #[error("Chunk index {index} out of range for tree with {count} chunks")]
+    ChunkIndexOutOfRange { index: usize, count: usize },
+
     #[error("Empty data: cannot build Merkle tree from empty input")]
     EmptyData,
 
@@ -37,10 +37,14 @@
 //! assert!(diff.reuse_ratio > 0.7); // ~75% reuse
 //! ```
 
+mod cdc;
 mod error;
 mod types;
 mod tree;
 
 pub use error::MerkleError;
-pub use types::{DataChunk, MerkleConfig, MerkleDiff, MerkleNode, MerkleTree};
-pub use tree::{build_tree, diff_trees, verify_tree};
+pub use types::{
+    Chunking, DataChunk, MerkleConfig, MerkleDiff, MerkleNode, MerkleProof, MerkleTree, ProofStep,
+    TreeSummary,
+};
+pub use tree::{build_tree, build_tree_from_chunks, diff_trees, read_range, rebuild_tree, verify_proof, verify_tree};
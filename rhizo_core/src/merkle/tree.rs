@@ -1,25 +1,42 @@
+use super::cdc::content_defined_ranges;
 use super::error::MerkleError;
-use super::types::{DataChunk, MerkleConfig, MerkleDiff, MerkleNode, MerkleTree};
-use std::collections::HashSet;
+use super::types::{Chunking, DataChunk, MerkleConfig, MerkleDiff, MerkleNode, MerkleProof, MerkleTree};
+use crate::chunk_store::{ChunkStore, ChunkStoreError};
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
+
+/// Chunk count at which `build_tree` hashes leaves, and builds each tree
+/// level, on the Rayon pool; smaller inputs stay sequential.
+const PARALLEL_CHUNK_THRESHOLD: usize = 64;
 
 /// Build a Merkle tree from raw data bytes
 pub fn build_tree(data: &[u8], config: &MerkleConfig) -> Result<MerkleTree, MerkleError> {
+    build_tree_with_threshold(data, config, PARALLEL_CHUNK_THRESHOLD)
+}
+
+/// `build_tree` with an explicit parallelism threshold (`usize::MAX` forces
+/// the sequential path). The tree is identical either way.
+fn build_tree_with_threshold(
+    data: &[u8],
+    config: &MerkleConfig,
+    parallel_threshold: usize,
+) -> Result<MerkleTree, MerkleError> {
+    config.validate()?;
     if data.is_empty() {
         return Err(MerkleError::EmptyData);
     }
-    if config.chunk_size == 0 {
-        return Err(MerkleError::InvalidChunkSize(0));
-    }
 
     // Step 1: Split data into chunks and compute leaf hashes
-    let chunks = split_into_chunks(data, config.chunk_size);
+    let ranges = chunk_ranges(data, config);
+    let chunks = hash_chunks(data, &ranges, ranges.len() >= parallel_threshold);
 
     if chunks.is_empty() {
         return Err(MerkleError::EmptyData);
     }
 
     // Step 2: Build tree bottom-up
-    let (root_hash, internal_nodes, height) = build_tree_from_leaves(&chunks, config.branching_factor);
+    let (root_hash, internal_nodes, height) =
+        build_tree_from_leaves(&chunks, config.branching_factor, parallel_threshold);
 
     Ok(MerkleTree {
         root_hash,
@@ -31,35 +48,186 @@ pub fn build_tree(data: &[u8], config: &MerkleConfig) -> Result<MerkleTree, Merk
     })
 }
 
-/// Split data into fixed-size chunks and compute their hashes
-fn split_into_chunks(data: &[u8], chunk_size: usize) -> Vec<DataChunk> {
-    let mut chunks = Vec::new();
-    let mut offset = 0usize;
-    let mut index = 0usize;
-
-    while offset < data.len() {
-        let end = (offset + chunk_size).min(data.len());
-        let chunk_data = &data[offset..end];
-        let hash = blake3::hash(chunk_data).to_hex().to_string();
-
-        chunks.push(DataChunk::new(
-            hash,
-            offset as u64,
-            end as u64,
-            index,
-        ));
+/// Build a Merkle tree over data already stored as separate chunks
+///
+/// `chunks` are each chunk's `(content hash, size)` in order, laid end to
+/// end. Only `config.branching_factor` shapes the tree; `config.chunk_size`
+/// is recorded as is. The root matches `build_tree` over the concatenated
+/// data whenever its chunk boundaries are the same.
+pub fn build_tree_from_chunks(chunks: &[(String, u64)], config: &MerkleConfig) -> Result<MerkleTree, MerkleError> {
+    config.validate()?;
+    if chunks.is_empty() {
+        return Err(MerkleError::EmptyData);
+    }
+
+    let mut offset = 0u64;
+    let chunks: Vec<DataChunk> = chunks
+        .iter()
+        .enumerate()
+        .map(|(index, (hash, size))| {
+            let chunk = DataChunk::new(hash.clone(), offset, offset + size, index);
+            offset += size;
+            chunk
+        })
+        .collect();
+
+    let (root_hash, internal_nodes, height) =
+        build_tree_from_leaves(&chunks, config.branching_factor, PARALLEL_CHUNK_THRESHOLD);
+
+    Ok(MerkleTree {
+        root_hash,
+        chunks,
+        internal_nodes,
+        total_size: offset,
+        chunk_size: config.chunk_size,
+        height,
+    })
+}
+
+/// Rebuild a tree for edited data, reusing hashes from the previous tree
+///
+/// Each chunk of `new_data` is matched to the old leaves by its content
+/// hash, wherever it sits, so unchanged chunks keep their old leaf hashes
+/// and only new content is hashed into the tree. Internal nodes over
+/// unchanged children are copied from the old tree, so a fixed-size edit
+/// costs the changed leaves plus O(log n) parent hashes.
+///
+/// The result always equals `build_tree(new_data, config)`.
+pub fn rebuild_tree(
+    old_tree: &MerkleTree,
+    new_data: &[u8],
+    config: &MerkleConfig,
+) -> Result<MerkleTree, MerkleError> {
+    rebuild_tree_counted(old_tree, new_data, config).map(|(tree, _)| tree)
+}
 
-        offset = end;
-        index += 1;
+/// `rebuild_tree`, also returning how many leaf hashes were computed
+fn rebuild_tree_counted(
+    old_tree: &MerkleTree,
+    new_data: &[u8],
+    config: &MerkleConfig,
+) -> Result<(MerkleTree, usize), MerkleError> {
+    config.validate()?;
+    if new_data.is_empty() {
+        return Err(MerkleError::EmptyData);
     }
 
-    chunks
+    let ranges = chunk_ranges(new_data, config);
+    let chunks = hash_chunks(new_data, &ranges, ranges.len() >= PARALLEL_CHUNK_THRESHOLD);
+
+    let mut hashed = 0usize;
+    let (root_hash, internal_nodes, height) = if chunks.len() == 1 {
+        build_tree_from_leaves(&chunks, config.branching_factor, PARALLEL_CHUNK_THRESHOLD)
+    } else {
+        // The first level's children are the old leaf hashes, in chunk order
+        let old_leaves: HashMap<&str, &str> = match old_tree.internal_nodes.first() {
+            Some(nodes) => old_tree
+                .chunks
+                .iter()
+                .map(|c| c.hash.as_str())
+                .zip(nodes.iter().flat_map(|n| n.children.iter().map(String::as_str)))
+                .collect(),
+            None => HashMap::new(),
+        };
+        let leaves = chunks
+            .iter()
+            .map(|c| match old_leaves.get(c.hash.as_str()) {
+                Some(leaf) => leaf.to_string(),
+                None => {
+                    hashed += 1;
+                    leaf_hash(&c.hash)
+                }
+            })
+            .collect();
+        build_levels(leaves, config.branching_factor, PARALLEL_CHUNK_THRESHOLD, &old_tree.internal_nodes)
+    };
+
+    let tree = MerkleTree {
+        root_hash,
+        chunks,
+        internal_nodes,
+        total_size: new_data.len() as u64,
+        chunk_size: config.chunk_size,
+        height,
+    };
+    Ok((tree, hashed))
+}
+
+/// Chunk boundaries for `data` under `config`
+fn chunk_ranges(data: &[u8], config: &MerkleConfig) -> Vec<(usize, usize)> {
+    match config.chunking {
+        Chunking::Fixed => fixed_ranges(data.len(), config.chunk_size),
+        Chunking::ContentDefined { min, avg, max } => content_defined_ranges(data, min, avg, max),
+    }
+}
+
+/// Split `len` bytes into fixed-size `[start, end)` ranges
+fn fixed_ranges(len: usize, chunk_size: usize) -> Vec<(usize, usize)> {
+    (0..len)
+        .step_by(chunk_size)
+        .map(|start| (start, (start + chunk_size).min(len)))
+        .collect()
+}
+
+/// Hash each range of `data` into a leaf chunk, in range order
+fn hash_chunks(data: &[u8], ranges: &[(usize, usize)], parallel: bool) -> Vec<DataChunk> {
+    let leaf = |(index, &(start, end)): (usize, &(usize, usize))| {
+        let hash = blake3::hash(&data[start..end]).to_hex().to_string();
+        DataChunk::new(hash, start as u64, end as u64, index)
+    };
+
+    if parallel {
+        crate::parallel::install(|| ranges.par_iter().enumerate().map(leaf).collect())
+    } else {
+        ranges.iter().enumerate().map(leaf).collect()
+    }
+}
+
+// BLAKE3 key-derivation contexts for leaf and internal node hashes. Keyed
+// hashes can't collide with a chunk's plain content hash, and the two
+// contexts keep a node from passing as a leaf (or the reverse) in a
+// shortened proof. Changing either changes every multi-chunk root.
+const LEAF_CONTEXT: &str = "rhizo 2026-10 merkle leaf";
+const NODE_CONTEXT: &str = "rhizo 2026-10 merkle node";
+
+/// Tree hash of a leaf, derived from the chunk's content hash
+pub(crate) fn leaf_hash(chunk_hash: &str) -> String {
+    let mut hasher = blake3::Hasher::new_derive_key(LEAF_CONTEXT);
+    hasher.update(chunk_hash.as_bytes());
+    hasher.finalize().to_hex().to_string()
+}
+
+/// Tree hash of an internal node over its children's hashes
+fn node_hash<S: AsRef<str>>(children: &[S]) -> String {
+    let mut hasher = blake3::Hasher::new_derive_key(NODE_CONTEXT);
+    for child in children {
+        hasher.update(child.as_ref().as_bytes());
+    }
+    hasher.finalize().to_hex().to_string()
+}
+
+/// Parent node over a group of child hashes
+///
+/// Takes the hash of a node in `reuse` with exactly these children, instead
+/// of hashing again.
+fn parent_node(
+    children: &[String],
+    level: u32,
+    index: usize,
+    reuse: Option<&HashMap<&[String], &str>>,
+) -> MerkleNode {
+    let hash = match reuse.and_then(|nodes| nodes.get(children)) {
+        Some(hash) => hash.to_string(),
+        None => node_hash(children),
+    };
+    MerkleNode::new(hash, children.to_vec(), level, index)
 }
 
 /// Build tree from leaf chunks, returning (root_hash, internal_nodes, height)
 fn build_tree_from_leaves(
     chunks: &[DataChunk],
     branching_factor: usize,
+    parallel_threshold: usize,
 ) -> (String, Vec<Vec<MerkleNode>>, u32) {
     if chunks.is_empty() {
         return (String::new(), Vec::new(), 0);
@@ -70,33 +238,47 @@ fn build_tree_from_leaves(
         return (chunks[0].hash.clone(), Vec::new(), 1);
     }
 
-    // Current level hashes (start with leaf hashes)
-    let mut current_level: Vec<String> = chunks.iter().map(|c| c.hash.clone()).collect();
+    let leaves = chunks.iter().map(|c| leaf_hash(&c.hash)).collect();
+    build_levels(leaves, branching_factor, parallel_threshold, &[])
+}
+
+/// Hash two or more leaf hashes up to a single root, returning
+/// (root_hash, internal_nodes, height). Nodes whose children match a node
+/// at the same level of `reuse` take its hash.
+fn build_levels(
+    leaves: Vec<String>,
+    branching_factor: usize,
+    parallel_threshold: usize,
+    reuse: &[Vec<MerkleNode>],
+) -> (String, Vec<Vec<MerkleNode>>, u32) {
+    let mut current_level = leaves;
     let mut internal_nodes: Vec<Vec<MerkleNode>> = Vec::new();
     let mut level = 1u32;
 
     // Build up until we have a single root
     while current_level.len() > 1 {
-        let mut next_level = Vec::new();
-        let mut level_nodes = Vec::new();
-
-        for (i, chunk) in current_level.chunks(branching_factor).enumerate() {
-            // Concatenate child hashes and hash the result
-            let combined: String = chunk.join("");
-            let parent_hash = blake3::hash(combined.as_bytes()).to_hex().to_string();
-
-            level_nodes.push(MerkleNode::new(
-                parent_hash.clone(),
-                chunk.to_vec(),
-                level,
-                i,
-            ));
-
-            next_level.push(parent_hash);
-        }
-
+        let old_nodes: Option<HashMap<&[String], &str>> = reuse.get(level as usize - 1).map(|nodes| {
+            nodes.iter().map(|n| (n.children.as_slice(), n.hash.as_str())).collect()
+        });
+        let old_nodes = old_nodes.as_ref();
+        let level_nodes: Vec<MerkleNode> = if current_level.len() >= parallel_threshold {
+            crate::parallel::install(|| {
+                current_level
+                    .par_chunks(branching_factor)
+                    .enumerate()
+                    .map(|(i, group)| parent_node(group, level, i, old_nodes))
+                    .collect()
+            })
+        } else {
+            current_level
+                .chunks(branching_factor)
+                .enumerate()
+                .map(|(i, group)| parent_node(group, level, i, old_nodes))
+                .collect()
+        };
+
+        current_level = level_nodes.iter().map(|node| node.hash.clone()).collect();
         internal_nodes.push(level_nodes);
-        current_level = next_level;
         level += 1;
     }
 
@@ -152,14 +334,13 @@ pub fn verify_tree(tree: &MerkleTree, get_chunk_data: impl Fn(&str) -> Option<Ve
     }
 
     // Verify internal nodes bottom-up
-    let mut current_level: Vec<String> = tree.chunks.iter().map(|c| c.hash.clone()).collect();
+    let mut current_level: Vec<String> = tree.chunks.iter().map(|c| leaf_hash(&c.hash)).collect();
 
     for level_nodes in &tree.internal_nodes {
         let mut next_level = Vec::new();
 
         for node in level_nodes {
-            let combined: String = node.children.join("");
-            let computed_hash = blake3::hash(combined.as_bytes()).to_hex().to_string();
+            let computed_hash = node_hash(&node.children);
 
             if computed_hash != node.hash {
                 return Err(MerkleError::IntegrityError {
@@ -187,6 +368,70 @@ pub fn verify_tree(tree: &MerkleTree, get_chunk_data: impl Fn(&str) -> Option<Ve
     }
 }
 
+/// Check a Merkle inclusion proof against a root hash
+///
+/// Recomputes each parent hash from `chunk_hash` and the proof's siblings.
+/// A proof without steps is for a single-chunk tree, whose root is the
+/// chunk's hash. Returns false for any mismatch, including a malformed proof.
+pub fn verify_proof(root_hash: &str, chunk_hash: &str, proof: &MerkleProof) -> bool {
+    if proof.steps.is_empty() {
+        return chunk_hash == root_hash;
+    }
+
+    let mut current = leaf_hash(chunk_hash);
+
+    for step in &proof.steps {
+        if step.position > step.siblings.len() {
+            return false;
+        }
+        // Hashes are fixed-width hex, so a sibling of another length could
+        // shift bytes across the concatenation
+        if step.siblings.iter().any(|h| h.len() != current.len()) {
+            return false;
+        }
+
+        let mut children: Vec<&str> = step.siblings.iter().map(String::as_str).collect();
+        children.insert(step.position, &current);
+        current = node_hash(&children);
+    }
+
+    current == root_hash
+}
+
+/// Read the exact bytes [start, end) of a blob, fetching only the covering chunks
+///
+/// `end` is clamped to the tree's total size; an empty range yields no bytes.
+pub fn read_range(tree: &MerkleTree, store: &ChunkStore, start: u64, end: u64) -> Result<Vec<u8>, MerkleError> {
+    let end = end.min(tree.total_size);
+    if start >= end {
+        return Ok(Vec::new());
+    }
+
+    let mut result = Vec::with_capacity((end - start) as usize);
+    for chunk in tree.chunks_in_range(start, end) {
+        let data = store.get(&chunk.hash).map_err(|e| match e {
+            ChunkStoreError::NotFound(hash) => MerkleError::ChunkNotFound(hash),
+            other => MerkleError::ChunkStore(other.to_string()),
+        })?;
+
+        // Slice the overlap of [start, end) with this chunk, relative to the chunk
+        let (chunk_start, chunk_end) = chunk.byte_range;
+        let from = (start.max(chunk_start) - chunk_start) as usize;
+        let to = (end.min(chunk_end) - chunk_start) as usize;
+        if to > data.len() {
+            return Err(MerkleError::TreeCorruption(format!(
+                "chunk {} is {} bytes, expected {}",
+                chunk.hash,
+                data.len(),
+                chunk.size
+            )));
+        }
+        result.extend_from_slice(&data[from..to]);
+    }
+
+    Ok(result)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -251,6 +496,19 @@ mod tests {
         assert!(matches!(result, Err(MerkleError::InvalidChunkSize(0))));
     }
 
+    #[test]
+    fn test_build_tree_from_chunks_matches_build_tree() {
+        let data: Vec<u8> = (0..3072).map(|i| (i % 251) as u8).collect();
+        let config = default_config();
+        let tree = build_tree(&data, &config).unwrap();
+
+        let chunks: Vec<(String, u64)> = tree.chunks.iter().map(|c| (c.hash.clone(), c.size)).collect();
+        let from_chunks = build_tree_from_chunks(&chunks, &config).unwrap();
+        assert_eq!(from_chunks, tree);
+
+        assert!(matches!(build_tree_from_chunks(&[], &config), Err(MerkleError::EmptyData)));
+    }
+
     #[test]
     fn test_diff_identical_trees() {
         let data = b"identical data for both trees";
@@ -422,6 +680,38 @@ mod tests {
         assert_eq!(chunks.len(), 4);
     }
 
+    #[test]
+    fn test_read_range() {
+        let dir = std::env::temp_dir().join(format!("udr_merkle_test_{}", uuid::Uuid::new_v4()));
+        let store = ChunkStore::new(&dir).unwrap();
+
+        let data: Vec<u8> = (0..4096u64)
+            .flat_map(|i| blake3::hash(&i.to_le_bytes()).as_bytes()[0..1].to_vec())
+            .collect();
+        let config = MerkleConfig::new(1024);
+        let tree = build_tree(&data, &config).unwrap();
+        for chunk in &tree.chunks {
+            store.put(&data[chunk.byte_range.0 as usize..chunk.byte_range.1 as usize]).unwrap();
+        }
+
+        // Mid-blob range spanning chunks 0-2
+        let hashes = tree.chunks_for_byte_range(1000, 2100);
+        assert_eq!(hashes, vec![
+            tree.chunks[0].hash.clone(),
+            tree.chunks[1].hash.clone(),
+            tree.chunks[2].hash.clone(),
+        ]);
+
+        let bytes = read_range(&tree, &store, 1000, 2100).unwrap();
+        assert_eq!(bytes, &data[1000..2100]);
+
+        // End past the blob is clamped; empty range yields nothing
+        assert_eq!(read_range(&tree, &store, 4000, 9999).unwrap(), &data[4000..]);
+        assert!(read_range(&tree, &store, 500, 500).unwrap().is_empty());
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
     #[test]
     fn test_tree_height() {
         let config = MerkleConfig::new(1024);
@@ -477,4 +767,307 @@ mod tests {
         // Should be 95% reuse
         assert!((diff.reuse_ratio - 0.95).abs() < 0.001);
     }
+
+    #[test]
+    fn test_content_defined_chunking_survives_insertion() {
+        // 1 MB of pseudo-random bytes
+        let mut state = 0x9E37_79B9_7F4A_7C15u64;
+        let data: Vec<u8> = (0..1024 * 1024)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                (state >> 56) as u8
+            })
+            .collect();
+        let mut edited = data.clone();
+        edited.insert(100, 0xAB);
+
+        // Fixed-size: every boundary after the insertion shifts
+        let fixed = MerkleConfig::new(8 * 1024);
+        let diff = diff_trees(&build_tree(&data, &fixed).unwrap(), &build_tree(&edited, &fixed).unwrap());
+        assert!(diff.reuse_ratio < 0.1, "fixed reuse {}", diff.reuse_ratio);
+
+        // Content-defined: boundaries resynchronize right after the edit
+        let cdc = MerkleConfig::content_defined(2 * 1024, 8 * 1024, 32 * 1024);
+        let old = build_tree(&data, &cdc).unwrap();
+        let new = build_tree(&edited, &cdc).unwrap();
+        let diff = diff_trees(&old, &new);
+        assert!(diff.reuse_ratio > 0.9, "content-defined reuse {}", diff.reuse_ratio);
+        assert_eq!(new.total_size, data.len() as u64 + 1);
+        assert!(old.chunks.iter().any(|c| c.size != old.chunks[0].size));
+    }
+
+    #[test]
+    fn test_content_defined_rejects_bad_bounds() {
+        let data = vec![1u8; 4096];
+        for (min, avg, max) in [(0, 8, 16), (16, 8, 32), (4, 64, 32)] {
+            assert!(matches!(
+                build_tree(&data, &MerkleConfig::content_defined(min, avg, max)),
+                Err(MerkleError::InvalidChunkBounds { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_configs() {
+        assert!(MerkleConfig::default().validate().is_ok());
+        assert!(matches!(
+            MerkleConfig::new(0).validate(),
+            Err(MerkleError::InvalidChunkSize(0))
+        ));
+        for factor in [0, 1] {
+            let config = MerkleConfig::new(1024).with_branching_factor(factor);
+            assert!(matches!(
+                config.validate(),
+                Err(MerkleError::InvalidBranchingFactor(f)) if f == factor
+            ));
+            // Rejected up front instead of looping forever
+            assert!(build_tree(b"data", &config).is_err());
+        }
+        assert!(matches!(
+            MerkleConfig::content_defined(0, 0, 0).validate(),
+            Err(MerkleError::InvalidChunkBounds { .. })
+        ));
+    }
+
+    #[test]
+    fn test_estimates_match_build_tree() {
+        for (chunk_size, factor) in [(1024, 2), (1000, 3), (512, 4), (4096, 2)] {
+            let config = MerkleConfig::new(chunk_size).with_branching_factor(factor);
+            for len in [1, 1000, 1024, 1025, 5000, 20_000] {
+                let data: Vec<u8> = (0..len).map(|i| (i % 251) as u8).collect();
+                let tree = build_tree(&data, &config).unwrap();
+                assert_eq!(config.estimate_chunks(len), tree.chunks.len(), "{:?} len {}", config, len);
+                assert_eq!(config.estimate_height(len), tree.height, "{:?} len {}", config, len);
+            }
+        }
+
+        let config = MerkleConfig::default();
+        assert_eq!(config.estimate_chunks(0), 0);
+        assert_eq!(config.estimate_height(0), 0);
+    }
+
+    #[test]
+    fn test_proofs_verify_for_every_chunk_and_branching_factor() {
+        for factor in [2, 3, 4, 16] {
+            let config = MerkleConfig::new(100).with_branching_factor(factor);
+            // 23 chunks leaves a short last group at most levels
+            let data: Vec<u8> = (0..2250).map(|i| (i % 251) as u8).collect();
+            let tree = build_tree(&data, &config).unwrap();
+
+            for chunk in &tree.chunks {
+                let proof = tree.generate_proof(chunk.index).unwrap();
+                assert_eq!(proof.steps.len(), tree.internal_nodes.len());
+                assert!(verify_proof(&tree.root_hash, &chunk.hash, &proof), "factor {} chunk {}", factor, chunk.index);
+
+                let restored = MerkleProof::from_json(&proof.to_json().unwrap()).unwrap();
+                assert_eq!(restored, proof);
+            }
+
+            // Right proof, wrong chunk
+            let proof = tree.generate_proof(0).unwrap();
+            assert!(!verify_proof(&tree.root_hash, &tree.chunks[1].hash, &proof));
+        }
+
+        let single = build_tree(b"tiny", &default_config()).unwrap();
+        let proof = single.generate_proof(0).unwrap();
+        assert!(proof.steps.is_empty());
+        assert!(verify_proof(&single.root_hash, &single.chunks[0].hash, &proof));
+
+        assert!(matches!(
+            single.generate_proof(1),
+            Err(MerkleError::ChunkIndexOutOfRange { index: 1, count: 1 })
+        ));
+    }
+
+    #[test]
+    fn test_tampered_proof_fails_verification() {
+        let config = MerkleConfig::new(100).with_branching_factor(3);
+        let data: Vec<u8> = (0..2250).map(|i| (i % 251) as u8).collect();
+        let tree = build_tree(&data, &config).unwrap();
+        let chunk = &tree.chunks[10];
+        let proof = tree.generate_proof(chunk.index).unwrap();
+        assert!(verify_proof(&tree.root_hash, &chunk.hash, &proof));
+
+        // Flip one hex digit of every sibling hash in turn
+        for step in 0..proof.steps.len() {
+            for sibling in 0..proof.steps[step].siblings.len() {
+                let mut tampered = proof.clone();
+                let hash = &mut tampered.steps[step].siblings[sibling];
+                let flipped = if hash.starts_with('0') { "1" } else { "0" };
+                hash.replace_range(0..1, flipped);
+                assert!(!verify_proof(&tree.root_hash, &chunk.hash, &tampered), "step {} sibling {}", step, sibling);
+            }
+
+            let mut moved = proof.clone();
+            let len = moved.steps[step].siblings.len();
+            moved.steps[step].position = (moved.steps[step].position + 1) % (len + 1);
+            assert!(!verify_proof(&tree.root_hash, &chunk.hash, &moved), "step {} position", step);
+
+            let mut dropped = proof.clone();
+            dropped.steps.remove(step);
+            assert!(!verify_proof(&tree.root_hash, &chunk.hash, &dropped), "step {} dropped", step);
+        }
+
+        let mut out_of_range = proof.clone();
+        out_of_range.steps[0].position = 99;
+        assert!(!verify_proof(&tree.root_hash, &chunk.hash, &out_of_range));
+        assert!(!verify_proof(&tree.chunks[0].hash, &chunk.hash, &proof));
+    }
+
+    #[test]
+    fn test_internal_node_cannot_pass_as_chunk() {
+        let config = MerkleConfig::new(100).with_branching_factor(3);
+        let data: Vec<u8> = (0..2250).map(|i| (i % 251) as u8).collect();
+        let tree = build_tree(&data, &config).unwrap();
+        let proof = tree.generate_proof(10).unwrap();
+
+        // The chunk's parent, proven with the rest of the chunk's path
+        let parent = &tree.internal_nodes[0][10 / 3];
+        let shortened = MerkleProof {
+            chunk_index: parent.index,
+            steps: proof.steps[1..].to_vec(),
+        };
+        assert!(!verify_proof(&tree.root_hash, &parent.hash, &shortened));
+
+        // Only the root itself passes as a single-chunk tree
+        let empty = MerkleProof { chunk_index: 0, steps: Vec::new() };
+        assert!(!verify_proof(&tree.root_hash, &tree.chunks[0].hash, &empty));
+        assert!(!verify_proof(&tree.root_hash, &parent.hash, &empty));
+
+        // Neither tree hash equals the plain content hash of its input
+        assert_ne!(leaf_hash(&parent.hash), blake3::hash(parent.hash.as_bytes()).to_hex().to_string());
+
+        // Leaf and node hashes of the same input differ
+        assert_ne!(leaf_hash(&parent.hash), node_hash(&[parent.hash.as_str()]));
+    }
+
+    #[test]
+    fn test_parallel_build_matches_sequential() {
+        let data: Vec<u8> = (0..300_000u32).map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8).collect();
+        let configs = [
+            MerkleConfig::new(256),
+            MerkleConfig::new(1000).with_branching_factor(3),
+            MerkleConfig::content_defined(256, 1024, 4096).with_branching_factor(4),
+        ];
+
+        for config in &configs {
+            let sequential = build_tree_with_threshold(&data, config, usize::MAX).unwrap();
+            let parallel = build_tree_with_threshold(&data, config, 0).unwrap();
+            assert!(sequential.chunks.len() > PARALLEL_CHUNK_THRESHOLD);
+            assert_eq!(parallel.root_hash, sequential.root_hash, "{:?}", config);
+            assert_eq!(parallel, sequential, "{:?}", config);
+            assert_eq!(build_tree(&data, config).unwrap(), sequential);
+        }
+    }
+
+    #[test]
+    fn test_rebuild_hashes_only_changed_leaves() {
+        let config = MerkleConfig::new(1024);
+        let data: Vec<u8> = (0..64 * 1024u32).map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8).collect();
+        let old_tree = build_tree(&data, &config).unwrap();
+
+        // Edit bytes inside chunks 3 and 40
+        let mut new_data = data.clone();
+        new_data[3 * 1024 + 10] ^= 0xFF;
+        new_data[40 * 1024 + 500..40 * 1024 + 520].fill(7);
+
+        let (rebuilt, hashed) = rebuild_tree_counted(&old_tree, &new_data, &config).unwrap();
+        assert_eq!(hashed, 2);
+        assert_eq!(rebuilt, build_tree(&new_data, &config).unwrap());
+        assert_ne!(rebuilt.root_hash, old_tree.root_hash);
+
+        // Appending a chunk only hashes the new tail
+        let mut appended = data.clone();
+        appended.extend_from_slice(&[1u8; 1024]);
+        let (rebuilt, hashed) = rebuild_tree_counted(&old_tree, &appended, &config).unwrap();
+        assert_eq!(hashed, 1);
+        assert_eq!(rebuilt, build_tree(&appended, &config).unwrap());
+
+        // No edits: nothing is hashed and the tree is unchanged
+        let (rebuilt, hashed) = rebuild_tree_counted(&old_tree, &data, &config).unwrap();
+        assert_eq!(hashed, 0);
+        assert_eq!(rebuilt, old_tree);
+
+        // A different branching factor reuses leaves but still builds a correct tree
+        let ternary = MerkleConfig::new(1024).with_branching_factor(3);
+        let rebuilt = rebuild_tree(&old_tree, &new_data, &ternary).unwrap();
+        assert_eq!(rebuilt, build_tree(&new_data, &ternary).unwrap());
+
+        // Content-defined chunks shifted by an insert are still found
+        let cdc = MerkleConfig::content_defined(256, 1024, 4096);
+        let old_tree = build_tree(&data, &cdc).unwrap();
+        let mut inserted = data.clone();
+        inserted.splice(30 * 1024..30 * 1024, [9u8; 100]);
+        let (rebuilt, hashed) = rebuild_tree_counted(&old_tree, &inserted, &cdc).unwrap();
+        let expected = build_tree(&inserted, &cdc).unwrap();
+        let changed = expected.chunks.iter().filter(|c| !old_tree.chunks.iter().any(|o| o.hash == c.hash)).count();
+        assert_eq!(hashed, changed);
+        assert!(hashed < 4);
+        assert_eq!(rebuilt, expected);
+    }
+
+    #[test]
+    fn test_tree_bytes_roundtrip() {
+        let config = MerkleConfig::new(1000).with_branching_factor(3);
+        let data: Vec<u8> = (0..12_345u32).map(|i| (i % 251) as u8).collect();
+        let tree = build_tree(&data, &config).unwrap();
+
+        let restored = MerkleTree::from_bytes(&tree.to_bytes().unwrap()).unwrap();
+        assert_eq!(restored.root_hash, tree.root_hash);
+        assert_eq!(restored.chunks, tree.chunks);
+        assert_eq!(restored.internal_nodes, tree.internal_nodes);
+        assert_eq!(restored.total_size, tree.total_size);
+        assert_eq!(restored.chunk_size, tree.chunk_size);
+        assert_eq!(restored.height, tree.height);
+
+        for offset in [0, 999, 1000, 6500, 12_344, 12_345] {
+            assert_eq!(restored.chunk_for_offset(offset), tree.chunk_for_offset(offset), "offset {}", offset);
+        }
+        for (start, end) in [(0, 1), (950, 2050), (5000, 5000), (12_000, 20_000)] {
+            assert_eq!(restored.chunks_in_range(start, end), tree.chunks_in_range(start, end));
+        }
+
+        assert!(matches!(
+            MerkleTree::from_bytes(b"not a tree"),
+            Err(MerkleError::Serialization(_))
+        ));
+    }
+
+    #[test]
+    fn test_summary_diverges_only_on_changed_path() {
+        // 8 distinct chunks: levels 0 (chunks), 1, 2 and 3 (root)
+        let data: Vec<u8> = (0..8192u32)
+            .map(|i| ((i / 1024) as u8).wrapping_add((i % 256) as u8))
+            .collect();
+        let mut changed = data.clone();
+        changed[5 * 1024] ^= 0xFF;
+
+        let old = build_tree(&data, &default_config()).unwrap();
+        let new = build_tree(&changed, &default_config()).unwrap();
+        assert_eq!(old.height, 4);
+
+        let top = old.summary(2);
+        assert_eq!(top.max_level, 2);
+        assert_eq!(top.nodes.len(), 3);
+        assert_eq!(top.nodes[0].hash, old.root_hash);
+        assert_eq!(top.level(2).len(), 2);
+
+        // Chunk 5 sits under level-2 node 1 and level-1 node 2; everything
+        // off that path matches
+        assert_eq!(top.differing_nodes(&new.summary(2)), vec![(3, 0), (2, 1)]);
+        assert_eq!(old.summary(2).level(2)[0], new.summary(2).level(2)[0]);
+        assert_eq!(
+            old.summary(0).differing_nodes(&new.summary(0)),
+            vec![(3, 0), (2, 1), (1, 2), (0, 5)]
+        );
+        assert_eq!(old.summary(0).nodes.len(), 15);
+
+        // Levels above the root clamp to just the root
+        let root_only = old.summary(10);
+        assert_eq!(root_only.max_level, 3);
+        assert_eq!(root_only.nodes.len(), 1);
+        assert!(old.summary(1).differing_nodes(&old.summary(1)).is_empty());
+    }
 }
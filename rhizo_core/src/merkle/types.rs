@@ -1,5 +1,10 @@
+use std::collections::HashMap;
+
 use serde::{Deserialize, Serialize};
 
+use super::error::MerkleError;
+use super::tree::leaf_hash;
+
 /// A leaf node in the Merkle tree - contains actual data
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct DataChunk {
@@ -27,7 +32,7 @@ impl DataChunk {
 /// Internal node in the Merkle tree
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct MerkleNode {
-    /// BLAKE3 hash of concatenated child hashes
+    /// Keyed BLAKE3 hash of the concatenated child hashes
     pub hash: String,
     /// Hashes of child nodes (2 for binary tree, but can vary)
     pub children: Vec<String>,
@@ -50,7 +55,7 @@ impl MerkleNode {
     /// Create a leaf node reference (points to a DataChunk)
     pub fn leaf(chunk_hash: String, index: usize) -> Self {
         Self {
-            hash: chunk_hash.clone(),
+            hash: leaf_hash(&chunk_hash),
             children: vec![chunk_hash],
             level: 0,
             index,
@@ -96,6 +101,204 @@ impl MerkleTree {
             c.byte_range.0 < end && c.byte_range.1 > start
         }).collect()
     }
+
+    /// Get the hashes of the chunks needed to read a byte range [start, end)
+    pub fn chunks_for_byte_range(&self, start: u64, end: u64) -> Vec<String> {
+        self.chunks_in_range(start, end)
+            .into_iter()
+            .map(|c| c.hash.clone())
+            .collect()
+    }
+
+    /// Serialize the whole tree (chunks, internal nodes and sizes) to JSON
+    /// bytes, so it can be stored next to its chunks
+    pub fn to_bytes(&self) -> Result<Vec<u8>, MerkleError> {
+        serde_json::to_vec(self).map_err(|e| MerkleError::Serialization(e.to_string()))
+    }
+
+    /// Load a tree written by `to_bytes`
+    pub fn from_bytes(data: &[u8]) -> Result<Self, MerkleError> {
+        serde_json::from_slice(data).map_err(|e| MerkleError::Serialization(e.to_string()))
+    }
+
+    /// Build an inclusion proof for the chunk at `chunk_index`
+    ///
+    /// The proof holds, for each level from the leaves up, the sibling hashes
+    /// of the node on the path to the root. Works for any branching factor,
+    /// including a last group with fewer children.
+    pub fn generate_proof(&self, chunk_index: usize) -> Result<MerkleProof, MerkleError> {
+        let chunk = self.chunks.get(chunk_index).ok_or(MerkleError::ChunkIndexOutOfRange {
+            index: chunk_index,
+            count: self.chunks.len(),
+        })?;
+
+        let mut steps = Vec::with_capacity(self.internal_nodes.len());
+        // A single chunk is the root itself
+        let mut current = if self.internal_nodes.is_empty() {
+            chunk.hash.clone()
+        } else {
+            leaf_hash(&chunk.hash)
+        };
+        let mut index = chunk_index;
+
+        for level_nodes in &self.internal_nodes {
+            // Parents are stored in order, so walk them until one covers `index`
+            let mut first_child = 0usize;
+            let mut parent = None;
+            for (i, node) in level_nodes.iter().enumerate() {
+                if index < first_child + node.children.len() {
+                    parent = Some((i, node));
+                    break;
+                }
+                first_child += node.children.len();
+            }
+            let (parent_index, node) = parent.ok_or_else(|| {
+                MerkleError::TreeCorruption(format!("No parent for node {} at level {}", index, steps.len()))
+            })?;
+
+            let position = index - first_child;
+            if node.children[position] != current {
+                return Err(MerkleError::TreeCorruption(format!(
+                    "Child {} of node {} does not match the path being proven",
+                    position, node.hash
+                )));
+            }
+
+            let siblings = node
+                .children
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != position)
+                .map(|(_, h)| h.clone())
+                .collect();
+            steps.push(ProofStep { position, siblings });
+
+            current = node.hash.clone();
+            index = parent_index;
+        }
+
+        if current != self.root_hash {
+            return Err(MerkleError::TreeCorruption(
+                "Proof path does not end at the root hash".to_string(),
+            ));
+        }
+
+        Ok(MerkleProof {
+            chunk_index,
+            steps,
+        })
+    }
+
+    /// Summarize the tree down to `max_level`
+    ///
+    /// Keeps every node at `max_level` or above (levels count up from the
+    /// chunks at 0), ordered root first. Comparing two summaries with
+    /// `TreeSummary::differing_nodes` narrows a diff without shipping the
+    /// whole tree. A `max_level` above the root yields just the root.
+    pub fn summary(&self, max_level: u32) -> TreeSummary {
+        let top = self.height.saturating_sub(1);
+        let max_level = max_level.min(top);
+
+        let mut nodes = Vec::new();
+        for level in (max_level..=top).rev() {
+            if level == 0 {
+                nodes.extend(
+                    self.chunks
+                        .iter()
+                        .map(|c| MerkleNode::leaf(c.hash.clone(), c.index)),
+                );
+            } else if let Some(level_nodes) = self.internal_nodes.get(level as usize - 1) {
+                nodes.extend(level_nodes.iter().cloned());
+            }
+        }
+
+        TreeSummary {
+            root_hash: self.root_hash.clone(),
+            height: self.height,
+            max_level,
+            nodes,
+        }
+    }
+}
+
+/// The upper levels of a Merkle tree, from `MerkleTree::summary`
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TreeSummary {
+    /// Root hash of the summarized tree
+    pub root_hash: String,
+    /// Height of the summarized tree
+    pub height: u32,
+    /// Lowest level included (clamped to the root level)
+    pub max_level: u32,
+    /// Nodes at `max_level` or above, root level first
+    pub nodes: Vec<MerkleNode>,
+}
+
+impl TreeSummary {
+    /// Nodes at one level, ordered by index
+    pub fn level(&self, level: u32) -> Vec<&MerkleNode> {
+        self.nodes.iter().filter(|n| n.level == level).collect()
+    }
+
+    /// `(level, index)` of every position whose hash differs from `other`
+    ///
+    /// A position present in only one summary counts as differing. Levels
+    /// are compared from the leaves up, so trees of different heights only
+    /// line up if they have the same shape.
+    pub fn differing_nodes(&self, other: &TreeSummary) -> Vec<(u32, usize)> {
+        let theirs: HashMap<(u32, usize), &str> = other
+            .nodes
+            .iter()
+            .map(|n| ((n.level, n.index), n.hash.as_str()))
+            .collect();
+        let ours: HashMap<(u32, usize), &str> = self
+            .nodes
+            .iter()
+            .map(|n| ((n.level, n.index), n.hash.as_str()))
+            .collect();
+
+        let mut differing: Vec<(u32, usize)> = ours
+            .iter()
+            .filter(|(key, hash)| theirs.get(*key) != Some(*hash))
+            .map(|(key, _)| *key)
+            .chain(theirs.keys().filter(|key| !ours.contains_key(*key)).copied())
+            .collect();
+        differing.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        differing
+    }
+}
+
+/// One level of a Merkle inclusion proof
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProofStep {
+    /// Position of the proven node among its parent's children
+    pub position: usize,
+    /// The parent's other children, in order
+    pub siblings: Vec<String>,
+}
+
+/// Inclusion proof that a chunk belongs to a tree with a given root
+///
+/// Verify with `verify_proof`. A single-chunk tree has no steps, since the
+/// chunk's hash is the root.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MerkleProof {
+    /// Index of the proven chunk
+    pub chunk_index: usize,
+    /// Sibling hashes from the leaf level up to the root
+    pub steps: Vec<ProofStep>,
+}
+
+impl MerkleProof {
+    /// Serialize to JSON
+    pub fn to_json(&self) -> Result<String, MerkleError> {
+        serde_json::to_string(self).map_err(|e| MerkleError::Serialization(e.to_string()))
+    }
+
+    /// Deserialize from JSON
+    pub fn from_json(json: &str) -> Result<Self, MerkleError> {
+        serde_json::from_str(json).map_err(|e| MerkleError::Serialization(e.to_string()))
+    }
 }
 
 /// Result of comparing two Merkle trees
@@ -138,6 +341,24 @@ impl Default for MerkleDiff {
     }
 }
 
+/// How `build_tree` splits data into chunks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Chunking {
+    /// Fixed `chunk_size` blocks (the last one may be shorter)
+    #[default]
+    Fixed,
+    /// Content-defined boundaries from a rolling hash (FastCDC), so an
+    /// insertion only changes the chunks around it
+    ContentDefined {
+        /// Smallest chunk, except for the last one
+        min: usize,
+        /// Target average chunk size
+        avg: usize,
+        /// Largest chunk
+        max: usize,
+    },
+}
+
 /// Configuration for Merkle tree building
 #[derive(Debug, Clone)]
 pub struct MerkleConfig {
@@ -145,6 +366,8 @@ pub struct MerkleConfig {
     pub chunk_size: usize,
     /// Branching factor for tree (default: 2 for binary)
     pub branching_factor: usize,
+    /// Chunk boundary strategy (default: fixed-size)
+    pub chunking: Chunking,
 }
 
 impl Default for MerkleConfig {
@@ -152,6 +375,7 @@ impl Default for MerkleConfig {
         Self {
             chunk_size: 64 * 1024, // 64 KB
             branching_factor: 2,
+            chunking: Chunking::Fixed,
         }
     }
 }
@@ -164,8 +388,69 @@ impl MerkleConfig {
         }
     }
 
+    /// Content-defined chunking with sizes between `min` and `max` bytes,
+    /// averaging about `avg`.
+    ///
+    /// `chunk_size` is set to `avg`. Bounds must satisfy
+    /// `0 < min <= avg <= max`, which `build_tree` checks.
+    pub fn content_defined(min: usize, avg: usize, max: usize) -> Self {
+        Self {
+            chunk_size: avg,
+            chunking: Chunking::ContentDefined { min, avg, max },
+            ..Default::default()
+        }
+    }
+
     pub fn with_branching_factor(mut self, factor: usize) -> Self {
         self.branching_factor = factor;
         self
     }
+
+    /// Check that the config can build a tree.
+    ///
+    /// # Errors
+    /// - `MerkleError::InvalidChunkSize` if fixed-size chunking has `chunk_size` 0
+    /// - `MerkleError::InvalidChunkBounds` unless content-defined bounds
+    ///   satisfy `0 < min <= avg <= max`
+    /// - `MerkleError::InvalidBranchingFactor` if `branching_factor` is below 2
+    pub fn validate(&self) -> Result<(), MerkleError> {
+        match self.chunking {
+            Chunking::Fixed if self.chunk_size == 0 => {
+                return Err(MerkleError::InvalidChunkSize(0));
+            }
+            Chunking::ContentDefined { min, avg, max } if min == 0 || min > avg || avg > max => {
+                return Err(MerkleError::InvalidChunkBounds { min, avg, max });
+            }
+            _ => {}
+        }
+        if self.branching_factor < 2 {
+            return Err(MerkleError::InvalidBranchingFactor(self.branching_factor));
+        }
+        Ok(())
+    }
+
+    /// Number of leaf chunks `build_tree` produces for `data_len` bytes.
+    ///
+    /// Exact for fixed-size chunking; for content-defined chunking it
+    /// assumes every chunk is `avg` bytes, so treat it as an estimate.
+    pub fn estimate_chunks(&self, data_len: usize) -> usize {
+        data_len.div_ceil(self.chunk_size.max(1))
+    }
+
+    /// Tree height `build_tree` produces for `data_len` bytes (0 if empty).
+    ///
+    /// Exact whenever `estimate_chunks` is.
+    pub fn estimate_height(&self, data_len: usize) -> u32 {
+        let mut nodes = self.estimate_chunks(data_len);
+        if nodes == 0 {
+            return 0;
+        }
+        let factor = self.branching_factor.max(2);
+        let mut height = 1;
+        while nodes > 1 {
+            nodes = nodes.div_ceil(factor);
+            height += 1;
+        }
+        height
+    }
 }
@@ -0,0 +1,117 @@
+//! Thread pool used by the crate's parallel operations.
+//!
+//! Batch operations such as `ChunkStore::put_batch`,
+//! `ParquetDecoder::decode_batch` and `build_tree` run on Rayon. By default
+//! they share Rayon's global pool, which sizes itself to every CPU on the
+//! host. Call [`configure_parallelism`] at startup to run them on a
+//! dedicated pool of a fixed size instead.
+//!
+//! # Example
+//!
+//! ```
+//! use rhizo_core::{configure_parallelism, current_parallelism};
+//!
+//! configure_parallelism(2).unwrap();
+//! assert_eq!(current_parallelism(), 2);
+//!
+//! // Back to Rayon's global pool
+//! configure_parallelism(0).unwrap();
+//! ```
+
+use rayon::{ThreadPool, ThreadPoolBuildError, ThreadPoolBuilder};
+use std::sync::{Arc, RwLock};
+
+/// Dedicated pool (None = Rayon's global pool)
+static POOL: RwLock<Option<Arc<ThreadPool>>> = RwLock::new(None);
+
+/// Run the crate's parallel operations on a dedicated pool of `n` threads.
+///
+/// Passing 0 drops the dedicated pool and returns to Rayon's global pool.
+/// Meant to be called once at startup; operations already running keep the
+/// pool they started on.
+///
+/// # Errors
+/// Returns the Rayon error if the pool's threads cannot be spawned.
+pub fn configure_parallelism(n: usize) -> Result<(), ThreadPoolBuildError> {
+    let pool = match n {
+        0 => None,
+        n => Some(Arc::new(
+            ThreadPoolBuilder::new()
+                .num_threads(n)
+                .thread_name(|i| format!("rhizo-{}", i))
+                .build()?,
+        )),
+    };
+    *POOL.write().unwrap_or_else(|e| e.into_inner()) = pool;
+    Ok(())
+}
+
+/// Number of threads the crate's parallel operations run on.
+pub fn current_parallelism() -> usize {
+    match pool() {
+        Some(pool) => pool.current_num_threads(),
+        None => rayon::current_num_threads(),
+    }
+}
+
+/// Run `op` on the configured pool, so any Rayon work inside it uses that
+/// pool.
+pub(crate) fn install<R, F>(op: F) -> R
+where
+    R: Send,
+    F: FnOnce() -> R + Send,
+{
+    match pool() {
+        Some(pool) => pool.install(op),
+        None => op(),
+    }
+}
+
+fn pool() -> Option<Arc<ThreadPool>> {
+    POOL.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chunk_store::ChunkStore;
+    use crate::merkle::{build_tree, MerkleConfig};
+    use crate::parquet::{ParquetDecoder, ParquetEncoder};
+    use arrow::array::Int64Array;
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::record_batch::RecordBatch;
+
+    #[test]
+    fn test_results_match_across_pool_sizes() {
+        let dir = std::env::temp_dir().join(format!("rhizo_parallel_{}", std::process::id()));
+        let store = ChunkStore::new(&dir).unwrap();
+        let schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)]));
+        let batches: Vec<RecordBatch> = (0..16)
+            .map(|i| {
+                let ids: Vec<i64> = (i * 100..(i + 1) * 100).collect();
+                RecordBatch::try_new(schema.clone(), vec![Arc::new(Int64Array::from(ids))]).unwrap()
+            })
+            .collect();
+        let data: Vec<u8> = (0..200_000u32).flat_map(|i| i.to_le_bytes()).collect();
+        let config = MerkleConfig::new(1024);
+
+        let mut results = Vec::new();
+        for threads in [1, 8] {
+            configure_parallelism(threads).unwrap();
+            assert_eq!(current_parallelism(), threads);
+
+            let encoded = ParquetEncoder::new().encode_batch(&batches).unwrap();
+            let chunks: Vec<&[u8]> = encoded.iter().map(|e| e.as_slice()).collect();
+            let hashes = store.put_batch(&chunks).unwrap();
+            let decoded = ParquetDecoder::new().decode_batch(&chunks).unwrap();
+            let tree = build_tree(&data, &config).unwrap();
+
+            assert_eq!(decoded, batches);
+            results.push((hashes, tree.root_hash));
+        }
+        configure_parallelism(0).unwrap();
+
+        assert_eq!(results[0], results[1]);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,169 @@
+//! Row-level equivalence checks between decoded batches.
+//!
+//! Used to confirm that two encodings of the same logical data (for example
+//! with different compression or row group settings) decode to the same
+//! rows.
+
+use arrow::array::{Array, AsArray};
+use arrow::datatypes::{DataType, Float32Type, Float64Type};
+use arrow::error::ArrowError;
+use arrow::record_batch::RecordBatch;
+
+use super::error::ParquetError;
+
+/// Default absolute tolerance for float comparisons.
+pub const DEFAULT_FLOAT_TOLERANCE: f64 = 1e-9;
+
+/// Check whether two batches hold the same rows, using
+/// `DEFAULT_FLOAT_TOLERANCE` for float columns.
+pub fn batches_equivalent(a: &RecordBatch, b: &RecordBatch) -> Result<bool, ParquetError> {
+    batches_equivalent_with_tolerance(a, b, DEFAULT_FLOAT_TOLERANCE)
+}
+
+/// Check whether two batches hold the same rows.
+///
+/// Field names, types and nullability must match. Nulls must be in the same
+/// positions. Top-level Float32/Float64 values may differ by up to
+/// `float_tolerance` (NaN equals NaN); every other column, including floats
+/// nested in lists or structs, must be exactly equal.
+///
+/// # Errors
+/// Returns `ParquetError::Arrow` if `float_tolerance` is negative or NaN.
+pub fn batches_equivalent_with_tolerance(
+    a: &RecordBatch,
+    b: &RecordBatch,
+    float_tolerance: f64,
+) -> Result<bool, ParquetError> {
+    if float_tolerance.is_nan() || float_tolerance < 0.0 {
+        return Err(ArrowError::InvalidArgumentError(format!(
+            "float tolerance must be a non-negative number, got {}",
+            float_tolerance
+        ))
+        .into());
+    }
+
+    if a.schema().fields() != b.schema().fields() || a.num_rows() != b.num_rows() {
+        return Ok(false);
+    }
+
+    for (col_a, col_b) in a.columns().iter().zip(b.columns()) {
+        let equal = match col_a.data_type() {
+            DataType::Float64 => floats_close(
+                col_a.as_primitive::<Float64Type>(),
+                col_b.as_primitive::<Float64Type>(),
+                float_tolerance,
+            ),
+            DataType::Float32 => floats_close(
+                col_a.as_primitive::<Float32Type>(),
+                col_b.as_primitive::<Float32Type>(),
+                float_tolerance,
+            ),
+            _ => col_a.to_data() == col_b.to_data(),
+        };
+        if !equal {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+fn floats_close<T>(
+    a: &arrow::array::PrimitiveArray<T>,
+    b: &arrow::array::PrimitiveArray<T>,
+    tolerance: f64,
+) -> bool
+where
+    T: arrow::datatypes::ArrowPrimitiveType,
+    T::Native: Into<f64>,
+{
+    a.iter().zip(b.iter()).all(|pair| match pair {
+        (None, None) => true,
+        (Some(x), Some(y)) => {
+            let (x, y): (f64, f64) = (x.into(), y.into());
+            (x.is_nan() && y.is_nan()) || x == y || (x - y).abs() <= tolerance
+        }
+        _ => false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parquet::{ParquetCompression, ParquetDecoder, ParquetEncoder};
+    use arrow::array::{Float64Array, Int64Array, StringArray};
+    use arrow::datatypes::{Field, Schema};
+    use std::sync::Arc;
+
+    fn batch(values: Vec<Option<f64>>, names: Vec<Option<&str>>) -> RecordBatch {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("value", DataType::Float64, true),
+            Field::new("name", DataType::Utf8, true),
+        ]);
+        let ids: Vec<i64> = (0..values.len() as i64).collect();
+        RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int64Array::from(ids)),
+                Arc::new(Float64Array::from(values)),
+                Arc::new(StringArray::from(names)),
+            ],
+        )
+        .unwrap()
+    }
+
+    fn sample() -> RecordBatch {
+        batch(
+            vec![Some(1.5), None, Some(f64::NAN), Some(-2.25)],
+            vec![Some("a"), None, Some("c"), Some("d")],
+        )
+    }
+
+    fn roundtrip(batch: &RecordBatch, compression: ParquetCompression) -> RecordBatch {
+        let encoded = ParquetEncoder::with_compression(compression).encode(batch).unwrap();
+        ParquetDecoder::new().decode(&encoded).unwrap()
+    }
+
+    #[test]
+    fn test_zstd_and_snappy_encodings_are_equivalent() {
+        let original = sample();
+        let zstd = roundtrip(&original, ParquetCompression::Zstd);
+        let snappy = roundtrip(&original, ParquetCompression::Snappy);
+
+        assert!(batches_equivalent(&zstd, &snappy).unwrap());
+        assert!(batches_equivalent(&original, &zstd).unwrap());
+    }
+
+    #[test]
+    fn test_modified_batch_is_not_equivalent() {
+        let original = roundtrip(&sample(), ParquetCompression::Zstd);
+
+        let changed_name = batch(
+            vec![Some(1.5), None, Some(f64::NAN), Some(-2.25)],
+            vec![Some("a"), None, Some("x"), Some("d")],
+        );
+        assert!(!batches_equivalent(&original, &changed_name).unwrap());
+
+        let moved_null = batch(
+            vec![Some(1.5), Some(0.0), Some(f64::NAN), Some(-2.25)],
+            vec![Some("a"), None, Some("c"), Some("d")],
+        );
+        assert!(!batches_equivalent(&original, &moved_null).unwrap());
+
+        let projected = original.project(&[0, 1]).unwrap();
+        assert!(!batches_equivalent(&original, &projected).unwrap());
+    }
+
+    #[test]
+    fn test_float_tolerance() {
+        let a = sample();
+        let b = batch(
+            vec![Some(1.5 + 1e-12), None, Some(f64::NAN), Some(-2.25)],
+            vec![Some("a"), None, Some("c"), Some("d")],
+        );
+        assert!(batches_equivalent(&a, &b).unwrap());
+        assert!(!batches_equivalent_with_tolerance(&a, &b, 0.0).unwrap());
+        assert!(batches_equivalent_with_tolerance(&a, &b, -1.0).is_err());
+    }
+}
@@ -10,20 +10,27 @@
 //! - `MAX_BATCH_SIZE`: Maximum rows per batch (1M) - prevents excessive memory per batch
 //! - Checked arithmetic for row counts - prevents integer overflow
 
-use arrow::array::{Array, AsArray, BooleanArray};
+use arrow::array::{Array, AsArray, BooleanArray, Datum};
+use arrow::compute::kernels::boolean::{and_kleene, not, or, or_kleene};
 use arrow::compute::kernels::cmp::{eq, gt, gt_eq, lt, lt_eq, neq};
+use arrow::compute::kernels::comparison::{contains, ends_with, starts_with};
+use arrow::compute::{can_cast_types, cast_with_options, CastOptions};
+use arrow::datatypes::{DataType, Schema};
 use arrow::record_batch::RecordBatch;
 use bytes::Bytes;
 use parquet::arrow::arrow_reader::{
-    ArrowPredicateFn, ParquetRecordBatchReaderBuilder, RowFilter, RowSelection,
+    ArrowPredicateFn, ArrowReaderMetadata, ArrowReaderOptions, ParquetRecordBatchReaderBuilder,
+    RowFilter, RowSelection,
 };
 use parquet::arrow::ProjectionMask;
 use parquet::file::metadata::RowGroupMetaData;
 use parquet::file::statistics::Statistics;
 use rayon::prelude::*;
+use std::sync::Arc;
 
+use super::encoder::ParquetEncoder;
 use super::error::ParquetError;
-use super::filter::{FilterOp, PredicateFilter, ScalarValue};
+use super::filter::{FilterOp, PredicateExpr, PredicateFilter, ScalarValue};
 
 /// Maximum file size to decode (100 GB).
 ///
@@ -36,6 +43,67 @@ const MAX_DECODE_SIZE: usize = 100 * 1024 * 1024 * 1024;
 /// At ~1KB/row average, this is ~1GB per batch - a reasonable memory limit.
 const MAX_BATCH_SIZE: usize = 1_000_000;
 
+/// Structured explanation of a filtered decode.
+///
+/// Produced by [`ParquetDecoder::explain_filter`] for query debugging.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterExplain {
+    /// Total number of row groups in the file
+    pub total_row_groups: usize,
+    /// Row groups skipped using min/max statistics
+    pub pruned_row_groups: usize,
+    /// Rows in the row groups that survived pruning
+    pub rows_scanned: usize,
+    /// Rows that matched all filters
+    pub rows_returned: usize,
+    /// Distinct filter columns, in first-seen order
+    pub filter_columns: Vec<String>,
+}
+
+/// Row groups a filtered decode would read, from footer statistics alone.
+///
+/// Produced by [`ParquetDecoder::plan_filter`] for cost estimation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilterPlan {
+    /// Indices of row groups that survive pruning, in file order
+    pub row_groups_to_scan: Vec<usize>,
+    /// Total rows in those row groups (an upper bound on matches)
+    pub estimated_rows: i64,
+}
+
+/// Aggregate to compute with [`ParquetDecoder::aggregate`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AggregateSpec {
+    /// Total number of rows (`COUNT(*)`, nulls included)
+    Count,
+    /// Smallest non-null value of a column
+    Min(String),
+    /// Largest non-null value of a column
+    Max(String),
+}
+
+/// Result of [`ParquetDecoder::aggregate`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AggregateResult {
+    /// The aggregate; `Int64` for `Count`, `Null` for min/max with no non-null values
+    pub value: ScalarValue,
+    /// Whether the value came from footer statistics alone (no data decoded)
+    pub from_statistics: bool,
+}
+
+/// Outcome of [`ParquetDecoder::repair`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepairReport {
+    /// Row groups in the input file
+    pub total_row_groups: usize,
+    /// Indices of row groups that failed to decode and were dropped
+    pub dropped_row_groups: Vec<usize>,
+    /// Rows kept in the repaired file
+    pub rows_kept: usize,
+    /// Rows lost with the dropped row groups (per the footer metadata)
+    pub rows_lost: usize,
+}
+
 /// High-performance Parquet decoder.
 ///
 /// Converts Parquet bytes to Arrow RecordBatches. Supports parallel batch
@@ -109,6 +177,44 @@ impl ParquetDecoder {
         arrow::compute::concat_batches(&schema, &batches).map_err(ParquetError::Arrow)
     }
 
+    /// Decode Parquet bytes as a stream of batches.
+    ///
+    /// Unlike `decode`, batches are not concatenated: each item is one read
+    /// step of at most `batch_size` rows, so decoded data in memory is
+    /// bounded by the batch size rather than the file size. The encoded
+    /// file is taken by value (a `Vec<u8>` or `Bytes` is not copied) and
+    /// held until the iterator is dropped.
+    ///
+    /// # Errors
+    /// * `FileTooLarge` - If data exceeds `MAX_DECODE_SIZE` (100GB)
+    /// * Any footer or schema error; per-batch decode errors are yielded
+    ///   by the iterator
+    ///
+    /// # Example
+    /// ```ignore
+    /// for batch in decoder.decode_stream(bytes)? {
+    ///     process(batch?);
+    /// }
+    /// ```
+    pub fn decode_stream(
+        &self,
+        data: impl Into<Bytes>,
+    ) -> Result<impl Iterator<Item = Result<RecordBatch, ParquetError>> + Send, ParquetError> {
+        let bytes = data.into();
+        if bytes.len() > MAX_DECODE_SIZE {
+            return Err(ParquetError::FileTooLarge {
+                size: bytes.len(),
+                max: MAX_DECODE_SIZE,
+            });
+        }
+
+        let reader = ParquetRecordBatchReaderBuilder::try_new(bytes)?
+            .with_batch_size(self.batch_size)
+            .build()?;
+
+        Ok(reader.map(|batch| batch.map_err(ParquetError::Arrow)))
+    }
+
     /// Decode multiple Parquet chunks in parallel using Rayon.
     ///
     /// This is significantly faster than decoding sequentially when you have
@@ -121,7 +227,7 @@ impl ParquetDecoder {
     /// * `Ok(Vec<RecordBatch>)` - Decoded batches for each chunk, in same order
     /// * `Err(ParquetError)` - If any decoding fails (returns first error)
     pub fn decode_batch(&self, chunks: &[&[u8]]) -> Result<Vec<RecordBatch>, ParquetError> {
-        chunks.par_iter().map(|chunk| self.decode(chunk)).collect()
+        crate::parallel::install(|| chunks.par_iter().map(|chunk| self.decode(chunk)).collect())
     }
 
     /// Decode from owned Vec<u8> slices (convenience for FFI).
@@ -129,10 +235,12 @@ impl ParquetDecoder {
         &self,
         chunks: &[Vec<u8>],
     ) -> Result<Vec<RecordBatch>, ParquetError> {
-        chunks
-            .par_iter()
-            .map(|chunk| self.decode(chunk.as_slice()))
-            .collect()
+        crate::parallel::install(|| {
+            chunks
+                .par_iter()
+                .map(|chunk| self.decode(chunk.as_slice()))
+                .collect()
+        })
     }
 
     /// Decode only specific columns by index (projection pushdown).
@@ -263,6 +371,225 @@ impl ParquetDecoder {
         self.decode_columns(data, &column_indices)
     }
 
+    /// Decode and cast selected columns to target types.
+    ///
+    /// Useful for reading chunks written under an older schema (e.g. `id`
+    /// stored as Int32 where the current schema expects Int64). Columns not
+    /// listed are returned unchanged.
+    ///
+    /// Casts use Arrow's cast kernels in strict mode: float to integer casts
+    /// truncate toward zero (`2.9` becomes `2`, `-2.9` becomes `-2`), and a
+    /// value that does not fit the target type is an error rather than null.
+    ///
+    /// # Arguments
+    /// * `data` - Parquet file bytes
+    /// * `casts` - (column name, target type) pairs
+    ///
+    /// # Returns
+    /// * `Ok(RecordBatch)` - Decoded data with the listed columns cast
+    /// * `Err(ParquetError)` - If decoding fails, a column is missing, the
+    ///   types are incompatible, or a value cannot be represented
+    pub fn decode_cast(
+        &self,
+        data: &[u8],
+        casts: &[(&str, DataType)],
+    ) -> Result<RecordBatch, ParquetError> {
+        let batch = self.decode(data)?;
+        let schema = batch.schema();
+
+        let mut fields: Vec<_> = schema.fields().iter().map(|f| f.as_ref().clone()).collect();
+        let mut columns = batch.columns().to_vec();
+        let options = CastOptions {
+            safe: false,
+            ..Default::default()
+        };
+
+        for (name, to_type) in casts {
+            let idx = schema.index_of(name).map_err(|_| {
+                ParquetError::InvalidColumn(format!("Column '{}' not found in schema", name))
+            })?;
+
+            let from_type = fields[idx].data_type().clone();
+            if !can_cast_types(&from_type, to_type) {
+                return Err(ParquetError::IncompatibleCast {
+                    column: name.to_string(),
+                    from: from_type.to_string(),
+                    to: to_type.to_string(),
+                });
+            }
+
+            columns[idx] = cast_with_options(&columns[idx], to_type, &options)?;
+            fields[idx] = fields[idx].clone().with_data_type(to_type.clone());
+        }
+
+        let schema = Schema::new_with_metadata(fields, schema.metadata().clone());
+        Ok(RecordBatch::try_new(Arc::new(schema), columns)?)
+    }
+
+    /// Decode Parquet data with every top-level field marked nullable.
+    ///
+    /// The column data is left untouched; only the schema changes. Use this
+    /// before concatenating batches from sources that disagree on
+    /// nullability. Nullability of nested child fields is not changed.
+    ///
+    /// # Arguments
+    /// * `data` - Parquet file bytes
+    ///
+    /// # Returns
+    /// * `Ok(RecordBatch)` - Decoded data with a fully nullable schema
+    /// * `Err(ParquetError)` - If decoding fails
+    pub fn decode_all_nullable(&self, data: &[u8]) -> Result<RecordBatch, ParquetError> {
+        let batch = self.decode(data)?;
+        let schema = batch.schema();
+
+        let fields: Vec<_> = schema
+            .fields()
+            .iter()
+            .map(|f| f.as_ref().clone().with_nullable(true))
+            .collect();
+        let schema = Schema::new_with_metadata(fields, schema.metadata().clone());
+        Ok(RecordBatch::try_new(Arc::new(schema), batch.columns().to_vec())?)
+    }
+
+    /// Decode only the rows at the given 0-based indices.
+    ///
+    /// Indices are sorted and deduplicated, so rows come back in ascending
+    /// index order. The selection is pushed down to the reader, and pages
+    /// that contain none of the requested rows are skipped.
+    ///
+    /// # Arguments
+    /// * `data` - Parquet file bytes
+    /// * `indices` - Row indices to decode
+    ///
+    /// # Returns
+    /// * `Ok(RecordBatch)` - The selected rows, all columns
+    /// * `Err(ParquetError)` - If decoding fails, `indices` is empty, or an
+    ///   index is past the end of the file
+    pub fn decode_rows(&self, data: &[u8], indices: &[usize]) -> Result<RecordBatch, ParquetError> {
+        // Bounds check: prevent OOM from huge files
+        if data.len() > MAX_DECODE_SIZE {
+            return Err(ParquetError::FileTooLarge {
+                size: data.len(),
+                max: MAX_DECODE_SIZE,
+            });
+        }
+
+        if indices.is_empty() {
+            return Err(ParquetError::EmptyData);
+        }
+
+        let mut sorted = indices.to_vec();
+        sorted.sort_unstable();
+        sorted.dedup();
+
+        let bytes = Bytes::copy_from_slice(data);
+        let builder = ParquetRecordBatchReaderBuilder::try_new(bytes)?;
+
+        let raw_rows = builder.metadata().file_metadata().num_rows();
+        let num_rows =
+            usize::try_from(raw_rows).map_err(|_| ParquetError::InvalidRowCount(raw_rows))?;
+
+        // Sorted, so only the last index needs checking
+        if let Some(&last) = sorted.last() {
+            if last >= num_rows {
+                return Err(ParquetError::RowIndexOutOfRange {
+                    index: last,
+                    num_rows,
+                });
+            }
+        }
+
+        let selection =
+            RowSelection::from_consecutive_ranges(sorted.iter().map(|&i| i..i + 1), num_rows);
+
+        let reader = builder
+            .with_row_selection(selection)
+            .with_batch_size(self.batch_size)
+            .build()?;
+
+        let batches: Vec<RecordBatch> = reader.collect::<Result<Vec<_>, _>>()?;
+
+        if batches.is_empty() {
+            return Err(ParquetError::EmptyData);
+        }
+
+        if batches.len() == 1 {
+            return Ok(batches.into_iter().next().unwrap());
+        }
+
+        let schema = batches[0].schema();
+        arrow::compute::concat_batches(&schema, &batches).map_err(ParquetError::Arrow)
+    }
+
+    /// Salvage a file with corrupt row groups.
+    ///
+    /// Decodes each row group on its own, drops those that fail, and
+    /// re-encodes the survivors (one row group each, default encoder
+    /// settings). The footer must be readable; a file whose metadata is
+    /// corrupt cannot be repaired.
+    ///
+    /// # Returns
+    /// * `Ok((bytes, report))` - The repaired file and what was dropped
+    /// * `Err(ParquetError)` - If the footer is unreadable or no row group
+    ///   survives (`EmptyData`)
+    pub fn repair(&self, data: &[u8]) -> Result<(Vec<u8>, RepairReport), ParquetError> {
+        // Bounds check: prevent OOM from huge files
+        if data.len() > MAX_DECODE_SIZE {
+            return Err(ParquetError::FileTooLarge {
+                size: data.len(),
+                max: MAX_DECODE_SIZE,
+            });
+        }
+
+        let bytes = Bytes::copy_from_slice(data);
+        let metadata = ArrowReaderMetadata::load(&bytes, ArrowReaderOptions::default())?;
+        let total_row_groups = metadata.metadata().num_row_groups();
+
+        let mut stream = ParquetEncoder::new().stream_to(Vec::new());
+        let mut dropped_row_groups = Vec::new();
+        let mut rows_kept = 0;
+        let mut rows_lost = 0;
+
+        for i in 0..total_row_groups {
+            let group_rows = metadata.metadata().row_group(i).num_rows();
+            let group_rows =
+                usize::try_from(group_rows).map_err(|_| ParquetError::InvalidRowCount(group_rows))?;
+
+            let decoded = ParquetRecordBatchReaderBuilder::new_with_metadata(bytes.clone(), metadata.clone())
+                .with_row_groups(vec![i])
+                .with_batch_size(self.batch_size)
+                .build()
+                .and_then(|reader| {
+                    reader
+                        .collect::<Result<Vec<_>, _>>()
+                        .map_err(|e| parquet::errors::ParquetError::External(Box::new(e)))
+                });
+
+            match decoded {
+                Ok(batches) => {
+                    let schema = metadata.schema().clone();
+                    stream.write_batch(&arrow::compute::concat_batches(&schema, &batches)?)?;
+                    rows_kept += group_rows;
+                }
+                Err(_) => {
+                    dropped_row_groups.push(i);
+                    rows_lost += group_rows;
+                }
+            }
+        }
+
+        let repaired = stream.finish()?;
+        Ok((
+            repaired,
+            RepairReport {
+                total_row_groups,
+                dropped_row_groups,
+                rows_kept,
+                rows_lost,
+            },
+        ))
+    }
+
     /// Decode with predicate pushdown (row-level filtering and row-group pruning).
     ///
     /// This method applies filter predicates during decoding using a two-level
@@ -297,6 +624,29 @@ impl ParquetDecoder {
         data: &[u8],
         filters: &[PredicateFilter],
         column_indices: Option<&[usize]>,
+    ) -> Result<RecordBatch, ParquetError> {
+        self.decode_with_expr(data, &PredicateExpr::all_of(filters), column_indices)
+    }
+
+    /// Decode with a boolean predicate expression (AND / OR groups).
+    ///
+    /// Works like `decode_with_filter`, which is the special case of an
+    /// `And` of leaves. A row group is pruned only when its statistics
+    /// prove that the whole expression matches no row in it.
+    ///
+    /// # Arguments
+    /// * `data` - Parquet file bytes
+    /// * `expr` - Predicate expression to apply
+    /// * `column_indices` - Optional column projection (None = all columns)
+    ///
+    /// # Returns
+    /// * `Ok(RecordBatch)` - Decoded Arrow data with the expression applied
+    /// * `Err(ParquetError)` - If decoding fails, or `EmptyData` if no row matches
+    pub fn decode_with_expr(
+        &self,
+        data: &[u8],
+        expr: &PredicateExpr,
+        column_indices: Option<&[usize]>,
     ) -> Result<RecordBatch, ParquetError> {
         // Bounds check: prevent OOM from huge files
         if data.len() > MAX_DECODE_SIZE {
@@ -306,8 +656,12 @@ impl ParquetDecoder {
             });
         }
 
-        if filters.is_empty() {
-            // No filters, use regular decode (size already checked)
+        let leaves = expr.leaves();
+        if leaves.is_empty() {
+            // Constant expression: empty AND keeps every row, empty OR none
+            if expr.can_prune_row_group(&|_: &PredicateFilter| false) {
+                return Err(ParquetError::EmptyData);
+            }
             return match column_indices {
                 Some(cols) => self.decode_columns(data, cols),
                 None => self.decode(data),
@@ -322,11 +676,9 @@ impl ParquetDecoder {
 
         // Resolve filter column names to indices
         let mut filter_column_indices = Vec::new();
-        let mut filter_to_column_idx = Vec::new();
-        for filter in filters {
+        for filter in &leaves {
             match arrow_schema.index_of(&filter.column) {
                 Ok(idx) => {
-                    filter_to_column_idx.push(idx);
                     if !filter_column_indices.contains(&idx) {
                         filter_column_indices.push(idx);
                     }
@@ -360,7 +712,11 @@ impl ParquetDecoder {
                 .map_err(|_| ParquetError::InvalidRowCount(num_rows_i64))?;
 
             // Check if this row group can be pruned
-            if can_prune_row_group(row_group, filters, &filter_to_column_idx) {
+            let prune_leaf = |filter: &PredicateFilter| match arrow_schema.index_of(&filter.column) {
+                Ok(idx) => can_prune_filter(row_group, filter, idx),
+                Err(_) => false,
+            };
+            if expr.can_prune_row_group(&prune_leaf) {
                 _pruned_groups += 1;
                 // Don't add this range - it will be skipped
             } else {
@@ -397,13 +753,13 @@ impl ParquetDecoder {
         // Create projection mask for filter columns
         let filter_mask = ProjectionMask::leaves(parquet_schema, filter_column_indices.iter().copied());
 
-        // Clone filters for the closure
-        let filters_owned: Vec<PredicateFilter> = filters.to_vec();
+        // Clone the expression for the closure
+        let expr_owned = expr.clone();
         let schema_for_closure = arrow_schema.clone();
 
         // Create the row filter predicate
         let predicate = ArrowPredicateFn::new(filter_mask, move |batch: RecordBatch| {
-            apply_filters(&batch, &filters_owned, &schema_for_closure)
+            apply_expr(&batch, &expr_owned, &schema_for_closure)
         });
 
         let row_filter = RowFilter::new(vec![Box::new(predicate)]);
@@ -441,6 +797,62 @@ impl ParquetDecoder {
         arrow::compute::concat_batches(&schema, &batches).map_err(ParquetError::Arrow)
     }
 
+    /// Decode all rows along with a mask of the rows that match `filters`.
+    ///
+    /// Unlike `decode_with_filter`, no rows are dropped, so row positions
+    /// line up with the file. A row whose filter column is null does not
+    /// match (its mask entry is `false`, never null), so the mask's true
+    /// count equals the number of rows `decode_with_filter` returns.
+    ///
+    /// # Arguments
+    /// * `data` - Parquet file bytes
+    /// * `filters` - Predicate filters to evaluate (empty = every row matches)
+    /// * `column_indices` - Optional projection of top-level columns for the
+    ///   returned batch; filters may still reference any column
+    ///
+    /// # Returns
+    /// * `Ok((RecordBatch, BooleanArray))` - Every row, and one mask entry per row
+    /// * `Err(ParquetError)` - If decoding fails or a column is unknown
+    pub fn decode_with_filter_masked(
+        &self,
+        data: &[u8],
+        filters: &[PredicateFilter],
+        column_indices: Option<&[usize]>,
+    ) -> Result<(RecordBatch, BooleanArray), ParquetError> {
+        let batch = self.decode(data)?;
+        let schema = batch.schema();
+
+        for filter in filters {
+            if schema.index_of(&filter.column).is_err() {
+                return Err(ParquetError::InvalidColumn(format!(
+                    "Filter column '{}' not found in schema",
+                    filter.column
+                )));
+            }
+        }
+        let mask = apply_filters(&batch, filters, &schema)?;
+        // Null comparisons count as non-matching
+        let mask = if mask.null_count() > 0 {
+            arrow::compute::prep_null_mask_filter(&mask)
+        } else {
+            mask
+        };
+
+        let batch = match column_indices {
+            Some([]) => {
+                return Err(ParquetError::InvalidColumn(
+                    "No columns specified for projection".to_string(),
+                ))
+            }
+            Some(cols) => batch
+                .project(cols)
+                .map_err(|e| ParquetError::InvalidColumn(e.to_string()))?,
+            None => batch,
+        };
+
+        Ok((batch, mask))
+    }
+
     /// Get row-group pruning statistics for a filtered decode.
     ///
     /// This is useful for debugging and understanding pruning effectiveness.
@@ -489,86 +901,470 @@ impl ParquetDecoder {
 
         Ok((total, pruned, total - pruned))
     }
-}
 
-/// Extract min/max statistics from a row group for a specific column.
-///
-/// Returns (min, max) as ScalarValues, or None if statistics are unavailable.
-fn extract_column_stats(
-    row_group: &RowGroupMetaData,
-    column_idx: usize,
-) -> (Option<ScalarValue>, Option<ScalarValue>) {
-    let column_chunk = row_group.column(column_idx);
+    /// Plan a filtered decode without decoding any data.
+    ///
+    /// Only the footer is read: row groups are pruned with their min/max
+    /// and null-count statistics, so this is much cheaper than
+    /// [`explain_filter`](Self::explain_filter) and suitable for cost
+    /// estimation before running a query.
+    ///
+    /// # Arguments
+    /// * `data` - Parquet file bytes
+    /// * `filters` - Predicate filters to apply (empty = scan everything)
+    ///
+    /// # Returns
+    /// * `Ok(FilterPlan)` - Surviving row groups and their row count
+    /// * `Err(ParquetError)` - If the footer is invalid or a filter column is unknown
+    pub fn plan_filter(
+        &self,
+        data: &[u8],
+        filters: &[PredicateFilter],
+    ) -> Result<FilterPlan, ParquetError> {
+        let bytes = Bytes::copy_from_slice(data);
+        let builder = ParquetRecordBatchReaderBuilder::try_new(bytes)?;
+        let arrow_schema = builder.schema();
+        let file_metadata = builder.metadata();
 
-    match column_chunk.statistics() {
-        Some(stats) => {
-            let min = stats_to_scalar(stats, true);
-            let max = stats_to_scalar(stats, false);
-            (min, max)
+        let mut filter_to_column_idx = Vec::with_capacity(filters.len());
+        for filter in filters {
+            let idx = arrow_schema.index_of(&filter.column).map_err(|_| {
+                ParquetError::InvalidColumn(format!(
+                    "Filter column '{}' not found in schema",
+                    filter.column
+                ))
+            })?;
+            filter_to_column_idx.push(idx);
         }
-        None => (None, None),
-    }
-}
 
-/// Convert Parquet Statistics to ScalarValue.
-///
-/// The `is_min` parameter indicates whether to extract min (true) or max (false).
-fn stats_to_scalar(stats: &Statistics, is_min: bool) -> Option<ScalarValue> {
-    match stats {
-        Statistics::Int64(typed_stats) => {
-            let val = if is_min { typed_stats.min_opt()? } else { typed_stats.max_opt()? };
-            Some(ScalarValue::Int64(*val))
-        }
-        Statistics::Int32(typed_stats) => {
-            let val = if is_min { typed_stats.min_opt()? } else { typed_stats.max_opt()? };
-            Some(ScalarValue::Int32(*val))
-        }
-        Statistics::Double(typed_stats) => {
-            let val = if is_min { typed_stats.min_opt()? } else { typed_stats.max_opt()? };
-            Some(ScalarValue::Float64(*val))
-        }
-        Statistics::ByteArray(typed_stats) => {
-            let val = if is_min { typed_stats.min_opt()? } else { typed_stats.max_opt()? };
-            let s = std::str::from_utf8(val.data()).ok()?;
-            Some(ScalarValue::Utf8(s.to_string()))
-        }
-        Statistics::Boolean(typed_stats) => {
-            let val = if is_min { typed_stats.min_opt()? } else { typed_stats.max_opt()? };
-            Some(ScalarValue::Boolean(*val))
+        let mut row_groups_to_scan = Vec::new();
+        let mut estimated_rows = 0i64;
+        for rg_idx in 0..file_metadata.num_row_groups() {
+            let row_group = file_metadata.row_group(rg_idx);
+            if filters.is_empty() || !can_prune_row_group(row_group, filters, &filter_to_column_idx) {
+                row_groups_to_scan.push(rg_idx);
+                estimated_rows = estimated_rows
+                    .checked_add(row_group.num_rows())
+                    .ok_or(ParquetError::RowCountOverflow)?;
+            }
         }
-        _ => None,
-    }
-}
-
-/// Check if a row group can be pruned based on filter predicates and statistics.
-///
-/// Returns true if ALL filters indicate the row group can be skipped.
-fn can_prune_row_group(
-    row_group: &RowGroupMetaData,
-    filters: &[PredicateFilter],
-    column_indices: &[usize], // Filter column indices in the same order as filters
-) -> bool {
-    for (filter, &col_idx) in filters.iter().zip(column_indices.iter()) {
-        let (min, max) = extract_column_stats(row_group, col_idx);
 
-        // If ANY filter can prune, skip this row group
-        if filter.can_prune_row_group(min.as_ref(), max.as_ref()) {
-            return true;
-        }
+        Ok(FilterPlan {
+            row_groups_to_scan,
+            estimated_rows,
+        })
     }
 
-    // Can't prune - might have matching rows
-    false
-}
-
-/// Apply multiple filters to a record batch, returning a boolean mask.
-fn apply_filters(
-    batch: &RecordBatch,
-    filters: &[PredicateFilter],
-    schema: &arrow::datatypes::SchemaRef,
-) -> Result<BooleanArray, arrow::error::ArrowError> {
-    let num_rows = batch.num_rows();
-
+    /// Explain a filtered decode.
+    ///
+    /// Builds on [`get_pruning_stats`](Self::get_pruning_stats) and also
+    /// performs the decode, so the report includes how many rows were
+    /// scanned (rows in non-pruned row groups) and how many were returned.
+    ///
+    /// # Arguments
+    /// * `data` - Parquet file bytes
+    /// * `filters` - Predicate filters to apply
+    ///
+    /// # Returns
+    /// * `Ok(FilterExplain)` - Pruning and row counts for the decode
+    /// * `Err(ParquetError)` - If decoding fails or a filter column is invalid
+    pub fn explain_filter(
+        &self,
+        data: &[u8],
+        filters: &[PredicateFilter],
+    ) -> Result<FilterExplain, ParquetError> {
+        let (total_row_groups, pruned_row_groups, _) = self.get_pruning_stats(data, filters)?;
+
+        let bytes = Bytes::copy_from_slice(data);
+        let builder = ParquetRecordBatchReaderBuilder::try_new(bytes)?;
+        let arrow_schema = builder.schema();
+        let file_metadata = builder.metadata();
+
+        let filter_to_column_idx: Vec<usize> = filters
+            .iter()
+            .map(|f| arrow_schema.index_of(&f.column))
+            .collect::<Result<_, _>>()?;
+
+        let mut rows_scanned = 0usize;
+        for rg_idx in 0..file_metadata.num_row_groups() {
+            let row_group = file_metadata.row_group(rg_idx);
+            if filters.is_empty() || !can_prune_row_group(row_group, filters, &filter_to_column_idx) {
+                let num_rows: usize = row_group
+                    .num_rows()
+                    .try_into()
+                    .map_err(|_| ParquetError::InvalidRowCount(row_group.num_rows()))?;
+                rows_scanned = rows_scanned
+                    .checked_add(num_rows)
+                    .ok_or(ParquetError::RowCountOverflow)?;
+            }
+        }
+
+        let rows_returned = match self.decode_with_filter(data, filters, None) {
+            Ok(batch) => batch.num_rows(),
+            Err(ParquetError::EmptyData) => 0,
+            Err(e) => return Err(e),
+        };
+
+        let mut filter_columns: Vec<String> = Vec::new();
+        for filter in filters {
+            if !filter_columns.contains(&filter.column) {
+                filter_columns.push(filter.column.clone());
+            }
+        }
+
+        Ok(FilterExplain {
+            total_row_groups,
+            pruned_row_groups,
+            rows_scanned,
+            rows_returned,
+            filter_columns,
+        })
+    }
+
+    /// Count rows matching the filters across multiple chunks in parallel.
+    ///
+    /// This is the `COUNT(*) WHERE ...` primitive for a table stored as many
+    /// chunks. Each chunk is filtered independently (with row-group pruning)
+    /// and the per-chunk counts are summed.
+    ///
+    /// Empty chunks, and chunks where no rows match, contribute 0.
+    ///
+    /// # Arguments
+    /// * `chunks` - Parquet bytes for each chunk
+    /// * `filters` - Predicate filters to apply (AND semantics)
+    ///
+    /// # Returns
+    /// * `Ok(usize)` - Total number of matching rows
+    /// * `Err(ParquetError)` - If any chunk fails to decode (returns first error)
+    pub fn count_batch_with_filter(
+        &self,
+        chunks: &[Vec<u8>],
+        filters: &[PredicateFilter],
+    ) -> Result<usize, ParquetError> {
+        let counts: Vec<usize> = crate::parallel::install(|| {
+            chunks
+                .par_iter()
+                .map(|chunk| {
+                    if chunk.is_empty() {
+                        return Ok(0);
+                    }
+                    match self.decode_with_filter(chunk, filters, None) {
+                        Ok(batch) => Ok(batch.num_rows()),
+                        Err(ParquetError::EmptyData) => Ok(0),
+                        Err(e) => Err(e),
+                    }
+                })
+                .collect::<Result<_, _>>()
+        })?;
+
+        counts
+            .into_iter()
+            .try_fold(0usize, |acc, n| acc.checked_add(n))
+            .ok_or(ParquetError::RowCountOverflow)
+    }
+
+    /// Compute the `q`-th quantile of a numeric column over rows matching
+    /// `filters`.
+    ///
+    /// The result is exact, not an estimate: matching values are decoded
+    /// (only the target column is projected, and filters are pushed down),
+    /// sorted, and linearly interpolated between the two nearest ranks, the
+    /// same as NumPy's default `percentile`. Nulls and NaNs are ignored.
+    /// Memory use is proportional to the number of matching rows.
+    ///
+    /// # Arguments
+    /// * `data` - Parquet file bytes
+    /// * `column` - Name of a numeric column
+    /// * `q` - Quantile in `[0.0, 1.0]` (0.5 = median, 0.95 = p95)
+    /// * `filters` - Predicate filters to apply (empty = all rows)
+    ///
+    /// # Returns
+    /// * `Ok(f64)` - The quantile value
+    /// * `Err(ParquetError)` - `InvalidColumn` if the column is missing or
+    ///   not numeric, `Arrow` if `q` is out of range, `EmptyData` if no
+    ///   non-null values match
+    pub fn percentile(
+        &self,
+        data: &[u8],
+        column: &str,
+        q: f64,
+        filters: &[PredicateFilter],
+    ) -> Result<f64, ParquetError> {
+        if !(0.0..=1.0).contains(&q) {
+            return Err(arrow::error::ArrowError::InvalidArgumentError(format!(
+                "quantile must be between 0 and 1, got {}",
+                q
+            ))
+            .into());
+        }
+
+        let bytes = Bytes::copy_from_slice(data);
+        let metadata = ArrowReaderMetadata::load(&bytes, ArrowReaderOptions::default())?;
+        let idx = metadata.schema().index_of(column).map_err(|_| {
+            ParquetError::InvalidColumn(format!("Column '{}' not found in schema", column))
+        })?;
+        if !metadata.schema().field(idx).data_type().is_numeric() {
+            return Err(ParquetError::InvalidColumn(format!(
+                "Column '{}' is not numeric",
+                column
+            )));
+        }
+
+        let batch = self.decode_with_filter(data, filters, Some(&[idx]))?;
+        let values = arrow::compute::cast(batch.column(0), &DataType::Float64)?;
+        let mut values: Vec<f64> = values
+            .as_primitive::<arrow::datatypes::Float64Type>()
+            .iter()
+            .flatten()
+            .filter(|v| !v.is_nan())
+            .collect();
+        if values.is_empty() {
+            return Err(ParquetError::EmptyData);
+        }
+        values.sort_unstable_by(f64::total_cmp);
+
+        let rank = q * (values.len() - 1) as f64;
+        let lower = values[rank.floor() as usize];
+        let upper = values[rank.ceil() as usize];
+        Ok(lower + (upper - lower) * rank.fract())
+    }
+
+    /// Compute a count, min or max, from footer statistics where possible.
+    ///
+    /// `Count` always comes from the footer. `Min`/`Max` fold the per-row-group
+    /// statistics, and fall back to decoding the column when any row group
+    /// cannot be answered from its statistics:
+    /// - the row group has no statistics for the column (written with
+    ///   statistics disabled, or by a writer that omits them)
+    /// - the bound is inexact (e.g. a string truncated by the writer)
+    /// - the bound is missing although the row group has non-null values
+    /// - the statistics' physical type differs from the Arrow column type
+    ///   (e.g. `Int32` statistics for an `Int16` column)
+    ///
+    /// A row group whose values are all null contributes nothing, and a
+    /// column with no non-null values yields `ScalarValue::Null`.
+    ///
+    /// # Errors
+    /// * `InvalidColumn` - If the column is missing, or the scan fallback
+    ///   is needed for a type other than Int32, Int64, Float64, Utf8 or Boolean
+    pub fn aggregate(&self, data: &[u8], spec: &AggregateSpec) -> Result<AggregateResult, ParquetError> {
+        let bytes = Bytes::copy_from_slice(data);
+        let metadata = ArrowReaderMetadata::load(&bytes, ArrowReaderOptions::default())?;
+        let file_metadata = metadata.metadata();
+
+        let (column, is_min) = match spec {
+            AggregateSpec::Count => {
+                let count = file_metadata
+                    .row_groups()
+                    .iter()
+                    .try_fold(0i64, |acc, rg| acc.checked_add(rg.num_rows()))
+                    .ok_or(ParquetError::RowCountOverflow)?;
+                return Ok(AggregateResult {
+                    value: ScalarValue::Int64(count),
+                    from_statistics: true,
+                });
+            }
+            AggregateSpec::Min(column) => (column, true),
+            AggregateSpec::Max(column) => (column, false),
+        };
+
+        let idx = metadata.schema().index_of(column).map_err(|_| {
+            ParquetError::InvalidColumn(format!("Column '{}' not found in schema", column))
+        })?;
+        let data_type = metadata.schema().field(idx).data_type().clone();
+
+        if let Some(value) = min_max_from_stats(file_metadata.row_groups(), idx, &data_type, is_min) {
+            return Ok(AggregateResult {
+                value,
+                from_statistics: true,
+            });
+        }
+
+        let value = match self.decode_columns(data, &[idx]) {
+            Ok(batch) => min_max_scan(batch.column(0), column, is_min)?,
+            Err(ParquetError::EmptyData) => ScalarValue::Null,
+            Err(e) => return Err(e),
+        };
+        Ok(AggregateResult {
+            value,
+            from_statistics: false,
+        })
+    }
+}
+
+/// Fold per-row-group min/max statistics for one column.
+///
+/// Returns `None` if any row group cannot be answered from its statistics;
+/// see [`ParquetDecoder::aggregate`] for the cases.
+fn min_max_from_stats(
+    row_groups: &[RowGroupMetaData],
+    column_idx: usize,
+    data_type: &DataType,
+    is_min: bool,
+) -> Option<ScalarValue> {
+    let better = if is_min { FilterOp::Lt } else { FilterOp::Gt };
+    let mut result: Option<ScalarValue> = None;
+
+    for row_group in row_groups {
+        let stats = row_group.column(column_idx).statistics()?;
+        let bound = match stats_to_scalar(stats, is_min) {
+            Some(bound) => bound,
+            None if stats.null_count_opt() == Some(row_group.num_rows() as u64) => continue,
+            None => return None,
+        };
+        let exact = if is_min { stats.min_is_exact() } else { stats.max_is_exact() };
+        if !exact || !scalar_matches_type(&bound, data_type) {
+            return None;
+        }
+        result = match result {
+            Some(current) if bound.compare(&current, better) != Some(true) => Some(current),
+            _ => Some(bound),
+        };
+    }
+
+    Some(result.unwrap_or(ScalarValue::Null))
+}
+
+/// Whether statistics decoded as `value` describe a column of `data_type`.
+fn scalar_matches_type(value: &ScalarValue, data_type: &DataType) -> bool {
+    matches!(
+        (value, data_type),
+        (ScalarValue::Int64(_), DataType::Int64)
+            | (ScalarValue::Int32(_), DataType::Int32)
+            | (ScalarValue::Float64(_), DataType::Float64)
+            | (ScalarValue::Utf8(_), DataType::Utf8 | DataType::LargeUtf8)
+            | (ScalarValue::Boolean(_), DataType::Boolean)
+    )
+}
+
+/// Compute min/max of a decoded column.
+fn min_max_scan(array: &dyn Array, column: &str, is_min: bool) -> Result<ScalarValue, ParquetError> {
+    use arrow::compute::{max, max_boolean, max_string, min, min_boolean, min_string};
+    use arrow::datatypes::{Float64Type, Int32Type, Int64Type};
+
+    let value = match array.data_type() {
+        DataType::Int64 => {
+            let a = array.as_primitive::<Int64Type>();
+            if is_min { min(a) } else { max(a) }.map(ScalarValue::Int64)
+        }
+        DataType::Int32 => {
+            let a = array.as_primitive::<Int32Type>();
+            if is_min { min(a) } else { max(a) }.map(ScalarValue::Int32)
+        }
+        DataType::Float64 => {
+            let a = array.as_primitive::<Float64Type>();
+            if is_min { min(a) } else { max(a) }.map(ScalarValue::Float64)
+        }
+        DataType::Utf8 => {
+            let a = array.as_string::<i32>();
+            if is_min { min_string(a) } else { max_string(a) }.map(|s| ScalarValue::Utf8(s.to_string()))
+        }
+        DataType::LargeUtf8 => {
+            let a = array.as_string::<i64>();
+            if is_min { min_string(a) } else { max_string(a) }.map(|s| ScalarValue::Utf8(s.to_string()))
+        }
+        DataType::Boolean => {
+            let a = array.as_boolean();
+            if is_min { min_boolean(a) } else { max_boolean(a) }.map(ScalarValue::Boolean)
+        }
+        other => {
+            return Err(ParquetError::InvalidColumn(format!(
+                "Column '{}' of type {} does not support min/max",
+                column, other
+            )))
+        }
+    };
+    Ok(value.unwrap_or(ScalarValue::Null))
+}
+
+/// Extract min/max statistics from a row group for a specific column.
+///
+/// Returns (min, max) as ScalarValues, or None if statistics are unavailable.
+fn extract_column_stats(
+    row_group: &RowGroupMetaData,
+    column_idx: usize,
+) -> (Option<ScalarValue>, Option<ScalarValue>) {
+    let column_chunk = row_group.column(column_idx);
+
+    match column_chunk.statistics() {
+        Some(stats) => {
+            let min = stats_to_scalar(stats, true);
+            let max = stats_to_scalar(stats, false);
+            (min, max)
+        }
+        None => (None, None),
+    }
+}
+
+/// Convert Parquet Statistics to ScalarValue.
+///
+/// The `is_min` parameter indicates whether to extract min (true) or max (false).
+fn stats_to_scalar(stats: &Statistics, is_min: bool) -> Option<ScalarValue> {
+    match stats {
+        Statistics::Int64(typed_stats) => {
+            let val = if is_min { typed_stats.min_opt()? } else { typed_stats.max_opt()? };
+            Some(ScalarValue::Int64(*val))
+        }
+        Statistics::Int32(typed_stats) => {
+            let val = if is_min { typed_stats.min_opt()? } else { typed_stats.max_opt()? };
+            Some(ScalarValue::Int32(*val))
+        }
+        Statistics::Double(typed_stats) => {
+            let val = if is_min { typed_stats.min_opt()? } else { typed_stats.max_opt()? };
+            Some(ScalarValue::Float64(*val))
+        }
+        Statistics::ByteArray(typed_stats) => {
+            let val = if is_min { typed_stats.min_opt()? } else { typed_stats.max_opt()? };
+            let s = std::str::from_utf8(val.data()).ok()?;
+            Some(ScalarValue::Utf8(s.to_string()))
+        }
+        Statistics::Boolean(typed_stats) => {
+            let val = if is_min { typed_stats.min_opt()? } else { typed_stats.max_opt()? };
+            Some(ScalarValue::Boolean(*val))
+        }
+        _ => None,
+    }
+}
+
+/// Check if a row group can be pruned based on filter predicates and statistics.
+///
+/// Returns true if ALL filters indicate the row group can be skipped.
+fn can_prune_row_group(
+    row_group: &RowGroupMetaData,
+    filters: &[PredicateFilter],
+    column_indices: &[usize], // Filter column indices in the same order as filters
+) -> bool {
+    for (filter, &col_idx) in filters.iter().zip(column_indices.iter()) {
+        // If ANY filter can prune, skip this row group
+        if can_prune_filter(row_group, filter, col_idx) {
+            return true;
+        }
+    }
+
+    // Can't prune - might have matching rows
+    false
+}
+
+/// Check if a single filter rules out a row group, using min/max statistics
+/// or, for null checks, the null count.
+fn can_prune_filter(row_group: &RowGroupMetaData, filter: &PredicateFilter, column_idx: usize) -> bool {
+    if let FilterOp::IsNull | FilterOp::IsNotNull = filter.op {
+        let null_count = row_group.column(column_idx).statistics().and_then(|s| s.null_count_opt());
+        let num_rows = u64::try_from(row_group.num_rows()).unwrap_or(0);
+        return filter.can_prune_with_null_count(null_count, num_rows);
+    }
+    let (min, max) = extract_column_stats(row_group, column_idx);
+    filter.can_prune_row_group(min.as_ref(), max.as_ref())
+}
+
+/// Apply multiple filters to a record batch, returning a boolean mask.
+fn apply_filters(
+    batch: &RecordBatch,
+    filters: &[PredicateFilter],
+    schema: &arrow::datatypes::SchemaRef,
+) -> Result<BooleanArray, arrow::error::ArrowError> {
+    let num_rows = batch.num_rows();
+
     // Start with all true
     let mut result = BooleanArray::from(vec![true; num_rows]);
 
@@ -596,6 +1392,34 @@ fn apply_filters(
     Ok(result)
 }
 
+/// Evaluate a predicate expression on a record batch, returning a boolean mask.
+///
+/// Groups combine with Kleene logic, so `null OR true` is true.
+fn apply_expr(
+    batch: &RecordBatch,
+    expr: &PredicateExpr,
+    schema: &arrow::datatypes::SchemaRef,
+) -> Result<BooleanArray, arrow::error::ArrowError> {
+    let num_rows = batch.num_rows();
+    match expr {
+        PredicateExpr::Leaf(filter) => apply_filters(batch, std::slice::from_ref(filter), schema),
+        PredicateExpr::And(children) => {
+            let mut result = BooleanArray::from(vec![true; num_rows]);
+            for child in children {
+                result = and_kleene(&result, &apply_expr(batch, child, schema)?)?;
+            }
+            Ok(result)
+        }
+        PredicateExpr::Or(children) => {
+            let mut result = BooleanArray::from(vec![false; num_rows]);
+            for child in children {
+                result = or_kleene(&result, &apply_expr(batch, child, schema)?)?;
+            }
+            Ok(result)
+        }
+    }
+}
+
 /// Apply a single filter to a column.
 fn apply_single_filter(
     column: &dyn Array,
@@ -605,53 +1429,74 @@ fn apply_single_filter(
     use arrow::array::{Float64Array, Int32Array, Int64Array, StringArray};
     use arrow::datatypes::DataType;
 
+    match op {
+        FilterOp::IsNull => return arrow::compute::is_null(column),
+        FilterOp::IsNotNull => return arrow::compute::is_not_null(column),
+        FilterOp::StartsWith | FilterOp::EndsWith | FilterOp::Contains => {
+            let (DataType::Utf8, ScalarValue::Utf8(v)) = (column.data_type(), value) else {
+                return Err(arrow::error::ArrowError::SchemaError(format!(
+                    "Unsupported filter: {} needs a Utf8 column and value, got {:?} with {:?}",
+                    op,
+                    column.data_type(),
+                    value
+                )));
+            };
+            let col = column.as_string::<i32>();
+            let pattern = StringArray::new_scalar(v);
+            return match op {
+                FilterOp::StartsWith => starts_with(col, &pattern),
+                FilterOp::EndsWith => ends_with(col, &pattern),
+                _ => contains(col, &pattern),
+            };
+        }
+        _ => {}
+    }
+
+    if let FilterOp::In | FilterOp::NotIn = op {
+        let ScalarValue::List(members) = value else {
+            return Err(arrow::error::ArrowError::SchemaError(format!(
+                "Unsupported filter: {} requires a list value, got {}",
+                op, value
+            )));
+        };
+        // OR together one equality mask per member
+        let mut mask = BooleanArray::from(vec![false; column.len()]);
+        for member in members {
+            mask = or(&mask, &apply_single_filter(column, &FilterOp::Eq, member)?)?;
+        }
+        return match op {
+            FilterOp::NotIn => not(&mask),
+            _ => Ok(mask),
+        };
+    }
+
     match (column.data_type(), value) {
         (DataType::Int64, ScalarValue::Int64(v)) => {
             let col = column.as_primitive::<arrow::datatypes::Int64Type>();
-            let scalar = Int64Array::new_scalar(*v);
-            match op {
-                FilterOp::Eq => eq(col, &scalar),
-                FilterOp::Ne => neq(col, &scalar),
-                FilterOp::Lt => lt(col, &scalar),
-                FilterOp::Le => lt_eq(col, &scalar),
-                FilterOp::Gt => gt(col, &scalar),
-                FilterOp::Ge => gt_eq(col, &scalar),
-            }
+            compare_with_scalar(col, &Int64Array::new_scalar(*v), op)
         }
         (DataType::Int32, ScalarValue::Int32(v)) => {
             let col = column.as_primitive::<arrow::datatypes::Int32Type>();
-            let scalar = Int32Array::new_scalar(*v);
-            match op {
-                FilterOp::Eq => eq(col, &scalar),
-                FilterOp::Ne => neq(col, &scalar),
-                FilterOp::Lt => lt(col, &scalar),
-                FilterOp::Le => lt_eq(col, &scalar),
-                FilterOp::Gt => gt(col, &scalar),
-                FilterOp::Ge => gt_eq(col, &scalar),
-            }
+            compare_with_scalar(col, &Int32Array::new_scalar(*v), op)
         }
         (DataType::Float64, ScalarValue::Float64(v)) => {
             let col = column.as_primitive::<arrow::datatypes::Float64Type>();
-            let scalar = Float64Array::new_scalar(*v);
-            match op {
-                FilterOp::Eq => eq(col, &scalar),
-                FilterOp::Ne => neq(col, &scalar),
-                FilterOp::Lt => lt(col, &scalar),
-                FilterOp::Le => lt_eq(col, &scalar),
-                FilterOp::Gt => gt(col, &scalar),
-                FilterOp::Ge => gt_eq(col, &scalar),
-            }
+            compare_with_scalar(col, &Float64Array::new_scalar(*v), op)
         }
         (DataType::Utf8, ScalarValue::Utf8(v)) => {
             let col = column.as_string::<i32>();
-            let scalar = StringArray::new_scalar(v);
+            compare_with_scalar(col, &StringArray::new_scalar(v), op)
+        }
+        (DataType::Boolean, ScalarValue::Boolean(v)) => {
+            let col = column.as_boolean();
+            let scalar = BooleanArray::new_scalar(*v);
             match op {
                 FilterOp::Eq => eq(col, &scalar),
                 FilterOp::Ne => neq(col, &scalar),
-                FilterOp::Lt => lt(col, &scalar),
-                FilterOp::Le => lt_eq(col, &scalar),
-                FilterOp::Gt => gt(col, &scalar),
-                FilterOp::Ge => gt_eq(col, &scalar),
+                _ => Err(arrow::error::ArrowError::SchemaError(format!(
+                    "Unsupported filter: {} on a boolean column",
+                    op
+                ))),
             }
         }
         _ => Err(arrow::error::ArrowError::SchemaError(format!(
@@ -662,6 +1507,28 @@ fn apply_single_filter(
     }
 }
 
+/// Compare a column against a scalar with one of the ordering kernels.
+fn compare_with_scalar(
+    col: &dyn Datum,
+    scalar: &dyn Datum,
+    op: &FilterOp,
+) -> Result<BooleanArray, arrow::error::ArrowError> {
+    match op {
+        FilterOp::Eq => eq(col, scalar),
+        FilterOp::Ne => neq(col, scalar),
+        FilterOp::Lt => lt(col, scalar),
+        FilterOp::Le => lt_eq(col, scalar),
+        FilterOp::Gt => gt(col, scalar),
+        FilterOp::Ge => gt_eq(col, scalar),
+        _ => {
+            Err(arrow::error::ArrowError::SchemaError(format!(
+                "Unsupported filter: {} is not a scalar comparison",
+                op
+            )))
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -799,6 +1666,26 @@ mod tests {
         assert_eq!(decoded.num_rows(), 10_000);
     }
 
+    #[test]
+    fn test_decode_stream() {
+        let original = create_test_batch(100_000);
+        let encoded = encode_batch(&original);
+
+        let decoder = ParquetDecoder::with_batch_size(8192);
+        let batches: Vec<RecordBatch> = decoder
+            .decode_stream(encoded.clone())
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap();
+
+        // ceil(100_000 / 8192) read steps, none larger than the batch size
+        assert_eq!(batches.len(), 13);
+        assert!(batches.iter().all(|b| b.num_rows() <= 8192));
+
+        let reassembled = arrow::compute::concat_batches(&batches[0].schema(), &batches).unwrap();
+        assert_eq!(reassembled, decoder.decode(&encoded).unwrap());
+    }
+
     // ========== Projection Pushdown Tests ==========
 
     #[test]
@@ -927,6 +1814,134 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_decode_cast() {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("score", DataType::Float64, false),
+            Field::new("name", DataType::Utf8, true),
+        ]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(arrow::array::Int32Array::from(vec![1, 2, 3])),
+                Arc::new(Float64Array::from(vec![2.9, -2.9, 7.0])),
+                Arc::new(StringArray::from(vec![Some("a"), None, Some("c")])),
+            ],
+        )
+        .unwrap();
+        let encoded = encode_batch(&batch);
+
+        let decoder = ParquetDecoder::new();
+        let decoded = decoder
+            .decode_cast(&encoded, &[("id", DataType::Int64), ("score", DataType::Int64)])
+            .unwrap();
+
+        assert_eq!(decoded.schema().field(0).data_type(), &DataType::Int64);
+        assert_eq!(decoded.schema().field(1).data_type(), &DataType::Int64);
+        assert_eq!(decoded.schema().field(2).data_type(), &DataType::Utf8);
+
+        let ids = decoded.column(0).as_primitive::<arrow::datatypes::Int64Type>();
+        assert_eq!(ids.values(), &[1, 2, 3]);
+
+        // Float to integer truncates toward zero
+        let scores = decoded.column(1).as_primitive::<arrow::datatypes::Int64Type>();
+        assert_eq!(scores.values(), &[2, -2, 7]);
+
+        let result = decoder.decode_cast(&encoded, &[("id", DataType::Struct(Default::default()))]);
+        assert!(matches!(result, Err(ParquetError::IncompatibleCast { .. })));
+
+        let result = decoder.decode_cast(&encoded, &[("missing", DataType::Int64)]);
+        assert!(matches!(result, Err(ParquetError::InvalidColumn(_))));
+    }
+
+    #[test]
+    fn test_decode_all_nullable() {
+        let strict = RecordBatch::try_new(
+            Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, false)])),
+            vec![Arc::new(Int64Array::from(vec![1, 2]))],
+        )
+        .unwrap();
+        let with_nulls = RecordBatch::try_new(
+            Arc::new(Schema::new(vec![Field::new("id", DataType::Int64, true)])),
+            vec![Arc::new(Int64Array::from(vec![Some(3), None]))],
+        )
+        .unwrap();
+        let encoded = encode_batch(&strict);
+        let decoder = ParquetDecoder::new();
+
+        let raw = decoder.decode(&encoded).unwrap();
+        assert!(!raw.schema().field(0).is_nullable());
+        assert!(arrow::compute::concat_batches(&raw.schema(), [&raw, &with_nulls]).is_err());
+
+        let relaxed = decoder.decode_all_nullable(&encoded).unwrap();
+        assert!(relaxed.schema().field(0).is_nullable());
+        assert_eq!(relaxed.column(0), raw.column(0));
+
+        let combined =
+            arrow::compute::concat_batches(&relaxed.schema(), [&relaxed, &with_nulls]).unwrap();
+        assert_eq!(combined.num_rows(), 4);
+        assert_eq!(combined.column(0).null_count(), 1);
+    }
+
+    #[test]
+    fn test_decode_rows() {
+        let original = create_test_batch(1000);
+        let encoded = encode_batch(&original);
+
+        let decoder = ParquetDecoder::new();
+        let decoded = decoder.decode_rows(&encoded, &[999, 0, 5, 5]).unwrap();
+
+        assert_eq!(decoded.num_rows(), 3);
+        assert_eq!(decoded.num_columns(), 3);
+        let ids = decoded.column(0).as_primitive::<arrow::datatypes::Int64Type>();
+        assert_eq!(ids.values(), &[0, 5, 999]);
+
+        let result = decoder.decode_rows(&encoded, &[0, 1000]);
+        assert!(matches!(
+            result,
+            Err(ParquetError::RowIndexOutOfRange { index: 1000, num_rows: 1000 })
+        ));
+
+        let result = decoder.decode_rows(&encoded, &[]);
+        assert!(matches!(result, Err(ParquetError::EmptyData)));
+    }
+
+    #[test]
+    fn test_repair_drops_corrupt_row_group() {
+        let batches: Vec<RecordBatch> = (0..3).map(|_| create_test_batch(200)).collect();
+        let mut stream = ParquetEncoder::with_compression(ParquetCompression::Uncompressed).stream_to(Vec::new());
+        for batch in &batches {
+            stream.write_batch(batch).unwrap();
+        }
+        let mut encoded = stream.finish().unwrap();
+
+        // Overwrite the page header at the start of row group 1's first column
+        let metadata = ArrowReaderMetadata::load(&Bytes::from(encoded.clone()), Default::default()).unwrap();
+        let (start, _) = metadata.metadata().row_group(1).column(0).byte_range();
+        for byte in &mut encoded[start as usize..start as usize + 16] {
+            *byte = 0xFF;
+        }
+
+        let decoder = ParquetDecoder::new();
+        assert!(decoder.decode(&encoded).is_err());
+
+        let (repaired, report) = decoder.repair(&encoded).unwrap();
+        assert_eq!(
+            report,
+            RepairReport {
+                total_row_groups: 3,
+                dropped_row_groups: vec![1],
+                rows_kept: 400,
+                rows_lost: 200,
+            }
+        );
+
+        let decoded = decoder.decode(&repaired).unwrap();
+        assert_eq!(decoded.num_rows(), 400);
+        assert_eq!(decoded.slice(0, 200), batches[0]);
+    }
+
     #[test]
     fn test_projection_all_columns_equals_full_decode() {
         let original = create_test_batch(1000);
@@ -987,6 +2002,69 @@ mod tests {
         assert_eq!(ids.value(0), 500);
     }
 
+    fn extremes_batch() -> RecordBatch {
+        let schema = Schema::new(vec![
+            Field::new("v", DataType::Int64, false),
+            Field::new("flag", DataType::Boolean, false),
+        ]);
+        let values = vec![i64::MIN, -5, -1, 0, 1, 7, i64::MAX];
+        let flags: Vec<bool> = values.iter().map(|v| *v == 0 || *v == 1).collect();
+        RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int64Array::from(values)),
+                Arc::new(BooleanArray::from(flags)),
+            ],
+        )
+        .unwrap()
+    }
+
+    fn filtered_values(encoded: &[u8], op: FilterOp, value: i64) -> Vec<i64> {
+        let filter = PredicateFilter::new("v", op, ScalarValue::Int64(value));
+        match ParquetDecoder::new().decode_with_filter(encoded, &[filter], None) {
+            Ok(batch) => batch
+                .column(0)
+                .as_primitive::<arrow::datatypes::Int64Type>()
+                .values()
+                .to_vec(),
+            Err(ParquetError::EmptyData) => vec![],
+            Err(e) => panic!("unexpected error: {}", e),
+        }
+    }
+
+    #[test]
+    fn test_filter_int64_extremes_and_negatives() {
+        let encoded = encode_batch(&extremes_batch());
+
+        assert_eq!(filtered_values(&encoded, FilterOp::Eq, i64::MIN), vec![i64::MIN]);
+        assert_eq!(filtered_values(&encoded, FilterOp::Eq, i64::MAX), vec![i64::MAX]);
+        assert_eq!(filtered_values(&encoded, FilterOp::Eq, 0), vec![0]);
+        assert_eq!(filtered_values(&encoded, FilterOp::Eq, -5), vec![-5]);
+        assert_eq!(filtered_values(&encoded, FilterOp::Lt, 0), vec![i64::MIN, -5, -1]);
+        assert_eq!(filtered_values(&encoded, FilterOp::Le, -1), vec![i64::MIN, -5, -1]);
+        assert_eq!(filtered_values(&encoded, FilterOp::Gt, -1), vec![0, 1, 7, i64::MAX]);
+        assert_eq!(filtered_values(&encoded, FilterOp::Ge, i64::MAX), vec![i64::MAX]);
+        assert_eq!(filtered_values(&encoded, FilterOp::Ne, 0).len(), 6);
+        assert_eq!(filtered_values(&encoded, FilterOp::Ge, i64::MIN).len(), 7);
+        assert!(filtered_values(&encoded, FilterOp::Gt, i64::MAX).is_empty());
+        assert!(filtered_values(&encoded, FilterOp::Lt, i64::MIN).is_empty());
+    }
+
+    #[test]
+    fn test_filter_boolean_column() {
+        let encoded = encode_batch(&extremes_batch());
+        let decoder = ParquetDecoder::new();
+
+        let filter = PredicateFilter::new("flag", FilterOp::Eq, ScalarValue::Boolean(true));
+        let filtered = decoder.decode_with_filter(&encoded, &[filter], None).unwrap();
+        let values = filtered.column(0).as_primitive::<arrow::datatypes::Int64Type>();
+        assert_eq!(values.values().to_vec(), vec![0, 1]);
+
+        // A boolean scalar never matches an integer column by coercion
+        let filter = PredicateFilter::new("v", FilterOp::Eq, ScalarValue::Boolean(true));
+        assert!(decoder.decode_with_filter(&encoded, &[filter], None).is_err());
+    }
+
     #[test]
     fn test_filter_int64_gt() {
         // 1000 rows with id 0-999
@@ -1074,6 +2152,158 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_decode_with_filter_masked() {
+        let original = create_test_batch(1000);
+        let encoded = encode_batch(&original);
+        let decoder = ParquetDecoder::new();
+
+        // Filters on a nullable column too: null names never match
+        let filters = vec![
+            PredicateFilter::new("id", FilterOp::Ge, ScalarValue::Int64(250)),
+            PredicateFilter::new("name", FilterOp::Eq, ScalarValue::Utf8("test".to_string())),
+        ];
+        let filtered = decoder.decode_with_filter(&encoded, &filters, None).unwrap();
+        let (batch, mask) = decoder
+            .decode_with_filter_masked(&encoded, &filters, Some(&[0]))
+            .unwrap();
+
+        assert_eq!(batch.num_rows(), 1000);
+        assert_eq!(batch.num_columns(), 1);
+        assert_eq!(mask.len(), 1000);
+        assert_eq!(mask.null_count(), 0);
+        assert_eq!(mask.true_count(), filtered.num_rows());
+        assert!(!mask.value(249) && !mask.value(251) && mask.value(250));
+
+        // Row positions are preserved: the masked rows are the filtered rows
+        let kept = arrow::compute::filter_record_batch(&batch, &mask).unwrap();
+        assert_eq!(kept.column(0).as_ref(), filtered.column(0).as_ref());
+
+        let (_, all) = decoder.decode_with_filter_masked(&encoded, &[], None).unwrap();
+        assert_eq!(all.true_count(), 1000);
+
+        let missing = PredicateFilter::new("missing", FilterOp::Eq, ScalarValue::Int64(1));
+        assert!(matches!(
+            decoder.decode_with_filter_masked(&encoded, &[missing], None),
+            Err(ParquetError::InvalidColumn(_))
+        ));
+    }
+
+    #[test]
+    fn test_percentile() {
+        // value = id * 1.5 for ids 0..1001, so the median is 500 * 1.5
+        let encoded = encode_batch(&create_test_batch(1001));
+        let decoder = ParquetDecoder::new();
+
+        assert_eq!(decoder.percentile(&encoded, "value", 0.5, &[]).unwrap(), 750.0);
+        assert_eq!(decoder.percentile(&encoded, "id", 0.0, &[]).unwrap(), 0.0);
+        assert_eq!(decoder.percentile(&encoded, "id", 1.0, &[]).unwrap(), 1000.0);
+        assert_eq!(decoder.percentile(&encoded, "id", 0.95, &[]).unwrap(), 950.0);
+        // Interpolates between ranks
+        assert_eq!(decoder.percentile(&encoded, "id", 0.0005, &[]).unwrap(), 0.5);
+
+        // Only ids 500..=1000 match
+        let filter = PredicateFilter::new("id", FilterOp::Ge, ScalarValue::Int64(500));
+        assert_eq!(decoder.percentile(&encoded, "id", 0.5, &[filter]).unwrap(), 750.0);
+
+        assert!(matches!(
+            decoder.percentile(&encoded, "name", 0.5, &[]),
+            Err(ParquetError::InvalidColumn(_))
+        ));
+        assert!(matches!(
+            decoder.percentile(&encoded, "missing", 0.5, &[]),
+            Err(ParquetError::InvalidColumn(_))
+        ));
+        assert!(decoder.percentile(&encoded, "id", 1.5, &[]).is_err());
+        let none = PredicateFilter::new("id", FilterOp::Gt, ScalarValue::Int64(5000));
+        assert!(matches!(
+            decoder.percentile(&encoded, "id", 0.5, &[none]),
+            Err(ParquetError::EmptyData)
+        ));
+    }
+
+    #[test]
+    fn test_aggregate_from_statistics() {
+        use crate::parquet::ParquetWriterConfig;
+
+        let encoded = ParquetEncoder::new()
+            .with_properties(ParquetWriterConfig {
+                max_row_group_size: 250,
+                ..Default::default()
+            })
+            .encode(&create_test_batch(1000))
+            .unwrap();
+        let decoder = ParquetDecoder::new();
+
+        let count = decoder.aggregate(&encoded, &AggregateSpec::Count).unwrap();
+        assert_eq!(count.value, ScalarValue::Int64(1000));
+        assert!(count.from_statistics);
+
+        let min = decoder.aggregate(&encoded, &AggregateSpec::Min("id".into())).unwrap();
+        let max = decoder.aggregate(&encoded, &AggregateSpec::Max("value".into())).unwrap();
+        assert_eq!(min, AggregateResult { value: ScalarValue::Int64(0), from_statistics: true });
+        assert_eq!(max, AggregateResult { value: ScalarValue::Float64(999.0 * 1.5), from_statistics: true });
+
+        // Null names are skipped
+        let name = decoder.aggregate(&encoded, &AggregateSpec::Max("name".into())).unwrap();
+        assert_eq!(name.value, ScalarValue::Utf8("test".into()));
+
+        assert!(matches!(
+            decoder.aggregate(&encoded, &AggregateSpec::Min("missing".into())),
+            Err(ParquetError::InvalidColumn(_))
+        ));
+    }
+
+    #[test]
+    fn test_aggregate_falls_back_without_statistics() {
+        use crate::parquet::ParquetWriterConfig;
+
+        let encoded = ParquetEncoder::new()
+            .with_properties(ParquetWriterConfig {
+                write_statistics: false,
+                ..Default::default()
+            })
+            .encode(&create_test_batch(1000))
+            .unwrap();
+        let decoder = ParquetDecoder::new();
+
+        // Row counts live in the footer regardless of statistics
+        let count = decoder.aggregate(&encoded, &AggregateSpec::Count).unwrap();
+        assert!(count.from_statistics);
+
+        let max = decoder.aggregate(&encoded, &AggregateSpec::Max("id".into())).unwrap();
+        assert_eq!(max, AggregateResult { value: ScalarValue::Int64(999), from_statistics: false });
+    }
+
+    #[test]
+    fn test_aggregate_partial_statistics() {
+        let decoder = ParquetDecoder::new();
+        let long = "z".repeat(100);
+
+        // An all-null row group has no bounds but is still answered by statistics
+        let mut stream = ParquetEncoder::new().stream_to(Vec::new());
+        stream.write_batch(&names_batch(vec![Some("b"), Some("m")])).unwrap();
+        stream.write_batch(&names_batch(vec![None, None])).unwrap();
+        let encoded = stream.finish().unwrap();
+        let min = decoder.aggregate(&encoded, &AggregateSpec::Min("name".into())).unwrap();
+        assert_eq!(min, AggregateResult { value: ScalarValue::Utf8("b".into()), from_statistics: true });
+
+        // The writer truncates long string bounds, so the max must be scanned
+        let mut stream = ParquetEncoder::new().stream_to(Vec::new());
+        stream.write_batch(&names_batch(vec![Some("b"), Some("m")])).unwrap();
+        stream.write_batch(&names_batch(vec![Some("a"), Some(long.as_str())])).unwrap();
+        let encoded = stream.finish().unwrap();
+        let max = decoder.aggregate(&encoded, &AggregateSpec::Max("name".into())).unwrap();
+        assert_eq!(max, AggregateResult { value: ScalarValue::Utf8(long), from_statistics: false });
+        let min = decoder.aggregate(&encoded, &AggregateSpec::Min("name".into())).unwrap();
+        assert_eq!(min, AggregateResult { value: ScalarValue::Utf8("a".into()), from_statistics: true });
+
+        // A column with no values at all
+        let encoded = encode_batch(&names_batch(vec![None, None]));
+        let min = decoder.aggregate(&encoded, &AggregateSpec::Min("name".into())).unwrap();
+        assert_eq!(min.value, ScalarValue::Null);
+    }
+
     #[test]
     fn test_filter_no_matches_error() {
         let original = create_test_batch(100);
@@ -1179,4 +2409,457 @@ mod tests {
         // Either pruned via stats OR row-level filter returns empty
         assert!(matches!(result, Err(ParquetError::EmptyData)));
     }
+
+    // ========== IN / NOT IN Tests ==========
+
+    fn int64_list(values: &[i64]) -> ScalarValue {
+        ScalarValue::List(values.iter().map(|v| ScalarValue::Int64(*v)).collect())
+    }
+
+    #[test]
+    fn test_filter_int64_in_list() {
+        let encoded = encode_batch(&extremes_batch());
+        let decoder = ParquetDecoder::new();
+
+        let filter = PredicateFilter::new("v", FilterOp::In, int64_list(&[-5, 7, 42, i64::MAX]));
+        let filtered = decoder.decode_with_filter(&encoded, &[filter], None).unwrap();
+        let values = filtered.column(0).as_primitive::<arrow::datatypes::Int64Type>();
+        assert_eq!(values.values().to_vec(), vec![-5, 7, i64::MAX]);
+
+        let filter = PredicateFilter::new("v", FilterOp::NotIn, int64_list(&[i64::MIN, 0, 1]));
+        let filtered = decoder.decode_with_filter(&encoded, &[filter], None).unwrap();
+        let values = filtered.column(0).as_primitive::<arrow::datatypes::Int64Type>();
+        assert_eq!(values.values().to_vec(), vec![-5, -1, 7, i64::MAX]);
+
+        // An empty IN-list matches nothing
+        let filter = PredicateFilter::new("v", FilterOp::In, int64_list(&[]));
+        let result = decoder.decode_with_filter(&encoded, &[filter], None);
+        assert!(matches!(result, Err(ParquetError::EmptyData)));
+
+        // IN requires a list value
+        let filter = PredicateFilter::new("v", FilterOp::In, ScalarValue::Int64(7));
+        assert!(decoder.decode_with_filter(&encoded, &[filter], None).is_err());
+    }
+
+    #[test]
+    fn test_filter_utf8_in_list() {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("status", DataType::Utf8, true),
+        ]);
+        let batch = RecordBatch::try_new(
+            Arc::new(schema),
+            vec![
+                Arc::new(Int64Array::from(vec![0, 1, 2, 3, 4, 5])),
+                Arc::new(StringArray::from(vec![
+                    Some("active"),
+                    Some("churned"),
+                    Some("trial"),
+                    None,
+                    Some("paid"),
+                    Some("churned"),
+                ])),
+            ],
+        )
+        .unwrap();
+        let encoded = encode_batch(&batch);
+        let decoder = ParquetDecoder::new();
+        let statuses = ScalarValue::List(
+            ["active", "trial", "paid"]
+                .iter()
+                .map(|s| ScalarValue::Utf8(s.to_string()))
+                .collect(),
+        );
+
+        let filter = PredicateFilter::new("status", FilterOp::In, statuses.clone());
+        let filtered = decoder.decode_with_filter(&encoded, &[filter], None).unwrap();
+        let ids = filtered.column(0).as_primitive::<arrow::datatypes::Int64Type>();
+        assert_eq!(ids.values().to_vec(), vec![0, 2, 4]);
+
+        // Nulls match neither IN nor NOT IN
+        let filter = PredicateFilter::new("status", FilterOp::NotIn, statuses);
+        let filtered = decoder.decode_with_filter(&encoded, &[filter], None).unwrap();
+        let ids = filtered.column(0).as_primitive::<arrow::datatypes::Int64Type>();
+        assert_eq!(ids.values().to_vec(), vec![1, 5]);
+    }
+
+    #[test]
+    fn test_row_group_pruning_in_list() {
+        // Three row groups holding 0..100, 100..200 and 200..300
+        let schema = Arc::new(Schema::new(vec![Field::new("v", DataType::Int64, false)]));
+        let mut stream = ParquetEncoder::new().stream_to(Vec::new());
+        for start in [0i64, 100, 200] {
+            let batch = RecordBatch::try_new(
+                schema.clone(),
+                vec![Arc::new(Int64Array::from((start..start + 100).collect::<Vec<_>>()))],
+            )
+            .unwrap();
+            stream.write_batch(&batch).unwrap();
+        }
+        let encoded = stream.finish().unwrap();
+        let decoder = ParquetDecoder::new();
+
+        // Only the middle row group has no listed value
+        let filter = PredicateFilter::new("v", FilterOp::In, int64_list(&[5, 250, 1000]));
+        let (total, pruned, kept) = decoder.get_pruning_stats(&encoded, std::slice::from_ref(&filter)).unwrap();
+        assert_eq!((total, pruned, kept), (3, 1, 2));
+        assert_eq!(filtered_values_with(&encoded, filter), vec![5, 250]);
+
+        // No row group overlaps the list
+        let filter = PredicateFilter::new("v", FilterOp::In, int64_list(&[-1, 300]));
+        let (_, pruned, _) = decoder.get_pruning_stats(&encoded, &[filter]).unwrap();
+        assert_eq!(pruned, 3);
+
+        // NOT IN cannot rule out a row group with varying values
+        let filter = PredicateFilter::new("v", FilterOp::NotIn, int64_list(&[5, 250]));
+        let (_, pruned, _) = decoder.get_pruning_stats(&encoded, std::slice::from_ref(&filter)).unwrap();
+        assert_eq!(pruned, 0);
+        assert_eq!(filtered_values_with(&encoded, filter).len(), 298);
+    }
+
+    fn filtered_values_with(encoded: &[u8], filter: PredicateFilter) -> Vec<i64> {
+        let batch = ParquetDecoder::new().decode_with_filter(encoded, &[filter], None).unwrap();
+        batch.column(0).as_primitive::<arrow::datatypes::Int64Type>().values().to_vec()
+    }
+
+    // ========== String Match Tests ==========
+
+    fn names_batch(names: Vec<Option<&str>>) -> RecordBatch {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("name", DataType::Utf8, true),
+        ]);
+        let ids: Vec<i64> = (0..names.len() as i64).collect();
+        RecordBatch::try_new(
+            Arc::new(schema),
+            vec![Arc::new(Int64Array::from(ids)), Arc::new(StringArray::from(names))],
+        )
+        .unwrap()
+    }
+
+    fn matching_ids(encoded: &[u8], op: FilterOp, pattern: &str) -> Vec<i64> {
+        let filter = PredicateFilter::new("name", op, ScalarValue::Utf8(pattern.to_string()));
+        match ParquetDecoder::new().decode_with_filter(encoded, &[filter], None) {
+            Ok(batch) => batch
+                .column(0)
+                .as_primitive::<arrow::datatypes::Int64Type>()
+                .values()
+                .to_vec(),
+            Err(ParquetError::EmptyData) => vec![],
+            Err(e) => panic!("unexpected error: {}", e),
+        }
+    }
+
+    #[test]
+    fn test_filter_string_matches() {
+        let encoded = encode_batch(&names_batch(vec![
+            Some("prod-eu"),
+            Some("staging"),
+            Some("prod-us"),
+            None,
+            Some("dev-prod"),
+            Some("PROD"),
+        ]));
+
+        assert_eq!(matching_ids(&encoded, FilterOp::StartsWith, "prod"), vec![0, 2]);
+        assert_eq!(matching_ids(&encoded, FilterOp::EndsWith, "prod"), vec![4]);
+        assert_eq!(matching_ids(&encoded, FilterOp::Contains, "prod"), vec![0, 2, 4]);
+        assert_eq!(matching_ids(&encoded, FilterOp::Contains, "-"), vec![0, 2, 4]);
+        assert!(matching_ids(&encoded, FilterOp::StartsWith, "qa").is_empty());
+
+        // An empty pattern matches every non-null value
+        assert_eq!(matching_ids(&encoded, FilterOp::Contains, ""), vec![0, 1, 2, 4, 5]);
+
+        // String matches need a Utf8 column and value
+        let filter = PredicateFilter::new("id", FilterOp::StartsWith, ScalarValue::Utf8("1".to_string()));
+        assert!(ParquetDecoder::new().decode_with_filter(&encoded, &[filter], None).is_err());
+    }
+
+    #[test]
+    fn test_row_group_pruning_starts_with() {
+        let mut stream = ParquetEncoder::new().stream_to(Vec::new());
+        for names in [
+            vec![Some("alpha"), Some("beta")],
+            vec![Some("prod-eu"), Some("prod-us")],
+            vec![Some("qa"), Some("zeta")],
+        ] {
+            stream.write_batch(&names_batch(names)).unwrap();
+        }
+        let encoded = stream.finish().unwrap();
+
+        let filter = PredicateFilter::new("name", FilterOp::StartsWith, ScalarValue::Utf8("prod".to_string()));
+        let (total, pruned, kept) = ParquetDecoder::new()
+            .get_pruning_stats(&encoded, std::slice::from_ref(&filter))
+            .unwrap();
+        assert_eq!((total, pruned, kept), (3, 2, 1));
+
+        let filter = PredicateFilter::new("name", FilterOp::EndsWith, ScalarValue::Utf8("a".to_string()));
+        let (_, pruned, _) = ParquetDecoder::new().get_pruning_stats(&encoded, &[filter]).unwrap();
+        assert_eq!(pruned, 0);
+    }
+
+    // ========== Null Check Tests ==========
+
+    #[test]
+    fn test_filter_is_null_and_is_not_null() {
+        // Even rows have name = 'test', odd rows are null
+        let encoded = encode_batch(&create_test_batch(100));
+        let decoder = ParquetDecoder::new();
+
+        let filter = PredicateFilter::new("name", FilterOp::IsNull, ScalarValue::Null);
+        let filtered = decoder.decode_with_filter(&encoded, &[filter], None).unwrap();
+        let ids = filtered.column(0).as_primitive::<arrow::datatypes::Int64Type>();
+        assert_eq!(ids.values().to_vec(), (1..100).step_by(2).collect::<Vec<i64>>());
+        assert_eq!(filtered.column(2).null_count(), 50);
+
+        let filter = PredicateFilter::new("name", FilterOp::IsNotNull, ScalarValue::Null);
+        let filtered = decoder.decode_with_filter(&encoded, &[filter], None).unwrap();
+        let ids = filtered.column(0).as_primitive::<arrow::datatypes::Int64Type>();
+        assert_eq!(ids.values().to_vec(), (0..100).step_by(2).collect::<Vec<i64>>());
+        assert_eq!(filtered.column(2).null_count(), 0);
+
+        // The value is ignored
+        let filter = PredicateFilter::new("id", FilterOp::IsNull, ScalarValue::Int64(7));
+        let result = decoder.decode_with_filter(&encoded, &[filter], None);
+        assert!(matches!(result, Err(ParquetError::EmptyData)));
+    }
+
+    #[test]
+    fn test_row_group_pruning_null_count() {
+        // Row group 0 has no nulls, row group 1 is all nulls, row group 2 is mixed
+        let schema = Arc::new(Schema::new(vec![Field::new("name", DataType::Utf8, true)]));
+        let groups: [Vec<Option<&str>>; 3] = [
+            vec![Some("a"), Some("b")],
+            vec![None, None],
+            vec![Some("c"), None],
+        ];
+        let mut stream = ParquetEncoder::new().stream_to(Vec::new());
+        for names in groups {
+            let batch =
+                RecordBatch::try_new(schema.clone(), vec![Arc::new(StringArray::from(names))]).unwrap();
+            stream.write_batch(&batch).unwrap();
+        }
+        let encoded = stream.finish().unwrap();
+        let decoder = ParquetDecoder::new();
+
+        let is_null = PredicateFilter::new("name", FilterOp::IsNull, ScalarValue::Null);
+        let (total, pruned, kept) = decoder.get_pruning_stats(&encoded, std::slice::from_ref(&is_null)).unwrap();
+        assert_eq!((total, pruned, kept), (3, 1, 2));
+        let filtered = decoder.decode_with_filter(&encoded, &[is_null], None).unwrap();
+        assert_eq!(filtered.num_rows(), 3);
+
+        let is_not_null = PredicateFilter::new("name", FilterOp::IsNotNull, ScalarValue::Null);
+        let (_, pruned, _) = decoder.get_pruning_stats(&encoded, std::slice::from_ref(&is_not_null)).unwrap();
+        assert_eq!(pruned, 1);
+        let filtered = decoder.decode_with_filter(&encoded, &[is_not_null], None).unwrap();
+        assert_eq!(filtered.num_rows(), 3);
+    }
+
+    // ========== Predicate Expression Tests ==========
+
+    fn int64_leaf(column: &str, op: FilterOp, value: i64) -> PredicateExpr {
+        PredicateExpr::Leaf(PredicateFilter::new(column, op, ScalarValue::Int64(value)))
+    }
+
+    #[test]
+    fn test_decode_with_expr_or() {
+        // ids 0-999; id < 18 OR id > 995
+        let encoded = encode_batch(&create_test_batch(1000));
+        let decoder = ParquetDecoder::new();
+        let expr = PredicateExpr::Or(vec![
+            int64_leaf("id", FilterOp::Lt, 18),
+            int64_leaf("id", FilterOp::Gt, 995),
+        ]);
+
+        let filtered = decoder.decode_with_expr(&encoded, &expr, Some(&[0])).unwrap();
+        let ids = filtered.column(0).as_primitive::<arrow::datatypes::Int64Type>();
+        let expected: Vec<i64> = (0..18).chain(996..1000).collect();
+        assert_eq!(ids.values().to_vec(), expected);
+
+        // (id < 10 OR id >= 990) AND name = 'test' keeps even ids only
+        let expr = PredicateExpr::And(vec![
+            PredicateExpr::Or(vec![
+                int64_leaf("id", FilterOp::Lt, 10),
+                int64_leaf("id", FilterOp::Ge, 990),
+            ]),
+            PredicateExpr::Leaf(PredicateFilter::new(
+                "name",
+                FilterOp::Eq,
+                ScalarValue::Utf8("test".to_string()),
+            )),
+        ]);
+        let filtered = decoder.decode_with_expr(&encoded, &expr, None).unwrap();
+        let ids = filtered.column(0).as_primitive::<arrow::datatypes::Int64Type>();
+        assert_eq!(ids.values().to_vec(), vec![0, 2, 4, 6, 8, 990, 992, 994, 996, 998]);
+
+        // A leaf on an unknown column is rejected
+        let expr = PredicateExpr::Or(vec![int64_leaf("missing", FilterOp::Eq, 1)]);
+        assert!(matches!(
+            decoder.decode_with_expr(&encoded, &expr, None),
+            Err(ParquetError::InvalidColumn(_))
+        ));
+
+        // Constant expressions
+        let all = decoder.decode_with_expr(&encoded, &PredicateExpr::And(vec![]), None).unwrap();
+        assert_eq!(all.num_rows(), 1000);
+        assert!(matches!(
+            decoder.decode_with_expr(&encoded, &PredicateExpr::Or(vec![]), None),
+            Err(ParquetError::EmptyData)
+        ));
+    }
+
+    #[test]
+    fn test_decode_with_expr_or_null_is_kleene() {
+        // Odd rows have a null name; `name = 'x' OR id < 4` still keeps ids 1 and 3
+        let encoded = encode_batch(&create_test_batch(10));
+        let expr = PredicateExpr::Or(vec![
+            PredicateExpr::Leaf(PredicateFilter::new(
+                "name",
+                FilterOp::Eq,
+                ScalarValue::Utf8("x".to_string()),
+            )),
+            int64_leaf("id", FilterOp::Lt, 4),
+        ]);
+        let filtered = ParquetDecoder::new().decode_with_expr(&encoded, &expr, None).unwrap();
+        let ids = filtered.column(0).as_primitive::<arrow::datatypes::Int64Type>();
+        assert_eq!(ids.values().to_vec(), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_decode_with_expr_prunes_only_when_whole_expr_fails() {
+        // Three row groups holding 0..100, 100..200 and 200..300
+        let schema = Arc::new(Schema::new(vec![Field::new("v", DataType::Int64, false)]));
+        let mut stream = ParquetEncoder::new().stream_to(Vec::new());
+        for start in [0i64, 100, 200] {
+            let batch = RecordBatch::try_new(
+                schema.clone(),
+                vec![Arc::new(Int64Array::from((start..start + 100).collect::<Vec<_>>()))],
+            )
+            .unwrap();
+            stream.write_batch(&batch).unwrap();
+        }
+        let encoded = stream.finish().unwrap();
+        let decoder = ParquetDecoder::new();
+
+        // v < 5 OR v > 295: the middle group can be skipped, the others cannot
+        let expr = PredicateExpr::Or(vec![
+            int64_leaf("v", FilterOp::Lt, 5),
+            int64_leaf("v", FilterOp::Gt, 295),
+        ]);
+        let filtered = decoder.decode_with_expr(&encoded, &expr, None).unwrap();
+        let values = filtered.column(0).as_primitive::<arrow::datatypes::Int64Type>();
+        assert_eq!(values.values().to_vec(), vec![0, 1, 2, 3, 4, 296, 297, 298, 299]);
+
+        // No branch can match anywhere
+        let expr = PredicateExpr::Or(vec![
+            int64_leaf("v", FilterOp::Lt, -1),
+            int64_leaf("v", FilterOp::Gt, 1000),
+        ]);
+        assert!(matches!(
+            decoder.decode_with_expr(&encoded, &expr, None),
+            Err(ParquetError::EmptyData)
+        ));
+    }
+
+    // ========== Explain Tests ==========
+
+    #[test]
+    fn test_explain_filter() {
+        let original = create_test_batch(1000);
+        let encoded = encode_batch(&original);
+
+        let decoder = ParquetDecoder::new();
+        let filters = vec![
+            PredicateFilter::new("id", FilterOp::Ge, ScalarValue::Int64(990)),
+            PredicateFilter::new("id", FilterOp::Lt, ScalarValue::Int64(995)),
+        ];
+
+        let explain = decoder.explain_filter(&encoded, &filters).unwrap();
+        let (total, pruned, _) = decoder.get_pruning_stats(&encoded, &filters).unwrap();
+
+        assert_eq!(explain.total_row_groups, total);
+        assert_eq!(explain.pruned_row_groups, pruned);
+        assert_eq!(explain.rows_scanned, 1000);
+        assert_eq!(explain.rows_returned, 5);
+        assert!(explain.rows_returned <= explain.rows_scanned);
+        assert_eq!(explain.filter_columns, vec!["id"]);
+    }
+
+    #[test]
+    fn test_plan_filter() {
+        use crate::parquet::ParquetWriterConfig;
+
+        // Four row groups of 100 rows: ids 0..100, 100..200, ...
+        let encoded = ParquetEncoder::new()
+            .with_properties(ParquetWriterConfig {
+                max_row_group_size: 100,
+                ..Default::default()
+            })
+            .encode(&create_test_batch(400))
+            .unwrap();
+
+        let decoder = ParquetDecoder::new();
+        let filter = PredicateFilter::new("id", FilterOp::Ge, ScalarValue::Int64(250));
+        let plan = decoder.plan_filter(&encoded, std::slice::from_ref(&filter)).unwrap();
+        assert_eq!(plan.row_groups_to_scan, vec![2, 3]);
+        assert_eq!(plan.estimated_rows, 200);
+
+        let (total, pruned, kept) = decoder.get_pruning_stats(&encoded, &[filter]).unwrap();
+        assert_eq!((total, pruned, kept), (4, 2, 2));
+
+        let plan = decoder.plan_filter(&encoded, &[]).unwrap();
+        assert_eq!(plan.row_groups_to_scan, vec![0, 1, 2, 3]);
+        assert_eq!(plan.estimated_rows, 400);
+
+        let bad = PredicateFilter::new("missing", FilterOp::Eq, ScalarValue::Int64(1));
+        assert!(matches!(
+            decoder.plan_filter(&encoded, &[bad]),
+            Err(ParquetError::InvalidColumn(_))
+        ));
+    }
+
+    #[test]
+    fn test_explain_filter_all_pruned() {
+        let original = create_test_batch(100);
+        let encoded = encode_batch(&original);
+
+        let decoder = ParquetDecoder::new();
+        let filter = PredicateFilter::new("id", FilterOp::Gt, ScalarValue::Int64(9999));
+
+        let explain = decoder.explain_filter(&encoded, &[filter]).unwrap();
+
+        assert_eq!(explain.pruned_row_groups, explain.total_row_groups);
+        assert_eq!(explain.rows_scanned, 0);
+        assert_eq!(explain.rows_returned, 0);
+    }
+
+    // ========== Batch Count Tests ==========
+
+    #[test]
+    fn test_count_batch_with_filter() {
+        // Each chunk has ids 0..n, so `id < 50` matches min(n, 50) rows
+        let chunks: Vec<Vec<u8>> = [10, 100, 30, 1000]
+            .iter()
+            .map(|&n| encode_batch(&create_test_batch(n)))
+            .collect();
+
+        let decoder = ParquetDecoder::new();
+        let filter = PredicateFilter::new("id", FilterOp::Lt, ScalarValue::Int64(50));
+
+        let total = decoder.count_batch_with_filter(&chunks, &[filter]).unwrap();
+
+        assert_eq!(total, 10 + 50 + 30 + 50);
+    }
+
+    #[test]
+    fn test_count_batch_with_filter_no_matches_and_empty_chunks() {
+        let chunks = vec![encode_batch(&create_test_batch(100)), Vec::new()];
+
+        let decoder = ParquetDecoder::new();
+        let filter = PredicateFilter::new("id", FilterOp::Gt, ScalarValue::Int64(9999));
+
+        let total = decoder.count_batch_with_filter(&chunks, &[filter]).unwrap();
+
+        assert_eq!(total, 0);
+    }
 }
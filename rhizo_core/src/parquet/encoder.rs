@@ -3,12 +3,16 @@
 //! This module provides high-performance Parquet encoding using the Rust
 //! parquet crate, with support for parallel batch encoding via Rayon.
 
+use std::collections::HashMap;
+use std::io::Write;
 use std::str::FromStr;
+use std::sync::Arc;
 
+use arrow::datatypes::{Field, Schema};
 use arrow::record_batch::RecordBatch;
 use parquet::arrow::ArrowWriter;
 use parquet::basic::Compression;
-use parquet::file::properties::WriterProperties;
+use parquet::file::properties::{EnabledStatistics, WriterProperties, DEFAULT_MAX_ROW_GROUP_SIZE};
 use rayon::prelude::*;
 
 use super::error::ParquetError;
@@ -57,6 +61,33 @@ impl FromStr for ParquetCompression {
     }
 }
 
+/// Writer settings applied to every file produced by a `ParquetEncoder`.
+///
+/// Smaller row groups give the decoder more opportunities to skip data
+/// using column statistics, at the cost of a slightly larger footer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParquetWriterConfig {
+    /// Maximum rows per row group (zero is treated as one)
+    pub max_row_group_size: usize,
+    /// Whether to dictionary-encode columns
+    pub enable_dictionary: bool,
+    /// Whether to write column statistics (for predicate pushdown)
+    pub write_statistics: bool,
+    /// Target data page size in bytes; `None` keeps the parquet default
+    pub data_page_size: Option<usize>,
+}
+
+impl Default for ParquetWriterConfig {
+    fn default() -> Self {
+        Self {
+            max_row_group_size: DEFAULT_MAX_ROW_GROUP_SIZE,
+            enable_dictionary: true,
+            write_statistics: true,
+            data_page_size: None,
+        }
+    }
+}
+
 /// High-performance Parquet encoder.
 ///
 /// Converts Arrow RecordBatches to Parquet bytes with configurable compression.
@@ -64,14 +95,16 @@ impl FromStr for ParquetCompression {
 #[derive(Debug, Clone)]
 pub struct ParquetEncoder {
     compression: ParquetCompression,
-    write_statistics: bool,
+    config: ParquetWriterConfig,
+    preserve_field_metadata: bool,
 }
 
 impl Default for ParquetEncoder {
     fn default() -> Self {
         Self {
             compression: ParquetCompression::Zstd,
-            write_statistics: true,
+            config: ParquetWriterConfig::default(),
+            preserve_field_metadata: true,
         }
     }
 }
@@ -92,7 +125,36 @@ impl ParquetEncoder {
 
     /// Set whether to write column statistics (for predicate pushdown).
     pub fn with_statistics(mut self, enabled: bool) -> Self {
-        self.write_statistics = enabled;
+        self.config.write_statistics = enabled;
+        self
+    }
+
+    /// Replace all writer settings (row group size, dictionary, statistics, page size).
+    ///
+    /// # Example
+    /// ```ignore
+    /// let encoder = ParquetEncoder::new().with_properties(ParquetWriterConfig {
+    ///     max_row_group_size: 10_000,
+    ///     ..Default::default()
+    /// });
+    /// ```
+    pub fn with_properties(mut self, config: ParquetWriterConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// The writer settings this encoder uses.
+    pub fn properties(&self) -> &ParquetWriterConfig {
+        &self.config
+    }
+
+    /// Set whether Arrow field metadata (units, descriptions, ...) is kept.
+    ///
+    /// On by default: the Arrow schema, field metadata included, is stored
+    /// in the Parquet key-value metadata and `ParquetDecoder` restores it.
+    /// When off, field metadata is dropped before writing.
+    pub fn with_preserve_field_metadata(mut self, enabled: bool) -> Self {
+        self.preserve_field_metadata = enabled;
         self
     }
 
@@ -127,19 +189,11 @@ impl ParquetEncoder {
         }
 
         let mut buffer = Vec::new();
-        let props = WriterProperties::builder()
-            .set_compression(self.compression.to_parquet_compression())
-            .set_statistics_enabled(
-                if self.write_statistics {
-                    parquet::file::properties::EnabledStatistics::Chunk
-                } else {
-                    parquet::file::properties::EnabledStatistics::None
-                }
-            )
-            .build();
+        let props = self.writer_properties();
+        let batch = prepare_batch(batch, self.preserve_field_metadata)?;
 
         let mut writer = ArrowWriter::try_new(&mut buffer, batch.schema(), Some(props))?;
-        writer.write(batch)?;
+        writer.write(&batch)?;
         writer.close()?;
 
         Ok(buffer)
@@ -157,11 +211,119 @@ impl ParquetEncoder {
     /// * `Ok(Vec<Vec<u8>>)` - Parquet bytes for each batch, in same order
     /// * `Err(ParquetError)` - If any encoding fails (returns first error)
     pub fn encode_batch(&self, batches: &[RecordBatch]) -> Result<Vec<Vec<u8>>, ParquetError> {
-        batches
-            .par_iter()
-            .map(|batch| self.encode(batch))
-            .collect()
+        crate::parallel::install(|| batches.par_iter().map(|batch| self.encode(batch)).collect())
+    }
+
+    /// Start a streaming encode into `writer`.
+    ///
+    /// Each batch passed to `write_batch` becomes its own row group and is
+    /// written out immediately, so the full file is never held in memory.
+    /// The schema is taken from the first batch; `finish` writes the footer
+    /// and returns the writer.
+    ///
+    /// # Example
+    /// ```ignore
+    /// let mut stream = ParquetEncoder::new().stream_to(File::create("out.parquet")?);
+    /// for batch in batches {
+    ///     stream.write_batch(&batch)?;
+    /// }
+    /// stream.finish()?;
+    /// ```
+    pub fn stream_to<W: Write + Send>(&self, writer: W) -> ParquetStreamWriter<W> {
+        ParquetStreamWriter {
+            state: StreamState::Pending(writer, self.writer_properties()),
+            preserve_field_metadata: self.preserve_field_metadata,
+        }
     }
+
+    fn writer_properties(&self) -> WriterProperties {
+        let mut builder = WriterProperties::builder()
+            .set_compression(self.compression.to_parquet_compression())
+            .set_max_row_group_size(self.config.max_row_group_size.max(1))
+            .set_dictionary_enabled(self.config.enable_dictionary)
+            .set_statistics_enabled(
+                if self.config.write_statistics {
+                    EnabledStatistics::Chunk
+                } else {
+                    EnabledStatistics::None
+                }
+            );
+        if let Some(size) = self.config.data_page_size {
+            builder = builder.set_data_page_size_limit(size);
+        }
+        builder.build()
+    }
+}
+
+/// Incremental Parquet writer returned by `ParquetEncoder::stream_to`.
+pub struct ParquetStreamWriter<W: Write + Send> {
+    state: StreamState<W>,
+    preserve_field_metadata: bool,
+}
+
+enum StreamState<W: Write + Send> {
+    /// No batch yet; the first batch fixes the schema
+    Pending(W, WriterProperties),
+    Open(ArrowWriter<W>),
+    /// Opening the writer failed and consumed the sink
+    Failed,
+}
+
+impl<W: Write + Send> ParquetStreamWriter<W> {
+    /// Append a batch as a new row group.
+    ///
+    /// Empty batches are skipped. A batch whose schema differs from the
+    /// first batch is an error.
+    pub fn write_batch(&mut self, batch: &RecordBatch) -> Result<(), ParquetError> {
+        if batch.num_rows() == 0 {
+            return Ok(());
+        }
+        let batch = prepare_batch(batch, self.preserve_field_metadata)?;
+
+        let mut writer = match std::mem::replace(&mut self.state, StreamState::Failed) {
+            StreamState::Pending(sink, props) => ArrowWriter::try_new(sink, batch.schema(), Some(props))?,
+            StreamState::Open(writer) => writer,
+            StreamState::Failed => return Err(stream_failed()),
+        };
+
+        let result = writer.write(&batch).and_then(|_| writer.flush());
+        self.state = StreamState::Open(writer);
+        Ok(result?)
+    }
+
+    /// Write the footer and return the underlying writer.
+    ///
+    /// Returns `EmptyData` if no rows were written.
+    pub fn finish(self) -> Result<W, ParquetError> {
+        match self.state {
+            StreamState::Open(writer) => Ok(writer.into_inner()?),
+            StreamState::Pending(..) => Err(ParquetError::EmptyData),
+            StreamState::Failed => Err(stream_failed()),
+        }
+    }
+}
+
+/// The batch to hand to `ArrowWriter`, with field metadata stripped unless
+/// it should be preserved.
+fn prepare_batch(batch: &RecordBatch, preserve_field_metadata: bool) -> Result<RecordBatch, ParquetError> {
+    let schema = batch.schema();
+    if preserve_field_metadata || schema.fields().iter().all(|f| f.metadata().is_empty()) {
+        return Ok(batch.clone());
+    }
+
+    let fields: Vec<Field> = schema
+        .fields()
+        .iter()
+        .map(|f| f.as_ref().clone().with_metadata(HashMap::new()))
+        .collect();
+    let stripped = Schema::new_with_metadata(fields, schema.metadata().clone());
+    Ok(RecordBatch::try_new(Arc::new(stripped), batch.columns().to_vec())?)
+}
+
+fn stream_failed() -> ParquetError {
+    ParquetError::Parquet(parquet::errors::ParquetError::General(
+        "stream writer failed to open".to_string(),
+    ))
 }
 
 #[cfg(test)]
@@ -246,6 +408,28 @@ mod tests {
         assert!(sizes[0] > sizes[2], "Uncompressed should be larger than Zstd");
     }
 
+    #[test]
+    fn test_stream_to() {
+        let batches: Vec<RecordBatch> = [100, 250, 50].iter().map(|&n| create_test_batch(n)).collect();
+
+        let mut stream = ParquetEncoder::new().stream_to(Vec::new());
+        for batch in &batches {
+            stream.write_batch(batch).unwrap();
+        }
+        let bytes = stream.finish().unwrap();
+
+        let decoded = crate::parquet::ParquetDecoder::new().decode(&bytes).unwrap();
+        let expected = arrow::compute::concat_batches(&batches[0].schema(), &batches).unwrap();
+        assert_eq!(decoded, expected);
+
+        // One row group per batch
+        let reader = parquet::file::reader::SerializedFileReader::new(bytes::Bytes::from(bytes)).unwrap();
+        assert_eq!(parquet::file::reader::FileReader::metadata(&reader).num_row_groups(), 3);
+
+        let empty = ParquetEncoder::new().stream_to(Vec::new()).finish();
+        assert!(matches!(empty, Err(ParquetError::EmptyData)));
+    }
+
     #[test]
     fn test_encode_batch_parallel() {
         let batches: Vec<RecordBatch> = (0..10)
@@ -299,6 +483,66 @@ mod tests {
         let encoder = ParquetEncoder::with_compression(ParquetCompression::Snappy)
             .with_statistics(false);
 
-        assert!(!encoder.write_statistics);
+        assert!(!encoder.config.write_statistics);
+    }
+
+    #[test]
+    fn test_row_group_size_enables_pruning() {
+        use crate::parquet::{FilterOp, ParquetDecoder, PredicateFilter, ScalarValue};
+
+        let batch = create_test_batch(100_000);
+        let encoder = ParquetEncoder::new().with_properties(ParquetWriterConfig {
+            max_row_group_size: 10_000,
+            enable_dictionary: false,
+            write_statistics: true,
+            data_page_size: Some(64 * 1024),
+        });
+        let bytes = encoder.encode(&batch).unwrap();
+
+        let decoder = ParquetDecoder::new();
+        assert_eq!(decoder.decode(&bytes).unwrap(), batch);
+
+        let filter = PredicateFilter::new("id", FilterOp::Lt, ScalarValue::Int64(5_000));
+        let (total, pruned, kept) = decoder
+            .get_pruning_stats(&bytes, std::slice::from_ref(&filter))
+            .unwrap();
+        assert_eq!(total, 10);
+        assert_eq!(pruned, 9);
+        assert_eq!(kept, 1);
+
+        // The default config writes a single row group, so nothing can be pruned
+        let bytes = ParquetEncoder::new().encode(&batch).unwrap();
+        assert_eq!(decoder.get_pruning_stats(&bytes, &[filter]).unwrap(), (1, 0, 1));
+    }
+
+    #[test]
+    fn test_field_metadata_round_trip() {
+        use crate::parquet::ParquetDecoder;
+
+        let batch = create_test_batch(10);
+        let units = HashMap::from([("unit".to_string(), "m/s".to_string())]);
+        let description = HashMap::from([("description".to_string(), "Display name".to_string())]);
+        let schema = Schema::new(vec![
+            batch.schema().field(0).clone(),
+            batch.schema().field(1).clone().with_metadata(units.clone()),
+            batch.schema().field(2).clone().with_metadata(description.clone()),
+        ]);
+        let batch = batch.with_schema(Arc::new(schema)).unwrap();
+        let decoder = ParquetDecoder::new();
+
+        let decoded = decoder.decode(&ParquetEncoder::new().encode(&batch).unwrap()).unwrap();
+        assert!(decoded.schema().field(0).metadata().is_empty());
+        assert_eq!(decoded.schema().field(1).metadata(), &units);
+        assert_eq!(decoded.schema().field(2).metadata(), &description);
+
+        let mut streamed = ParquetEncoder::new().stream_to(Vec::new());
+        streamed.write_batch(&batch).unwrap();
+        let decoded = decoder.decode(&streamed.finish().unwrap()).unwrap();
+        assert_eq!(decoded.schema().field(1).metadata(), &units);
+
+        let stripped = ParquetEncoder::new().with_preserve_field_metadata(false);
+        let decoded = decoder.decode(&stripped.encode(&batch).unwrap()).unwrap();
+        assert!(decoded.schema().fields().iter().all(|f| f.metadata().is_empty()));
+        assert_eq!(decoded.num_rows(), 10);
     }
 }
@@ -41,4 +41,24 @@ pub enum ParquetError {
     /// Row count overflow during processing
     #[error("Row count overflow: total rows exceed usize::MAX")]
     RowCountOverflow,
+
+    /// Column cannot be cast to the requested type
+    #[error("Cannot cast column '{column}' from {from} to {to}")]
+    IncompatibleCast {
+        /// Column name
+        column: String,
+        /// Source type
+        from: String,
+        /// Requested type
+        to: String,
+    },
+
+    /// Requested row index is past the end of the file
+    #[error("Row index {index} out of range for {num_rows} rows")]
+    RowIndexOutOfRange {
+        /// Requested row index
+        index: usize,
+        /// Total rows in the file
+        num_rows: usize,
+    },
 }
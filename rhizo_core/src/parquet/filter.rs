@@ -26,6 +26,7 @@
 //!   Speedup can reach 50-100x
 //! ```
 
+use serde::{Deserialize, Serialize};
 use std::fmt;
 
 /// Comparison operations for filter predicates.
@@ -45,6 +46,20 @@ pub enum FilterOp {
     Gt,
     /// Greater than or equal: `column >= value`
     Ge,
+    /// List membership: `column IN (v1, v2, ...)`
+    In,
+    /// List exclusion: `column NOT IN (v1, v2, ...)`
+    NotIn,
+    /// Null check: `column IS NULL` (the value is ignored)
+    IsNull,
+    /// Non-null check: `column IS NOT NULL` (the value is ignored)
+    IsNotNull,
+    /// String prefix: `column LIKE 'value%'`
+    StartsWith,
+    /// String suffix: `column LIKE '%value'`
+    EndsWith,
+    /// Substring: `column LIKE '%value%'`
+    Contains,
 }
 
 impl fmt::Display for FilterOp {
@@ -56,6 +71,13 @@ impl fmt::Display for FilterOp {
             FilterOp::Le => write!(f, "<="),
             FilterOp::Gt => write!(f, ">"),
             FilterOp::Ge => write!(f, ">="),
+            FilterOp::In => write!(f, "IN"),
+            FilterOp::NotIn => write!(f, "NOT IN"),
+            FilterOp::IsNull => write!(f, "IS NULL"),
+            FilterOp::IsNotNull => write!(f, "IS NOT NULL"),
+            FilterOp::StartsWith => write!(f, "STARTS WITH"),
+            FilterOp::EndsWith => write!(f, "ENDS WITH"),
+            FilterOp::Contains => write!(f, "CONTAINS"),
         }
     }
 }
@@ -73,12 +95,21 @@ impl FilterOp {
     pub fn can_prune_with_min(&self) -> bool {
         matches!(self, FilterOp::Lt | FilterOp::Le | FilterOp::Eq)
     }
+
+    /// Whether this is one of the six ordering comparisons (`=`, `!=`, `<`,
+    /// `<=`, `>`, `>=`) rather than a membership, null or string match.
+    pub fn is_scalar_comparison(&self) -> bool {
+        matches!(
+            self,
+            FilterOp::Eq | FilterOp::Ne | FilterOp::Lt | FilterOp::Le | FilterOp::Gt | FilterOp::Ge
+        )
+    }
 }
 
 /// Scalar values for predicate comparison.
 ///
 /// Supports the common types used in analytical queries.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum ScalarValue {
     /// 64-bit signed integer
     Int64(i64),
@@ -92,6 +123,8 @@ pub enum ScalarValue {
     Int32(i32),
     /// Null value (for IS NULL / IS NOT NULL predicates)
     Null,
+    /// List of values (for IN / NOT IN predicates)
+    List(Vec<ScalarValue>),
 }
 
 impl fmt::Display for ScalarValue {
@@ -103,6 +136,16 @@ impl fmt::Display for ScalarValue {
             ScalarValue::Boolean(v) => write!(f, "{}", v),
             ScalarValue::Int32(v) => write!(f, "{}", v),
             ScalarValue::Null => write!(f, "NULL"),
+            ScalarValue::List(values) => {
+                write!(f, "(")?;
+                for (i, v) in values.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", v)?;
+                }
+                write!(f, ")")
+            }
         }
     }
 }
@@ -111,6 +154,9 @@ impl ScalarValue {
     /// Compare this scalar with another for ordering.
     /// Returns None if types don't match or comparison is not meaningful.
     pub fn compare(&self, other: &ScalarValue, op: FilterOp) -> Option<bool> {
+        if !op.is_scalar_comparison() {
+            return None;
+        }
         match (self, other) {
             (ScalarValue::Int64(a), ScalarValue::Int64(b)) => Some(compare_ord(*a, *b, op)),
             (ScalarValue::Int32(a), ScalarValue::Int32(b)) => Some(compare_ord(*a, *b, op)),
@@ -147,6 +193,7 @@ fn compare_ord<T: PartialOrd>(a: T, b: T, op: FilterOp) -> bool {
         FilterOp::Le => a <= b,
         FilterOp::Gt => a > b,
         FilterOp::Ge => a >= b,
+        _ => false, // Rejected by `compare`
     }
 }
 
@@ -247,13 +294,143 @@ impl PredicateFilter {
                     false
                 }
             }
+            // column IN (...): skip if every member lies outside [min, max]
+            FilterOp::In => match &self.value {
+                ScalarValue::List(values) => values.iter().all(|v| {
+                    PredicateFilter::new(self.column.clone(), FilterOp::Eq, v.clone())
+                        .can_prune_row_group(min, max)
+                }),
+                _ => false,
+            },
+            // column NOT IN (...): skip only if min = max and that value is listed
+            FilterOp::NotIn => match &self.value {
+                ScalarValue::List(values) => values.iter().any(|v| {
+                    PredicateFilter::new(self.column.clone(), FilterOp::Ne, v.clone())
+                        .can_prune_row_group(min, max)
+                }),
+                _ => false,
+            },
+            // column STARTS WITH prefix: skip if every value sorts before the
+            // prefix, or min sorts after it without sharing it
+            FilterOp::StartsWith => match (&self.value, min, max) {
+                (ScalarValue::Utf8(prefix), min, max) => {
+                    let below = matches!(max, Some(ScalarValue::Utf8(m)) if m < prefix);
+                    let above = matches!(
+                        min,
+                        Some(ScalarValue::Utf8(m)) if m > prefix && !m.starts_with(prefix.as_str())
+                    );
+                    below || above
+                }
+                _ => false,
+            },
+            // Null checks are pruned with null counts instead; suffix and
+            // substring matches are not ordered by min/max
+            FilterOp::IsNull | FilterOp::IsNotNull | FilterOp::EndsWith | FilterOp::Contains => false,
+        }
+    }
+
+    /// Check if a row group can be pruned based on its null count.
+    ///
+    /// Only `IsNull` (no nulls in the group) and `IsNotNull` (only nulls)
+    /// can be pruned this way; other operations return `false`.
+    ///
+    /// # Arguments
+    /// * `null_count` - Number of nulls in the row group (None if unknown)
+    /// * `num_rows` - Number of rows in the row group
+    pub fn can_prune_with_null_count(&self, null_count: Option<u64>, num_rows: u64) -> bool {
+        match (self.op, null_count) {
+            (FilterOp::IsNull, Some(nulls)) => nulls == 0,
+            (FilterOp::IsNotNull, Some(nulls)) => nulls == num_rows,
+            _ => false,
         }
     }
 }
 
 impl fmt::Display for PredicateFilter {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} {} {}", self.column, self.op, self.value)
+        match self.op {
+            FilterOp::IsNull | FilterOp::IsNotNull => write!(f, "{} {}", self.column, self.op),
+            _ => write!(f, "{} {} {}", self.column, self.op, self.value),
+        }
+    }
+}
+
+/// A boolean combination of predicate filters.
+///
+/// An empty `And` matches every row and an empty `Or` matches none.
+///
+/// # Example
+///
+/// ```ignore
+/// use rhizo_core::parquet::{FilterOp, PredicateExpr, PredicateFilter, ScalarValue};
+///
+/// // age < 18 OR age > 65
+/// let expr = PredicateExpr::Or(vec![
+///     PredicateExpr::Leaf(PredicateFilter::new("age", FilterOp::Lt, ScalarValue::Int64(18))),
+///     PredicateExpr::Leaf(PredicateFilter::new("age", FilterOp::Gt, ScalarValue::Int64(65))),
+/// ]);
+/// ```
+#[derive(Debug, Clone)]
+pub enum PredicateExpr {
+    /// A single comparison
+    Leaf(PredicateFilter),
+    /// Matches rows that match every child
+    And(Vec<PredicateExpr>),
+    /// Matches rows that match at least one child
+    Or(Vec<PredicateExpr>),
+}
+
+impl PredicateExpr {
+    /// AND together a list of filters (the `decode_with_filter` semantics).
+    pub fn all_of(filters: &[PredicateFilter]) -> Self {
+        PredicateExpr::And(filters.iter().cloned().map(PredicateExpr::Leaf).collect())
+    }
+
+    /// All leaf filters in the expression, depth-first.
+    pub fn leaves(&self) -> Vec<&PredicateFilter> {
+        match self {
+            PredicateExpr::Leaf(filter) => vec![filter],
+            PredicateExpr::And(children) | PredicateExpr::Or(children) => {
+                children.iter().flat_map(|c| c.leaves()).collect()
+            }
+        }
+    }
+
+    /// Check if a row group can be pruned for the whole expression.
+    ///
+    /// `prune_leaf` decides whether a single filter rules out the row group
+    /// (usually from its statistics). An `And` prunes if any child does; an
+    /// `Or` only if every child does.
+    pub fn can_prune_row_group<F>(&self, prune_leaf: &F) -> bool
+    where
+        F: Fn(&PredicateFilter) -> bool,
+    {
+        match self {
+            PredicateExpr::Leaf(filter) => prune_leaf(filter),
+            PredicateExpr::And(children) => children.iter().any(|c| c.can_prune_row_group(prune_leaf)),
+            PredicateExpr::Or(children) => children.iter().all(|c| c.can_prune_row_group(prune_leaf)),
+        }
+    }
+}
+
+impl fmt::Display for PredicateExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (children, sep, empty) = match self {
+            PredicateExpr::Leaf(filter) => return write!(f, "{}", filter),
+            PredicateExpr::And(children) => (children, " AND ", "TRUE"),
+            PredicateExpr::Or(children) => (children, " OR ", "FALSE"),
+        };
+        if children.is_empty() {
+            return write!(f, "{}", empty);
+        }
+        write!(f, "(")?;
+        for (i, child) in children.iter().enumerate() {
+            if i > 0 {
+                write!(f, "{}", sep)?;
+            }
+            write!(f, "{}", child)?;
+        }
+        write!(f, ")")
     }
 }
 
@@ -367,6 +544,108 @@ mod tests {
         assert!(!can_prune, "Should not prune when value in [min, max]");
     }
 
+    #[test]
+    fn test_row_group_pruning_in_list() {
+        let filter = PredicateFilter::new(
+            "category",
+            FilterOp::In,
+            ScalarValue::List(vec![ScalarValue::Int64(5), ScalarValue::Int64(40)]),
+        );
+        assert_eq!(format!("{}", filter), "category IN (5, 40)");
+
+        // Range [10, 20]: neither 5 nor 40 can be present
+        assert!(filter.can_prune_row_group(
+            Some(&ScalarValue::Int64(10)),
+            Some(&ScalarValue::Int64(20)),
+        ));
+
+        // Range [30, 50]: 40 may be present
+        assert!(!filter.can_prune_row_group(
+            Some(&ScalarValue::Int64(30)),
+            Some(&ScalarValue::Int64(50)),
+        ));
+
+        // NOT IN: only a constant group holding a listed value can be skipped
+        let filter = PredicateFilter::new(
+            "category",
+            FilterOp::NotIn,
+            ScalarValue::List(vec![ScalarValue::Int64(5), ScalarValue::Int64(40)]),
+        );
+        assert!(filter.can_prune_row_group(
+            Some(&ScalarValue::Int64(40)),
+            Some(&ScalarValue::Int64(40)),
+        ));
+        assert!(!filter.can_prune_row_group(
+            Some(&ScalarValue::Int64(5)),
+            Some(&ScalarValue::Int64(40)),
+        ));
+    }
+
+    #[test]
+    fn test_predicate_expr_pruning() {
+        let leaf = |op, v| PredicateExpr::Leaf(PredicateFilter::new("age", op, ScalarValue::Int64(v)));
+        let expr = PredicateExpr::Or(vec![leaf(FilterOp::Lt, 18), leaf(FilterOp::Gt, 65)]);
+        assert_eq!(format!("{}", expr), "(age < 18 OR age > 65)");
+
+        let stats = |lo: i64, hi: i64| {
+            move |f: &PredicateFilter| {
+                f.can_prune_row_group(Some(&ScalarValue::Int64(lo)), Some(&ScalarValue::Int64(hi)))
+            }
+        };
+
+        // [20, 60] matches neither branch; [10, 30] matches the first
+        assert!(expr.can_prune_row_group(&stats(20, 60)));
+        assert!(!expr.can_prune_row_group(&stats(10, 30)));
+
+        // AND prunes if any branch does
+        let expr = PredicateExpr::And(vec![leaf(FilterOp::Gt, 18), leaf(FilterOp::Lt, 25)]);
+        assert!(expr.can_prune_row_group(&stats(30, 40)));
+        assert!(!expr.can_prune_row_group(&stats(20, 40)));
+
+        // Empty OR matches nothing; empty AND matches everything
+        assert!(PredicateExpr::Or(vec![]).can_prune_row_group(&stats(0, 1)));
+        assert!(!PredicateExpr::And(vec![]).can_prune_row_group(&stats(0, 1)));
+    }
+
+    #[test]
+    fn test_row_group_pruning_null_count() {
+        let is_null = PredicateFilter::new("name", FilterOp::IsNull, ScalarValue::Null);
+        let is_not_null = PredicateFilter::new("name", FilterOp::IsNotNull, ScalarValue::Null);
+        assert_eq!(format!("{}", is_null), "name IS NULL");
+
+        assert!(is_null.can_prune_with_null_count(Some(0), 100));
+        assert!(!is_null.can_prune_with_null_count(Some(1), 100));
+        assert!(is_not_null.can_prune_with_null_count(Some(100), 100));
+        assert!(!is_not_null.can_prune_with_null_count(Some(99), 100));
+
+        // Unknown null counts and min/max statistics never prune null checks
+        assert!(!is_null.can_prune_with_null_count(None, 100));
+        assert!(!is_null.can_prune_row_group(
+            Some(&ScalarValue::Int64(1)),
+            Some(&ScalarValue::Int64(2)),
+        ));
+    }
+
+    #[test]
+    fn test_row_group_pruning_starts_with() {
+        let filter = PredicateFilter::new("name", FilterOp::StartsWith, ScalarValue::Utf8("prod".to_string()));
+        let utf8 = |s: &str| ScalarValue::Utf8(s.to_string());
+        assert_eq!(format!("{}", filter), "name STARTS WITH 'prod'");
+
+        // Every value sorts before the prefix
+        assert!(filter.can_prune_row_group(Some(&utf8("alpha")), Some(&utf8("beta"))));
+        // Every value sorts after any "prod..." string
+        assert!(filter.can_prune_row_group(Some(&utf8("qa")), Some(&utf8("zeta"))));
+        // The range straddles or starts within the prefix
+        assert!(!filter.can_prune_row_group(Some(&utf8("alpha")), Some(&utf8("zeta"))));
+        assert!(!filter.can_prune_row_group(Some(&utf8("production")), Some(&utf8("zeta"))));
+        assert!(!filter.can_prune_row_group(Some(&utf8("beta")), Some(&utf8("prod"))));
+
+        // Suffix and substring filters never prune
+        let filter = PredicateFilter::new("name", FilterOp::Contains, ScalarValue::Utf8("x".to_string()));
+        assert!(!filter.can_prune_row_group(Some(&utf8("alpha")), Some(&utf8("beta"))));
+    }
+
     #[test]
     fn test_row_group_pruning_no_stats() {
         let filter = PredicateFilter::new("value", FilterOp::Gt, ScalarValue::Int64(50));
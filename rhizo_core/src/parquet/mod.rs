@@ -37,12 +37,14 @@
 //! let batch = decoder.decode(&parquet_bytes)?;
 //! ```
 
+mod compare;
 mod decoder;
 mod encoder;
 mod error;
 mod filter;
 
-pub use decoder::ParquetDecoder;
-pub use encoder::{ParquetEncoder, ParquetCompression};
+pub use compare::{batches_equivalent, batches_equivalent_with_tolerance, DEFAULT_FLOAT_TOLERANCE};
+pub use decoder::{AggregateResult, AggregateSpec, FilterExplain, FilterPlan, ParquetDecoder, RepairReport};
+pub use encoder::{ParquetEncoder, ParquetCompression, ParquetStreamWriter, ParquetWriterConfig};
 pub use error::ParquetError;
-pub use filter::{FilterOp, ScalarValue, PredicateFilter};
+pub use filter::{FilterOp, ScalarValue, PredicateExpr, PredicateFilter};
@@ -0,0 +1,33 @@
+use thiserror::Error;
+
+use crate::branch::BranchError;
+use crate::catalog::CatalogError;
+use crate::chunk_store::ChunkStoreError;
+use crate::transaction::TransactionError;
+
+#[derive(Error, Debug)]
+pub enum RepositoryError {
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Repository already initialized: {0}")]
+    AlreadyInitialized(String),
+
+    #[error("Not a repository: {0}")]
+    NotInitialized(String),
+
+    #[error("Directory is not empty: {0}")]
+    NotEmpty(String),
+
+    #[error(transparent)]
+    ChunkStore(#[from] ChunkStoreError),
+
+    #[error(transparent)]
+    Catalog(#[from] CatalogError),
+
+    #[error(transparent)]
+    Branch(#[from] BranchError),
+
+    #[error(transparent)]
+    Transaction(#[from] TransactionError),
+}
@@ -0,0 +1,18 @@
+//! Repository layout and a single handle over its components.
+//!
+//! A repository is a directory holding the chunk store, catalog, branches
+//! and transaction log side by side:
+//!
+//! ```text
+//! <root>/
+//!   chunks/        ChunkStore
+//!   catalog/       FileCatalog
+//!   branches/      BranchManager
+//!   transactions/  TransactionManager
+//! ```
+
+pub mod error;
+pub mod repo;
+
+pub use error::RepositoryError;
+pub use repo::Repository;
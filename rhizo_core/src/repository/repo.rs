@@ -0,0 +1,199 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::error::RepositoryError;
+use crate::branch::BranchManager;
+use crate::catalog::FileCatalog;
+use crate::chunk_store::ChunkStore;
+use crate::transaction::TransactionManager;
+
+pub const CHUNKS_DIR: &str = "chunks";
+pub const CATALOG_DIR: &str = "catalog";
+pub const BRANCHES_DIR: &str = "branches";
+pub const TRANSACTIONS_DIR: &str = "transactions";
+
+/// Handle over a repository's chunk store, catalog, branches and
+/// transaction manager.
+///
+/// The transaction manager shares the repository's catalog and branch
+/// manager, so commits update both.
+pub struct Repository {
+    path: PathBuf,
+    chunks: Arc<ChunkStore>,
+    catalog: Arc<FileCatalog>,
+    branches: Arc<BranchManager>,
+    transactions: Arc<TransactionManager>,
+}
+
+impl Repository {
+    /// Create a new repository at `path`.
+    ///
+    /// The layout (including the default branch) is built in a staging
+    /// directory next to `path` and renamed into place, so an interrupted
+    /// init never leaves a partial repository behind. `path` must not exist
+    /// or be an empty directory.
+    ///
+    /// # Errors
+    /// - `RepositoryError::AlreadyInitialized` if `path` is already a repository
+    /// - `RepositoryError::NotEmpty` if `path` holds other files
+    pub fn init(path: impl AsRef<Path>) -> Result<Self, RepositoryError> {
+        let path = path.as_ref().to_path_buf();
+        if path.exists() {
+            if Self::is_repository(&path) {
+                return Err(RepositoryError::AlreadyInitialized(path.display().to_string()));
+            }
+            if fs::read_dir(&path)?.next().is_some() {
+                return Err(RepositoryError::NotEmpty(path.display().to_string()));
+            }
+        }
+
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "repository".to_string());
+        let parent = match path.parent() {
+            Some(p) if !p.as_os_str().is_empty() => p.to_path_buf(),
+            _ => PathBuf::from("."),
+        };
+        fs::create_dir_all(&parent)?;
+        let staging = parent.join(format!(".{}.init-{}", name, uuid::Uuid::new_v4()));
+
+        if let Err(e) = Self::build_layout(&staging).and_then(|()| {
+            if path.exists() {
+                fs::remove_dir(&path)?;
+            }
+            fs::rename(&staging, &path)?;
+            Ok(())
+        }) {
+            fs::remove_dir_all(&staging).ok();
+            return Err(e);
+        }
+
+        Self::open(path)
+    }
+
+    /// Open an existing repository.
+    ///
+    /// # Errors
+    /// Returns `RepositoryError::NotInitialized` if `path` lacks the layout
+    /// created by `init`.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, RepositoryError> {
+        let path = path.as_ref().to_path_buf();
+        if !Self::is_repository(&path) {
+            return Err(RepositoryError::NotInitialized(path.display().to_string()));
+        }
+
+        let chunks = Arc::new(ChunkStore::new(path.join(CHUNKS_DIR))?);
+        let catalog = Arc::new(FileCatalog::new(path.join(CATALOG_DIR))?);
+        let branches = Arc::new(BranchManager::new(path.join(BRANCHES_DIR))?);
+        let transactions = Arc::new(TransactionManager::new(
+            path.join(TRANSACTIONS_DIR),
+            catalog.clone(),
+            Some(branches.clone()),
+        )?);
+
+        Ok(Self {
+            path,
+            chunks,
+            catalog,
+            branches,
+            transactions,
+        })
+    }
+
+    /// Whether `path` has the repository layout.
+    pub fn is_repository(path: impl AsRef<Path>) -> bool {
+        let path = path.as_ref();
+        [CHUNKS_DIR, CATALOG_DIR, BRANCHES_DIR, TRANSACTIONS_DIR]
+            .iter()
+            .all(|dir| path.join(dir).is_dir())
+    }
+
+    /// Root directory of the repository.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn chunks(&self) -> &Arc<ChunkStore> {
+        &self.chunks
+    }
+
+    pub fn catalog(&self) -> &Arc<FileCatalog> {
+        &self.catalog
+    }
+
+    pub fn branches(&self) -> &Arc<BranchManager> {
+        &self.branches
+    }
+
+    pub fn transactions(&self) -> &Arc<TransactionManager> {
+        &self.transactions
+    }
+
+    fn build_layout(root: &Path) -> Result<(), RepositoryError> {
+        for dir in [CHUNKS_DIR, CATALOG_DIR, TRANSACTIONS_DIR] {
+            fs::create_dir_all(root.join(dir))?;
+        }
+        // Creates the default branch
+        BranchManager::new(root.join(BRANCHES_DIR))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction::TableWrite;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_init_then_write_and_read() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("repo");
+
+        let repo = Repository::init(&root).unwrap();
+        assert!(Repository::is_repository(&root));
+        assert_eq!(repo.branches().list().unwrap(), vec!["main".to_string()]);
+
+        // Write: store a chunk and commit it through a transaction
+        let hash = repo.chunks().put(b"hello repository").unwrap();
+        let tx = repo.transactions().begin(None).unwrap();
+        repo.transactions()
+            .add_write(tx, TableWrite::new("greetings", 1, vec![hash.clone()]))
+            .unwrap();
+        repo.transactions().commit(tx).unwrap();
+
+        // Read it back through a freshly opened handle
+        drop(repo);
+        let repo = Repository::open(&root).unwrap();
+        assert_eq!(repo.branches().get_table_version("main", "greetings").unwrap(), Some(1));
+        let version = repo.catalog().get_version("greetings", None).unwrap();
+        assert_eq!(version.chunk_hashes, vec![hash.clone()]);
+        assert_eq!(repo.chunks().get(&hash).unwrap(), b"hello repository");
+
+        // No staging directories are left next to the repository
+        let leftovers: Vec<_> = fs::read_dir(temp.path()).unwrap().collect();
+        assert_eq!(leftovers.len(), 1);
+    }
+
+    #[test]
+    fn test_init_rejects_existing_and_open_rejects_missing() {
+        let temp = TempDir::new().unwrap();
+
+        // An empty directory can be initialized in place
+        let root = temp.path().join("empty");
+        fs::create_dir(&root).unwrap();
+        Repository::init(&root).unwrap();
+        assert!(matches!(
+            Repository::init(&root),
+            Err(RepositoryError::AlreadyInitialized(_))
+        ));
+
+        let other = temp.path().join("other");
+        fs::create_dir(&other).unwrap();
+        fs::write(other.join("file.txt"), b"x").unwrap();
+        assert!(matches!(Repository::init(&other), Err(RepositoryError::NotEmpty(_))));
+        assert!(matches!(Repository::open(&other), Err(RepositoryError::NotInitialized(_))));
+    }
+}
@@ -0,0 +1,24 @@
+use thiserror::Error;
+
+use crate::catalog::CatalogError;
+use crate::chunk_store::ChunkStoreError;
+use crate::merkle::MerkleError;
+use crate::parquet::ParquetError;
+
+#[derive(Error, Debug)]
+pub enum TableError {
+    #[error(transparent)]
+    Catalog(#[from] CatalogError),
+
+    #[error(transparent)]
+    ChunkStore(#[from] ChunkStoreError),
+
+    #[error(transparent)]
+    Parquet(#[from] ParquetError),
+
+    #[error(transparent)]
+    Merkle(#[from] MerkleError),
+
+    #[error("Table has no rows: {0}")]
+    EmptyTable(String),
+}
@@ -0,0 +1,350 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use arrow::record_batch::RecordBatch;
+
+use super::error::TableError;
+use crate::catalog::{CatalogError, FileCatalog, TableVersion};
+use crate::chunk_store::ChunkStore;
+use crate::merkle::{build_tree_from_chunks, diff_trees, MerkleConfig, MerkleTree};
+use crate::parquet::{ParquetDecoder, ParquetEncoder};
+
+/// Target encoded bytes per chunk, matching the Python `TableWriter`
+pub const DEFAULT_CHUNK_SIZE_BYTES: usize = 64 * 1024 * 1024;
+
+/// Rows encoded to estimate the encoded size of a row
+const SAMPLE_ROWS: usize = 1000;
+/// Bounds on the estimated rows per chunk
+const MIN_ROWS_PER_CHUNK: usize = 1000;
+const MAX_ROWS_PER_CHUNK: usize = 10_000_000;
+
+/// What writing a batch as a table's next version would change.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreviewReport {
+    /// Distinct chunks the latest version does not reference
+    pub new_chunks: usize,
+    /// Distinct chunks the latest version already references
+    pub reused_chunks: usize,
+    /// Bytes of the new chunks
+    pub new_bytes: u64,
+    /// Whether the write reproduces the latest version's Merkle root
+    pub would_be_noop: bool,
+}
+
+/// Handle for reading and writing one table's versions.
+///
+/// Chunks are encoded with the default [`ParquetEncoder`] (Zstd), as the
+/// native path of the Python `TableWriter` does, so a `Table` and the
+/// writer produce the same chunks for the same rows and chunk size.
+pub struct Table {
+    name: String,
+    catalog: Arc<FileCatalog>,
+    store: Arc<ChunkStore>,
+    chunk_size: usize,
+    chunk_rows: Option<usize>,
+    encoder: ParquetEncoder,
+    decoder: ParquetDecoder,
+}
+
+impl Table {
+    /// Create a handle for `name`, chunking at [`DEFAULT_CHUNK_SIZE_BYTES`]
+    pub fn new(name: impl Into<String>, catalog: Arc<FileCatalog>, store: Arc<ChunkStore>) -> Self {
+        Self {
+            name: name.into(),
+            catalog,
+            store,
+            chunk_size: DEFAULT_CHUNK_SIZE_BYTES,
+            chunk_rows: None,
+            encoder: ParquetEncoder::new(),
+            decoder: ParquetDecoder::new(),
+        }
+    }
+
+    /// Set the target encoded bytes per chunk for writes
+    pub fn with_chunk_size(mut self, bytes: usize) -> Self {
+        self.chunk_size = bytes;
+        self
+    }
+
+    /// Use a fixed row count per chunk for writes instead of estimating it
+    /// from the chunk size, like `TableWriter(chunk_size_rows=...)`
+    pub fn with_chunk_rows(mut self, rows: usize) -> Self {
+        self.chunk_rows = Some(rows.max(1));
+        self
+    }
+
+    /// Table name
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Decode every chunk of a version (latest if `None`), in row order
+    pub fn read(&self, version: Option<u64>) -> Result<Vec<RecordBatch>, TableError> {
+        let table_version = self.catalog.get_version(&self.name, version)?;
+        table_version
+            .chunk_hashes
+            .iter()
+            .map(|hash| Ok(self.decoder.decode(&self.store.get(hash)?)?))
+            .collect()
+    }
+
+    /// Store `batch` as the next version of the table.
+    ///
+    /// Records the batch's schema with the version, as `TableWriter.write`
+    /// does. Returns the committed version number.
+    pub fn write(&self, batch: &RecordBatch) -> Result<u64, TableError> {
+        let chunk_hashes = self.store_chunks(batch, self.write_rows_per_chunk(batch)?)?;
+        let mut table_version = TableVersion::new(&self.name, self.next_version()?, chunk_hashes);
+        table_version.schema_hash = Some(self.catalog.store_schema(&batch.schema())?);
+        Ok(self.catalog.commit(table_version)?)
+    }
+
+    /// Preview writing `batch` as the next version, without storing anything.
+    ///
+    /// The batch is chunked and encoded as [`Table::write`] would, and the
+    /// Merkle tree over the resulting chunks is diffed against the tree over
+    /// the latest version's chunks. A table with no versions yet has nothing
+    /// to reuse.
+    pub fn preview_write(&self, batch: &RecordBatch) -> Result<PreviewReport, TableError> {
+        if batch.num_rows() == 0 {
+            return Err(TableError::EmptyTable(self.name.clone()));
+        }
+        let config = MerkleConfig::new(self.chunk_size);
+        let encoded = self.encode_chunks(batch, self.write_rows_per_chunk(batch)?)?;
+        let leaves: Vec<(String, u64)> = encoded
+            .iter()
+            .map(|chunk| (blake3::hash(chunk).to_hex().to_string(), chunk.len() as u64))
+            .collect();
+        let new_tree = build_tree_from_chunks(&leaves, &config)?;
+
+        let old_tree = match self.latest_tree(&config)? {
+            Some(tree) => tree,
+            None => {
+                let distinct: HashSet<&String> = leaves.iter().map(|(hash, _)| hash).collect();
+                return Ok(PreviewReport {
+                    new_chunks: distinct.len(),
+                    reused_chunks: 0,
+                    new_bytes: distinct_bytes(&new_tree, &distinct),
+                    would_be_noop: false,
+                });
+            }
+        };
+
+        let diff = diff_trees(&old_tree, &new_tree);
+        let added: HashSet<&String> = diff.added_chunks.iter().collect();
+        Ok(PreviewReport {
+            new_chunks: added.len(),
+            reused_chunks: diff.unchanged_chunks.len(),
+            new_bytes: distinct_bytes(&new_tree, &added),
+            would_be_noop: old_tree.root_hash == new_tree.root_hash,
+        })
+    }
+
+    /// Re-chunk the latest version under `config` and commit the result.
+    ///
+    /// The rows are decoded, split again into chunks of about
+    /// `config.chunk_size` encoded bytes and re-encoded, so the new version
+    /// holds the same rows in a new chunk layout. It keeps the source's
+    /// schema hash and records the source version and chunk size in its
+    /// metadata. Returns the new version number.
+    pub fn rechunk(&self, config: &MerkleConfig) -> Result<u64, TableError> {
+        config.validate()?;
+        let source = self.catalog.get_version(&self.name, None)?;
+        let batches = self.read(Some(source.version))?;
+        let schema = match batches.first() {
+            Some(batch) => batch.schema(),
+            None => return Err(TableError::EmptyTable(self.name.clone())),
+        };
+        let rows = arrow::compute::concat_batches(&schema, &batches)
+            .map_err(crate::parquet::ParquetError::Arrow)?;
+
+        let rows_per_chunk = self.estimate_rows_per_chunk(&rows, config.chunk_size)?;
+        let chunk_hashes = self.store_chunks(&rows, rows_per_chunk)?;
+        let mut rechunked = TableVersion::new(&self.name, source.version + 1, chunk_hashes)
+            .with_metadata("rechunked_from", source.version.to_string())
+            .with_metadata("chunk_size", config.chunk_size.to_string());
+        rechunked.schema_hash = source.schema_hash;
+        Ok(self.catalog.commit(rechunked)?)
+    }
+
+    /// Split `batch` into chunks of `rows_per_chunk` rows and encode each,
+    /// as `TableWriter` does
+    fn encode_chunks(&self, batch: &RecordBatch, rows_per_chunk: usize) -> Result<Vec<Vec<u8>>, TableError> {
+        let total_rows = batch.num_rows();
+        if total_rows == 0 {
+            return Ok(Vec::new());
+        }
+
+        let slices: Vec<RecordBatch> = (0..total_rows)
+            .step_by(rows_per_chunk)
+            .map(|offset| batch.slice(offset, rows_per_chunk.min(total_rows - offset)))
+            .collect();
+        Ok(self.encoder.encode_batch(&slices)?)
+    }
+
+    /// Rows per chunk for writes: the fixed row count if set, otherwise
+    /// estimated from the chunk size
+    fn write_rows_per_chunk(&self, batch: &RecordBatch) -> Result<usize, TableError> {
+        match self.chunk_rows {
+            Some(rows) => Ok(rows),
+            None => self.estimate_rows_per_chunk(batch, self.chunk_size),
+        }
+    }
+
+    /// Rows per chunk from the encoded size of a sample of `batch`
+    fn estimate_rows_per_chunk(&self, batch: &RecordBatch, chunk_size: usize) -> Result<usize, TableError> {
+        let sample_rows = SAMPLE_ROWS.min(batch.num_rows());
+        let sample_bytes = self.encoder.encode(&batch.slice(0, sample_rows))?.len();
+        let bytes_per_row = sample_bytes as f64 / sample_rows as f64;
+        let estimated = (chunk_size as f64 / bytes_per_row) as usize;
+        Ok(estimated.clamp(MIN_ROWS_PER_CHUNK, MAX_ROWS_PER_CHUNK))
+    }
+
+    fn store_chunks(&self, batch: &RecordBatch, rows_per_chunk: usize) -> Result<Vec<String>, TableError> {
+        self.encode_chunks(batch, rows_per_chunk)?
+            .iter()
+            .map(|chunk| Ok(self.store.put(chunk)?))
+            .collect()
+    }
+
+    /// Merkle tree over the latest version's stored chunks, if it has any
+    fn latest_tree(&self, config: &MerkleConfig) -> Result<Option<MerkleTree>, TableError> {
+        let latest = match self.catalog.get_version(&self.name, None) {
+            Ok(latest) => latest,
+            Err(CatalogError::TableNotFound(_)) => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        if latest.chunk_hashes.is_empty() {
+            return Ok(None);
+        }
+        let leaves = latest
+            .chunk_hashes
+            .into_iter()
+            .map(|hash| {
+                let size = self.store.size(&hash)?;
+                Ok((hash, size))
+            })
+            .collect::<Result<Vec<_>, TableError>>()?;
+        Ok(Some(build_tree_from_chunks(&leaves, config)?))
+    }
+
+    fn next_version(&self) -> Result<u64, TableError> {
+        match self.catalog.get_version(&self.name, None) {
+            Ok(latest) => Ok(latest.version + 1),
+            Err(CatalogError::TableNotFound(_)) => Ok(1),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
+
+/// Bytes of the distinct chunks of `tree` whose hashes are in `hashes`
+fn distinct_bytes(tree: &MerkleTree, hashes: &HashSet<&String>) -> u64 {
+    let mut seen = HashSet::new();
+    tree.chunks
+        .iter()
+        .filter(|chunk| hashes.contains(&chunk.hash) && seen.insert(&chunk.hash))
+        .map(|chunk| chunk.size)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Int64Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use tempfile::TempDir;
+
+    fn create_test_table() -> (Table, TempDir) {
+        let temp_dir = TempDir::new().unwrap();
+        let catalog = Arc::new(FileCatalog::new(temp_dir.path().join("catalog")).unwrap());
+        let store = Arc::new(ChunkStore::new(temp_dir.path().join("chunks")).unwrap());
+        (Table::new("events", catalog, store), temp_dir)
+    }
+
+    fn make_batch(rows: i64) -> RecordBatch {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int64, false),
+            Field::new("name", DataType::Utf8, false),
+        ]));
+        let ids = Int64Array::from((0..rows).collect::<Vec<_>>());
+        let names = StringArray::from((0..rows).map(|i| format!("event-{}", i)).collect::<Vec<_>>());
+        RecordBatch::try_new(schema, vec![Arc::new(ids), Arc::new(names)]).unwrap()
+    }
+
+    #[test]
+    fn test_write_and_read() {
+        let (table, _temp) = create_test_table();
+        let batch = make_batch(2500);
+
+        assert_eq!(table.write(&batch).unwrap(), 1);
+        let read = table.read(None).unwrap();
+        assert_eq!(read.len(), 1);
+        assert_eq!(read[0], batch);
+    }
+
+    #[test]
+    fn test_preview_write() {
+        let (table, _temp) = create_test_table();
+        let table = table.with_chunk_rows(1000);
+        let batch = make_batch(3500);
+
+        // Nothing to reuse before the first write
+        let report = table.preview_write(&batch).unwrap();
+        assert_eq!(report.new_chunks, 4);
+        assert_eq!(report.reused_chunks, 0);
+        assert!(report.new_bytes > 0);
+        assert!(!report.would_be_noop);
+
+        table.write(&batch).unwrap();
+        let report = table.preview_write(&batch).unwrap();
+        assert_eq!(
+            report,
+            PreviewReport { new_chunks: 0, reused_chunks: 4, new_bytes: 0, would_be_noop: true }
+        );
+
+        // Appending rows changes only the last chunk
+        let report = table.preview_write(&make_batch(3600)).unwrap();
+        assert_eq!(report.new_chunks, 1);
+        assert_eq!(report.reused_chunks, 3);
+        assert!(!report.would_be_noop);
+
+        // Nothing was written
+        assert_eq!(table.catalog.list_versions("events").unwrap(), vec![1]);
+        assert!(matches!(table.preview_write(&make_batch(0)), Err(TableError::EmptyTable(_))));
+    }
+
+    #[test]
+    fn test_rechunk_keeps_rows_in_new_layout() {
+        let (table, _temp) = create_test_table();
+        let batch = make_batch(4500);
+        table.write(&batch).unwrap();
+        let original = table.catalog.get_version("events", Some(1)).unwrap();
+        assert_eq!(original.chunk_hashes.len(), 1);
+
+        // A tiny target bottoms out at the minimum rows per chunk
+        let version = table.rechunk(&MerkleConfig::new(1024)).unwrap();
+        assert_eq!(version, 2);
+
+        let rechunked = table.catalog.get_version("events", None).unwrap();
+        assert_eq!(rechunked.chunk_hashes.len(), 5);
+        assert_eq!(rechunked.schema_hash, original.schema_hash);
+        assert_eq!(rechunked.metadata.get("rechunked_from").map(String::as_str), Some("1"));
+
+        let batches = table.read(None).unwrap();
+        let sizes: Vec<usize> = batches.iter().map(|b| b.num_rows()).collect();
+        assert_eq!(sizes, vec![1000, 1000, 1000, 1000, 500]);
+        let rows = arrow::compute::concat_batches(&batch.schema(), &batches).unwrap();
+        assert_eq!(rows, batch);
+    }
+
+    #[test]
+    fn test_rechunk_rejects_invalid_config() {
+        let (table, _temp) = create_test_table();
+        table.write(&make_batch(10)).unwrap();
+
+        assert!(matches!(
+            table.rechunk(&MerkleConfig::new(0)),
+            Err(TableError::Merkle(crate::merkle::MerkleError::InvalidChunkSize(0)))
+        ));
+    }
+}
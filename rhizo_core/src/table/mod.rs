@@ -0,0 +1,12 @@
+//! Table-level operations over the catalog and chunk store.
+//!
+//! A [`Table`] lays out rows the way the Python `TableWriter` does: rows are
+//! split into chunks of roughly a target number of encoded bytes, each chunk
+//! is stored as one Zstd-compressed Parquet file, and a version lists its
+//! chunk hashes in row order.
+
+pub mod error;
+pub mod handle;
+
+pub use error::TableError;
+pub use handle::{PreviewReport, Table, DEFAULT_CHUNK_SIZE_BYTES};
@@ -2,13 +2,18 @@
 //!
 //! This module provides pluggable conflict detection strategies:
 //! - `TableLevelConflictDetector` - Two transactions conflict if they write the same table
+//! - `RowRangeConflictDetector` - Like table-level, but key-range writes conflict only
+//!   when their ranges overlap (the `TransactionManager` default)
 //! - `PartitionLevelConflictDetector` - (Future) Conflict on same partition
 //! - `RowLevelConflictDetector` - (Future) Conflict on same row keys
 //!
 //! The conflict detection strategy determines the concurrency/isolation trade-off.
 
-use std::collections::HashSet;
-use super::types::{TransactionRecord, WriteGranularity};
+use std::collections::{BTreeSet, HashSet};
+use std::str::FromStr;
+use super::error::TransactionError;
+use super::types::{TableWrite, TransactionRecord, WriteGranularity};
+use crate::parquet::FilterOp;
 
 /// Represents a detected conflict between two transactions
 #[derive(Debug, Clone)]
@@ -47,6 +52,34 @@ impl Conflict {
     }
 }
 
+/// Which committed transactions a commit is checked against
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ConflictWindow {
+    /// Every recently committed transaction (default)
+    #[default]
+    All,
+    /// Only transactions that committed after the committing transaction
+    /// began. Earlier commits, including everything from older epochs,
+    /// were already visible in its snapshot, so long-lived transactions in
+    /// append-mostly workloads see fewer false conflicts.
+    Epoch,
+}
+
+impl FromStr for ConflictWindow {
+    type Err = TransactionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "all" => Ok(ConflictWindow::All),
+            "epoch" => Ok(ConflictWindow::Epoch),
+            _ => Err(TransactionError::InvalidConfig(format!(
+                "unknown conflict window '{}', expected 'all' or 'epoch'",
+                s
+            ))),
+        }
+    }
+}
+
 /// Trait for conflict detection strategies
 pub trait ConflictDetector: Send + Sync {
     /// Detect conflicts between two transactions
@@ -99,6 +132,61 @@ impl ConflictDetector for TableLevelConflictDetector {
     }
 }
 
+/// Row-range conflict detection
+///
+/// Two writes to the same table conflict unless both have `KeyRange`
+/// granularity and their inclusive key ranges are disjoint. Writes that
+/// declare no range behave exactly as under `TableLevelConflictDetector`.
+/// Ranges whose keys cannot be compared (different types, nulls) are
+/// treated as overlapping. `TransactionManager` rebases a range write onto
+/// any version of its table committed in the meantime, so concurrent
+/// disjoint writes keep each other's chunks.
+#[derive(Debug, Default)]
+pub struct RowRangeConflictDetector;
+
+impl RowRangeConflictDetector {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl ConflictDetector for RowRangeConflictDetector {
+    fn detect(&self, tx1: &TransactionRecord, tx2: &TransactionRecord) -> Option<Conflict> {
+        let mut conflicts = BTreeSet::new();
+        for write1 in &tx1.writes {
+            for write2 in &tx2.writes {
+                if write1.table_name == write2.table_name && !key_ranges_disjoint(write1, write2) {
+                    conflicts.insert(write1.table_name.clone());
+                }
+            }
+        }
+
+        if conflicts.is_empty() {
+            None
+        } else {
+            Some(Conflict::new(conflicts.into_iter().collect(), tx1.tx_id, tx2.tx_id))
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "RowRangeConflictDetector"
+    }
+}
+
+fn key_ranges_disjoint(write1: &TableWrite, write2: &TableWrite) -> bool {
+    if !matches!(write1.granularity, WriteGranularity::KeyRange)
+        || !matches!(write2.granularity, WriteGranularity::KeyRange)
+    {
+        return false;
+    }
+    match (&write1.key_range, &write2.key_range) {
+        (Some((min1, max1)), Some((min2, max2))) => {
+            max1.compare(min2, FilterOp::Lt) == Some(true) || max2.compare(min1, FilterOp::Lt) == Some(true)
+        }
+        _ => false,
+    }
+}
+
 /// Partition-level conflict detection (Phase 5.5 - future)
 ///
 /// Two transactions conflict only if they write to the same partition
@@ -370,6 +458,39 @@ mod tests {
         assert!(conflict.is_none());
     }
 
+    #[test]
+    fn test_row_range_detector() {
+        use crate::parquet::ScalarValue;
+
+        let detector = RowRangeConflictDetector::new();
+        let range_tx = |tx_id, table: &str, min, max| {
+            let mut tx = TransactionRecord::new(tx_id, 1, "main".to_string());
+            tx.add_write(TableWrite::new(table, 1, vec!["chunk".to_string()])
+                .with_key_range(ScalarValue::Int64(min), ScalarValue::Int64(max)));
+            tx
+        };
+
+        let tx1 = range_tx(1, "users", 0, 99);
+        assert!(detector.detect(&tx1, &range_tx(2, "users", 100, 199)).is_none());
+        assert!(detector.detect(&range_tx(2, "users", 100, 199), &tx1).is_none());
+
+        // Inclusive bounds: sharing an endpoint overlaps
+        let conflict = detector.detect(&tx1, &range_tx(2, "users", 99, 150)).unwrap();
+        assert_eq!(conflict.tables, vec!["users"]);
+        assert!(detector.detect(&tx1, &range_tx(2, "users", 10, 20)).is_some());
+
+        // Whole-table writes still conflict with ranged ones
+        let whole = create_tx_with_writes(3, &["users"]);
+        assert!(detector.detect(&tx1, &whole).is_some());
+        assert!(detector.detect(&whole, &create_tx_with_writes(4, &["orders"])).is_none());
+
+        // Incomparable keys are treated as overlapping
+        let mut text = TransactionRecord::new(5, 1, "main".to_string());
+        text.add_write(TableWrite::new("users", 1, vec![])
+            .with_key_range(ScalarValue::Utf8("a".to_string()), ScalarValue::Utf8("b".to_string())));
+        assert!(detector.detect(&tx1, &text).is_some());
+    }
+
     #[test]
     fn test_conflict_description() {
         let conflict = Conflict::new(
@@ -387,6 +508,7 @@ mod tests {
     #[test]
     fn test_detector_names() {
         assert_eq!(TableLevelConflictDetector::new().name(), "TableLevelConflictDetector");
+        assert_eq!(RowRangeConflictDetector::new().name(), "RowRangeConflictDetector");
         assert_eq!(PartitionLevelConflictDetector::new().name(), "PartitionLevelConflictDetector");
         assert_eq!(RowLevelConflictDetector::new().name(), "RowLevelConflictDetector");
         assert_eq!(AdaptiveConflictDetector::new().name(), "AdaptiveConflictDetector");
@@ -270,12 +270,12 @@ impl CoordinationFreeManager {
 
             if let Some((existing_op_type, existing_value)) = state.get(&key) {
                 // Merge with existing value
-                if *existing_op_type == op.op_type() {
+                if *existing_op_type == op.op_type().merge_family() {
                     let merge_result =
                         AlgebraicMerger::merge(op.op_type(), existing_value, op.value());
                     match merge_result {
                         MergeResult::Merged(merged_value) => {
-                            state.insert(key, (op.op_type(), merged_value));
+                            state.insert(key, (op.op_type().merge_family(), merged_value));
                         }
                         MergeResult::Conflict { reason, .. } => {
                             return Err(CoordinationFreeError::MergeError(format!(
@@ -304,7 +304,7 @@ impl CoordinationFreeManager {
                 }
             } else {
                 // First value for this key
-                state.insert(key, (op.op_type(), op.value().clone()));
+                state.insert(key, (op.op_type().merge_family(), op.value().clone()));
             }
         }
 
@@ -77,10 +77,22 @@ pub enum TransactionError {
     #[error("Transaction {0} timed out after {1}ms")]
     Timeout(TxId, u64),
 
+    /// Attempted to write (or record a read) in a read-only transaction
+    #[error("Transaction {0} is read-only")]
+    ReadOnly(TxId),
+
+    /// Savepoint unknown to the transaction, or discarded by a rollback
+    #[error("Savepoint {savepoint} not found in transaction {tx_id}")]
+    SavepointNotFound { tx_id: TxId, savepoint: u64 },
+
     /// Nested transactions not supported
     #[error("Nested transactions not supported")]
     NestedTransaction,
 
+    /// Changelog cursor token could not be decoded
+    #[error("Invalid changelog cursor: {0}")]
+    InvalidCursor(String),
+
     /// Invalid transaction configuration
     #[error("Invalid configuration: {0}")]
     InvalidConfig(String),
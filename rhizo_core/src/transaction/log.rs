@@ -6,6 +6,7 @@
 //! +-- _config.json                # Epoch configuration
 //! +-- _sequence                   # Current tx_id counter (atomic)
 //! +-- _epoch_sequence             # Current epoch_id counter (atomic)
+//! +-- _commit_sequence            # Last assigned commit sequence number (atomic)
 //! |
 //! +-- epochs/                     # Epoch-organized transaction logs
 //!     +-- 000001/                 # Epoch directory
@@ -19,6 +20,7 @@
 
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
 
 use super::types::*;
 use super::epoch::*;
@@ -28,6 +30,7 @@ const EPOCHS_DIR: &str = "epochs";
 const CONFIG_FILE: &str = "_config.json";
 const SEQUENCE_FILE: &str = "_sequence";
 const EPOCH_SEQUENCE_FILE: &str = "_epoch_sequence";
+const COMMIT_SEQUENCE_FILE: &str = "_commit_sequence";
 const EPOCH_META_FILE: &str = "_meta.json";
 const EPOCH_COMMITTED_MARKER: &str = "_committed";
 
@@ -64,6 +67,8 @@ impl StorageConfig {
 /// Persistent transaction log
 pub struct TransactionLog {
     base_path: PathBuf,
+    /// Serializes read-modify-write updates of the sequence and epoch files
+    lock: Mutex<()>,
 }
 
 impl TransactionLog {
@@ -73,7 +78,16 @@ impl TransactionLog {
         let epochs_dir = base_path.join(EPOCHS_DIR);
         fs::create_dir_all(&epochs_dir)?;
 
-        Ok(Self { base_path })
+        Ok(Self {
+            base_path,
+            lock: Mutex::new(()),
+        })
+    }
+
+    fn guard(&self) -> Result<MutexGuard<'_, ()>, TransactionError> {
+        self.lock
+            .lock()
+            .map_err(|_| TransactionError::LockError("transaction log".to_string()))
     }
 
     /// Get the base path
@@ -85,7 +99,30 @@ impl TransactionLog {
 
     /// Get next transaction ID (atomic increment)
     pub fn next_tx_id(&self) -> Result<TxId, TransactionError> {
-        let path = self.base_path.join(SEQUENCE_FILE);
+        self.increment_counter(SEQUENCE_FILE)
+    }
+
+    /// Get current transaction ID without incrementing
+    pub fn current_tx_id(&self) -> Result<TxId, TransactionError> {
+        self.read_counter(SEQUENCE_FILE)
+    }
+
+    /// Get next commit sequence number (atomic increment)
+    ///
+    /// Transaction IDs are assigned at begin, so they don't follow commit
+    /// order; this counter does.
+    pub fn next_commit_seq(&self) -> Result<u64, TransactionError> {
+        self.increment_counter(COMMIT_SEQUENCE_FILE)
+    }
+
+    /// Get the last assigned commit sequence number (0 if none)
+    pub fn current_commit_seq(&self) -> Result<u64, TransactionError> {
+        self.read_counter(COMMIT_SEQUENCE_FILE)
+    }
+
+    fn increment_counter(&self, file: &str) -> Result<u64, TransactionError> {
+        let _guard = self.guard()?;
+        let path = self.base_path.join(file);
 
         let current = if path.exists() {
             fs::read_to_string(&path)?
@@ -106,9 +143,8 @@ impl TransactionLog {
         Ok(next)
     }
 
-    /// Get current transaction ID without incrementing
-    pub fn current_tx_id(&self) -> Result<TxId, TransactionError> {
-        let path = self.base_path.join(SEQUENCE_FILE);
+    fn read_counter(&self, file: &str) -> Result<u64, TransactionError> {
+        let path = self.base_path.join(file);
 
         if path.exists() {
             Ok(fs::read_to_string(&path)?
@@ -122,6 +158,11 @@ impl TransactionLog {
 
     /// Get current epoch ID (or create first epoch)
     pub fn current_epoch_id(&self) -> Result<EpochId, TransactionError> {
+        let _guard = self.guard()?;
+        self.current_epoch_id_locked()
+    }
+
+    fn current_epoch_id_locked(&self) -> Result<EpochId, TransactionError> {
         let path = self.base_path.join(EPOCH_SEQUENCE_FILE);
 
         if path.exists() {
@@ -131,14 +172,15 @@ impl TransactionLog {
                 .unwrap_or(1))
         } else {
             // Create first epoch
-            self.create_epoch(1)?;
+            self.create_epoch_locked(1)?;
             Ok(1)
         }
     }
 
     /// Get next epoch ID (atomic increment)
     pub fn next_epoch_id(&self) -> Result<EpochId, TransactionError> {
-        let current = self.current_epoch_id()?;
+        let _guard = self.guard()?;
+        let current = self.current_epoch_id_locked()?;
         let next = current + 1;
 
         let path = self.base_path.join(EPOCH_SEQUENCE_FILE);
@@ -153,6 +195,19 @@ impl TransactionLog {
 
     /// Create a new epoch
     pub fn create_epoch(&self, epoch_id: EpochId) -> Result<EpochMetadata, TransactionError> {
+        let _guard = self.guard()?;
+        self.create_epoch_locked(epoch_id)
+    }
+
+    /// Create the epoch after the current one and make it current
+    pub fn advance_epoch(&self) -> Result<EpochId, TransactionError> {
+        let _guard = self.guard()?;
+        let epoch_id = self.current_epoch_id_locked()? + 1;
+        self.create_epoch_locked(epoch_id)?;
+        Ok(epoch_id)
+    }
+
+    fn create_epoch_locked(&self, epoch_id: EpochId) -> Result<EpochMetadata, TransactionError> {
         let epoch_dir = self.epoch_dir(epoch_id);
         fs::create_dir_all(&epoch_dir)?;
 
@@ -181,6 +236,19 @@ impl TransactionLog {
         Ok(meta)
     }
 
+    /// Read, modify and write back epoch metadata in one step
+    pub fn update_epoch(
+        &self,
+        epoch_id: EpochId,
+        update: impl FnOnce(&mut EpochMetadata),
+    ) -> Result<EpochMetadata, TransactionError> {
+        let _guard = self.guard()?;
+        let mut meta = self.get_epoch(epoch_id)?;
+        update(&mut meta);
+        self.write_epoch_metadata(&meta)?;
+        Ok(meta)
+    }
+
     /// Write epoch metadata
     pub fn write_epoch_metadata(&self, meta: &EpochMetadata) -> Result<(), TransactionError> {
         let epoch_dir = self.epoch_dir(meta.epoch_id);
@@ -353,8 +421,10 @@ impl TransactionLog {
 
     /// List all committed transactions in order.
     ///
-    /// Scans all epochs and returns committed transactions sorted by tx_id.
-    /// This is used by the changelog module to provide streaming access.
+    /// Scans all epochs and returns committed transactions sorted by
+    /// commit sequence, then tx_id. Transaction IDs are assigned at begin,
+    /// so they don't follow commit order on their own. This is used by the
+    /// changelog module to provide streaming access.
     pub fn list_committed_transactions(&self) -> Result<Vec<TransactionRecord>, TransactionError> {
         let epochs = self.list_epochs()?;
         let mut committed = Vec::new();
@@ -374,8 +444,8 @@ impl TransactionLog {
             }
         }
 
-        // Sort by tx_id for consistent ordering
-        committed.sort_by_key(|tx| tx.tx_id);
+        // Commits logged before commit sequence numbers existed sort first
+        committed.sort_by_key(|tx| (tx.commit_seq.unwrap_or(0), tx.tx_id));
 
         Ok(committed)
     }
@@ -383,7 +453,7 @@ impl TransactionLog {
     /// Get the latest committed transaction ID, if any.
     pub fn latest_committed_tx_id(&self) -> Result<Option<TxId>, TransactionError> {
         let committed = self.list_committed_transactions()?;
-        Ok(committed.last().map(|tx| tx.tx_id))
+        Ok(committed.iter().map(|tx| tx.tx_id).max())
     }
 
     /// List committed transactions since a specific tx_id (exclusive).
@@ -440,6 +510,48 @@ mod tests {
         assert_eq!(log.current_tx_id().unwrap(), 3);
     }
 
+    #[test]
+    fn test_commit_seq_is_independent_of_tx_ids() {
+        let (log, _temp) = create_test_log();
+        log.next_tx_id().unwrap();
+        log.next_tx_id().unwrap();
+
+        assert_eq!(log.current_commit_seq().unwrap(), 0);
+        assert_eq!(log.next_commit_seq().unwrap(), 1);
+        assert_eq!(log.next_commit_seq().unwrap(), 2);
+        assert_eq!(log.current_commit_seq().unwrap(), 2);
+        assert_eq!(log.current_tx_id().unwrap(), 2);
+    }
+
+    #[test]
+    fn test_concurrent_sequence_updates() {
+        let (log, _temp) = create_test_log();
+        let epoch = log.current_epoch_id().unwrap();
+
+        let ids: Vec<TxId> = std::thread::scope(|s| {
+            let handles: Vec<_> = (0..8)
+                .map(|_| {
+                    s.spawn(|| {
+                        (0..25)
+                            .map(|_| {
+                                let id = log.next_tx_id().unwrap();
+                                log.update_epoch(epoch, |meta| meta.add_transaction(id)).unwrap();
+                                id
+                            })
+                            .collect::<Vec<_>>()
+                    })
+                })
+                .collect();
+            handles.into_iter().flat_map(|h| h.join().unwrap()).collect()
+        });
+
+        // No ID is handed out twice and no epoch update is lost
+        let unique: std::collections::HashSet<TxId> = ids.iter().copied().collect();
+        assert_eq!(unique.len(), 200);
+        assert_eq!(log.current_tx_id().unwrap(), 200);
+        assert_eq!(log.get_epoch(epoch).unwrap().transaction_count(), 200);
+    }
+
     #[test]
     fn test_epoch_creation() {
         let (log, _temp) = create_test_log();
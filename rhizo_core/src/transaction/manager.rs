@@ -3,19 +3,25 @@
 //! The TransactionManager coordinates transactions across multiple tables,
 //! providing snapshot isolation with conflict detection.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 use super::types::*;
 use super::epoch::*;
 use super::error::TransactionError;
 use super::log::TransactionLog;
-use super::conflict::{ConflictDetector, TableLevelConflictDetector};
-use super::recovery::RecoveryReport;
-use crate::catalog::{FileCatalog, TableVersion};
+use super::conflict::{ConflictDetector, ConflictWindow, RowRangeConflictDetector};
+use super::recovery::{RecoveryReport, ReconcileReport};
+use super::metrics::{MetricsRecorder, TxMetrics};
+use crate::catalog::{CatalogError, FileCatalog, TableVersion};
 use crate::branch::BranchManager;
 
+/// Speculative transaction IDs start here, well above any logged ID.
+const SPECULATIVE_TX_ID_BASE: TxId = 1 << 63;
+
 /// Manages cross-table ACID transactions
 pub struct TransactionManager {
     /// Base path for transaction storage
@@ -37,11 +43,41 @@ pub struct TransactionManager {
     /// Conflict detector (pluggable strategy)
     conflict_detector: Arc<dyn ConflictDetector + Send + Sync>,
 
+    /// Which committed transactions `check_write_conflicts` considers
+    conflict_window: ConflictWindow,
+
     /// Reference to catalog (for version resolution)
     catalog: Arc<FileCatalog>,
 
     /// Reference to branch manager (optional)
     branch_manager: Option<Arc<BranchManager>>,
+
+    /// Operation counters and timings
+    metrics: MetricsRecorder,
+
+    /// IDs of speculative (in-memory only) transactions in `active_transactions`
+    speculative: RwLock<HashSet<TxId>>,
+
+    /// Next speculative ID, offset from `SPECULATIVE_TX_ID_BASE`
+    next_speculative_id: AtomicU64,
+
+    /// Next savepoint ID
+    next_savepoint_id: AtomicU64,
+
+    /// Age after which `reap_expired` aborts an active transaction
+    tx_timeout: Option<Duration>,
+
+    /// IDs of transactions between their commit checks and removal from
+    /// `active_transactions`
+    committing: RwLock<HashSet<TxId>>,
+
+    /// IDs of transactions begun by `commit_with_retry`, which are only
+    /// checked against commits made after they began
+    retries: RwLock<HashSet<TxId>>,
+
+    /// Held exclusively while a commit applies its writes and shared while
+    /// a snapshot is captured, so snapshots never see half a commit
+    apply_lock: RwLock<()>,
 }
 
 impl TransactionManager {
@@ -76,9 +112,18 @@ impl TransactionManager {
             config: storage_config.epoch_config,
             active_transactions: RwLock::new(HashMap::new()),
             recent_committed: RwLock::new(Vec::new()),
-            conflict_detector: Arc::new(TableLevelConflictDetector::new()),
+            conflict_detector: Arc::new(RowRangeConflictDetector::new()),
+            conflict_window: ConflictWindow::All,
             catalog,
             branch_manager,
+            metrics: MetricsRecorder::default(),
+            speculative: RwLock::new(HashSet::new()),
+            next_speculative_id: AtomicU64::new(0),
+            next_savepoint_id: AtomicU64::new(1),
+            tx_timeout: None,
+            committing: RwLock::new(HashSet::new()),
+            retries: RwLock::new(HashSet::new()),
+            apply_lock: RwLock::new(()),
         })
     }
 
@@ -90,13 +135,53 @@ impl TransactionManager {
         self.conflict_detector = detector;
     }
 
+    /// Set which committed transactions a commit is checked against
+    pub fn set_conflict_window(&mut self, window: ConflictWindow) {
+        self.conflict_window = window;
+    }
+
+    /// Current conflict window
+    pub fn conflict_window(&self) -> ConflictWindow {
+        self.conflict_window
+    }
+
+    /// Set the age after which `reap_expired` aborts active transactions
+    /// (None = never)
+    pub fn set_tx_timeout(&mut self, timeout: Option<Duration>) {
+        self.tx_timeout = timeout;
+    }
+
+    /// Current transaction timeout
+    pub fn tx_timeout(&self) -> Option<Duration> {
+        self.tx_timeout
+    }
+
     /// Get the epoch configuration
     pub fn config(&self) -> &EpochConfig {
         &self.config
     }
 
+    /// Start a new epoch and return its ID
+    ///
+    /// Transactions begun afterwards belong to the new epoch.
+    pub fn advance_epoch(&self) -> Result<EpochId, TransactionError> {
+        self.log.advance_epoch()
+    }
+
+    /// Snapshot of operation counters and cumulative timings
+    pub fn metrics(&self) -> TxMetrics {
+        self.metrics.snapshot()
+    }
+
     /// Begin a new transaction
     pub fn begin(&self, branch: Option<&str>) -> Result<TxId, TransactionError> {
+        let started = Instant::now();
+        let tx_id = self.begin_inner(branch)?;
+        self.metrics.record_begin(started.elapsed());
+        Ok(tx_id)
+    }
+
+    fn begin_inner(&self, branch: Option<&str>) -> Result<TxId, TransactionError> {
         // Get next transaction ID
         let tx_id = self.log.next_tx_id()?;
 
@@ -113,7 +198,7 @@ impl TransactionManager {
         let mut tx = TransactionRecord::new(tx_id, epoch_id, branch_name.clone());
 
         // Capture read snapshot (current versions of all tables on branch)
-        tx.read_snapshot = self.capture_snapshot(&branch_name)?;
+        self.capture_snapshot(&mut tx)?;
 
         // Add to active transactions
         {
@@ -126,13 +211,150 @@ impl TransactionManager {
         self.log.write_transaction(&tx)?;
 
         // Update epoch metadata
-        let mut epoch_meta = self.log.get_epoch(epoch_id)?;
-        epoch_meta.add_transaction(tx_id);
-        self.log.write_epoch_metadata(&epoch_meta)?;
+        self.log.update_epoch(epoch_id, |meta| meta.add_transaction(tx_id))?;
+
+        Ok(tx_id)
+    }
+
+    /// Begin a speculative transaction, kept entirely in memory.
+    ///
+    /// Reads, writes, `check_conflicts` and `abort` work as for a regular
+    /// transaction but never touch the transaction log, so aborting is free.
+    /// `commit` (or `promote`) turns it into a regular logged transaction
+    /// with a new ID first. Speculative IDs are at or above 2^63.
+    pub fn begin_speculative(&self, branch: Option<&str>) -> Result<TxId, TransactionError> {
+        let started = Instant::now();
+
+        let branch_name = match branch {
+            Some(b) => b.to_string(),
+            None => self.default_branch()?,
+        };
+        let tx_id = SPECULATIVE_TX_ID_BASE + self.next_speculative_id.fetch_add(1, Ordering::Relaxed);
+
+        // Epoch is assigned on promotion
+        let mut tx = TransactionRecord::new(tx_id, 0, branch_name.clone());
+        self.capture_snapshot(&mut tx)?;
+
+        {
+            let mut speculative = self.speculative.write()
+                .map_err(|_| TransactionError::LockError("speculative".to_string()))?;
+            let mut active = self.active_transactions.write()
+                .map_err(|_| TransactionError::LockError("active_transactions".to_string()))?;
+            speculative.insert(tx_id);
+            active.insert(tx_id, tx);
+        }
 
+        self.metrics.record_begin(started.elapsed());
         Ok(tx_id)
     }
 
+    /// Begin a read-only transaction pinned to the current versions of
+    /// every table on the branch.
+    ///
+    /// The versions are captured between commits, so tables resolved with
+    /// `snapshot_version` reflect a single point in time however many
+    /// writers commit afterwards. Like speculative transactions it is kept
+    /// in memory only; writes and `record_read` are rejected and `commit`
+    /// just releases it.
+    pub fn begin_read_only(&self, branch: Option<&str>) -> Result<TxId, TransactionError> {
+        let started = Instant::now();
+
+        let branch_name = match branch {
+            Some(b) => b.to_string(),
+            None => self.default_branch()?,
+        };
+        let tx_id = self.log.next_tx_id()?;
+        let epoch_id = self.log.current_epoch_id()?;
+
+        let mut tx = TransactionRecord::with_mode(tx_id, epoch_id, branch_name.clone(), TransactionMode::ReadOnly);
+        self.capture_snapshot(&mut tx)?;
+
+        {
+            let mut active = self.active_transactions.write()
+                .map_err(|_| TransactionError::LockError("active_transactions".to_string()))?;
+            active.insert(tx_id, tx);
+        }
+
+        self.metrics.record_begin(started.elapsed());
+        Ok(tx_id)
+    }
+
+    /// Version of `table` pinned in an active transaction's read snapshot
+    ///
+    /// `None` if the table did not exist on the branch when the
+    /// transaction began.
+    pub fn snapshot_version(&self, tx_id: TxId, table: &str) -> Result<Option<u64>, TransactionError> {
+        let active = self.active_transactions.read()
+            .map_err(|_| TransactionError::LockError("active_transactions".to_string()))?;
+        let tx = active.get(&tx_id)
+            .ok_or(TransactionError::TransactionNotFound(tx_id))?;
+        Ok(tx.read_snapshot.get(table).copied())
+    }
+
+    /// Whether `tx_id` is an active speculative transaction
+    pub fn is_speculative(&self, tx_id: TxId) -> Result<bool, TransactionError> {
+        let speculative = self.speculative.read()
+            .map_err(|_| TransactionError::LockError("speculative".to_string()))?;
+        Ok(speculative.contains(&tx_id))
+    }
+
+    /// Turn a speculative transaction into a regular logged one.
+    ///
+    /// The transaction keeps its reads and writes but gets a new ID from the
+    /// log, which is returned; the speculative ID is no longer valid.
+    ///
+    /// # Errors
+    /// Returns `TransactionError::InvalidState` if `tx_id` is not speculative.
+    pub fn promote(&self, tx_id: TxId) -> Result<TxId, TransactionError> {
+        let mut speculative = self.speculative.write()
+            .map_err(|_| TransactionError::LockError("speculative".to_string()))?;
+        if !speculative.contains(&tx_id) {
+            return Err(TransactionError::InvalidState {
+                expected: "speculative transaction".to_string(),
+                actual: format!("transaction {}", tx_id),
+            });
+        }
+
+        let mut active = self.active_transactions.write()
+            .map_err(|_| TransactionError::LockError("active_transactions".to_string()))?;
+        let mut tx = active.get(&tx_id)
+            .ok_or(TransactionError::TransactionNotFound(tx_id))?
+            .clone();
+
+        tx.tx_id = self.log.next_tx_id()?;
+        tx.epoch_id = self.log.current_epoch_id()?;
+        self.log.write_transaction(&tx)?;
+
+        self.log.update_epoch(tx.epoch_id, |meta| meta.add_transaction(tx.tx_id))?;
+
+        speculative.remove(&tx_id);
+        active.remove(&tx_id);
+        let promoted_id = tx.tx_id;
+        active.insert(promoted_id, tx);
+
+        Ok(promoted_id)
+    }
+
+    /// Check whether committing `tx_id` now would conflict, without committing.
+    ///
+    /// Runs the same write-conflict and snapshot checks as `commit`.
+    pub fn check_conflicts(&self, tx_id: TxId) -> Result<(), TransactionError> {
+        let tx = {
+            let active = self.active_transactions.read()
+                .map_err(|_| TransactionError::LockError("active_transactions".to_string()))?;
+            active.get(&tx_id)
+                .ok_or(TransactionError::TransactionNotFound(tx_id))?
+                .clone()
+        };
+
+        if !tx.is_active() {
+            return Err(TransactionError::TransactionNotActive(tx_id));
+        }
+
+        self.check_write_conflicts(&tx)?;
+        self.validate_snapshot(&tx)
+    }
+
     /// Add a read to the transaction (for conflict detection)
     pub fn record_read(
         &self,
@@ -148,11 +370,51 @@ impl TransactionManager {
         if !tx.is_active() {
             return Err(TransactionError::TransactionNotActive(tx_id));
         }
+        if tx.is_read_only() {
+            return Err(TransactionError::ReadOnly(tx_id));
+        }
 
         tx.read_snapshot.insert(table_name.to_string(), version);
         Ok(())
     }
 
+    /// Latest version of a table on the transaction's branch, recorded as a read.
+    ///
+    /// Returns `None` (and records nothing) if the table does not exist yet,
+    /// so callers can start a new table from version 1.
+    pub fn latest_version_or_none(
+        &self,
+        tx_id: TxId,
+        table_name: &str,
+    ) -> Result<Option<u64>, TransactionError> {
+        let branch = {
+            let active = self.active_transactions.read()
+                .map_err(|_| TransactionError::LockError("active_transactions".to_string()))?;
+            let tx = active.get(&tx_id)
+                .ok_or(TransactionError::TransactionNotFound(tx_id))?;
+            if !tx.is_active() {
+                return Err(TransactionError::TransactionNotActive(tx_id));
+            }
+            tx.branch.clone()
+        };
+
+        let latest = if let Some(ref bm) = self.branch_manager {
+            bm.get_table_version(&branch, table_name)
+                .map_err(|e| TransactionError::BranchError(e.to_string()))?
+        } else {
+            match self.catalog.get_version(table_name, None) {
+                Ok(version) => Some(version.version),
+                Err(CatalogError::TableNotFound(_)) => None,
+                Err(e) => return Err(TransactionError::CatalogError(e.to_string())),
+            }
+        };
+
+        if let Some(version) = latest {
+            self.record_read(tx_id, table_name, version)?;
+        }
+        Ok(latest)
+    }
+
     /// Add a write to the transaction
     pub fn add_write(
         &self,
@@ -167,56 +429,166 @@ impl TransactionManager {
         if !tx.is_active() {
             return Err(TransactionError::TransactionNotActive(tx_id));
         }
+        if tx.is_read_only() {
+            return Err(TransactionError::ReadOnly(tx_id));
+        }
 
         tx.writes.push(write);
         Ok(())
     }
 
+    /// Mark the transaction's current writes and reads so that later ones
+    /// can be undone with `rollback_to`
+    pub fn savepoint(&self, tx_id: TxId) -> Result<SavepointId, TransactionError> {
+        let mut active = self.active_transactions.write()
+            .map_err(|_| TransactionError::LockError("active_transactions".to_string()))?;
+        let tx = active.get_mut(&tx_id)
+            .ok_or(TransactionError::TransactionNotFound(tx_id))?;
+
+        if !tx.is_active() {
+            return Err(TransactionError::TransactionNotActive(tx_id));
+        }
+
+        let id = self.next_savepoint_id.fetch_add(1, Ordering::Relaxed);
+        tx.savepoints.push(Savepoint {
+            id,
+            writes_len: tx.writes.len(),
+            read_snapshot: tx.read_snapshot.clone(),
+        });
+        Ok(id)
+    }
+
+    /// Undo every write and read recorded since `savepoint` was taken
+    ///
+    /// The read snapshot is restored exactly, so versions re-read after the
+    /// savepoint no longer take part in snapshot validation. Savepoints
+    /// taken after `savepoint` are discarded; `savepoint` itself stays valid
+    /// and the transaction stays active.
+    pub fn rollback_to(&self, tx_id: TxId, savepoint: SavepointId) -> Result<(), TransactionError> {
+        let mut active = self.active_transactions.write()
+            .map_err(|_| TransactionError::LockError("active_transactions".to_string()))?;
+        let tx = active.get_mut(&tx_id)
+            .ok_or(TransactionError::TransactionNotFound(tx_id))?;
+
+        if !tx.is_active() {
+            return Err(TransactionError::TransactionNotActive(tx_id));
+        }
+
+        let index = tx.savepoints.iter()
+            .position(|sp| sp.id == savepoint)
+            .ok_or(TransactionError::SavepointNotFound { tx_id, savepoint })?;
+        tx.savepoints.truncate(index + 1);
+        let sp = &tx.savepoints[index];
+        tx.writes.truncate(sp.writes_len);
+        tx.read_snapshot = sp.read_snapshot.clone();
+        Ok(())
+    }
+
     /// Commit a transaction
+    ///
+    /// A speculative transaction is checked for conflicts, promoted to a
+    /// logged transaction (see `promote`) and then committed.
     pub fn commit(&self, tx_id: TxId) -> Result<(), TransactionError> {
-        // Get transaction from active set
+        let started = Instant::now();
+        let result = self.commit_inner(tx_id);
+        match &result {
+            Ok(()) => self.metrics.record_commit(started.elapsed()),
+            Err(e) if e.is_conflict() => self.metrics.record_conflict(),
+            Err(_) => {}
+        }
+        result
+    }
+
+    fn commit_inner(&self, tx_id: TxId) -> Result<(), TransactionError> {
+        if self.release_read_only(tx_id)? {
+            return Ok(());
+        }
+
+        // Don't log a speculative transaction that is bound to fail
+        if self.is_speculative(tx_id)? {
+            self.check_conflicts(tx_id)?;
+            let promoted_id = self.promote(tx_id)?;
+            return self.commit_registered(promoted_id).inspect_err(|e| {
+                // The caller only holds the speculative ID and can't abort
+                // the promoted one, so abort it here; the commit error is
+                // what gets reported either way
+                self.abort(promoted_id, &e.to_string()).ok();
+            });
+        }
+
+        self.commit_registered(tx_id)
+    }
+
+    /// Commit an active (non-speculative) transaction.
+    fn commit_registered(&self, tx_id: TxId) -> Result<(), TransactionError> {
+        // Get transaction from active set, marking it as committing while
+        // the lock is held so `reap_expired` cannot abort it midway
         let tx = {
             let active = self.active_transactions.read()
                 .map_err(|_| TransactionError::LockError("active_transactions".to_string()))?;
-            active.get(&tx_id)
+            let tx = active.get(&tx_id)
                 .ok_or(TransactionError::TransactionNotFound(tx_id))?
-                .clone()
+                .clone();
+
+            if !tx.is_active() {
+                return Err(TransactionError::TransactionNotActive(tx_id));
+            }
+
+            self.committing.write()
+                .map_err(|_| TransactionError::LockError("committing".to_string()))?
+                .insert(tx_id);
+            tx
         };
 
-        if !tx.is_active() {
-            return Err(TransactionError::TransactionNotActive(tx_id));
-        }
+        let result = self.commit_active(tx);
+        self.committing.write()
+            .map_err(|_| TransactionError::LockError("committing".to_string()))?
+            .remove(&tx_id);
+        result
+    }
+
+    fn commit_active(&self, tx: TransactionRecord) -> Result<(), TransactionError> {
+        let tx_id = tx.tx_id;
 
-        // Check for conflicts with recently committed transactions
-        self.check_conflicts(&tx)?;
+        // Checks and writes happen under one lock, so no other commit can
+        // land between them and the commit sequence follows apply order
+        let committed_tx = {
+            let _applying = self.apply_lock.write()
+                .map_err(|_| TransactionError::LockError("apply_lock".to_string()))?;
 
-        // Validate snapshot (tables we read haven't changed)
-        self.validate_snapshot(&tx)?;
+            // Check for conflicts with recently committed transactions
+            self.check_write_conflicts(&tx)?;
 
-        // Prepare commit (update status)
-        let mut committed_tx = tx.clone();
-        committed_tx.mark_committed();
+            // Validate snapshot (tables we read haven't changed)
+            self.validate_snapshot(&tx)?;
 
-        // Apply writes to catalog
-        self.apply_writes(&committed_tx)?;
+            // Prepare commit (update status)
+            let mut committed_tx = tx.clone();
+            committed_tx.mark_committed();
+            self.rebase_key_range_writes(&mut committed_tx)?;
 
-        // Update branch heads (if branch manager configured)
-        self.update_branch_heads(&committed_tx)?;
+            // Apply writes to catalog
+            self.apply_writes(&committed_tx)?;
 
-        // Persist committed status
-        self.log.write_transaction(&committed_tx)?;
+            // Update branch heads (if branch manager configured)
+            self.update_branch_heads(&committed_tx)?;
 
-        // Update epoch metadata
-        let mut epoch_meta = self.log.get_epoch(committed_tx.epoch_id)?;
-        epoch_meta.record_commit();
-        self.log.write_epoch_metadata(&epoch_meta)?;
+            committed_tx.commit_seq = Some(self.log.next_commit_seq()?);
 
-        // Add to recently committed for conflict detection
-        {
+            // Persist committed status before releasing the lock, so a
+            // changelog reader never sees a later commit sequence number
+            // logged ahead of an earlier one
+            self.log.write_transaction(&committed_tx)?;
+
+            // Add to recently committed for conflict detection
             let mut recent = self.recent_committed.write()
                 .map_err(|_| TransactionError::LockError("recent_committed".to_string()))?;
             recent.push(committed_tx.clone());
-        }
+            committed_tx
+        };
+
+        // Update epoch metadata
+        self.log.update_epoch(committed_tx.epoch_id, |meta| meta.record_commit())?;
 
         // Remove from active set
         {
@@ -228,8 +600,80 @@ impl TransactionManager {
         Ok(())
     }
 
+    /// Commit, retrying conflicts with writes re-derived from a fresh snapshot
+    ///
+    /// After a conflict the attempt is aborted, a new transaction is begun
+    /// on the same branch and `rebuild` is called with its `read_snapshot`
+    /// to produce the writes to commit instead. Returns the number of
+    /// attempts used. After `max_attempts` conflicts the last attempt is
+    /// aborted and its conflict returned; other errors are returned at once.
+    ///
+    /// `tx_id` itself is aborted after its first conflict, so on a retry
+    /// the commit is made by a new transaction. Its writes were derived
+    /// from a snapshot that already saw every earlier commit, so whatever
+    /// the `ConflictWindow`, a rebuilt attempt is only checked against
+    /// commits made after it began.
+    pub fn commit_with_retry<F>(
+        &self,
+        tx_id: TxId,
+        max_attempts: usize,
+        mut rebuild: F,
+    ) -> Result<usize, TransactionError>
+    where
+        F: FnMut(&HashMap<String, u64>) -> Result<Vec<TableWrite>, TransactionError>,
+    {
+        if max_attempts == 0 {
+            return Err(TransactionError::InvalidConfig("max_attempts must be at least 1".to_string()));
+        }
+        let branch = self.get_transaction(tx_id)?.branch;
+
+        let mut tx_id = tx_id;
+        let mut attempt = 1;
+        loop {
+            let committed = self.commit(tx_id);
+            self.retries.write()
+                .map_err(|_| TransactionError::LockError("retries".to_string()))?
+                .remove(&tx_id);
+            let conflict = match committed {
+                Ok(()) => return Ok(attempt),
+                Err(e) if e.is_conflict() => e,
+                Err(e) => return Err(e),
+            };
+            self.abort(tx_id, &format!("conflict on attempt {}: {}", attempt, conflict))?;
+            if attempt == max_attempts {
+                return Err(conflict);
+            }
+            attempt += 1;
+
+            tx_id = self.begin(Some(&branch))?;
+            self.retries.write()
+                .map_err(|_| TransactionError::LockError("retries".to_string()))?
+                .insert(tx_id);
+            let rebuilt = self.get_transaction(tx_id)
+                .and_then(|tx| rebuild(&tx.read_snapshot))
+                .and_then(|writes| writes.into_iter().try_for_each(|w| self.add_write(tx_id, w)));
+            if let Err(e) = rebuilt {
+                self.retries.write()
+                    .map_err(|_| TransactionError::LockError("retries".to_string()))?
+                    .remove(&tx_id);
+                self.abort(tx_id, "rebuild failed")?;
+                return Err(e);
+            }
+        }
+    }
+
     /// Abort a transaction
+    ///
+    /// Aborting a speculative transaction only drops it from memory.
     pub fn abort(&self, tx_id: TxId, reason: &str) -> Result<(), TransactionError> {
+        if self.is_speculative(tx_id)? {
+            return self.abort_speculative(tx_id);
+        }
+        if self.release_read_only(tx_id)? {
+            self.metrics.record_abort();
+            return Ok(());
+        }
+
         let mut active = self.active_transactions.write()
             .map_err(|_| TransactionError::LockError("active_transactions".to_string()))?;
         let tx = active.get_mut(&tx_id)
@@ -245,14 +689,86 @@ impl TransactionManager {
         self.log.write_transaction(tx)?;
 
         // Update epoch metadata
-        let mut epoch_meta = self.log.get_epoch(tx.epoch_id)?;
-        epoch_meta.record_abort();
-        self.log.write_epoch_metadata(&epoch_meta)?;
+        self.log.update_epoch(tx.epoch_id, |meta| meta.record_abort())?;
 
         // Remove from active set
         let tx_id = tx.tx_id;
         active.remove(&tx_id);
 
+        self.metrics.record_abort();
+        Ok(())
+    }
+
+    /// Abort every active transaction older than the configured timeout
+    ///
+    /// Returns the reaped IDs, sorted. Does nothing without a timeout.
+    /// Transactions that are committing are never reaped, even if expired;
+    /// age is measured from `started_at`, which has one-second resolution.
+    pub fn reap_expired(&self) -> Result<Vec<TxId>, TransactionError> {
+        let Some(timeout) = self.tx_timeout else {
+            return Ok(Vec::new());
+        };
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+        let reason = format!("timed out after {}ms", timeout.as_millis());
+
+        // Same lock order as promote and commit
+        let mut speculative = self.speculative.write()
+            .map_err(|_| TransactionError::LockError("speculative".to_string()))?;
+        let mut active = self.active_transactions.write()
+            .map_err(|_| TransactionError::LockError("active_transactions".to_string()))?;
+        let committing = self.committing.read()
+            .map_err(|_| TransactionError::LockError("committing".to_string()))?;
+
+        let mut expired: Vec<TxId> = active.values()
+            .filter(|tx| tx.is_active() && !committing.contains(&tx.tx_id))
+            .filter(|tx| now.saturating_sub(Duration::from_secs(tx.started_at.max(0) as u64)) > timeout)
+            .map(|tx| tx.tx_id)
+            .collect();
+        expired.sort_unstable();
+
+        for tx_id in &expired {
+            // Speculative and read-only transactions were never logged;
+            // dropping them is the whole abort
+            if !speculative.remove(tx_id) {
+                if let Some(tx) = active.get_mut(tx_id).filter(|tx| !tx.is_read_only()) {
+                    tx.mark_aborted(reason.as_str());
+                    self.log.write_transaction(tx)?;
+                    self.log.update_epoch(tx.epoch_id, |meta| meta.record_abort())?;
+                }
+            }
+            active.remove(tx_id);
+            self.metrics.record_abort();
+        }
+
+        Ok(expired)
+    }
+
+    /// Drop `tx_id` if it is an active read-only transaction
+    fn release_read_only(&self, tx_id: TxId) -> Result<bool, TransactionError> {
+        let mut active = self.active_transactions.write()
+            .map_err(|_| TransactionError::LockError("active_transactions".to_string()))?;
+        match active.get(&tx_id) {
+            Some(tx) if tx.is_read_only() && tx.is_active() => {
+                active.remove(&tx_id);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    fn abort_speculative(&self, tx_id: TxId) -> Result<(), TransactionError> {
+        let mut speculative = self.speculative.write()
+            .map_err(|_| TransactionError::LockError("speculative".to_string()))?;
+        let mut active = self.active_transactions.write()
+            .map_err(|_| TransactionError::LockError("active_transactions".to_string()))?;
+
+        speculative.remove(&tx_id);
+        active.remove(&tx_id)
+            .ok_or(TransactionError::TransactionNotFound(tx_id))?;
+
+        self.metrics.record_abort();
         Ok(())
     }
 
@@ -278,6 +794,28 @@ impl TransactionManager {
         Ok(active.values().cloned().collect())
     }
 
+    /// Tables being written by active transactions, with the ids writing each
+    ///
+    /// Ids are sorted and listed once per table, however many writes a
+    /// transaction has queued for it. Transactions that are no longer
+    /// active are left out.
+    pub fn write_set_map(&self) -> Result<HashMap<String, Vec<u64>>, TransactionError> {
+        let active = self.active_transactions.read()
+            .map_err(|_| TransactionError::LockError("active_transactions".to_string()))?;
+
+        let mut map: HashMap<String, Vec<u64>> = HashMap::new();
+        for tx in active.values().filter(|tx| tx.is_active()) {
+            for write in &tx.writes {
+                map.entry(write.table_name.clone()).or_default().push(tx.tx_id);
+            }
+        }
+        for tx_ids in map.values_mut() {
+            tx_ids.sort_unstable();
+            tx_ids.dedup();
+        }
+        Ok(map)
+    }
+
     /// Get count of active transactions
     pub fn active_count(&self) -> Result<usize, TransactionError> {
         let active = self.active_transactions.read()
@@ -306,8 +844,11 @@ impl TransactionManager {
     /// mark pending transactions as aborted.
     pub fn recover(&self) -> Result<RecoveryReport, TransactionError> {
         use super::recovery::RecoveryManager;
+        let started = Instant::now();
         let recovery = RecoveryManager::new(&self.log);
-        recovery.recover()
+        let report = recovery.recover()?;
+        self.metrics.record_recovery(started.elapsed());
+        Ok(report)
     }
 
     /// Perform recovery and apply rollbacks
@@ -316,8 +857,11 @@ impl TransactionManager {
     /// This is typically called on startup to ensure clean state.
     pub fn recover_and_apply(&self) -> Result<RecoveryReport, TransactionError> {
         use super::recovery::RecoveryManager;
+        let started = Instant::now();
         let recovery = RecoveryManager::new(&self.log);
-        recovery.recover_and_apply()
+        let report = recovery.recover_and_apply()?;
+        self.metrics.record_recovery(started.elapsed());
+        Ok(report)
     }
 
     /// Verify consistency of the transaction system
@@ -327,8 +871,123 @@ impl TransactionManager {
         super::recovery::verify_consistency(&self.log)
     }
 
+    /// Cross-check the changelog against the catalog
+    ///
+    /// Every write recorded by a committed transaction must exist in the
+    /// catalog with the same chunk hashes, and every catalog version must be
+    /// covered by some committed transaction. Catalog versions committed
+    /// outside the transaction system show up in `missing_in_changelog`.
+    ///
+    /// When `repair` is true, a committed transaction is appended to the log
+    /// for each such version, reconstructed from the catalog, so it appears
+    /// in the changelog. Repaired transactions carry `reconciled=true` in
+    /// their metadata, and `reconciled_previous` names the catalog version
+    /// before theirs, since they land in the log after later commits.
+    pub fn reconcile_changelog(&self, repair: bool) -> Result<ReconcileReport, TransactionError> {
+        use std::collections::HashSet;
+
+        let mut report = ReconcileReport::new();
+        let committed = self.log.list_committed_transactions()?;
+        let mut recorded: HashSet<(String, u64)> = HashSet::new();
+
+        for tx in &committed {
+            report.transactions_checked += 1;
+            for write in &tx.writes {
+                recorded.insert((write.table_name.clone(), write.new_version));
+                match self.catalog.get_version(&write.table_name, Some(write.new_version)) {
+                    Ok(version) => {
+                        if version.chunk_hashes != write.chunk_hashes {
+                            report.chunk_mismatches.push(
+                                (tx.tx_id, write.table_name.clone(), write.new_version),
+                            );
+                        }
+                    }
+                    Err(_) => {
+                        report.missing_in_catalog.push(
+                            (tx.tx_id, write.table_name.clone(), write.new_version),
+                        );
+                    }
+                }
+            }
+        }
+
+        let mut tables = self.catalog.list_tables()
+            .map_err(|e| TransactionError::CatalogError(e.to_string()))?;
+        tables.sort();
+
+        for table in tables {
+            let versions = self.catalog.list_versions(&table)
+                .map_err(|e| TransactionError::CatalogError(e.to_string()))?;
+            for version in versions {
+                report.versions_checked += 1;
+                if !recorded.contains(&(table.clone(), version)) {
+                    report.missing_in_changelog.push((table.clone(), version));
+                }
+            }
+        }
+
+        if repair && !report.missing_in_changelog.is_empty() {
+            let branch = self.default_branch()?;
+            for (table, version) in &report.missing_in_changelog {
+                let tx_id = self.append_reconciled(table, *version, &branch)?;
+                report.repaired.push(tx_id);
+            }
+        }
+
+        Ok(report)
+    }
+
     // === Private helpers ===
 
+    /// Write a committed transaction describing an existing catalog version.
+    ///
+    /// Holds `apply_lock` like a commit, so no commit can take a sequence
+    /// number or update the epoch between this one's.
+    fn append_reconciled(
+        &self,
+        table: &str,
+        version: u64,
+        branch: &str,
+    ) -> Result<TxId, TransactionError> {
+        let _applying = self.apply_lock.write()
+            .map_err(|_| TransactionError::LockError("apply_lock".to_string()))?;
+
+        let table_version = self.catalog.get_version(table, Some(version))
+            .map_err(|e| TransactionError::CatalogError(e.to_string()))?;
+        let previous = self.catalog.list_versions(table)
+            .map_err(|e| TransactionError::CatalogError(e.to_string()))?
+            .into_iter()
+            .filter(|v| *v < version)
+            .max();
+
+        let tx_id = self.log.next_tx_id()?;
+        let epoch_id = self.log.current_epoch_id()?;
+
+        let mut write = TableWrite::new(table, version, table_version.chunk_hashes);
+        if let Some(hash) = table_version.schema_hash {
+            write = write.with_schema_hash(hash);
+        }
+
+        let mut tx = TransactionRecord::new(tx_id, epoch_id, branch.to_string());
+        tx.add_write(write);
+        tx.set_metadata("reconciled", "true");
+        if let Some(previous) = previous {
+            tx.set_metadata("reconciled_previous", previous.to_string());
+        }
+        tx.mark_committed();
+        tx.committed_at = Some(table_version.created_at);
+        tx.commit_seq = Some(self.log.next_commit_seq()?);
+
+        self.log.write_transaction(&tx)?;
+
+        self.log.update_epoch(epoch_id, |meta| {
+            meta.add_transaction(tx_id);
+            meta.record_commit();
+        })?;
+
+        Ok(tx_id)
+    }
+
     fn default_branch(&self) -> Result<String, TransactionError> {
         if let Some(ref bm) = self.branch_manager {
             bm.get_default()
@@ -339,12 +998,15 @@ impl TransactionManager {
         }
     }
 
-    fn capture_snapshot(&self, branch: &str) -> Result<HashMap<String, u64>, TransactionError> {
+    /// Set `tx`'s read snapshot and `begin_seq` to the current state of its branch
+    fn capture_snapshot(&self, tx: &mut TransactionRecord) -> Result<(), TransactionError> {
+        let _applying = self.apply_lock.read()
+            .map_err(|_| TransactionError::LockError("apply_lock".to_string()))?;
         let mut snapshot = HashMap::new();
 
         if let Some(ref bm) = self.branch_manager {
             // Use branch heads
-            let branch_data = bm.get(branch)
+            let branch_data = bm.get(&tx.branch)
                 .map_err(|e| TransactionError::BranchError(e.to_string()))?;
             snapshot = branch_data.head;
         } else {
@@ -358,17 +1020,25 @@ impl TransactionManager {
             }
         }
 
-        Ok(snapshot)
+        tx.read_snapshot = snapshot;
+        tx.begin_seq = self.log.current_commit_seq()?;
+        Ok(())
     }
 
-    fn check_conflicts(&self, tx: &TransactionRecord) -> Result<(), TransactionError> {
+    fn check_write_conflicts(&self, tx: &TransactionRecord) -> Result<(), TransactionError> {
         // Check against recently committed transactions
+        let epoch_window = self.conflict_window == ConflictWindow::Epoch
+            || self.retries.read()
+                .map_err(|_| TransactionError::LockError("retries".to_string()))?
+                .contains(&tx.tx_id);
         let recent = self.recent_committed.read()
             .map_err(|_| TransactionError::LockError("recent_committed".to_string()))?;
 
         for committed_tx in recent.iter() {
-            // Only check transactions that started before us and committed after
-            if committed_tx.tx_id >= tx.tx_id {
+            // Commits up to `begin_seq` were already visible when we began
+            if epoch_window
+                && committed_tx.commit_seq.is_some_and(|seq| seq <= tx.begin_seq)
+            {
                 continue;
             }
 
@@ -399,18 +1069,29 @@ impl TransactionManager {
         Ok(())
     }
 
+    /// Version of `table` currently visible on `branch`
+    fn current_table_version(&self, branch: &str, table: &str) -> Result<Option<u64>, TransactionError> {
+        if let Some(ref bm) = self.branch_manager {
+            bm.get_table_version(branch, table)
+                .map_err(|e| TransactionError::BranchError(e.to_string()))
+        } else {
+            Ok(self.catalog.get_version(table, None).ok().map(|v| v.version))
+        }
+    }
+
     fn validate_snapshot(&self, tx: &TransactionRecord) -> Result<(), TransactionError> {
         for (table, read_version) in &tx.read_snapshot {
-            let current_version = if let Some(ref bm) = self.branch_manager {
-                bm.get_table_version(&tx.branch, table)
-                    .map_err(|e| TransactionError::BranchError(e.to_string()))?
-            } else {
-                self.catalog.get_version(table, None)
-                    .map(|v| Some(v.version))
-                    .unwrap_or(None)
-            };
+            // Tables written only by key range are rebased onto the current
+            // version instead (see `rebase_key_range_writes`); overlapping
+            // ranges were already rejected by the write conflict check
+            let mut writes = tx.writes.iter().filter(|w| &w.table_name == table).peekable();
+            if writes.peek().is_some()
+                && writes.all(|w| matches!(w.granularity, WriteGranularity::KeyRange))
+            {
+                continue;
+            }
 
-            if let Some(current) = current_version {
+            if let Some(current) = self.current_table_version(&tx.branch, table)? {
                 if current != *read_version {
                     return Err(TransactionError::SnapshotConflict {
                         table: table.clone(),
@@ -424,6 +1105,60 @@ impl TransactionManager {
         Ok(())
     }
 
+    /// Rebuild key-range writes on top of versions committed since `tx` began.
+    ///
+    /// Disjoint key-range writes to one table may commit concurrently (see
+    /// `RowRangeConflictDetector`), but each write carries the table's full
+    /// chunk list. A write whose table moved past the read snapshot is
+    /// therefore applied to the current version instead: chunks `tx` added
+    /// are appended, chunks it removed are dropped, and the version number
+    /// follows the table's latest.
+    fn rebase_key_range_writes(&self, tx: &mut TransactionRecord) -> Result<(), TransactionError> {
+        let catalog_err = |e: CatalogError| TransactionError::CatalogError(e.to_string());
+
+        for write in tx.writes.iter_mut() {
+            if !matches!(write.granularity, WriteGranularity::KeyRange) {
+                continue;
+            }
+            let branch = write.branch.as_deref().unwrap_or(&tx.branch);
+            let base = tx.read_snapshot.get(&write.table_name).copied();
+            let current = match self.current_table_version(branch, &write.table_name)? {
+                Some(current) if Some(current) != base => current,
+                _ => continue,
+            };
+
+            let base_chunks: HashSet<String> = match base {
+                Some(v) => self.catalog.get_version(&write.table_name, Some(v))
+                    .map_err(catalog_err)?
+                    .chunk_hashes
+                    .into_iter()
+                    .collect(),
+                None => HashSet::new(),
+            };
+            let ours: HashSet<&String> = write.chunk_hashes.iter().collect();
+
+            let mut chunks: Vec<String> = self.catalog.get_version(&write.table_name, Some(current))
+                .map_err(catalog_err)?
+                .chunk_hashes
+                .into_iter()
+                .filter(|h| !base_chunks.contains(h) || ours.contains(h))
+                .collect();
+            let kept: HashSet<String> = chunks.iter().cloned().collect();
+            chunks.extend(
+                write.chunk_hashes.iter()
+                    .filter(|h| !base_chunks.contains(*h) && !kept.contains(*h))
+                    .cloned(),
+            );
+
+            write.chunk_hashes = chunks;
+            write.new_version = self.catalog.get_version(&write.table_name, None)
+                .map_err(catalog_err)?
+                .version + 1;
+        }
+
+        Ok(())
+    }
+
     fn apply_writes(&self, tx: &TransactionRecord) -> Result<(), TransactionError> {
         for write in &tx.writes {
             let table_version = TableVersion::new(
@@ -458,6 +1193,10 @@ impl TransactionManager {
     /// Query changelog entries matching the given criteria.
     ///
     /// This provides the streaming interface for the unified batch/stream model.
+    /// Entries are in commit order, newest first when descending.
+    ///
+    /// Returns `InvalidCursor` if the query's cursor was issued for the
+    /// opposite direction.
     pub fn get_changelog(
         &self,
         query: crate::changelog::ChangelogQuery,
@@ -465,6 +1204,13 @@ impl TransactionManager {
         use crate::changelog::ChangelogEntry;
         use std::collections::HashMap;
 
+        // A cursor only bounds entries in the direction it was issued for
+        if let Some(cursor) = query.after_cursor {
+            if cursor.is_descending() != query.descending {
+                return Err(TransactionError::InvalidCursor(cursor.encode()));
+            }
+        }
+
         // Get committed transactions from log
         let committed = self.log.list_committed_transactions()?;
 
@@ -472,24 +1218,31 @@ impl TransactionManager {
         let mut previous_versions: HashMap<String, u64> = HashMap::new();
 
         for tx in committed {
-            // Filter by tx_id
-            if let Some(since_tx) = query.since_tx_id {
-                if tx.tx_id <= since_tx {
-                    // Still need to track versions for later entries
-                    for w in &tx.writes {
-                        previous_versions.insert(w.table_name.clone(), w.new_version);
-                    }
-                    continue;
-                }
+            // Filter by tx_id and cursor position. Transactions are in commit
+            // order, not tx_id order, so keep scanning past a miss
+            if !query.tx_in_range(tx.tx_id)
+                || !query.is_past_cursor(tx.commit_seq.unwrap_or(0), tx.tx_id)
+            {
+                // Still need to track versions for later entries
+                track_versions(&mut previous_versions, &tx);
+                continue;
             }
 
             // Filter by timestamp
             if let Some(since_ts) = query.since_timestamp {
                 if let Some(committed_at) = tx.committed_at {
                     if committed_at < since_ts {
-                        for w in &tx.writes {
-                            previous_versions.insert(w.table_name.clone(), w.new_version);
-                        }
+                        track_versions(&mut previous_versions, &tx);
+                        continue;
+                    }
+                }
+            }
+
+            // Commit times aren't strictly ordered, so keep scanning past this
+            if let Some(until_ts) = query.until_timestamp {
+                if let Some(committed_at) = tx.committed_at {
+                    if committed_at >= until_ts {
+                        track_versions(&mut previous_versions, &tx);
                         continue;
                     }
                 }
@@ -499,9 +1252,7 @@ impl TransactionManager {
             if let Some(ref branch) = query.branch {
                 if &tx.branch != branch {
                     // Track versions even for non-matching branches
-                    for w in &tx.writes {
-                        previous_versions.insert(w.table_name.clone(), w.new_version);
-                    }
+                    track_versions(&mut previous_versions, &tx);
                     continue;
                 }
             }
@@ -514,30 +1265,138 @@ impl TransactionManager {
             };
 
             if include {
-                let entry = ChangelogEntry::from_transaction(&tx, &previous_versions);
+                let entry = match reconciled_previous(&tx) {
+                    Some(previous) => ChangelogEntry::from_transaction(&tx, &previous),
+                    None => ChangelogEntry::from_transaction(&tx, &previous_versions),
+                };
                 entries.push(entry);
 
-                // Check limit
+                // Check limit (descending keeps the tail, so it scans to the end)
                 if let Some(limit) = query.limit {
-                    if entries.len() >= limit {
+                    if !query.descending && entries.len() >= limit {
                         break;
                     }
                 }
             }
 
             // Track versions for future entries
-            for w in &tx.writes {
-                previous_versions.insert(w.table_name.clone(), w.new_version);
+            track_versions(&mut previous_versions, &tx);
+        }
+
+        if query.descending {
+            entries.reverse();
+            if let Some(limit) = query.limit {
+                entries.truncate(limit);
             }
         }
 
         Ok(entries)
     }
 
-    /// Get the latest committed transaction ID.
-    pub fn latest_tx_id(&self) -> Result<Option<u64>, TransactionError> {
-        self.log.latest_committed_tx_id()
+    /// One page of [`get_changelog`](Self::get_changelog) plus the cursor
+    /// to pass to [`ChangelogQuery::with_cursor`](crate::changelog::ChangelogQuery::with_cursor)
+    /// for the next page.
+    ///
+    /// The cursor is `None` only when the page is empty and the query had
+    /// no cursor; an empty page otherwise returns the cursor it was given,
+    /// so callers can keep polling from the same position.
+    pub fn get_changelog_page(
+        &self,
+        query: crate::changelog::ChangelogQuery,
+    ) -> Result<(Vec<crate::changelog::ChangelogEntry>, Option<crate::changelog::ChangelogCursor>), TransactionError> {
+        let after = query.after_cursor;
+        let descending = query.descending;
+        let entries = self.get_changelog(query)?;
+        let next = match entries.last() {
+            Some(last) => Some(crate::changelog::ChangelogCursor::new(
+                last.commit_seq,
+                last.tx_id,
+                after.map_or(0, |c| c.offset()) + entries.len() as u64,
+                descending,
+            )),
+            None => after,
+        };
+        Ok((entries, next))
+    }
+
+    /// Changelog entries from several branches, interleaved by commit time.
+    ///
+    /// Includes entries committed at or after `since_timestamp` on any of
+    /// `branches`, sorted by `committed_at` with ties broken by `tx_id`.
+    pub fn merged_changelog(
+        &self,
+        branches: &[&str],
+        since_timestamp: i64,
+    ) -> Result<Vec<crate::changelog::ChangelogEntry>, TransactionError> {
+        let query = crate::changelog::ChangelogQuery::new().since_time(since_timestamp);
+        let mut entries: Vec<_> = self
+            .get_changelog(query)?
+            .into_iter()
+            .filter(|e| branches.contains(&e.branch.as_str()))
+            .collect();
+        entries.sort_by_key(|e| (e.committed_at, e.tx_id));
+        Ok(entries)
+    }
+
+    /// Tables changed on any branch by transactions committed at or after
+    /// `since_timestamp` (Unix seconds, inclusive, like every `since`
+    /// timestamp in the changelog API).
+    ///
+    /// Returns `(branch, table, new_version)` in commit order.
+    pub fn recently_modified(
+        &self,
+        since_timestamp: i64,
+    ) -> Result<Vec<(String, String, u64)>, TransactionError> {
+        let mut modified = Vec::new();
+        for tx in self.log.list_committed_transactions()? {
+            if tx.committed_at.is_none_or(|at| at < since_timestamp) {
+                continue;
+            }
+            for w in tx.writes {
+                modified.push((tx.branch.clone(), w.table_name, w.new_version));
+            }
+        }
+        Ok(modified)
+    }
+
+    /// Get the latest committed transaction ID.
+    pub fn latest_tx_id(&self) -> Result<Option<u64>, TransactionError> {
+        self.log.latest_committed_tx_id()
+    }
+}
+
+/// Whether `tx` was appended by `reconcile_changelog` rather than committed
+fn is_reconciled(tx: &TransactionRecord) -> bool {
+    tx.metadata.get("reconciled").is_some_and(|v| v == "true")
+}
+
+/// Record `tx`'s writes as the latest versions seen by a changelog scan.
+///
+/// A reconciled transaction is logged after commits that followed its
+/// version, so it only moves a table's version forward.
+fn track_versions(previous_versions: &mut HashMap<String, u64>, tx: &TransactionRecord) {
+    let reconciled = is_reconciled(tx);
+    for w in &tx.writes {
+        let tracked = previous_versions.entry(w.table_name.clone()).or_insert(w.new_version);
+        if !reconciled || *tracked < w.new_version {
+            *tracked = w.new_version;
+        }
+    }
+}
+
+/// The versions before a reconciled transaction's writes, from the catalog
+/// position recorded when it was appended (None for other transactions)
+fn reconciled_previous(tx: &TransactionRecord) -> Option<HashMap<String, u64>> {
+    if !is_reconciled(tx) {
+        return None;
     }
+    let previous = tx.metadata.get("reconciled_previous").and_then(|v| v.parse().ok());
+    Some(
+        tx.writes
+            .iter()
+            .filter_map(|w| previous.map(|v| (w.table_name.clone(), v)))
+            .collect(),
+    )
 }
 
 #[cfg(test)]
@@ -660,6 +1519,27 @@ mod tests {
         assert_eq!(tx.read_snapshot.get("orders"), Some(&3));
     }
 
+    #[test]
+    fn test_latest_version_or_none() {
+        let (manager, branches, _temp) = create_test_manager_with_branches();
+        branches.create("dev", None, None).unwrap();
+
+        let tx_id = manager.begin(None).unwrap();
+        branches.update_head("main", "users", 3).unwrap();
+
+        assert_eq!(manager.latest_version_or_none(tx_id, "users").unwrap(), Some(3));
+        let tx = manager.get_transaction(tx_id).unwrap();
+        assert_eq!(tx.read_snapshot.get("users"), Some(&3));
+
+        assert_eq!(manager.latest_version_or_none(tx_id, "orders").unwrap(), None);
+        let tx = manager.get_transaction(tx_id).unwrap();
+        assert!(!tx.read_snapshot.contains_key("orders"));
+
+        // Resolved against the transaction's own branch
+        let dev_tx = manager.begin(Some("dev")).unwrap();
+        assert_eq!(manager.latest_version_or_none(dev_tx, "users").unwrap(), None);
+    }
+
     #[test]
     fn test_transaction_not_found() {
         let (manager, _temp) = create_test_manager();
@@ -715,6 +1595,461 @@ mod tests {
         assert!(matches!(result, Err(TransactionError::WriteConflict(_))));
     }
 
+    #[test]
+    fn test_commit_with_retry_rebuilds_after_conflict() {
+        let (manager, _temp) = create_test_manager();
+        let setup = manager.begin(None).unwrap();
+        manager.add_write(setup, TableWrite::new("prices", 1, vec![])).unwrap();
+        manager.commit(setup).unwrap();
+        manager.clear_recent_committed().unwrap();
+
+        // `orders` is derived from the `prices` version in the snapshot
+        let tx = manager.begin(None).unwrap();
+        manager.add_write(tx, TableWrite::new("orders", 1, vec!["from_prices_1".to_string()])).unwrap();
+
+        // A concurrent writer moves `prices` on before `tx` commits
+        let other = manager.begin(None).unwrap();
+        manager.add_write(other, TableWrite::new("prices", 2, vec![])).unwrap();
+        manager.commit(other).unwrap();
+
+        let mut snapshots = Vec::new();
+        let attempts = manager.commit_with_retry(tx, 3, |snapshot| {
+            snapshots.push(snapshot.clone());
+            let prices = snapshot["prices"];
+            Ok(vec![TableWrite::new("orders", 1, vec![format!("from_prices_{}", prices)])])
+        }).unwrap();
+
+        assert_eq!(attempts, 2);
+        assert_eq!(snapshots.len(), 1);
+        assert_eq!(snapshots[0].get("prices"), Some(&2));
+        assert!(manager.get_transaction(tx).unwrap().is_aborted());
+        let orders = manager.catalog.get_version("orders", None).unwrap();
+        assert_eq!(orders.chunk_hashes, vec!["from_prices_2"]);
+        assert_eq!(manager.active_count().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_commit_with_retry_succeeds_after_write_conflict() {
+        let (mut manager, _temp) = create_test_manager();
+        manager.set_conflict_window(ConflictWindow::Epoch);
+
+        let tx1 = manager.begin(None).unwrap();
+        let tx2 = manager.begin(None).unwrap();
+        manager.add_write(tx1, TableWrite::new("users", 1, vec!["a".to_string()])).unwrap();
+        manager.add_write(tx2, TableWrite::new("users", 1, vec!["b".to_string()])).unwrap();
+        manager.commit(tx1).unwrap();
+        assert!(matches!(manager.check_conflicts(tx2), Err(TransactionError::WriteConflict(_))));
+
+        // The rebuilt attempt begins after tx1 committed, so it doesn't conflict
+        let attempts = manager.commit_with_retry(tx2, 3, |snapshot| {
+            Ok(vec![TableWrite::new("users", snapshot["users"] + 1, vec!["b".to_string()])])
+        }).unwrap();
+
+        assert_eq!(attempts, 2);
+        assert!(manager.get_transaction(tx2).unwrap().is_aborted());
+        let users = manager.catalog.get_version("users", None).unwrap();
+        assert_eq!((users.version, users.chunk_hashes), (2, vec!["b".to_string()]));
+        assert_eq!(manager.active_count().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_commit_with_retry_under_all_window() {
+        let (manager, _temp) = create_test_manager();
+        assert_eq!(manager.conflict_window(), ConflictWindow::All);
+
+        let tx1 = manager.begin(None).unwrap();
+        let tx2 = manager.begin(None).unwrap();
+        manager.add_write(tx1, TableWrite::new("users", 1, vec!["a".to_string()])).unwrap();
+        manager.add_write(tx2, TableWrite::new("users", 1, vec!["b".to_string()])).unwrap();
+        manager.commit(tx1).unwrap();
+
+        // tx1 stays in the window, but the rebuilt attempt began after it
+        let attempts = manager.commit_with_retry(tx2, 3, |snapshot| {
+            Ok(vec![TableWrite::new("users", snapshot["users"] + 1, vec!["b".to_string()])])
+        }).unwrap();
+
+        assert_eq!(attempts, 2);
+        assert!(manager.get_transaction(tx2).unwrap().is_aborted());
+        assert_eq!(manager.catalog.get_version("users", None).unwrap().version, 2);
+
+        // Plain commits under the "all" window still see tx1
+        let tx3 = manager.begin(None).unwrap();
+        manager.add_write(tx3, TableWrite::new("users", 3, vec![])).unwrap();
+        assert!(matches!(manager.commit(tx3), Err(TransactionError::WriteConflict(_))));
+    }
+
+    #[test]
+    fn test_commit_with_retry_gives_up() {
+        let (manager, _temp) = create_test_manager();
+        let tx1 = manager.begin(None).unwrap();
+        let tx2 = manager.begin(None).unwrap();
+        manager.add_write(tx1, TableWrite::new("users", 1, vec![])).unwrap();
+        manager.add_write(tx2, TableWrite::new("users", 1, vec![])).unwrap();
+        manager.commit(tx1).unwrap();
+
+        // A concurrent writer lands after every rebuilt attempt begins
+        let mut rebuilds = 0;
+        let result = manager.commit_with_retry(tx2, 3, |snapshot| {
+            rebuilds += 1;
+            let next = snapshot["users"] + 1;
+            manager.clear_recent_committed()?;
+            let other = manager.begin(None)?;
+            manager.add_write(other, TableWrite::new("users", next, vec![]))?;
+            manager.commit(other)?;
+            Ok(vec![TableWrite::new("users", next, vec![])])
+        });
+        assert!(matches!(result, Err(TransactionError::WriteConflict(_))));
+        assert_eq!(rebuilds, 2);
+        assert!(manager.get_transaction(tx2).unwrap().is_aborted());
+        assert_eq!(manager.active_count().unwrap(), 0);
+
+        assert!(matches!(
+            manager.commit_with_retry(tx2, 0, |_| Ok(vec![])),
+            Err(TransactionError::InvalidConfig(_))
+        ));
+    }
+
+    #[test]
+    fn test_read_only_snapshot_ignores_later_commits() {
+        let (manager, branches, _temp) = create_test_manager_with_branches();
+        let write_both = |version| {
+            manager.clear_recent_committed().unwrap();
+            let tx = manager.begin(None).unwrap();
+            manager.add_write(tx, TableWrite::new("users", version, vec![])).unwrap();
+            manager.add_write(tx, TableWrite::new("orders", version, vec![])).unwrap();
+            manager.commit(tx).unwrap();
+        };
+        write_both(1);
+
+        let ro = manager.begin_read_only(None).unwrap();
+        write_both(2);
+        assert_eq!(branches.get_table_version("main", "users").unwrap(), Some(2));
+
+        assert_eq!(manager.snapshot_version(ro, "users").unwrap(), Some(1));
+        assert_eq!(manager.snapshot_version(ro, "orders").unwrap(), Some(1));
+        assert_eq!(manager.snapshot_version(ro, "events").unwrap(), None);
+        assert!(manager.get_transaction(ro).unwrap().is_read_only());
+
+        assert!(matches!(
+            manager.add_write(ro, TableWrite::new("users", 3, vec![])),
+            Err(TransactionError::ReadOnly(_))
+        ));
+        assert!(matches!(
+            manager.record_read(ro, "users", 2),
+            Err(TransactionError::ReadOnly(_))
+        ));
+
+        // Committing only releases the snapshot
+        manager.commit(ro).unwrap();
+        assert!(matches!(
+            manager.snapshot_version(ro, "users"),
+            Err(TransactionError::TransactionNotFound(_))
+        ));
+        assert_eq!(manager.active_count().unwrap(), 0);
+
+        let ro = manager.begin_read_only(None).unwrap();
+        assert_eq!(manager.snapshot_version(ro, "users").unwrap(), Some(2));
+        manager.abort(ro, "done").unwrap();
+        assert_eq!(manager.active_count().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_reap_expired_transactions() {
+        let (mut manager, _temp) = create_test_manager();
+        assert!(manager.reap_expired().unwrap().is_empty());
+
+        let stale = manager.begin(None).unwrap();
+        let stale_speculative = manager.begin_speculative(None).unwrap();
+        let committing = manager.begin(None).unwrap();
+        let fresh = manager.begin(None).unwrap();
+        let committed = manager.begin(None).unwrap();
+        manager.add_write(committed, TableWrite::new("users", 1, vec![])).unwrap();
+        manager.commit(committed).unwrap();
+
+        // Backdate everything except `fresh`
+        {
+            let mut active = manager.active_transactions.write().unwrap();
+            for tx_id in [stale, stale_speculative, committing] {
+                active.get_mut(&tx_id).unwrap().started_at -= 60;
+            }
+        }
+        manager.set_tx_timeout(Some(Duration::from_secs(30)));
+        // A commit in flight must survive the reaper
+        manager.committing.write().unwrap().insert(committing);
+
+        assert_eq!(manager.reap_expired().unwrap(), vec![stale, stale_speculative]);
+        assert!(manager.reap_expired().unwrap().is_empty());
+
+        let record = manager.get_transaction(stale).unwrap();
+        assert!(record.is_aborted());
+        assert!(matches!(
+            manager.get_transaction(stale_speculative),
+            Err(TransactionError::TransactionNotFound(_))
+        ));
+        assert!(manager.get_transaction(committed).unwrap().is_committed());
+        assert!(manager.get_transaction(fresh).unwrap().is_active());
+        assert_eq!(manager.active_count().unwrap(), 2);
+        assert_eq!(manager.metrics().aborts, 2);
+
+        manager.committing.write().unwrap().remove(&committing);
+        manager.commit(committing).unwrap();
+    }
+
+    #[test]
+    fn test_rollback_to_savepoint_keeps_earlier_writes() {
+        let (manager, _temp) = create_test_manager();
+
+        let setup = manager.begin(None).unwrap();
+        manager.add_write(setup, TableWrite::new("products", 1, vec![])).unwrap();
+        manager.commit(setup).unwrap();
+
+        let tx = manager.begin(None).unwrap();
+        manager.record_read(tx, "products", 1).unwrap();
+        manager.add_write(tx, TableWrite::new("users", 1, vec![])).unwrap();
+        let sp1 = manager.savepoint(tx).unwrap();
+
+        // A stale read and more writes after the savepoint
+        manager.record_read(tx, "products", 7).unwrap();
+        manager.add_write(tx, TableWrite::new("orders", 1, vec![])).unwrap();
+        let sp2 = manager.savepoint(tx).unwrap();
+        manager.add_write(tx, TableWrite::new("events", 1, vec![])).unwrap();
+        assert!(matches!(
+            manager.check_conflicts(tx),
+            Err(TransactionError::SnapshotConflict { .. })
+        ));
+
+        manager.rollback_to(tx, sp1).unwrap();
+        let record = manager.get_transaction(tx).unwrap();
+        assert!(record.is_active());
+        assert_eq!(record.written_tables(), vec!["users"]);
+        assert_eq!(record.read_snapshot.get("products"), Some(&1));
+
+        // Later savepoints are discarded; the target stays usable
+        assert!(matches!(
+            manager.rollback_to(tx, sp2),
+            Err(TransactionError::SavepointNotFound { .. })
+        ));
+        manager.rollback_to(tx, sp1).unwrap();
+        let other = manager.begin(None).unwrap();
+        assert!(manager.rollback_to(other, sp1).is_err());
+
+        manager.commit(tx).unwrap();
+        assert_eq!(manager.catalog.list_tables().unwrap(), vec!["products", "users"]);
+        assert!(matches!(
+            manager.savepoint(tx),
+            Err(TransactionError::TransactionNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_disjoint_key_ranges_commit_concurrently() {
+        use crate::parquet::ScalarValue;
+
+        let (manager, _temp) = create_test_manager();
+        let ranged = |version, chunk: &str, min, max| {
+            TableWrite::new("users", version, vec![chunk.to_string()])
+                .with_key_range(ScalarValue::Int64(min), ScalarValue::Int64(max))
+        };
+
+        // Both build version 1 from the same (empty) snapshot
+        let tx1 = manager.begin(None).unwrap();
+        let tx2 = manager.begin(None).unwrap();
+        manager.add_write(tx1, ranged(1, "low", 0, 99)).unwrap();
+        manager.add_write(tx2, ranged(1, "high", 100, 199)).unwrap();
+        manager.commit(tx1).unwrap();
+        manager.commit(tx2).unwrap();
+
+        // The second commit is rebased onto the first, keeping both chunks
+        let latest = manager.catalog.get_version("users", None).unwrap();
+        assert_eq!(latest.version, 2);
+        assert_eq!(latest.chunk_hashes, vec!["low", "high"]);
+        let logged = manager.log.read_transaction(tx2).unwrap();
+        assert_eq!(logged.writes[0].new_version, 2);
+        assert_eq!(logged.writes[0].chunk_hashes, vec!["low", "high"]);
+
+        // Overlapping ranges still conflict
+        let tx3 = manager.begin(None).unwrap();
+        let tx4 = manager.begin(None).unwrap();
+        manager.add_write(tx3, ranged(3, "a", 200, 250)).unwrap();
+        manager.add_write(tx4, ranged(3, "b", 240, 300)).unwrap();
+        manager.commit(tx3).unwrap();
+        let result = manager.commit(tx4);
+        assert!(matches!(result, Err(TransactionError::WriteConflict(tables)) if tables == vec!["users"]));
+    }
+
+    #[test]
+    fn test_disjoint_key_ranges_on_existing_table() {
+        use crate::parquet::ScalarValue;
+
+        let (mut manager, _temp) = create_test_manager();
+        manager.set_conflict_window(ConflictWindow::Epoch);
+        let tx = manager.begin(None).unwrap();
+        manager.add_write(tx, TableWrite::new("users", 1, vec!["base".to_string()])).unwrap();
+        manager.commit(tx).unwrap();
+
+        // Both snapshots pin users at v1
+        let tx1 = manager.begin(None).unwrap();
+        let tx2 = manager.begin(None).unwrap();
+        let ranged = |chunks: &[&str], min, max| {
+            TableWrite::new("users", 2, chunks.iter().map(|c| c.to_string()).collect())
+                .with_key_range(ScalarValue::Int64(min), ScalarValue::Int64(max))
+        };
+        manager.add_write(tx1, ranged(&["base", "low"], 0, 99)).unwrap();
+        manager.add_write(tx2, ranged(&["base", "high"], 100, 199)).unwrap();
+        manager.commit(tx1).unwrap();
+        manager.commit(tx2).unwrap();
+
+        let latest = manager.catalog.get_version("users", None).unwrap();
+        assert_eq!(latest.version, 3);
+        assert_eq!(latest.chunk_hashes, vec!["base", "low", "high"]);
+    }
+
+    #[test]
+    fn test_metrics_count_commits_and_conflicts() {
+        let (manager, _temp) = create_test_manager();
+        assert_eq!(manager.metrics(), TxMetrics::default());
+
+        for table in ["users", "items", "events"] {
+            let tx = manager.begin(None).unwrap();
+            manager.add_write(tx, TableWrite::new(table, 1, vec![])).unwrap();
+            manager.commit(tx).unwrap();
+        }
+
+        let metrics = manager.metrics();
+        assert_eq!(metrics.begins, 3);
+        assert_eq!(metrics.commits, 3);
+        assert_eq!(metrics.conflicts, 0);
+        assert!(metrics.commit_nanos > 0);
+
+        // Induce a write conflict
+        let tx1 = manager.begin(None).unwrap();
+        let tx2 = manager.begin(None).unwrap();
+        manager.add_write(tx1, TableWrite::new("orders", 1, vec![])).unwrap();
+        manager.add_write(tx2, TableWrite::new("orders", 1, vec![])).unwrap();
+        manager.commit(tx1).unwrap();
+        assert!(manager.commit(tx2).unwrap_err().is_conflict());
+        manager.abort(tx2, "conflict").unwrap();
+
+        let metrics = manager.metrics();
+        assert_eq!(metrics.commits, 4);
+        assert_eq!(metrics.conflicts, 1);
+        assert_eq!(metrics.aborts, 1);
+
+        manager.recover().unwrap();
+        assert_eq!(manager.metrics().recoveries, 1);
+    }
+
+    /// Every file under the transaction log, with its contents.
+    fn log_files(manager: &TransactionManager) -> Vec<(PathBuf, Vec<u8>)> {
+        fn walk(dir: &Path, files: &mut Vec<(PathBuf, Vec<u8>)>) {
+            for entry in std::fs::read_dir(dir).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    walk(&path, files);
+                } else {
+                    let contents = std::fs::read(&path).unwrap();
+                    files.push((path, contents));
+                }
+            }
+        }
+        let mut files = Vec::new();
+        walk(manager.log.base_path(), &mut files);
+        files.sort();
+        files
+    }
+
+    #[test]
+    fn test_speculative_abort_leaves_no_trace() {
+        let (manager, _branches, _temp) = create_test_manager_with_branches();
+        let before = log_files(&manager);
+
+        let tx_id = manager.begin_speculative(None).unwrap();
+        assert!(manager.is_speculative(tx_id).unwrap());
+        manager
+            .add_write(tx_id, TableWrite::new("users", 1, vec!["chunk1".to_string()]))
+            .unwrap();
+        manager.check_conflicts(tx_id).unwrap();
+        manager.abort(tx_id, "what-if").unwrap();
+
+        assert_eq!(log_files(&manager), before);
+        assert_eq!(manager.active_count().unwrap(), 0);
+        assert!(!manager.is_speculative(tx_id).unwrap());
+        assert!(manager.get_transaction(tx_id).is_err());
+        assert_eq!(manager.metrics().aborts, 1);
+
+        // Regular IDs are unaffected
+        assert_eq!(manager.begin(None).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_speculative_commit_is_promoted() {
+        let (manager, branches, _temp) = create_test_manager_with_branches();
+
+        let tx_id = manager.begin_speculative(None).unwrap();
+        manager
+            .add_write(tx_id, TableWrite::new("users", 1, vec!["chunk1".to_string()]))
+            .unwrap();
+        manager.commit(tx_id).unwrap();
+
+        // Logged under a regular ID; the speculative ID left no record
+        let logged = manager.log.list_committed_transactions().unwrap();
+        assert_eq!(logged.len(), 1);
+        assert_eq!(logged[0].tx_id, 1);
+        assert_eq!(logged[0].writes[0].table_name, "users");
+        assert!(matches!(
+            manager.log.read_transaction(tx_id),
+            Err(TransactionError::TransactionNotFound(_))
+        ));
+        assert_eq!(branches.get_table_version("main", "users").unwrap(), Some(1));
+        assert_eq!(manager.active_count().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_failed_speculative_commit_aborts_promoted_tx() {
+        let (manager, _branches, _temp) = create_test_manager_with_branches();
+
+        // Version 5 of a new table passes the conflict checks but is
+        // rejected by the catalog after promotion
+        let tx_id = manager.begin_speculative(None).unwrap();
+        manager.add_write(tx_id, TableWrite::new("users", 5, vec![])).unwrap();
+        assert!(matches!(manager.commit(tx_id), Err(TransactionError::CatalogError(_))));
+
+        assert_eq!(manager.active_count().unwrap(), 0);
+        let logged = manager.log.read_transaction(1).unwrap();
+        assert!(logged.is_aborted());
+    }
+
+    #[test]
+    fn test_speculative_check_conflicts_sees_committed_state() {
+        let (manager, _branches, _temp) = create_test_manager_with_branches();
+
+        let what_if = manager.begin_speculative(None).unwrap();
+        manager
+            .add_write(what_if, TableWrite::new("users", 1, vec!["chunk1".to_string()]))
+            .unwrap();
+
+        let real = manager.begin(None).unwrap();
+        manager
+            .add_write(real, TableWrite::new("users", 1, vec!["chunk2".to_string()]))
+            .unwrap();
+        manager.commit(real).unwrap();
+
+        assert!(matches!(
+            manager.check_conflicts(what_if),
+            Err(TransactionError::WriteConflict(_))
+        ));
+        // A conflicting speculative commit fails without logging anything
+        let before = log_files(&manager);
+        assert!(manager.commit(what_if).unwrap_err().is_conflict());
+        assert_eq!(log_files(&manager), before);
+        assert!(manager.is_speculative(what_if).unwrap());
+
+        assert!(matches!(
+            manager.promote(real),
+            Err(TransactionError::InvalidState { .. })
+        ));
+    }
+
     #[test]
     fn test_no_conflict_different_tables() {
         let (manager, _temp) = create_test_manager();
@@ -776,6 +2111,74 @@ mod tests {
         assert_eq!(version, Some(1));
     }
 
+    #[test]
+    fn test_merged_changelog_is_time_ordered() {
+        let (manager, branches, _temp) = create_test_manager_with_branches();
+        branches.create("feature", None, None).unwrap();
+        branches.create("other", None, None).unwrap();
+
+        // (branch, table, backdated commit time)
+        let commits = [
+            ("main", "users", 300),
+            ("feature", "orders", 100),
+            ("main", "events", 200),
+            ("feature", "items", 200),
+            ("other", "logs", 150),
+            ("main", "ancient", 10),
+        ];
+        for (branch, table, at) in commits {
+            let tx_id = manager.begin(Some(branch)).unwrap();
+            manager.add_write(tx_id, TableWrite::new(table, 1, vec![])).unwrap();
+            manager.commit(tx_id).unwrap();
+
+            let mut tx = manager.log.read_transaction(tx_id).unwrap();
+            tx.committed_at = Some(at);
+            manager.log.write_transaction(&tx).unwrap();
+        }
+
+        let merged = manager.merged_changelog(&["main", "feature"], 100).unwrap();
+        let order: Vec<(i64, u64, &str)> = merged
+            .iter()
+            .map(|e| (e.committed_at, e.tx_id, e.branch.as_str()))
+            .collect();
+        assert_eq!(
+            order,
+            vec![(100, 2, "feature"), (200, 3, "main"), (200, 4, "feature"), (300, 1, "main")]
+        );
+    }
+
+    #[test]
+    fn test_recently_modified_across_branches() {
+        let (manager, branches, _temp) = create_test_manager_with_branches();
+        branches.create("feature", None, None).unwrap();
+
+        let commit = |branch: &str, table: &str| {
+            let tx_id = manager.begin(Some(branch)).unwrap();
+            manager.add_write(tx_id, TableWrite::new(table, 1, vec![])).unwrap();
+            manager.commit(tx_id).unwrap();
+            tx_id
+        };
+        let old_tx = commit("main", "users");
+        commit("feature", "orders");
+        commit("main", "events");
+
+        // Backdate the first commit to before the cutoff
+        let mut tx = manager.log.read_transaction(old_tx).unwrap();
+        tx.committed_at = Some(1_000);
+        manager.log.write_transaction(&tx).unwrap();
+
+        let recent = manager.recently_modified(1_001).unwrap();
+        assert_eq!(
+            recent,
+            vec![
+                ("feature".to_string(), "orders".to_string(), 1),
+                ("main".to_string(), "events".to_string(), 1),
+            ]
+        );
+        assert_eq!(manager.recently_modified(1_000).unwrap().len(), 3);
+        assert!(manager.recently_modified(i64::MAX).unwrap().is_empty());
+    }
+
     #[test]
     fn test_transaction_persistence() {
         let temp_dir = TempDir::new().unwrap();
@@ -811,6 +2214,26 @@ mod tests {
         assert_eq!(active.len(), 2);
     }
 
+    #[test]
+    fn test_write_set_map() {
+        let (manager, _temp) = create_test_manager();
+
+        let tx1 = manager.begin(None).unwrap();
+        let tx2 = manager.begin(None).unwrap();
+        let tx3 = manager.begin(None).unwrap();
+        manager.add_write(tx1, TableWrite::new("users", 1, vec!["a".to_string()])).unwrap();
+        manager.add_write(tx1, TableWrite::new("orders", 1, vec!["b".to_string()])).unwrap();
+        manager.add_write(tx1, TableWrite::new("users", 2, vec!["c".to_string()])).unwrap();
+        manager.add_write(tx2, TableWrite::new("users", 1, vec!["d".to_string()])).unwrap();
+        manager.add_write(tx3, TableWrite::new("items", 1, vec!["e".to_string()])).unwrap();
+        manager.abort(tx3, "test").unwrap();
+
+        let map = manager.write_set_map().unwrap();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map["users"], vec![tx1, tx2]);
+        assert_eq!(map["orders"], vec![tx1]);
+    }
+
     #[test]
     fn test_conflict_detection_after_epoch_boundary_clear() {
         // This test verifies that conflict detection works even when
@@ -857,4 +2280,334 @@ mod tests {
         );
         assert!(is_conflict, "Expected conflict error, got: {:?}", err);
     }
+
+    #[test]
+    fn test_reconcile_changelog_reports_untracked_version() {
+        let (manager, _temp) = create_test_manager();
+
+        let tx_id = manager.begin(None).unwrap();
+        manager.add_write(tx_id, TableWrite::new("users", 1, vec!["c1".to_string()])).unwrap();
+        manager.commit(tx_id).unwrap();
+
+        let report = manager.reconcile_changelog(false).unwrap();
+        assert!(report.is_consistent());
+        assert_eq!(report.transactions_checked, 1);
+        assert_eq!(report.versions_checked, 1);
+
+        // Commit directly to the catalog, bypassing the transaction log,
+        // then v3 through it
+        manager.catalog.commit(TableVersion::new("users", 2, vec!["c2".to_string()])).unwrap();
+        manager.clear_recent_committed().unwrap();
+        let tx_id = manager.begin(None).unwrap();
+        manager.add_write(tx_id, TableWrite::new("users", 3, vec!["c3".to_string()])).unwrap();
+        manager.commit(tx_id).unwrap();
+
+        let report = manager.reconcile_changelog(false).unwrap();
+        assert!(!report.is_consistent());
+        assert_eq!(report.missing_in_changelog, vec![("users".to_string(), 2)]);
+        assert!(report.missing_in_catalog.is_empty());
+        assert!(report.repaired.is_empty());
+
+        // Repair appends a reconstructed entry to the changelog
+        let report = manager.reconcile_changelog(true).unwrap();
+        assert_eq!(report.repaired.len(), 1);
+
+        let entries = manager.get_changelog(crate::changelog::ChangelogQuery::new()).unwrap();
+        let last = entries.last().unwrap();
+        assert_eq!(last.tx_id, report.repaired[0]);
+        assert_eq!(last.changes[0].table_name, "users");
+        assert_eq!(last.changes[0].old_version, Some(1));
+        assert_eq!(last.changes[0].new_version, 2);
+
+        // The late v2 entry doesn't move the tracked version back
+        manager.clear_recent_committed().unwrap();
+        let tx_id = manager.begin(None).unwrap();
+        manager.add_write(tx_id, TableWrite::new("users", 4, vec!["c4".to_string()])).unwrap();
+        manager.commit(tx_id).unwrap();
+        let entries = manager.get_changelog(crate::changelog::ChangelogQuery::new()).unwrap();
+        let last = entries.last().unwrap();
+        assert_eq!((last.changes[0].old_version, last.changes[0].new_version), (Some(3), 4));
+
+        assert!(manager.reconcile_changelog(false).unwrap().is_consistent());
+    }
+
+    #[test]
+    fn test_changelog_pages_match_full_query() {
+        use crate::changelog::ChangelogQuery;
+
+        let (manager, _temp) = create_test_manager();
+        let commit = |table: &str| {
+            let tx = manager.begin(None).unwrap();
+            manager.add_write(tx, TableWrite::new(table, 1, vec![])).unwrap();
+            manager.commit(tx).unwrap();
+        };
+        for i in 0..7 {
+            commit(&format!("t{}", i));
+        }
+
+        let page_all = |query: ChangelogQuery| {
+            let mut seen = Vec::new();
+            let mut cursor = None;
+            loop {
+                let mut q = query.clone().with_limit(2);
+                if let Some(c) = cursor {
+                    q = q.with_cursor(c);
+                }
+                let (page, next) = manager.get_changelog_page(q).unwrap();
+                if page.is_empty() {
+                    assert_eq!(next, cursor);
+                    return (seen, cursor);
+                }
+                seen.extend(page.into_iter().map(|e| e.tx_id));
+                cursor = next;
+            }
+        };
+
+        let full: Vec<_> = manager.get_changelog(ChangelogQuery::new()).unwrap()
+            .into_iter().map(|e| e.tx_id).collect();
+        let (paged, cursor) = page_all(ChangelogQuery::new());
+        assert_eq!(paged, full);
+        assert_eq!(cursor.unwrap().offset(), 7);
+
+        let tables = vec!["t1".to_string(), "t4".to_string(), "t5".to_string()];
+        let filtered: Vec<_> = manager.get_changelog(ChangelogQuery::new().for_tables(tables.clone()))
+            .unwrap().into_iter().map(|e| e.tx_id).collect();
+        assert_eq!(page_all(ChangelogQuery::new().for_tables(tables)).0, filtered);
+
+        // Entries appended later are picked up from the last cursor
+        commit("t7");
+        let (page, next) = manager
+            .get_changelog_page(ChangelogQuery::new().with_cursor(cursor.unwrap()))
+            .unwrap();
+        assert_eq!(page.len(), 1);
+        assert_eq!(page[0].changes[0].table_name, "t7");
+        assert_eq!(next.unwrap().offset(), 8);
+    }
+
+    #[test]
+    fn test_changelog_cursor_follows_commit_order() {
+        use crate::changelog::ChangelogQuery;
+
+        let (manager, _temp) = create_test_manager();
+        let a = manager.begin(None).unwrap();
+        let b = manager.begin(None).unwrap();
+        manager.add_write(a, TableWrite::new("a", 1, vec![])).unwrap();
+        manager.add_write(b, TableWrite::new("b", 1, vec![])).unwrap();
+        manager.commit(b).unwrap();
+
+        let (page, cursor) = manager.get_changelog_page(ChangelogQuery::new()).unwrap();
+        assert_eq!(page.iter().map(|e| e.tx_id).collect::<Vec<_>>(), vec![b]);
+
+        // `a` began first but committed after the page was read
+        manager.commit(a).unwrap();
+        let (page, next) = manager
+            .get_changelog_page(ChangelogQuery::new().with_cursor(cursor.unwrap()))
+            .unwrap();
+        assert_eq!(page.iter().map(|e| e.tx_id).collect::<Vec<_>>(), vec![a]);
+        assert_eq!(next.unwrap().offset(), 2);
+
+        let full = manager.get_changelog(ChangelogQuery::new()).unwrap();
+        assert_eq!(full.iter().map(|e| e.tx_id).collect::<Vec<_>>(), vec![b, a]);
+        assert_eq!(full.iter().map(|e| e.commit_seq).collect::<Vec<_>>(), vec![1, 2]);
+        assert_eq!(manager.latest_tx_id().unwrap(), Some(b));
+    }
+
+    #[test]
+    fn test_changelog_descending_and_until_bounds() {
+        use crate::changelog::ChangelogQuery;
+
+        let (manager, _temp) = create_test_manager();
+        for i in 0..5 {
+            let tx = manager.begin(None).unwrap();
+            manager.add_write(tx, TableWrite::new(format!("t{}", i), 1, vec![])).unwrap();
+            manager.commit(tx).unwrap();
+        }
+        let ids = |query: ChangelogQuery| -> Vec<u64> {
+            manager.get_changelog(query).unwrap().into_iter().map(|e| e.tx_id).collect()
+        };
+        let all = ids(ChangelogQuery::new());
+        assert_eq!(all.len(), 5);
+
+        // Last 2 commits, newest first
+        assert_eq!(ids(ChangelogQuery::new().descending().with_limit(2)), vec![all[4], all[3]]);
+        let mut reversed = all.clone();
+        reversed.reverse();
+        assert_eq!(ids(ChangelogQuery::new().descending()), reversed);
+
+        // until_tx is inclusive; combined with since_tx gives (since, until]
+        assert_eq!(ids(ChangelogQuery::new().until_tx(all[2])), all[..3].to_vec());
+        assert_eq!(ids(ChangelogQuery::new().since_tx(all[0]).until_tx(all[2])), all[1..3].to_vec());
+        assert_eq!(
+            ids(ChangelogQuery::new().until_tx(all[3]).descending().with_limit(2)),
+            vec![all[3], all[2]]
+        );
+
+        // until_time is exclusive
+        let entries = manager.get_changelog(ChangelogQuery::new()).unwrap();
+        let first_ts = entries[0].committed_at;
+        let last_ts = entries[4].committed_at;
+        assert!(ids(ChangelogQuery::new().until_time(first_ts)).is_empty());
+        assert_eq!(ids(ChangelogQuery::new().until_time(last_ts + 1)), all);
+
+        // Descending pages walk towards older entries
+        let (page, cursor) = manager
+            .get_changelog_page(ChangelogQuery::new().descending().with_limit(3))
+            .unwrap();
+        assert_eq!(page.iter().map(|e| e.tx_id).collect::<Vec<_>>(), vec![all[4], all[3], all[2]]);
+        let (page, _) = manager
+            .get_changelog_page(ChangelogQuery::new().descending().with_limit(3).with_cursor(cursor.unwrap()))
+            .unwrap();
+        assert_eq!(page.iter().map(|e| e.tx_id).collect::<Vec<_>>(), vec![all[1], all[0]]);
+
+        // A cursor can't switch direction between pages
+        let (_, ascending) = manager
+            .get_changelog_page(ChangelogQuery::new().with_limit(2))
+            .unwrap();
+        assert!(matches!(
+            manager.get_changelog(ChangelogQuery::new().descending().with_cursor(ascending.unwrap())),
+            Err(TransactionError::InvalidCursor(_))
+        ));
+        assert!(matches!(
+            manager.get_changelog(ChangelogQuery::new().with_cursor(cursor.unwrap())),
+            Err(TransactionError::InvalidCursor(_))
+        ));
+    }
+
+    #[test]
+    fn test_changelog_descending_follows_commit_order() {
+        use crate::changelog::ChangelogQuery;
+
+        let (manager, _temp) = create_test_manager();
+        let a = manager.begin(None).unwrap();
+        let b = manager.begin(None).unwrap();
+        let c = manager.begin(None).unwrap();
+        for (tx, table) in [(a, "a"), (b, "b"), (c, "c")] {
+            manager.add_write(tx, TableWrite::new(table, 1, vec![])).unwrap();
+        }
+        manager.commit(c).unwrap();
+        manager.commit(a).unwrap();
+        manager.commit(b).unwrap();
+
+        let ids = |page: &[crate::changelog::ChangelogEntry]| -> Vec<u64> {
+            page.iter().map(|e| e.tx_id).collect()
+        };
+
+        // The latest commit is `b`, although `c` has the highest tx_id
+        let (page, cursor) = manager
+            .get_changelog_page(ChangelogQuery::new().descending().with_limit(2))
+            .unwrap();
+        assert_eq!(ids(&page), vec![b, a]);
+        let (page, _) = manager
+            .get_changelog_page(ChangelogQuery::new().descending().with_cursor(cursor.unwrap()))
+            .unwrap();
+        assert_eq!(ids(&page), vec![c]);
+    }
+
+    #[test]
+    fn test_shared_handle_across_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<TransactionManager>();
+
+        let (manager, _temp) = create_test_manager();
+        let manager = Arc::new(manager);
+
+        let handles: Vec<_> = ["left", "right"]
+            .into_iter()
+            .map(|table| {
+                let manager = Arc::clone(&manager);
+                std::thread::spawn(move || {
+                    let tx = manager.begin(None).unwrap();
+                    manager.add_write(tx, TableWrite::new(table, 1, vec![format!("{}_chunk", table)])).unwrap();
+                    manager.commit(tx).unwrap();
+                    tx
+                })
+            })
+            .collect();
+        let committed: Vec<TxId> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+
+        // Each thread's commit is visible through the shared manager
+        for tx in committed {
+            assert_eq!(manager.get_transaction(tx).unwrap().status, TransactionStatus::Committed);
+        }
+        assert_eq!(manager.active_count().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_epoch_conflict_window_skips_observed_epochs() {
+        let run = |window: ConflictWindow| {
+            let (mut manager, _temp) = create_test_manager();
+            manager.set_conflict_window(window);
+
+            // Written and committed in epoch 1
+            let old = manager.begin(None).unwrap();
+            manager.add_write(old, TableWrite::new("events", 1, vec!["batch1".to_string()])).unwrap();
+            manager.commit(old).unwrap();
+
+            // Begun in epoch 2, after observing that write
+            assert_eq!(manager.advance_epoch().unwrap(), 2);
+            let tx = manager.begin(None).unwrap();
+            assert_eq!(manager.get_transaction(tx).unwrap().epoch_id, 2);
+            manager.add_write(tx, TableWrite::new("events", 2, vec!["batch2".to_string()])).unwrap();
+            manager.commit(tx)
+        };
+
+        assert!(matches!(run(ConflictWindow::All), Err(TransactionError::WriteConflict(_))));
+        run(ConflictWindow::Epoch).unwrap();
+
+        assert_eq!("epoch".parse::<ConflictWindow>().unwrap(), ConflictWindow::Epoch);
+        assert_eq!("ALL".parse::<ConflictWindow>().unwrap(), ConflictWindow::All);
+        assert!(matches!("table".parse::<ConflictWindow>(), Err(TransactionError::InvalidConfig(_))));
+    }
+
+    #[test]
+    fn test_epoch_conflict_window_still_catches_same_epoch_writes() {
+        let (mut manager, _temp) = create_test_manager();
+        manager.set_conflict_window(ConflictWindow::Epoch);
+
+        let tx1 = manager.begin(None).unwrap();
+        let tx2 = manager.begin(None).unwrap();
+        manager.add_write(tx1, TableWrite::new("events", 1, vec!["a".to_string()])).unwrap();
+        manager.add_write(tx2, TableWrite::new("events", 2, vec!["b".to_string()])).unwrap();
+        manager.commit(tx1).unwrap();
+
+        assert!(matches!(manager.commit(tx2), Err(TransactionError::WriteConflict(_))));
+    }
+
+    #[test]
+    fn test_epoch_conflict_window_uses_commit_order() {
+        let run = |window: ConflictWindow| {
+            let (mut manager, _temp) = create_test_manager();
+            manager.set_conflict_window(window);
+
+            // Committed in this epoch before `tx` began
+            let seen = manager.begin(None).unwrap();
+            manager.add_write(seen, TableWrite::new("events", 1, vec!["a".to_string()])).unwrap();
+            manager.commit(seen).unwrap();
+
+            let tx = manager.begin(None).unwrap();
+            manager.add_write(tx, TableWrite::new("events", 2, vec!["b".to_string()])).unwrap();
+            manager.commit(tx)
+        };
+
+        assert!(matches!(run(ConflictWindow::All), Err(TransactionError::WriteConflict(_))));
+        run(ConflictWindow::Epoch).unwrap();
+    }
+
+    #[test]
+    fn test_epoch_conflict_window_catches_later_begun_commit() {
+        let (mut manager, _temp) = create_test_manager();
+        manager.set_conflict_window(ConflictWindow::Epoch);
+
+        // `late` begins after `early` but commits first
+        let early = manager.begin(None).unwrap();
+        let late = manager.begin(None).unwrap();
+        manager.add_write(early, TableWrite::new("events", 1, vec!["a".to_string()])).unwrap();
+        manager.add_write(late, TableWrite::new("events", 1, vec!["b".to_string()])).unwrap();
+        manager.commit(late).unwrap();
+
+        assert!(matches!(manager.commit(early), Err(TransactionError::WriteConflict(_))));
+        let committed = manager.log.read_transaction(late).unwrap();
+        assert_eq!(committed.commit_seq, Some(1));
+        assert_eq!(manager.get_transaction(early).unwrap().begin_seq, 0);
+    }
 }
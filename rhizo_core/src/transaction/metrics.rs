@@ -0,0 +1,92 @@
+//! Operation counters and timings for the transaction manager.
+//!
+//! Counters are updated with relaxed atomics on the hot path; `snapshot`
+//! returns a plain `TxMetrics` copy for reporting.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Snapshot of transaction manager metrics.
+///
+/// Durations are cumulative wall-clock nanoseconds across all calls.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TxMetrics {
+    /// Transactions begun
+    pub begins: u64,
+    /// Time spent in `begin`
+    pub begin_nanos: u64,
+    /// Transactions committed successfully
+    pub commits: u64,
+    /// Time spent in successful `commit` calls
+    pub commit_nanos: u64,
+    /// Commits rejected by a write or snapshot conflict
+    pub conflicts: u64,
+    /// Transactions aborted
+    pub aborts: u64,
+    /// Recovery runs (`recover` and `recover_and_apply`)
+    pub recoveries: u64,
+    /// Time spent in recovery
+    pub recover_nanos: u64,
+}
+
+impl TxMetrics {
+    /// Average successful commit latency in nanoseconds (0 if no commits).
+    pub fn avg_commit_nanos(&self) -> u64 {
+        self.commit_nanos.checked_div(self.commits).unwrap_or(0)
+    }
+}
+
+/// Atomic counters backing `TxMetrics`.
+#[derive(Debug, Default)]
+pub(crate) struct MetricsRecorder {
+    begins: AtomicU64,
+    begin_nanos: AtomicU64,
+    commits: AtomicU64,
+    commit_nanos: AtomicU64,
+    conflicts: AtomicU64,
+    aborts: AtomicU64,
+    recoveries: AtomicU64,
+    recover_nanos: AtomicU64,
+}
+
+impl MetricsRecorder {
+    pub(crate) fn record_begin(&self, elapsed: Duration) {
+        self.begins.fetch_add(1, Ordering::Relaxed);
+        self.begin_nanos.fetch_add(nanos(elapsed), Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_commit(&self, elapsed: Duration) {
+        self.commits.fetch_add(1, Ordering::Relaxed);
+        self.commit_nanos.fetch_add(nanos(elapsed), Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_conflict(&self) {
+        self.conflicts.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_abort(&self) {
+        self.aborts.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub(crate) fn record_recovery(&self, elapsed: Duration) {
+        self.recoveries.fetch_add(1, Ordering::Relaxed);
+        self.recover_nanos.fetch_add(nanos(elapsed), Ordering::Relaxed);
+    }
+
+    pub(crate) fn snapshot(&self) -> TxMetrics {
+        TxMetrics {
+            begins: self.begins.load(Ordering::Relaxed),
+            begin_nanos: self.begin_nanos.load(Ordering::Relaxed),
+            commits: self.commits.load(Ordering::Relaxed),
+            commit_nanos: self.commit_nanos.load(Ordering::Relaxed),
+            conflicts: self.conflicts.load(Ordering::Relaxed),
+            aborts: self.aborts.load(Ordering::Relaxed),
+            recoveries: self.recoveries.load(Ordering::Relaxed),
+            recover_nanos: self.recover_nanos.load(Ordering::Relaxed),
+        }
+    }
+}
+
+fn nanos(elapsed: Duration) -> u64 {
+    u64::try_from(elapsed.as_nanos()).unwrap_or(u64::MAX)
+}
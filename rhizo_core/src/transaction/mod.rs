@@ -15,18 +15,22 @@ mod log;
 mod conflict;
 mod manager;
 mod recovery;
+mod metrics;
 mod coordination_free;
 
 pub use types::{
-    TxId, EpochId, TransactionStatus, WriteGranularity,
+    TxId, EpochId, SavepointId, TransactionStatus, WriteGranularity,
     TableWrite, TransactionRecord, TransactionMode,
 };
 pub use epoch::{EpochConfig, EpochStatus, EpochMetadata};
 pub use error::TransactionError;
 pub use log::TransactionLog;
-pub use conflict::{Conflict, ConflictDetector, TableLevelConflictDetector};
+pub use conflict::{
+    Conflict, ConflictDetector, ConflictWindow, RowRangeConflictDetector, TableLevelConflictDetector,
+};
 pub use manager::TransactionManager;
-pub use recovery::{RecoveryReport, RecoveryManager};
+pub use recovery::{RecoveryReport, RecoveryManager, ReconcileReport};
+pub use metrics::TxMetrics;
 pub use coordination_free::{
     CoordinationFreeConfig, CoordinationFreeError, CoordinationFreeManager,
 };
@@ -85,6 +85,46 @@ impl RecoveryReport {
     }
 }
 
+/// Result of cross-checking the changelog against the catalog
+#[derive(Debug, Clone, Default)]
+pub struct ReconcileReport {
+    /// Committed transactions examined
+    pub transactions_checked: usize,
+
+    /// Catalog versions examined
+    pub versions_checked: usize,
+
+    /// Writes recorded by a committed transaction whose version is absent
+    /// from the catalog: (tx_id, table, version)
+    pub missing_in_catalog: Vec<(TxId, String, u64)>,
+
+    /// Writes whose recorded chunk hashes differ from the catalog's:
+    /// (tx_id, table, version)
+    pub chunk_mismatches: Vec<(TxId, String, u64)>,
+
+    /// Catalog versions not written by any committed transaction: (table, version)
+    pub missing_in_changelog: Vec<(String, u64)>,
+
+    /// Transactions appended to the log to cover `missing_in_changelog`
+    pub repaired: Vec<TxId>,
+}
+
+impl ReconcileReport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check if the changelog and catalog agree
+    ///
+    /// Versions that were repaired still count as discrepancies, since
+    /// the report describes the state found before repair.
+    pub fn is_consistent(&self) -> bool {
+        self.missing_in_catalog.is_empty()
+            && self.chunk_mismatches.is_empty()
+            && self.missing_in_changelog.is_empty()
+    }
+}
+
 /// Recovery state for a transaction
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum RecoveryDecision {
@@ -3,12 +3,17 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::parquet::ScalarValue;
+
 /// Unique transaction identifier (monotonically increasing)
 pub type TxId = u64;
 
 /// Unique epoch identifier
 pub type EpochId = u64;
 
+/// Savepoint identifier (unique per `TransactionManager`)
+pub type SavepointId = u64;
+
 /// Transaction status - designed for both single-node and distributed
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum TransactionStatus {
@@ -55,6 +60,9 @@ pub enum WriteGranularity {
         key_columns: Vec<String>,
         affected_keys: Vec<serde_json::Value>,
     },
+
+    /// A contiguous primary-key range, given by `TableWrite::key_range`
+    KeyRange,
 }
 
 /// Transaction mode - determines commit behavior
@@ -87,6 +95,10 @@ pub enum TransactionMode {
     /// Commits locally without distributed coordination, using
     /// vector clocks for causality and algebraic merge for convergence.
     CoordinationFree,
+
+    /// Reads only, against the versions pinned when it began.
+    /// Kept in memory; committing just releases the snapshot.
+    ReadOnly,
 }
 
 impl TransactionMode {
@@ -99,6 +111,11 @@ impl TransactionMode {
     pub fn is_coordinated(&self) -> bool {
         matches!(self, TransactionMode::Coordinated)
     }
+
+    /// Check if this is read-only mode
+    pub fn is_read_only(&self) -> bool {
+        matches!(self, TransactionMode::ReadOnly)
+    }
 }
 
 impl std::fmt::Display for TransactionMode {
@@ -106,6 +123,7 @@ impl std::fmt::Display for TransactionMode {
         match self {
             TransactionMode::Coordinated => write!(f, "Coordinated"),
             TransactionMode::CoordinationFree => write!(f, "CoordinationFree"),
+            TransactionMode::ReadOnly => write!(f, "ReadOnly"),
         }
     }
 }
@@ -128,6 +146,10 @@ pub struct TableWrite {
     /// Granularity of this write (for conflict detection)
     pub granularity: WriteGranularity,
 
+    /// Inclusive (min, max) primary keys written, for `KeyRange` granularity
+    #[serde(default)]
+    pub key_range: Option<(ScalarValue, ScalarValue)>,
+
     /// Branch this write targets (None = current transaction branch)
     pub branch: Option<String>,
 }
@@ -141,6 +163,7 @@ impl TableWrite {
             chunk_hashes,
             schema_hash: None,
             granularity: WriteGranularity::WholeTable,
+            key_range: None,
             branch: None,
         }
     }
@@ -162,6 +185,26 @@ impl TableWrite {
         self.granularity = granularity;
         self
     }
+
+    /// Limit the write to primary keys in `min..=max`
+    ///
+    /// Sets `KeyRange` granularity, so the write only conflicts with
+    /// writes to the same table whose ranges overlap (see
+    /// `RowRangeConflictDetector`).
+    pub fn with_key_range(mut self, min: ScalarValue, max: ScalarValue) -> Self {
+        self.granularity = WriteGranularity::KeyRange;
+        self.key_range = Some((min, max));
+        self
+    }
+}
+
+/// Write and read state captured by `TransactionManager::savepoint`
+#[derive(Debug, Clone)]
+pub(crate) struct Savepoint {
+    pub id: SavepointId,
+    /// Number of writes recorded when the savepoint was taken
+    pub writes_len: usize,
+    pub read_snapshot: HashMap<String, u64>,
 }
 
 /// Complete transaction record - the source of truth
@@ -181,6 +224,18 @@ pub struct TransactionRecord {
     /// Unix timestamp when committed (None if not yet committed)
     pub committed_at: Option<i64>,
 
+    // === Commit Order ===
+    /// Commit sequence number of the latest commit visible in the read
+    /// snapshot (0 = none)
+    #[serde(default)]
+    pub begin_seq: u64,
+
+    /// Position in commit order, assigned on commit (None if not yet
+    /// committed). Unlike `tx_id`, which is assigned at begin, this
+    /// follows the order in which commits were applied.
+    #[serde(default)]
+    pub commit_seq: Option<u64>,
+
     // === Read Set (Snapshot) ===
     /// Tables read and their versions at transaction start
     /// Used for conflict detection and debugging
@@ -203,6 +258,11 @@ pub struct TransactionRecord {
     #[serde(default)]
     pub mode: TransactionMode,
 
+    // === Savepoints ===
+    /// Savepoints taken while active, oldest first (in memory only)
+    #[serde(skip)]
+    pub(crate) savepoints: Vec<Savepoint>,
+
     // === Metadata ===
     /// User-provided metadata
     pub metadata: HashMap<String, String>,
@@ -236,11 +296,14 @@ impl TransactionRecord {
             epoch_id,
             started_at: now,
             committed_at: None,
+            begin_seq: 0,
+            commit_seq: None,
             read_snapshot: HashMap::new(),
             writes: Vec::new(),
             status: TransactionStatus::Active,
             branch,
             mode,
+            savepoints: Vec::new(),
             metadata: HashMap::new(),
             format_version: Self::CURRENT_FORMAT_VERSION,
             extensions: None,
@@ -252,6 +315,11 @@ impl TransactionRecord {
         self.mode.is_coordination_free()
     }
 
+    /// Check if this transaction is read-only
+    pub fn is_read_only(&self) -> bool {
+        self.mode.is_read_only()
+    }
+
     /// Check if transaction is still active
     pub fn is_active(&self) -> bool {
         matches!(self.status, TransactionStatus::Active)
@@ -1,9 +1,13 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::io::{Read, Write};
 use std::str::FromStr;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
 
 use pyo3::prelude::*;
-use pyo3::exceptions::{PyIOError, PyValueError, PyRuntimeError};
+use pyo3::IntoPyObjectExt;
+use pyo3::types::{PyBool, PyInt, PyList, PyTuple};
+use pyo3::exceptions::{PyIOError, PyIndexError, PyValueError, PyRuntimeError};
 
 // =============================================================================
 // Error Message Sanitization
@@ -124,30 +128,35 @@ fn sanitize_io_error(e: &std::io::Error) -> String {
     }
 }
 use rhizo_core::{
-    ChunkStore, ChunkStoreError,
-    FileCatalog, CatalogError, TableVersion,
+    ChunkStore, ChunkStoreError, chunk_store::DEFAULT_SHORT_HASH_LEN,
+    FileCatalog, CatalogError, ChainReuse, SchemaCompat, TableVersion, transfer_plan,
     Branch, BranchDiff, BranchError, BranchManager,
-    MergeAnalysis, MergeAnalyzer, MergeOutcome,
-    TransactionManager, TransactionRecord, TransactionError,
-    TableWrite, RecoveryReport,
-    ChangelogEntry, TableChange, ChangelogQuery,
-    MerkleTree, MerkleNode, DataChunk, MerkleDiff, MerkleConfig, MerkleError,
-    build_tree, diff_trees, verify_tree,
-    ParquetEncoder, ParquetDecoder, ParquetCompression, ParquetError,
-    FilterOp, ScalarValue, PredicateFilter,
+    MergeAnalysis, MergeAnalyzer, MergeOutcome, Resolution,
+    TransactionManager, TransactionRecord, TransactionError, ConflictWindow,
+    TableWrite, RecoveryReport, ReconcileReport,
+    ChangelogEntry, TableChange, ChangelogQuery, ChangelogCursor,
+    MerkleTree, MerkleNode, DataChunk, MerkleDiff, MerkleConfig, MerkleError, MerkleProof, Chunking,
+    build_tree, diff_trees, read_range, rebuild_tree, verify_proof, verify_tree,
+    ParquetEncoder, ParquetDecoder, ParquetCompression, ParquetError, ParquetStreamWriter, ParquetWriterConfig,
+    AggregateSpec, FilterExplain, FilterOp, FilterPlan, ScalarValue, PredicateExpr, PredicateFilter, RepairReport,
+    batches_equivalent_with_tolerance,
+    PreviewReport, Repository, RepositoryError, Table, TableError,
     // Algebraic types
     OpType, AlgebraicValue, AlgebraicMerger, MergeResult,
     TableAlgebraicSchema, AlgebraicSchemaRegistry,
     // Distributed types
     VectorClock, NodeId, CausalOrder,
     AlgebraicOperation, AlgebraicTransaction, VersionedUpdate,
-    LocalCommitProtocol,
+    LocalCommitProtocol, RepairEvent,
     // Simulation types
-    SimulatedCluster, SimulationConfig, SimulationStats, NetworkCondition,
+    SimulatedCluster, SimulationConfig, SimulationStats, NetworkCondition, DeliveryPriority,
+    ClusterSnapshot,
 };
 
 // Phase 4: Arrow pyarrow for zero-copy FFI
 use arrow_pyarrow::{ToPyArrow, FromPyArrow};
+use arrow::array::Array;
+use arrow::datatypes::{DataType, Schema};
 use arrow::record_batch::RecordBatch;
 
 /// Convert ChunkStoreError to appropriate Python exception
@@ -158,6 +167,14 @@ fn chunk_err_to_py(e: ChunkStoreError) -> PyErr {
         ChunkStoreError::HashMismatch { expected, actual } => {
             PyValueError::new_err(format!("Hash mismatch: expected {}, got {}", expected, actual))
         }
+        ChunkStoreError::AmbiguousPrefix { prefix, matches } => {
+            PyValueError::new_err(format!("Ambiguous hash prefix {}: matches {} chunks", prefix, matches))
+        }
+        ChunkStoreError::InvalidMeta { hash, reason } => PyValueError::new_err(format!(
+            "Invalid metadata for chunk {}: {}",
+            hash,
+            sanitize_error_message(&reason)
+        )),
         ChunkStoreError::Io(e) => PyIOError::new_err(sanitize_io_error(&e)),
     }
 }
@@ -177,6 +194,35 @@ fn catalog_err_to_py(e: CatalogError) -> PyErr {
         }
         CatalogError::Io(e) => PyIOError::new_err(sanitize_io_error(&e)),
         CatalogError::Json(e) => PyValueError::new_err(format!("JSON error: {}", sanitize_error_message(&e.to_string()))),
+        CatalogError::ChunkStore(msg) => {
+            PyIOError::new_err(format!("Chunk store error: {}", sanitize_error_message(&msg)))
+        }
+        CatalogError::DuplicateChunk { table, version, hash } => PyValueError::new_err(format!(
+            "Duplicate chunk hash in {} v{}: {}",
+            table, version, hash
+        )),
+        CatalogError::EmptyVersion(t, v) => {
+            PyValueError::new_err(format!("Version has no chunks: {} v{}", t, v))
+        }
+        CatalogError::TagExists(tag) => PyValueError::new_err(format!("Tag already exists: {}", tag)),
+        CatalogError::TagNotFound(tag) => PyIOError::new_err(format!("Tag not found: {}", tag)),
+        CatalogError::VersionProtected { table, version, reason } => {
+            PyValueError::new_err(format!("Cannot delete {} v{}: {}", table, version, reason))
+        }
+        CatalogError::NoVersionAsOf { table, timestamp } => {
+            PyIOError::new_err(format!("No version of {} exists as of {}", table, timestamp))
+        }
+        CatalogError::Schema(msg) => {
+            PyValueError::new_err(format!("Schema error: {}", sanitize_error_message(&msg)))
+        }
+        CatalogError::SchemaNotTracked { table, version } => {
+            PyIOError::new_err(format!("No stored schema for {} v{}", table, version))
+        }
+        CatalogError::BreakingSchemaChange { table, changes } => PyValueError::new_err(format!(
+            "Breaking schema change for {}: {}",
+            table,
+            changes.join("; ")
+        )),
     }
 }
 
@@ -207,6 +253,27 @@ fn branch_err_to_py(e: BranchError) -> PyErr {
         BranchError::AlgebraicConflict(tables) => {
             PyValueError::new_err(format!("Algebraic merge conflict on tables: {:?}", tables))
         }
+        BranchError::TableMergeConflict { table, reason } => {
+            PyValueError::new_err(format!("Cannot merge table '{}': {}", table, reason))
+        }
+        BranchError::ColumnMergeConflict { column, reason } => {
+            PyValueError::new_err(format!("Cannot merge column '{}': {}", column, reason))
+        }
+        BranchError::MergeAborted { table } => {
+            PyValueError::new_err(format!("Merge aborted by resolver at table '{}'", table))
+        }
+        BranchError::InvalidHead { table, version } => {
+            PyValueError::new_err(format!("Invalid head for table '{}': version {}", table, version))
+        }
+        BranchError::CatalogError(msg) => {
+            PyIOError::new_err(format!("Catalog error: {}", sanitize_error_message(&msg)))
+        }
+        BranchError::ChunkStore(msg) => {
+            PyIOError::new_err(format!("Chunk store error: {}", sanitize_error_message(&msg)))
+        }
+        BranchError::Parquet(msg) => {
+            PyIOError::new_err(format!("Parquet error: {}", sanitize_error_message(&msg)))
+        }
         BranchError::Io(e) => PyIOError::new_err(sanitize_io_error(&e)),
         BranchError::Json(e) => PyValueError::new_err(format!("JSON error: {}", sanitize_error_message(&e.to_string()))),
     }
@@ -222,6 +289,17 @@ fn merkle_err_to_py(e: MerkleError) -> PyErr {
         MerkleError::InvalidChunkSize(size) => {
             PyValueError::new_err(format!("Invalid chunk size: must be > 0, got {}", size))
         }
+        MerkleError::InvalidBranchingFactor(factor) => {
+            PyValueError::new_err(format!("Invalid branching factor: must be >= 2, got {}", factor))
+        }
+        MerkleError::InvalidChunkBounds { min, avg, max } => PyValueError::new_err(format!(
+            "Invalid content-defined chunk bounds: need 0 < min <= avg <= max, got {}/{}/{}",
+            min, avg, max
+        )),
+        MerkleError::ChunkIndexOutOfRange { index, count } => PyIndexError::new_err(format!(
+            "Chunk index {} out of range for tree with {} chunks",
+            index, count
+        )),
         MerkleError::EmptyData => {
             PyValueError::new_err("Cannot build Merkle tree from empty data")
         }
@@ -264,6 +342,12 @@ fn parquet_err_to_py(e: ParquetError) -> PyErr {
         ParquetError::RowCountOverflow => {
             PyValueError::new_err("Row count overflow: total rows exceed maximum")
         }
+        ParquetError::IncompatibleCast { column, from, to } => {
+            PyValueError::new_err(format!("Cannot cast column '{}' from {} to {}", column, from, to))
+        }
+        ParquetError::RowIndexOutOfRange { index, num_rows } => {
+            PyIndexError::new_err(format!("Row index {} out of range for {} rows", index, num_rows))
+        }
     }
 }
 
@@ -287,15 +371,50 @@ impl PyParquetEncoder {
     /// Args:
     ///     compression: Compression type ("zstd", "snappy", "gzip", "lz4", "none")
     ///                  Defaults to "zstd" for best compression/speed balance.
+    ///     max_row_group_size: Maximum rows per row group. Smaller groups let
+    ///                         filtered reads skip more data.
+    ///     enable_dictionary: Whether to dictionary-encode columns (default True)
+    ///     write_statistics: Whether to write column statistics (default True)
+    ///     data_page_size: Target data page size in bytes (default: parquet default)
+    ///     preserve_field_metadata: Whether to keep Arrow field metadata (default True)
+    ///
+    /// Raises:
+    ///     ValueError: If compression is unknown or max_row_group_size is 0
     #[new]
-    #[pyo3(signature = (compression = None))]
-    fn new(compression: Option<&str>) -> PyResult<Self> {
+    #[pyo3(signature = (
+        compression = None,
+        max_row_group_size = None,
+        enable_dictionary = true,
+        write_statistics = true,
+        data_page_size = None,
+        preserve_field_metadata = true
+    ))]
+    fn new(
+        compression: Option<&str>,
+        max_row_group_size: Option<usize>,
+        enable_dictionary: bool,
+        write_statistics: bool,
+        data_page_size: Option<usize>,
+        preserve_field_metadata: bool,
+    ) -> PyResult<Self> {
         let compression = match compression {
             Some(c) => ParquetCompression::from_str(c).map_err(parquet_err_to_py)?,
             None => ParquetCompression::Zstd,
         };
+        if max_row_group_size == Some(0) {
+            return Err(PyValueError::new_err("max_row_group_size must be greater than 0"));
+        }
+        let defaults = ParquetWriterConfig::default();
+        let config = ParquetWriterConfig {
+            max_row_group_size: max_row_group_size.unwrap_or(defaults.max_row_group_size),
+            enable_dictionary,
+            write_statistics,
+            data_page_size,
+        };
         Ok(Self {
-            inner: ParquetEncoder::with_compression(compression),
+            inner: ParquetEncoder::with_compression(compression)
+                .with_properties(config)
+                .with_preserve_field_metadata(preserve_field_metadata),
         })
     }
 
@@ -334,6 +453,275 @@ impl PyParquetEncoder {
     }
 }
 
+/// Writes to a Python file-like object through its `write` method.
+struct PyFileSink(Py<PyAny>);
+
+impl Write for PyFileSink {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        Python::attach(|py| {
+            let written = self
+                .0
+                .call_method1(py, "write", (pyo3::types::PyBytes::new(py, buf),))
+                .map_err(|e| std::io::Error::other(e.to_string()))?;
+            // Raw streams may report a short write; buffered ones return None
+            Ok(written.extract::<Option<usize>>(py).ok().flatten().unwrap_or(buf.len()))
+        })
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Python::attach(|py| {
+            if self.0.bind(py).hasattr("flush").unwrap_or(false) {
+                self.0
+                    .call_method0(py, "flush")
+                    .map_err(|e| std::io::Error::other(e.to_string()))?;
+            }
+            Ok(())
+        })
+    }
+}
+
+/// Reads from a Python file-like object through its `read` method.
+struct PyFileSource(Py<PyAny>);
+
+impl Read for PyFileSource {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        Python::attach(|py| {
+            let chunk = self
+                .0
+                .call_method1(py, "read", (buf.len(),))
+                .map_err(|e| std::io::Error::other(e.to_string()))?;
+            let bytes: &[u8] = chunk
+                .extract(py)
+                .map_err(|_| std::io::Error::other("read() must return bytes"))?;
+            if bytes.len() > buf.len() {
+                return Err(std::io::Error::other("read() returned more bytes than requested"));
+            }
+            buf[..bytes.len()].copy_from_slice(bytes);
+            Ok(bytes.len())
+        })
+    }
+}
+
+type BoxedStreamWriter = ParquetStreamWriter<Box<dyn Write + Send>>;
+
+type BoxedBatchIter = Box<dyn Iterator<Item = Result<RecordBatch, ParquetError>> + Send>;
+
+/// Iterator over the batches of a Parquet file, returned by
+/// `PyParquetDecoder.decode_iter`.
+#[pyclass]
+struct PyParquetBatchIterator {
+    // The record batch reader is Send but not Sync, which pyclasses require
+    inner: Mutex<BoxedBatchIter>,
+}
+
+#[pymethods]
+impl PyParquetBatchIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyAny>>> {
+        let next = self
+            .inner
+            .lock()
+            .map_err(|_| PyRuntimeError::new_err("Batch iterator lock poisoned"))?
+            .next();
+        match next {
+            Some(batch) => {
+                let batch = batch.map_err(parquet_err_to_py)?;
+                batch
+                    .to_pyarrow(py)
+                    .map(Some)
+                    .map_err(|e| PyValueError::new_err(sanitize_error_message(&e.to_string())))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+/// Streaming Parquet writer that appends one row group per batch.
+///
+/// Writes go straight to the file or file object, so the full output is
+/// never held in memory.
+#[pyclass]
+struct PyParquetStreamWriter {
+    // The Arrow writer is Send but not Sync, which pyclasses require
+    inner: Mutex<Option<BoxedStreamWriter>>,
+}
+
+#[pymethods]
+impl PyParquetStreamWriter {
+    /// Create a stream writer.
+    ///
+    /// Args:
+    ///     path_or_fileobj: File path, or a binary file object with `write`
+    ///     compression: Compression type ("zstd", "snappy", "gzip", "lz4", "none")
+    ///                  Defaults to "zstd".
+    #[new]
+    #[pyo3(signature = (path_or_fileobj, compression = None))]
+    fn new(path_or_fileobj: Bound<'_, PyAny>, compression: Option<&str>) -> PyResult<Self> {
+        let compression = match compression {
+            Some(c) => ParquetCompression::from_str(c).map_err(parquet_err_to_py)?,
+            None => ParquetCompression::Zstd,
+        };
+        let sink: Box<dyn Write + Send> = match path_or_fileobj.extract::<std::path::PathBuf>() {
+            Ok(path) => Box::new(
+                std::fs::File::create(&path).map_err(|e| PyIOError::new_err(sanitize_io_error(&e)))?,
+            ),
+            Err(_) if path_or_fileobj.hasattr("write")? => Box::new(PyFileSink(path_or_fileobj.unbind())),
+            Err(_) => {
+                return Err(PyValueError::new_err("Expected a path or a file object with write()"));
+            }
+        };
+        Ok(Self {
+            inner: Mutex::new(Some(ParquetEncoder::with_compression(compression).stream_to(sink))),
+        })
+    }
+
+    /// Append a batch as a new row group.
+    ///
+    /// Args:
+    ///     batch: PyArrow RecordBatch; must match the first batch's schema
+    fn write_batch(&self, batch: Bound<'_, PyAny>) -> PyResult<()> {
+        let rust_batch = RecordBatch::from_pyarrow_bound(&batch)
+            .map_err(|e| PyValueError::new_err(format!("Invalid RecordBatch: {}", sanitize_error_message(&e.to_string()))))?;
+        self.lock()?
+            .as_mut()
+            .ok_or_else(|| PyValueError::new_err("Stream writer is closed"))?
+            .write_batch(&rust_batch)
+            .map_err(parquet_err_to_py)
+    }
+
+    /// Write the Parquet footer and close the file.
+    ///
+    /// File objects passed in are flushed but left open.
+    ///
+    /// Raises:
+    ///     ValueError: If already closed or no rows were written
+    fn close(&self) -> PyResult<()> {
+        let stream = self
+            .lock()?
+            .take()
+            .ok_or_else(|| PyValueError::new_err("Stream writer is closed"))?;
+        let mut sink = stream.finish().map_err(parquet_err_to_py)?;
+        sink.flush().map_err(|e| PyIOError::new_err(sanitize_io_error(&e)))
+    }
+}
+
+impl PyParquetStreamWriter {
+    fn lock(&self) -> PyResult<MutexGuard<'_, Option<BoxedStreamWriter>>> {
+        self.inner
+            .lock()
+            .map_err(|_| PyRuntimeError::new_err("Stream writer lock poisoned"))
+    }
+}
+
+/// Structured explanation of a filtered decode (for query debugging).
+#[pyclass]
+#[derive(Clone)]
+struct PyFilterExplain {
+    #[pyo3(get)]
+    total_row_groups: usize,
+    #[pyo3(get)]
+    pruned_row_groups: usize,
+    #[pyo3(get)]
+    rows_scanned: usize,
+    #[pyo3(get)]
+    rows_returned: usize,
+    #[pyo3(get)]
+    filter_columns: Vec<String>,
+}
+
+impl From<FilterExplain> for PyFilterExplain {
+    fn from(e: FilterExplain) -> Self {
+        Self {
+            total_row_groups: e.total_row_groups,
+            pruned_row_groups: e.pruned_row_groups,
+            rows_scanned: e.rows_scanned,
+            rows_returned: e.rows_returned,
+            filter_columns: e.filter_columns,
+        }
+    }
+}
+
+#[pymethods]
+impl PyFilterExplain {
+    fn __repr__(&self) -> String {
+        format!(
+            "FilterExplain(pruned={}/{}, scanned={}, returned={}, columns={:?})",
+            self.pruned_row_groups,
+            self.total_row_groups,
+            self.rows_scanned,
+            self.rows_returned,
+            self.filter_columns
+        )
+    }
+}
+
+/// Row groups a filtered decode would read, from footer statistics alone.
+#[pyclass]
+#[derive(Clone)]
+struct PyFilterPlan {
+    #[pyo3(get)]
+    row_groups_to_scan: Vec<usize>,
+    #[pyo3(get)]
+    estimated_rows: i64,
+}
+
+impl From<FilterPlan> for PyFilterPlan {
+    fn from(p: FilterPlan) -> Self {
+        Self {
+            row_groups_to_scan: p.row_groups_to_scan,
+            estimated_rows: p.estimated_rows,
+        }
+    }
+}
+
+#[pymethods]
+impl PyFilterPlan {
+    fn __repr__(&self) -> String {
+        format!(
+            "FilterPlan(row_groups={:?}, estimated_rows={})",
+            self.row_groups_to_scan, self.estimated_rows
+        )
+    }
+}
+
+/// Outcome of a Parquet repair.
+#[pyclass]
+#[derive(Clone)]
+struct PyRepairReport {
+    #[pyo3(get)]
+    total_row_groups: usize,
+    #[pyo3(get)]
+    dropped_row_groups: Vec<usize>,
+    #[pyo3(get)]
+    rows_kept: usize,
+    #[pyo3(get)]
+    rows_lost: usize,
+}
+
+impl From<RepairReport> for PyRepairReport {
+    fn from(r: RepairReport) -> Self {
+        Self {
+            total_row_groups: r.total_row_groups,
+            dropped_row_groups: r.dropped_row_groups,
+            rows_kept: r.rows_kept,
+            rows_lost: r.rows_lost,
+        }
+    }
+}
+
+#[pymethods]
+impl PyRepairReport {
+    fn __repr__(&self) -> String {
+        format!(
+            "RepairReport(dropped={:?}/{}, kept={}, lost={})",
+            self.dropped_row_groups, self.total_row_groups, self.rows_kept, self.rows_lost
+        )
+    }
+}
+
 /// High-performance Parquet decoder using Rust's parquet crate.
 ///
 /// Provides zero-copy Arrow data transfer to Python via Arrow's pyarrow FFI,
@@ -365,6 +753,28 @@ impl PyParquetDecoder {
         batch.to_pyarrow(py).map_err(|e| PyValueError::new_err(sanitize_error_message(&e.to_string())))
     }
 
+    /// Decode Parquet bytes lazily, one batch at a time.
+    ///
+    /// Batches are not concatenated, so decoded data in memory is bounded
+    /// by the decoder's batch size rather than the file size. The encoded
+    /// bytes are copied once and kept until the iterator is dropped.
+    ///
+    /// Args:
+    ///     data: Parquet file bytes
+    ///
+    /// Returns:
+    ///     Iterator yielding PyArrow RecordBatches
+    ///
+    /// Example:
+    ///     >>> for batch in decoder.decode_iter(data):
+    ///     ...     process(batch)
+    fn decode_iter(&self, data: &[u8]) -> PyResult<PyParquetBatchIterator> {
+        let iter = self.inner.decode_stream(data.to_vec()).map_err(parquet_err_to_py)?;
+        Ok(PyParquetBatchIterator {
+            inner: Mutex::new(Box::new(iter)),
+        })
+    }
+
     /// Decode multiple Parquet chunks in parallel.
     ///
     /// Uses Rayon for parallel decoding, significantly faster than
@@ -448,6 +858,128 @@ impl PyParquetDecoder {
         batch.to_pyarrow(py).map_err(|e| PyValueError::new_err(sanitize_error_message(&e.to_string())))
     }
 
+    /// Decode and cast selected columns to target types.
+    ///
+    /// Float to integer casts truncate toward zero; values that do not fit
+    /// the target type raise an error rather than becoming null.
+    ///
+    /// Args:
+    ///     data: Parquet file bytes
+    ///     casts: List of (column_name, type) pairs, where type is an Arrow
+    ///         type name such as "Int64", "Float64" or "Utf8"
+    ///
+    /// Returns:
+    ///     PyArrow RecordBatch with the listed columns cast
+    ///
+    /// Raises:
+    ///     ValueError: If a type name is unknown, a column is missing, or a
+    ///         cast is not possible
+    fn decode_cast<'py>(
+        &self,
+        py: Python<'py>,
+        data: &[u8],
+        casts: Vec<(String, String)>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let parsed: Vec<(&str, DataType)> = casts
+            .iter()
+            .map(|(name, type_name)| {
+                DataType::from_str(type_name)
+                    .map(|dt| (name.as_str(), dt))
+                    .map_err(|_| PyValueError::new_err(format!("Unknown Arrow type: '{}'", type_name)))
+            })
+            .collect::<PyResult<_>>()?;
+        let batch = self
+            .inner
+            .decode_cast(data, &parsed)
+            .map_err(parquet_err_to_py)?;
+        batch.to_pyarrow(py).map_err(|e| PyValueError::new_err(sanitize_error_message(&e.to_string())))
+    }
+
+    /// Decode with every top-level field marked nullable.
+    ///
+    /// Column data is unchanged; use this before concatenating batches
+    /// from sources that disagree on nullability.
+    ///
+    /// Args:
+    ///     data: Parquet file bytes
+    ///
+    /// Returns:
+    ///     PyArrow RecordBatch with a fully nullable schema
+    fn decode_all_nullable<'py>(&self, py: Python<'py>, data: &[u8]) -> PyResult<Bound<'py, PyAny>> {
+        let batch = self
+            .inner
+            .decode_all_nullable(data)
+            .map_err(parquet_err_to_py)?;
+        batch.to_pyarrow(py).map_err(|e| PyValueError::new_err(sanitize_error_message(&e.to_string())))
+    }
+
+    /// Decode only the rows at the given 0-based indices.
+    ///
+    /// Indices are sorted and deduplicated, so rows are returned in
+    /// ascending index order.
+    ///
+    /// Args:
+    ///     data: Parquet file bytes
+    ///     indices: Row indices to decode
+    ///
+    /// Returns:
+    ///     PyArrow RecordBatch with the selected rows
+    ///
+    /// Raises:
+    ///     IndexError: If an index is past the end of the file
+    ///     ValueError: If indices is empty or decoding fails
+    fn decode_rows<'py>(
+        &self,
+        py: Python<'py>,
+        data: &[u8],
+        indices: Vec<usize>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let batch = self
+            .inner
+            .decode_rows(data, &indices)
+            .map_err(parquet_err_to_py)?;
+        batch.to_pyarrow(py).map_err(|e| PyValueError::new_err(sanitize_error_message(&e.to_string())))
+    }
+
+    /// Salvage a file with corrupt row groups.
+    ///
+    /// Decodes each row group on its own, drops those that fail, and
+    /// re-encodes the survivors with default encoder settings.
+    ///
+    /// Args:
+    ///     data: Parquet file bytes (the footer must be readable)
+    ///
+    /// Returns:
+    ///     Tuple of (repaired file bytes, PyRepairReport)
+    ///
+    /// Raises:
+    ///     ValueError: If the footer is unreadable or no row group survives
+    fn repair(&self, data: &[u8]) -> PyResult<(Vec<u8>, PyRepairReport)> {
+        self.inner
+            .repair(data)
+            .map(|(bytes, report)| (bytes, report.into()))
+            .map_err(parquet_err_to_py)
+    }
+
+    /// Check whether two Parquet files decode to the same rows.
+    ///
+    /// Schemas must match, nulls must be in the same positions, and float
+    /// columns may differ by up to float_tolerance.
+    ///
+    /// Args:
+    ///     data_a: First Parquet file bytes
+    ///     data_b: Second Parquet file bytes
+    ///     float_tolerance: Absolute tolerance for float values
+    ///
+    /// Returns:
+    ///     True if the files are row-equivalent
+    #[pyo3(signature = (data_a, data_b, float_tolerance=1e-9))]
+    fn compare(&self, data_a: &[u8], data_b: &[u8], float_tolerance: f64) -> PyResult<bool> {
+        let a = self.inner.decode(data_a).map_err(parquet_err_to_py)?;
+        let b = self.inner.decode(data_b).map_err(parquet_err_to_py)?;
+        batches_equivalent_with_tolerance(&a, &b, float_tolerance).map_err(parquet_err_to_py)
+    }
+
     /// Decode with predicate pushdown (row-level filtering).
     ///
     /// This method applies filter predicates during decoding, reducing the
@@ -495,37 +1027,296 @@ impl PyParquetDecoder {
         batch.to_pyarrow(py).map_err(|e| PyValueError::new_err(sanitize_error_message(&e.to_string())))
     }
 
-    /// Get row-group pruning statistics for a filtered decode.
+    /// Decode with a predicate expression (AND / OR groups).
     ///
-    /// This is useful for debugging and understanding pruning effectiveness.
-    /// Row-group pruning uses min/max statistics to skip entire row groups
-    /// that cannot contain matching rows, before doing any decoding.
+    /// Row groups are skipped only when their statistics rule out the
+    /// whole expression.
     ///
     /// Args:
     ///     data: Parquet file bytes
-    ///     filters: List of PyPredicateFilter objects
+    ///     expr: PyPredicateExpr to apply
+    ///     column_indices: Optional list of column indices to project
     ///
     /// Returns:
-    ///     Tuple of (total_row_groups, pruned_row_groups, kept_row_groups)
-    ///
-    /// Example:
-    ///     >>> decoder = PyParquetDecoder()
-    ///     >>> filter = PyPredicateFilter("id", "gt", 9000)
-    ///     >>> total, pruned, kept = decoder.get_pruning_stats(data, [filter])
-    ///     >>> print(f"Pruned {pruned}/{total} row groups ({100*pruned/total:.1f}%)")
-    fn get_pruning_stats(
+    ///     PyArrow RecordBatch with the expression applied
+    #[pyo3(signature = (data, expr, column_indices=None))]
+    fn decode_with_expr<'py>(
         &self,
+        py: Python<'py>,
         data: &[u8],
-        filters: Vec<PyPredicateFilter>,
-    ) -> PyResult<(usize, usize, usize)> {
-        let rust_filters: Vec<PredicateFilter> = filters
-            .into_iter()
-            .map(|f| f.into_inner())
-            .collect();
-
-        self.inner
-            .get_pruning_stats(data, &rust_filters)
-            .map_err(parquet_err_to_py)
+        expr: &PyPredicateExpr,
+        column_indices: Option<Vec<usize>>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        let batch = self
+            .inner
+            .decode_with_expr(data, &expr.inner, column_indices.as_deref())
+            .map_err(parquet_err_to_py)?;
+        batch.to_pyarrow(py).map_err(|e| PyValueError::new_err(sanitize_error_message(&e.to_string())))
+    }
+
+    /// Decode all rows along with a mask of the rows that match the filters.
+    ///
+    /// No rows are dropped, so positions line up with the file. Rows whose
+    /// filter column is null do not match.
+    ///
+    /// Args:
+    ///     data: Parquet file bytes
+    ///     filters: List of PyPredicateFilter objects
+    ///     column_indices: Optional list of column indices to project
+    ///
+    /// Returns:
+    ///     Tuple of (PyArrow RecordBatch with every row, PyArrow BooleanArray mask)
+    #[pyo3(signature = (data, filters, column_indices=None))]
+    fn decode_with_filter_masked<'py>(
+        &self,
+        py: Python<'py>,
+        data: &[u8],
+        filters: Vec<PyPredicateFilter>,
+        column_indices: Option<Vec<usize>>,
+    ) -> PyResult<(Bound<'py, PyAny>, Bound<'py, PyAny>)> {
+        let rust_filters: Vec<PredicateFilter> = filters
+            .into_iter()
+            .map(|f| f.into_inner())
+            .collect();
+
+        let (batch, mask) = self
+            .inner
+            .decode_with_filter_masked(data, &rust_filters, column_indices.as_deref())
+            .map_err(parquet_err_to_py)?;
+        let to_py_err = |e: PyErr| PyValueError::new_err(sanitize_error_message(&e.to_string()));
+        Ok((
+            batch.to_pyarrow(py).map_err(to_py_err)?,
+            mask.to_data().to_pyarrow(py).map_err(to_py_err)?,
+        ))
+    }
+
+    /// Get row-group pruning statistics for a filtered decode.
+    ///
+    /// This is useful for debugging and understanding pruning effectiveness.
+    /// Row-group pruning uses min/max statistics to skip entire row groups
+    /// that cannot contain matching rows, before doing any decoding.
+    ///
+    /// Args:
+    ///     data: Parquet file bytes
+    ///     filters: List of PyPredicateFilter objects
+    ///
+    /// Returns:
+    ///     Tuple of (total_row_groups, pruned_row_groups, kept_row_groups)
+    ///
+    /// Example:
+    ///     >>> decoder = PyParquetDecoder()
+    ///     >>> filter = PyPredicateFilter("id", "gt", 9000)
+    ///     >>> total, pruned, kept = decoder.get_pruning_stats(data, [filter])
+    ///     >>> print(f"Pruned {pruned}/{total} row groups ({100*pruned/total:.1f}%)")
+    fn get_pruning_stats(
+        &self,
+        data: &[u8],
+        filters: Vec<PyPredicateFilter>,
+    ) -> PyResult<(usize, usize, usize)> {
+        let rust_filters: Vec<PredicateFilter> = filters
+            .into_iter()
+            .map(|f| f.into_inner())
+            .collect();
+
+        self.inner
+            .get_pruning_stats(data, &rust_filters)
+            .map_err(parquet_err_to_py)
+    }
+
+    /// Plan a filtered decode using only footer statistics.
+    ///
+    /// No data is decoded, so this is cheaper than `explain_filter` and
+    /// suited to cost estimation before running a query.
+    ///
+    /// Args:
+    ///     data: Parquet file bytes
+    ///     filters: List of PyPredicateFilter objects
+    ///
+    /// Returns:
+    ///     PyFilterPlan with the surviving row group indices and their row count
+    ///
+    /// Example:
+    ///     >>> plan = decoder.plan_filter(data, [PyPredicateFilter("id", "gt", 9000)])
+    ///     >>> print(plan.row_groups_to_scan, plan.estimated_rows)
+    fn plan_filter(
+        &self,
+        data: &[u8],
+        filters: Vec<PyPredicateFilter>,
+    ) -> PyResult<PyFilterPlan> {
+        let rust_filters: Vec<PredicateFilter> = filters
+            .into_iter()
+            .map(|f| f.into_inner())
+            .collect();
+
+        self.inner
+            .plan_filter(data, &rust_filters)
+            .map(|p| p.into())
+            .map_err(parquet_err_to_py)
+    }
+
+    /// Explain a filtered decode.
+    ///
+    /// Reports row-group pruning (as in `get_pruning_stats`) together with
+    /// how many rows were scanned and returned by the actual decode.
+    ///
+    /// Args:
+    ///     data: Parquet file bytes
+    ///     filters: List of PyPredicateFilter objects
+    ///
+    /// Returns:
+    ///     PyFilterExplain with row group and row counts
+    ///
+    /// Example:
+    ///     >>> decoder = PyParquetDecoder()
+    ///     >>> explain = decoder.explain_filter(data, [PyPredicateFilter("id", "gt", 9000)])
+    ///     >>> print(explain.rows_scanned, explain.rows_returned)
+    fn explain_filter(
+        &self,
+        data: &[u8],
+        filters: Vec<PyPredicateFilter>,
+    ) -> PyResult<PyFilterExplain> {
+        let rust_filters: Vec<PredicateFilter> = filters
+            .into_iter()
+            .map(|f| f.into_inner())
+            .collect();
+
+        self.inner
+            .explain_filter(data, &rust_filters)
+            .map(|e| e.into())
+            .map_err(parquet_err_to_py)
+    }
+
+    /// Count rows matching the filters across multiple chunks in parallel.
+    ///
+    /// Equivalent to `COUNT(*) WHERE ...` over a table stored as many chunks.
+    /// Empty chunks and chunks with no matching rows contribute 0.
+    ///
+    /// Args:
+    ///     chunks: List of Parquet file bytes
+    ///     filters: List of PyPredicateFilter objects
+    ///
+    /// Returns:
+    ///     Total number of matching rows across all chunks
+    ///
+    /// Example:
+    ///     >>> decoder = PyParquetDecoder()
+    ///     >>> filter = PyPredicateFilter("age", "gt", 50)
+    ///     >>> count = decoder.count_batch_with_filter(chunks, [filter])
+    fn count_batch_with_filter(
+        &self,
+        chunks: Vec<Vec<u8>>,
+        filters: Vec<PyPredicateFilter>,
+    ) -> PyResult<usize> {
+        let rust_filters: Vec<PredicateFilter> = filters
+            .into_iter()
+            .map(|f| f.into_inner())
+            .collect();
+
+        self.inner
+            .count_batch_with_filter(&chunks, &rust_filters)
+            .map_err(parquet_err_to_py)
+    }
+
+    /// Compute the q-th quantile of a numeric column over matching rows.
+    ///
+    /// Exact: matching values are sorted and linearly interpolated between
+    /// the two nearest ranks (NumPy's default). Nulls and NaNs are ignored.
+    ///
+    /// Args:
+    ///     data: Parquet file bytes
+    ///     column: Name of a numeric column
+    ///     q: Quantile between 0 and 1 (0.5 = median, 0.95 = p95)
+    ///     filters: Optional list of PyPredicateFilter objects
+    ///
+    /// Returns:
+    ///     The quantile value as a float
+    ///
+    /// Example:
+    ///     >>> decoder = PyParquetDecoder()
+    ///     >>> p95 = decoder.percentile(data, "latency_ms", 0.95)
+    #[pyo3(signature = (data, column, q, filters=Vec::new()))]
+    fn percentile(
+        &self,
+        data: &[u8],
+        column: &str,
+        q: f64,
+        filters: Vec<PyPredicateFilter>,
+    ) -> PyResult<f64> {
+        let rust_filters: Vec<PredicateFilter> = filters
+            .into_iter()
+            .map(|f| f.into_inner())
+            .collect();
+
+        self.inner
+            .percentile(data, column, q, &rust_filters)
+            .map_err(parquet_err_to_py)
+    }
+
+    /// Count rows using only the file footer.
+    ///
+    /// Args:
+    ///     data: Parquet file bytes
+    ///
+    /// Returns:
+    ///     Total number of rows, nulls included
+    fn count(&self, data: &[u8]) -> PyResult<i64> {
+        match self.inner.aggregate(data, &AggregateSpec::Count).map_err(parquet_err_to_py)?.value {
+            ScalarValue::Int64(n) => Ok(n),
+            other => Err(PyRuntimeError::new_err(format!("Unexpected count value: {}", other))),
+        }
+    }
+
+    /// Get the smallest and largest non-null values of a column.
+    ///
+    /// Answered from row-group statistics when every row group has exact
+    /// ones; otherwise the column is decoded and scanned.
+    ///
+    /// Args:
+    ///     data: Parquet file bytes
+    ///     column: Column name
+    ///
+    /// Returns:
+    ///     Tuple of (min, max); both None if the column has no non-null values
+    ///
+    /// Raises:
+    ///     ValueError: If the column is missing or its type is unsupported
+    ///
+    /// Example:
+    ///     >>> lo, hi = decoder.min_max(data, "id")
+    fn min_max<'py>(
+        &self,
+        py: Python<'py>,
+        data: &[u8],
+        column: &str,
+    ) -> PyResult<(Bound<'py, PyAny>, Bound<'py, PyAny>)> {
+        let min = self
+            .inner
+            .aggregate(data, &AggregateSpec::Min(column.to_string()))
+            .map_err(parquet_err_to_py)?;
+        let max = self
+            .inner
+            .aggregate(data, &AggregateSpec::Max(column.to_string()))
+            .map_err(parquet_err_to_py)?;
+        Ok((scalar_to_py(py, &min.value)?, scalar_to_py(py, &max.value)?))
+    }
+}
+
+/// Convert a filter scalar to the matching Python value.
+fn scalar_to_py<'py>(py: Python<'py>, value: &ScalarValue) -> PyResult<Bound<'py, PyAny>> {
+    match value {
+        ScalarValue::Int64(v) => v.into_bound_py_any(py),
+        ScalarValue::Int32(v) => v.into_bound_py_any(py),
+        ScalarValue::Float64(v) => v.into_bound_py_any(py),
+        ScalarValue::Utf8(v) => v.into_bound_py_any(py),
+        ScalarValue::Boolean(v) => v.into_bound_py_any(py),
+        ScalarValue::Null => Ok(py.None().into_bound(py)),
+        ScalarValue::List(items) => {
+            let items = items
+                .iter()
+                .map(|item| scalar_to_py(py, item))
+                .collect::<PyResult<Vec<_>>>()?;
+            Ok(PyList::new(py, items)?.into_any())
+        }
     }
 }
 
@@ -542,6 +1333,13 @@ impl PyParquetDecoder {
 ///   - "le"  → column <= value
 ///   - "gt"  → column > value
 ///   - "ge"  → column >= value
+///   - "in"  → column IN (values)
+///   - "not_in" → column NOT IN (values)
+///   - "is_null" → column IS NULL
+///   - "is_not_null" → column IS NOT NULL
+///   - "starts_with" → column LIKE 'value%'
+///   - "ends_with" → column LIKE '%value'
+///   - "contains" → column LIKE '%value%'
 #[pyclass]
 #[derive(Clone)]
 struct PyFilterOp {
@@ -553,7 +1351,8 @@ impl PyFilterOp {
     /// Create a filter operation from a string.
     ///
     /// Args:
-    ///     op: One of "eq", "ne", "lt", "le", "gt", "ge"
+    ///     op: One of "eq", "ne", "lt", "le", "gt", "ge", "in", "not_in",
+    ///         "is_null", "is_not_null", "starts_with", "ends_with", "contains"
     #[new]
     fn new(op: &str) -> PyResult<Self> {
         let inner = match op.to_lowercase().as_str() {
@@ -563,8 +1362,16 @@ impl PyFilterOp {
             "le" | "<=" => FilterOp::Le,
             "gt" | ">" => FilterOp::Gt,
             "ge" | ">=" => FilterOp::Ge,
+            "in" => FilterOp::In,
+            "not_in" | "not in" => FilterOp::NotIn,
+            "is_null" | "is null" => FilterOp::IsNull,
+            "is_not_null" | "is not null" => FilterOp::IsNotNull,
+            "starts_with" => FilterOp::StartsWith,
+            "ends_with" => FilterOp::EndsWith,
+            "contains" => FilterOp::Contains,
             _ => return Err(PyValueError::new_err(format!(
-                "Invalid filter operation: '{}'. Use eq, ne, lt, le, gt, or ge",
+                "Invalid filter operation: '{}'. Use eq, ne, lt, le, gt, ge, in, not_in, is_null, \
+                 is_not_null, starts_with, ends_with, or contains",
                 op
             ))),
         };
@@ -598,17 +1405,25 @@ impl PyScalarValue {
     /// Create a scalar value.
     ///
     /// The type is inferred from the Python value:
-    ///   - int → Int64
+    ///   - int → Int64 (ValueError if outside the i64 range)
     ///   - float → Float64
     ///   - str → Utf8
     ///   - bool → Boolean
     ///   - None → Null
+    ///
+    /// Only real `bool` objects become Boolean; the integers 0 and 1 stay
+    /// Int64 so `eq` filters on integer columns keep working.
     #[new]
     fn new(value: &Bound<'_, PyAny>) -> PyResult<Self> {
         let inner = if value.is_none() {
             ScalarValue::Null
-        } else if let Ok(v) = value.extract::<bool>() {
-            ScalarValue::Boolean(v)
+        } else if value.is_instance_of::<PyBool>() {
+            ScalarValue::Boolean(value.extract::<bool>()?)
+        } else if value.is_instance_of::<PyInt>() {
+            let v = value.extract::<i64>().map_err(|_| {
+                PyValueError::new_err(format!("Integer {} is outside the Int64 range", value))
+            })?;
+            ScalarValue::Int64(v)
         } else if let Ok(v) = value.extract::<i64>() {
             ScalarValue::Int64(v)
         } else if let Ok(v) = value.extract::<f64>() {
@@ -641,6 +1456,8 @@ impl PyScalarValue {
 ///     >>> filter = PyPredicateFilter("age", "gt", 50)
 ///     >>> # status = 'active'
 ///     >>> filter = PyPredicateFilter("status", "eq", "active")
+///     >>> # status IN ('active', 'trial')
+///     >>> filter = PyPredicateFilter("status", "in", ["active", "trial"])
 #[pyclass]
 #[derive(Clone)]
 struct PyPredicateFilter {
@@ -653,15 +1470,22 @@ impl PyPredicateFilter {
     ///
     /// Args:
     ///     column: Column name to filter on
-    ///     op: Comparison operation (eq, ne, lt, le, gt, ge)
-    ///     value: Value to compare against (int, float, str, bool, or None)
+    ///     op: Comparison operation (eq, ne, lt, le, gt, ge, in, not_in,
+    ///         is_null, is_not_null, starts_with, ends_with, contains)
+    ///     value: Value to compare against (int, float, str, bool, or None);
+    ///         a list of such values for "in" and "not_in". Ignored by
+    ///         "is_null" and "is_not_null"
     #[new]
-    fn new(column: String, op: &str, value: &Bound<'_, PyAny>) -> PyResult<Self> {
+    #[pyo3(signature = (column, op, value=None))]
+    fn new(column: String, op: &str, value: Option<&Bound<'_, PyAny>>) -> PyResult<Self> {
         let filter_op = PyFilterOp::new(op)?;
-        let scalar_value = PyScalarValue::new(value)?;
+        let value = match (filter_op.inner, value) {
+            (FilterOp::IsNull | FilterOp::IsNotNull, _) | (_, None) => ScalarValue::Null,
+            (_, Some(value)) => Self::scalar_for(filter_op.inner, op, value)?,
+        };
 
         Ok(Self {
-            inner: PredicateFilter::new(column, filter_op.inner, scalar_value.inner),
+            inner: PredicateFilter::new(column, filter_op.inner, value),
         })
     }
 
@@ -693,29 +1517,160 @@ impl PyPredicateFilter {
     fn into_inner(self) -> PredicateFilter {
         self.inner
     }
+
+    /// Convert the Python value for `op`: a list for "in"/"not_in", else a scalar.
+    fn scalar_for(filter_op: FilterOp, op: &str, value: &Bound<'_, PyAny>) -> PyResult<ScalarValue> {
+        match filter_op {
+            FilterOp::In | FilterOp::NotIn => {
+                if !value.is_instance_of::<PyList>() && !value.is_instance_of::<PyTuple>() {
+                    return Err(PyValueError::new_err(format!(
+                        "Filter operation '{}' requires a list of values",
+                        op
+                    )));
+                }
+                let members = value
+                    .try_iter()?
+                    .map(|item| Ok(PyScalarValue::new(&item?)?.inner))
+                    .collect::<PyResult<Vec<_>>>()?;
+                Ok(ScalarValue::List(members))
+            }
+            _ => Ok(PyScalarValue::new(value)?.inner),
+        }
+    }
+}
+
+/// A boolean combination of predicate filters (AND / OR groups).
+///
+/// An empty AND matches every row and an empty OR matches none.
+///
+/// Example:
+///     >>> # age < 18 OR age > 65
+///     >>> expr = PyPredicateExpr.or_([
+///     ...     PyPredicateFilter("age", "lt", 18),
+///     ...     PyPredicateFilter("age", "gt", 65),
+///     ... ])
+///     >>> result = decoder.decode_with_expr(data, expr)
+#[pyclass]
+#[derive(Clone)]
+struct PyPredicateExpr {
+    inner: PredicateExpr,
+}
+
+#[pymethods]
+impl PyPredicateExpr {
+    /// Wrap a single filter.
+    #[staticmethod]
+    fn leaf(filter: PyPredicateFilter) -> Self {
+        Self { inner: PredicateExpr::Leaf(filter.into_inner()) }
+    }
+
+    /// Match rows that match every item.
+    ///
+    /// Args:
+    ///     items: List of PyPredicateExpr or PyPredicateFilter objects
+    #[staticmethod]
+    fn and_(items: Vec<Bound<'_, PyAny>>) -> PyResult<Self> {
+        Ok(Self { inner: PredicateExpr::And(Self::children(items)?) })
+    }
+
+    /// Match rows that match at least one item.
+    ///
+    /// Args:
+    ///     items: List of PyPredicateExpr or PyPredicateFilter objects
+    #[staticmethod]
+    fn or_(items: Vec<Bound<'_, PyAny>>) -> PyResult<Self> {
+        Ok(Self { inner: PredicateExpr::Or(Self::children(items)?) })
+    }
+
+    fn __repr__(&self) -> String {
+        format!("PyPredicateExpr({})", self.inner)
+    }
+
+    fn __str__(&self) -> String {
+        format!("{}", self.inner)
+    }
+}
+
+impl PyPredicateExpr {
+    fn children(items: Vec<Bound<'_, PyAny>>) -> PyResult<Vec<PredicateExpr>> {
+        items
+            .iter()
+            .map(|item| {
+                if let Ok(expr) = item.extract::<PyPredicateExpr>() {
+                    Ok(expr.inner)
+                } else if let Ok(filter) = item.extract::<PyPredicateFilter>() {
+                    Ok(PredicateExpr::Leaf(filter.into_inner()))
+                } else {
+                    Err(PyValueError::new_err(
+                        "Expected PyPredicateExpr or PyPredicateFilter items",
+                    ))
+                }
+            })
+            .collect()
+    }
 }
 
 #[pyclass]
 struct PyChunkStore {
-    inner: ChunkStore,
+    inner: Arc<ChunkStore>,
 }
 
 #[pymethods]
 impl PyChunkStore {
+    /// Open a chunk store.
+    ///
+    /// Args:
+    ///     path: Directory for chunk storage
+    ///     retries: Extra attempts for transient filesystem errors (default 0)
+    ///     short_hash_len: Length of hashes returned by short_hash (default 12)
     #[new]
-    fn new(path: &str) -> PyResult<Self> {
-        let inner = ChunkStore::new(path).map_err(chunk_err_to_py)?;
-        Ok(Self { inner })
+    #[pyo3(signature = (path, retries=0, short_hash_len=DEFAULT_SHORT_HASH_LEN))]
+    fn new(path: &str, retries: u32, short_hash_len: usize) -> PyResult<Self> {
+        let inner = ChunkStore::new(path)
+            .map_err(chunk_err_to_py)?
+            .with_retry(retries.saturating_add(1), Duration::from_millis(10))
+            .with_short_hash_len(short_hash_len);
+        Ok(Self { inner: Arc::new(inner) })
+    }
+
+    /// Shorten a hash to the configured length for display.
+    fn short_hash(&self, full: &str) -> String {
+        self.inner.short_hash(full)
+    }
+
+    /// Resolve a hash prefix (at least 4 characters) to a full chunk hash.
+    fn resolve_prefix(&self, prefix: &str) -> PyResult<String> {
+        self.inner.resolve_prefix(prefix).map_err(chunk_err_to_py)
     }
 
     fn put(&self, data: &[u8]) -> PyResult<String> {
         self.inner.put(data).map_err(chunk_err_to_py)
     }
 
+    /// Store a chunk read from a binary file object, returning its hash.
+    ///
+    /// Reads 64 KB at a time, so memory use stays constant regardless of
+    /// size. The hash matches put() on the same bytes.
+    fn put_stream(&self, fileobj: Bound<'_, PyAny>) -> PyResult<String> {
+        self.inner
+            .put_reader(PyFileSource(fileobj.unbind()))
+            .map_err(chunk_err_to_py)
+    }
+
     fn get(&self, hash: &str) -> PyResult<Vec<u8>> {
         self.inner.get(hash).map_err(chunk_err_to_py)
     }
 
+    /// Store a chunk with a metadata sidecar. The hash covers the data only.
+    fn put_with_meta(&self, data: &[u8], meta: HashMap<String, String>) -> PyResult<String> {
+        self.inner.put_with_meta(data, meta).map_err(chunk_err_to_py)
+    }
+
+    /// Get a chunk's metadata (empty dict if it was stored without any).
+    fn get_meta(&self, hash: &str) -> PyResult<HashMap<String, String>> {
+        self.inner.get_meta(hash).map_err(chunk_err_to_py)
+    }
+
     /// Get chunk data with integrity verification.
     /// Raises ValueError if the data doesn't match the expected hash.
     fn get_verified(&self, hash: &str) -> PyResult<Vec<u8>> {
@@ -726,10 +1681,47 @@ impl PyChunkStore {
         self.inner.exists(hash).map_err(chunk_err_to_py)
     }
 
+    /// Return the given hashes that are not stored here.
+    ///
+    /// Existence is checked in parallel; each missing hash appears once,
+    /// in first-seen order. Use it to find which chunks to send on push.
+    ///
+    /// Example:
+    ///     >>> to_send = remote.missing_from(local_hashes)
+    fn missing_from(&self, hashes: Vec<String>) -> PyResult<Vec<String>> {
+        let refs: Vec<&str> = hashes.iter().map(|s| s.as_str()).collect();
+        self.inner.missing_from(&refs).map_err(chunk_err_to_py)
+    }
+
     fn delete(&self, hash: &str) -> PyResult<()> {
         self.inner.delete(hash).map_err(chunk_err_to_py)
     }
 
+    /// Delete every chunk whose hash is not in live_hashes.
+    ///
+    /// Temp files are skipped. With prune_empty_dirs, shard directories left
+    /// empty are removed too. Don't run this alongside writers.
+    ///
+    /// Returns:
+    ///     Dict with keys removed_count, removed_bytes, scanned_count
+    #[pyo3(signature = (live_hashes, prune_empty_dirs=false))]
+    fn garbage_collect(
+        &self,
+        live_hashes: Vec<String>,
+        prune_empty_dirs: bool,
+    ) -> PyResult<HashMap<String, u64>> {
+        let live: HashSet<String> = live_hashes.into_iter().collect();
+        let report = self
+            .inner
+            .garbage_collect_with_options(&live, prune_empty_dirs)
+            .map_err(chunk_err_to_py)?;
+        Ok(HashMap::from([
+            ("removed_count".to_string(), report.removed_count as u64),
+            ("removed_bytes".to_string(), report.removed_bytes),
+            ("scanned_count".to_string(), report.scanned_count as u64),
+        ]))
+    }
+
     // =========================================================================
     // Batch Operations (Parallel)
     // =========================================================================
@@ -780,20 +1772,45 @@ impl PyChunkStore {
         self.inner.get_batch(&refs).map_err(chunk_err_to_py)
     }
 
-    /// Retrieve multiple chunks with integrity verification in parallel.
-    ///
-    /// Like `get_batch`, but verifies each chunk's integrity by comparing
-    /// its content hash to the expected hash.
+    /// Retrieve multiple chunks in parallel without stopping at failures.
     ///
     /// Args:
-    ///     hashes: List of hash strings to retrieve and verify
+    ///     hashes: List of hash strings to retrieve
     ///
     /// Returns:
-    ///     List of verified chunk data (bytes) in the same order as input
+    ///     One entry per hash, in order: the chunk bytes, or the exception
+    ///     (not raised) that `get` would have raised for that hash
     ///
-    /// Raises:
-    ///     ValueError: If any chunk fails integrity verification
-    fn get_batch_verified(&self, hashes: Vec<String>) -> PyResult<Vec<Vec<u8>>> {
+    /// Example:
+    ///     >>> results = store.get_batch_collect([h1, missing])
+    ///     >>> isinstance(results[1], IOError)
+    ///     True
+    fn get_batch_collect<'py>(&self, py: Python<'py>, hashes: Vec<String>) -> Vec<Bound<'py, PyAny>> {
+        let refs: Vec<&str> = hashes.iter().map(|s| s.as_str()).collect();
+        self.inner
+            .get_batch_collect(&refs)
+            .into_iter()
+            .map(|result| match result {
+                Ok(data) => pyo3::types::PyBytes::new(py, &data).into_any(),
+                Err(e) => chunk_err_to_py(e).into_value(py).into_bound(py).into_any(),
+            })
+            .collect()
+    }
+
+    /// Retrieve multiple chunks with integrity verification in parallel.
+    ///
+    /// Like `get_batch`, but verifies each chunk's integrity by comparing
+    /// its content hash to the expected hash.
+    ///
+    /// Args:
+    ///     hashes: List of hash strings to retrieve and verify
+    ///
+    /// Returns:
+    ///     List of verified chunk data (bytes) in the same order as input
+    ///
+    /// Raises:
+    ///     ValueError: If any chunk fails integrity verification
+    fn get_batch_verified(&self, hashes: Vec<String>) -> PyResult<Vec<Vec<u8>>> {
         let refs: Vec<&str> = hashes.iter().map(|s| s.as_str()).collect();
         self.inner.get_batch_verified(&refs).map_err(chunk_err_to_py)
     }
@@ -848,11 +1865,11 @@ struct PyTableVersion {
     version: u64,
     #[pyo3(get)]
     chunk_hashes: Vec<String>,
-    #[pyo3(get)]
+    #[pyo3(get, set)]
     schema_hash: Option<String>,
-    #[pyo3(get)]
+    #[pyo3(get, set)]
     created_at: i64,
-    #[pyo3(get)]
+    #[pyo3(get, set)]
     parent_version: Option<u64>,
     #[pyo3(get)]
     metadata: HashMap<String, String>,
@@ -875,6 +1892,11 @@ impl PyTableVersion {
             metadata: HashMap::new(),
         }
     }
+
+    /// BLAKE3 hash over the sorted chunk hashes and schema hash.
+    fn content_hash(&self) -> String {
+        TableVersion::from(self.clone()).content_hash()
+    }
 }
 
 impl From<TableVersion> for PyTableVersion {
@@ -905,25 +1927,193 @@ impl From<PyTableVersion> for TableVersion {
     }
 }
 
+#[pyclass]
+#[derive(Clone)]
+struct PyChainReuse {
+    #[pyo3(get)]
+    versions: usize,
+    #[pyo3(get)]
+    total_references: usize,
+    #[pyo3(get)]
+    unique_chunks: usize,
+    #[pyo3(get)]
+    avg_reuse_ratio: f64,
+    #[pyo3(get)]
+    referenced_bytes: u64,
+    #[pyo3(get)]
+    unique_bytes: u64,
+}
+
+impl From<ChainReuse> for PyChainReuse {
+    fn from(r: ChainReuse) -> Self {
+        Self {
+            versions: r.versions,
+            total_references: r.total_references,
+            unique_chunks: r.unique_chunks,
+            avg_reuse_ratio: r.avg_reuse_ratio,
+            referenced_bytes: r.referenced_bytes,
+            unique_bytes: r.unique_bytes,
+        }
+    }
+}
+
+/// What writing a batch as a table's next version would change.
+#[pyclass]
+#[derive(Clone)]
+struct PyPreviewReport {
+    #[pyo3(get)]
+    new_chunks: usize,
+    #[pyo3(get)]
+    reused_chunks: usize,
+    #[pyo3(get)]
+    new_bytes: u64,
+    #[pyo3(get)]
+    would_be_noop: bool,
+}
+
+impl From<PreviewReport> for PyPreviewReport {
+    fn from(r: PreviewReport) -> Self {
+        Self {
+            new_chunks: r.new_chunks,
+            reused_chunks: r.reused_chunks,
+            new_bytes: r.new_bytes,
+            would_be_noop: r.would_be_noop,
+        }
+    }
+}
+
+#[pymethods]
+impl PyPreviewReport {
+    fn __repr__(&self) -> String {
+        format!(
+            "PyPreviewReport(new_chunks={}, reused_chunks={}, new_bytes={}, would_be_noop={})",
+            self.new_chunks,
+            self.reused_chunks,
+            self.new_bytes,
+            if self.would_be_noop { "True" } else { "False" }
+        )
+    }
+}
+
+/// How a new schema relates to a table's current one.
+#[pyclass]
+#[derive(Clone)]
+struct PySchemaCompat {
+    /// "identical", "compatible" or "breaking"
+    #[pyo3(get)]
+    kind: String,
+    #[pyo3(get)]
+    added_columns: Vec<String>,
+    #[pyo3(get)]
+    changes: Vec<String>,
+}
+
+impl From<SchemaCompat> for PySchemaCompat {
+    fn from(c: SchemaCompat) -> Self {
+        let (kind, added_columns, changes) = match c {
+            SchemaCompat::Identical => ("identical", vec![], vec![]),
+            SchemaCompat::Compatible { added_columns } => ("compatible", added_columns, vec![]),
+            SchemaCompat::Breaking { changes } => ("breaking", vec![], changes),
+        };
+        Self { kind: kind.to_string(), added_columns, changes }
+    }
+}
+
+#[pymethods]
+impl PySchemaCompat {
+    #[getter]
+    fn is_breaking(&self) -> bool {
+        self.kind == "breaking"
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PySchemaCompat(kind={:?}, added_columns={:?}, changes={:?})",
+            self.kind, self.added_columns, self.changes
+        )
+    }
+}
+
+fn schema_from_pyarrow(schema: &Bound<'_, PyAny>) -> PyResult<Schema> {
+    Schema::from_pyarrow_bound(schema).map_err(|e| {
+        PyValueError::new_err(format!("Invalid Schema: {}", sanitize_error_message(&e.to_string())))
+    })
+}
+
 #[pyclass]
 struct PyCatalog {
-    inner: FileCatalog,
+    inner: Arc<FileCatalog>,
 }
 
 #[pymethods]
 impl PyCatalog {
+    /// Open a catalog. With strict_schemas, commits that make a breaking
+    /// change to a table's stored schema raise ValueError.
     #[new]
-    fn new(path: &str) -> PyResult<Self> {
-        let inner = FileCatalog::new(path).map_err(catalog_err_to_py)?;
-        Ok(Self { inner })
+    #[pyo3(signature = (path, strict_schemas=false))]
+    fn new(path: &str, strict_schemas: bool) -> PyResult<Self> {
+        let inner = FileCatalog::new(path)
+            .map_err(catalog_err_to_py)?
+            .with_strict_schemas(strict_schemas);
+        Ok(Self { inner: Arc::new(inner) })
+    }
+
+    /// Store a pyarrow schema and return its hash, for a version's schema_hash.
+    fn store_schema(&self, schema: Bound<'_, PyAny>) -> PyResult<String> {
+        self.inner
+            .store_schema(&schema_from_pyarrow(&schema)?)
+            .map_err(catalog_err_to_py)
+    }
+
+    /// The pyarrow schema stored under a hash, or None.
+    fn load_schema<'py>(&self, py: Python<'py>, schema_hash: &str) -> PyResult<Option<Bound<'py, PyAny>>> {
+        match self.inner.load_schema(schema_hash).map_err(catalog_err_to_py)? {
+            Some(schema) => schema
+                .to_pyarrow(py)
+                .map(Some)
+                .map_err(|e| PyValueError::new_err(sanitize_error_message(&e.to_string()))),
+            None => Ok(None),
+        }
+    }
+
+    /// Classify a pyarrow schema against the table's latest stored schema.
+    fn check_schema_compatibility(&self, table_name: &str, schema: Bound<'_, PyAny>) -> PyResult<PySchemaCompat> {
+        self.inner
+            .check_schema_compatibility(table_name, &schema_from_pyarrow(&schema)?)
+            .map(Into::into)
+            .map_err(catalog_err_to_py)
     }
 
     fn commit(&self, version: PyTableVersion) -> PyResult<u64> {
         self.inner.commit(version.into()).map_err(catalog_err_to_py)
     }
 
+    /// Commit after rejecting versions with no chunks or a repeated chunk hash.
+    fn commit_validated(&self, version: PyTableVersion) -> PyResult<u64> {
+        self.inner.commit_validated(version.into()).map_err(catalog_err_to_py)
+    }
+
+    /// Get a table version by number or tag (latest if omitted).
+    ///
+    /// Raises ValueError if a tag names a version of a different table.
     #[pyo3(signature = (table_name, version=None))]
-    fn get_version(&self, table_name: &str, version: Option<u64>) -> PyResult<PyTableVersion> {
+    fn get_version(&self, table_name: &str, version: Option<&Bound<'_, PyAny>>) -> PyResult<PyTableVersion> {
+        let version = match version {
+            None => None,
+            Some(v) => match v.extract::<String>() {
+                Ok(tag) => {
+                    let tagged = self.inner.get_tagged_version(&tag).map_err(catalog_err_to_py)?;
+                    if tagged.table_name != table_name {
+                        return Err(PyValueError::new_err(format!(
+                            "Tag '{}' points at table '{}', not '{}'",
+                            tag, tagged.table_name, table_name
+                        )));
+                    }
+                    return Ok(tagged.into());
+                }
+                Err(_) => Some(v.extract::<u64>()?),
+            },
+        };
         self.inner
             .get_version(table_name, version)
             .map(|tv| tv.into())
@@ -934,9 +2124,243 @@ impl PyCatalog {
         self.inner.list_versions(table_name).map_err(catalog_err_to_py)
     }
 
+    /// (version, created_at) pairs in version order.
+    fn list_versions_detailed(&self, table_name: &str) -> PyResult<Vec<(u64, i64)>> {
+        self.inner.list_versions_detailed(table_name).map_err(catalog_err_to_py)
+    }
+
+    /// The newest version created at or before a Unix timestamp.
+    fn get_version_as_of(&self, table_name: &str, timestamp: i64) -> PyResult<PyTableVersion> {
+        self.inner
+            .get_version_as_of(table_name, timestamp)
+            .map(Into::into)
+            .map_err(catalog_err_to_py)
+    }
+
     fn list_tables(&self) -> PyResult<Vec<String>> {
         self.inner.list_tables().map_err(catalog_err_to_py)
     }
+
+    fn schema_history(&self, table_name: &str) -> PyResult<Vec<(u64, Option<String>)>> {
+        self.inner.schema_history(table_name).map_err(catalog_err_to_py)
+    }
+
+    fn schema_change_points(&self, table_name: &str) -> PyResult<Vec<u64>> {
+        self.inner.schema_change_points(table_name).map_err(catalog_err_to_py)
+    }
+
+    #[pyo3(signature = (table_name, version=None))]
+    fn version_content_hash(&self, table_name: &str, version: Option<u64>) -> PyResult<String> {
+        self.inner
+            .version_content_hash(table_name, version)
+            .map_err(catalog_err_to_py)
+    }
+
+    fn chain_reuse(&self, table_name: &str, store: &PyChunkStore) -> PyResult<PyChainReuse> {
+        self.inner
+            .chain_reuse(table_name, &store.inner)
+            .map(PyChainReuse::from)
+            .map_err(catalog_err_to_py)
+    }
+
+    fn changed_chunks(&self, table_name: &str, from_version: u64, to_version: u64) -> PyResult<Vec<String>> {
+        self.inner
+            .changed_chunks(table_name, from_version, to_version)
+            .map_err(catalog_err_to_py)
+    }
+
+    /// Tag a table version. Raises ValueError if the tag names another version.
+    fn tag_version(&self, table_name: &str, tag: &str, version: u64) -> PyResult<()> {
+        self.inner.tag_version(table_name, tag, version).map_err(catalog_err_to_py)
+    }
+
+    /// Every tag grouped by table, as (tag, version) sorted by tag.
+    fn list_all_tags(&self) -> PyResult<HashMap<String, Vec<(String, u64)>>> {
+        self.inner.list_all_tags().map_err(catalog_err_to_py)
+    }
+
+    /// Create an immutable catalog-wide tag for a table version.
+    ///
+    /// Raises ValueError if the tag already exists, unless force is True.
+    #[pyo3(signature = (tag, table_name, version, force=false))]
+    fn create_tag(&self, tag: &str, table_name: &str, version: u64, force: bool) -> PyResult<()> {
+        self.inner
+            .create_tag(tag, table_name, version, force)
+            .map_err(catalog_err_to_py)
+    }
+
+    /// The (table_name, version) a tag points at, or None.
+    fn resolve_tag(&self, tag: &str) -> PyResult<Option<(String, u64)>> {
+        self.inner.resolve_tag(tag).map_err(catalog_err_to_py)
+    }
+
+    /// Every tag as (tag, table_name, version), sorted by tag.
+    fn list_tags(&self) -> PyResult<Vec<(String, String, u64)>> {
+        self.inner.list_tags().map_err(catalog_err_to_py)
+    }
+
+    /// Delete a tag. Returns False if it did not exist.
+    fn delete_tag(&self, tag: &str) -> PyResult<bool> {
+        self.inner.delete_tag(tag).map_err(catalog_err_to_py)
+    }
+
+    /// Delete one version, repointing its children at its parent.
+    ///
+    /// Raises ValueError for the latest version or a tagged one.
+    fn delete_version(&self, table_name: &str, version: u64) -> PyResult<()> {
+        self.inner.delete_version(table_name, version).map_err(catalog_err_to_py)
+    }
+
+    /// Delete all but the newest keep_last versions of a table.
+    ///
+    /// The latest version, tagged versions and versions in protected (e.g.
+    /// branch heads) are kept. Returns the deleted version numbers.
+    #[pyo3(signature = (table_name, keep_last, protected=None))]
+    fn prune_versions(
+        &self,
+        table_name: &str,
+        keep_last: usize,
+        protected: Option<Vec<u64>>,
+    ) -> PyResult<Vec<u64>> {
+        let protected: Option<HashSet<u64>> = protected.map(|v| v.into_iter().collect());
+        self.inner
+            .prune_versions(table_name, keep_last, protected.as_ref())
+            .map_err(catalog_err_to_py)
+    }
+
+    #[pyo3(signature = (table_name, remove_versions=false))]
+    fn drop_table(&self, table_name: &str, remove_versions: bool) -> PyResult<()> {
+        self.inner
+            .drop_table(table_name, remove_versions)
+            .map_err(catalog_err_to_py)
+    }
+
+    #[pyo3(signature = (table_name, remove_versions=false))]
+    fn drop_table_with_orphans(&self, table_name: &str, remove_versions: bool) -> PyResult<Vec<String>> {
+        self.inner
+            .drop_table_with_orphans(table_name, remove_versions)
+            .map_err(catalog_err_to_py)
+    }
+}
+
+/// List the chunks referenced by target versions that a store is missing.
+///
+/// Args:
+///     target_versions: Dict mapping table name to the PyTableVersion to sync
+///     store: The PyChunkStore being brought up to date
+///
+/// Returns:
+///     Missing chunk hashes, each listed once
+#[pyfunction(name = "transfer_plan")]
+fn py_transfer_plan(
+    target_versions: HashMap<String, PyTableVersion>,
+    store: &PyChunkStore,
+) -> PyResult<Vec<String>> {
+    let target: HashMap<String, TableVersion> = target_versions
+        .into_iter()
+        .map(|(table, version)| (table, version.into()))
+        .collect();
+    transfer_plan(&target, &store.inner).map_err(catalog_err_to_py)
+}
+
+// ============================================================================
+// Table Handle
+// ============================================================================
+
+fn table_err_to_py(e: TableError) -> PyErr {
+    match e {
+        TableError::Catalog(e) => catalog_err_to_py(e),
+        TableError::ChunkStore(e) => chunk_err_to_py(e),
+        TableError::Parquet(e) => parquet_err_to_py(e),
+        TableError::Merkle(e) => merkle_err_to_py(e),
+        TableError::EmptyTable(name) => PyValueError::new_err(format!("Table has no rows: {}", name)),
+    }
+}
+
+/// Handle for reading and writing one table's versions.
+///
+/// Rows are chunked and encoded as TableWriter does with the native
+/// Parquet encoder.
+#[pyclass]
+struct PyTable {
+    inner: Table,
+}
+
+#[pymethods]
+impl PyTable {
+    /// Create a handle for a table.
+    ///
+    /// Args:
+    ///     catalog: Catalog holding the table
+    ///     store: Chunk store holding its chunks
+    ///     name: Table name
+    ///     chunk_size_bytes: Target encoded bytes per chunk (default 64MB)
+    ///     chunk_size_rows: Fixed row count per chunk (overrides byte-based)
+    #[new]
+    #[pyo3(signature = (catalog, store, name, chunk_size_bytes=None, chunk_size_rows=None))]
+    fn new(
+        catalog: &PyCatalog,
+        store: &PyChunkStore,
+        name: &str,
+        chunk_size_bytes: Option<usize>,
+        chunk_size_rows: Option<usize>,
+    ) -> Self {
+        let mut inner = Table::new(name, Arc::clone(&catalog.inner), Arc::clone(&store.inner));
+        if let Some(bytes) = chunk_size_bytes {
+            inner = inner.with_chunk_size(bytes);
+        }
+        if let Some(rows) = chunk_size_rows {
+            inner = inner.with_chunk_rows(rows);
+        }
+        Self { inner }
+    }
+
+    #[getter]
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    /// Re-chunk the latest version and commit the result.
+    ///
+    /// The rows are decoded, split into chunks of about config.chunk_size
+    /// encoded bytes and re-encoded, so the new version holds the same rows
+    /// in a new chunk layout.
+    ///
+    /// Args:
+    ///     config: PyMerkleConfig whose chunk_size is the new target
+    ///
+    /// Returns:
+    ///     The new version number
+    ///
+    /// Raises:
+    ///     IOError: If the table does not exist or a chunk is missing
+    ///     ValueError: If the config is invalid or the table has no rows
+    fn rechunk(&self, config: &PyMerkleConfig) -> PyResult<u64> {
+        self.inner.rechunk(&config.inner).map_err(table_err_to_py)
+    }
+
+    /// Preview writing a batch as the next version, storing nothing.
+    ///
+    /// The batch is chunked and encoded as a write would, and the Merkle
+    /// tree over its chunks is diffed against the latest version's.
+    ///
+    /// Args:
+    ///     batch: PyArrow RecordBatch to preview
+    ///
+    /// Raises:
+    ///     ValueError: If the batch is invalid or has no rows
+    fn preview_write(&self, batch: Bound<'_, PyAny>) -> PyResult<PyPreviewReport> {
+        let rust_batch = RecordBatch::from_pyarrow_bound(&batch)
+            .map_err(|e| PyValueError::new_err(format!("Invalid RecordBatch: {}", sanitize_error_message(&e.to_string()))))?;
+        self.inner
+            .preview_write(&rust_batch)
+            .map(|r| r.into())
+            .map_err(table_err_to_py)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("PyTable(name={})", self.inner.name())
+    }
 }
 
 // ============================================================================
@@ -1005,19 +2429,30 @@ impl From<BranchDiff> for PyBranchDiff {
 
 #[pyclass]
 struct PyBranchManager {
-    inner: BranchManager,
+    inner: Arc<BranchManager>,
 }
 
 #[pymethods]
 impl PyBranchManager {
+    /// Open a branch manager.
+    ///
+    /// Args:
+    ///     path: Directory for branch storage
+    ///     default_branch: Name of the initial default branch (default "main")
+    ///     cache: Cache branch state in memory (default False). Only safe when
+    ///         this manager is the sole writer of the branches.
     #[new]
-    fn new(path: &str) -> PyResult<Self> {
-        let inner = BranchManager::new(path).map_err(branch_err_to_py)?;
-        Ok(Self { inner })
+    #[pyo3(signature = (path, default_branch="main", cache=false))]
+    fn new(path: &str, default_branch: &str, cache: bool) -> PyResult<Self> {
+        let mut inner = BranchManager::with_default(path, default_branch).map_err(branch_err_to_py)?;
+        if cache {
+            inner = inner.with_cache();
+        }
+        Ok(Self { inner: Arc::new(inner) })
     }
 
     /// Create a new branch from an existing branch.
-    /// If from_branch is None, creates from the default branch (main).
+    /// If from_branch is None, creates from the default branch.
     #[pyo3(signature = (name, from_branch=None, description=None))]
     fn create(
         &self,
@@ -1026,62 +2461,246 @@ impl PyBranchManager {
         description: Option<&str>,
     ) -> PyResult<PyBranch> {
         self.inner
-            .create(name, from_branch, description)
-            .map(|b| b.into())
+            .create(name, from_branch, description)
+            .map(|b| b.into())
+            .map_err(branch_err_to_py)
+    }
+
+    /// Get a branch by name.
+    fn get(&self, name: &str) -> PyResult<PyBranch> {
+        self.inner.get(name).map(|b| b.into()).map_err(branch_err_to_py)
+    }
+
+    /// List all branch names.
+    fn list(&self) -> PyResult<Vec<String>> {
+        self.inner.list().map_err(branch_err_to_py)
+    }
+
+    /// Delete a branch. Cannot delete the default branch.
+    fn delete(&self, name: &str) -> PyResult<()> {
+        self.inner.delete(name).map_err(branch_err_to_py)
+    }
+
+    /// Update the head pointer for a table on a branch.
+    ///
+    /// Raises ValueError for an empty table name or version 0.
+    fn update_head(&self, branch_name: &str, table_name: &str, version: u64) -> PyResult<()> {
+        self.inner
+            .update_head(branch_name, table_name, version)
+            .map_err(branch_err_to_py)
+    }
+
+    /// Update the head pointers for several tables on a branch atomically.
+    ///
+    /// Takes a list of (table_name, version) tuples. If any entry is
+    /// invalid, no heads are changed.
+    fn update_heads(&self, branch_name: &str, updates: Vec<(String, u64)>) -> PyResult<()> {
+        self.inner
+            .update_heads(branch_name, &updates)
+            .map_err(branch_err_to_py)
+    }
+
+    /// Get the version of a table on a branch.
+    fn get_table_version(&self, branch_name: &str, table_name: &str) -> PyResult<Option<u64>> {
+        self.inner
+            .get_table_version(branch_name, table_name)
+            .map_err(branch_err_to_py)
+    }
+
+    /// Compare two branches.
+    fn diff(&self, source: &str, target: &str) -> PyResult<PyBranchDiff> {
+        self.inner
+            .diff(source, target)
+            .map(|d| d.into())
+            .map_err(branch_err_to_py)
+    }
+
+    /// Compare one source branch against several targets.
+    fn diff_many(&self, source: &str, targets: Vec<String>) -> PyResult<Vec<PyBranchDiff>> {
+        let targets: Vec<&str> = targets.iter().map(String::as_str).collect();
+        self.inner
+            .diff_many(source, &targets)
+            .map(|diffs| diffs.into_iter().map(PyBranchDiff::from).collect())
+            .map_err(branch_err_to_py)
+    }
+
+    /// Check if a fast-forward merge is possible.
+    fn can_fast_forward(&self, source: &str, target: &str) -> PyResult<bool> {
+        self.inner
+            .can_fast_forward(source, target)
+            .map_err(branch_err_to_py)
+    }
+
+    /// Merge source branch into target branch (fast-forward only).
+    fn merge(&self, source: &str, into: &str) -> PyResult<()> {
+        self.inner
+            .merge_fast_forward(source, into)
+            .map_err(branch_err_to_py)
+    }
+
+    /// Merge several source branches into a target in a single pass.
+    ///
+    /// Conflict-free tables from all sources are applied at once; tables
+    /// that sources changed differently (or that diverged from the target)
+    /// are reported in the outcome's conflicts.
+    fn merge_many(&self, sources: Vec<String>, into: &str, catalog: &PyCatalog) -> PyResult<PyMergeOutcome> {
+        let sources: Vec<&str> = sources.iter().map(|s| s.as_str()).collect();
+        self.inner
+            .merge_many(&sources, into, &catalog.inner)
+            .map(|o| o.into())
             .map_err(branch_err_to_py)
     }
 
-    /// Get a branch by name.
-    fn get(&self, name: &str) -> PyResult<PyBranch> {
-        self.inner.get(name).map(|b| b.into()).map_err(branch_err_to_py)
+    /// Merge source into a target, settling diverged tables by policy.
+    ///
+    /// policy_map maps table names to "take_source", "take_target", "skip"
+    /// or "abort"; diverged tables without an entry are skipped (reported
+    /// as conflicts). Raises ValueError on an unknown policy or an abort.
+    fn merge_three_way_with_policy(
+        &self,
+        source: &str,
+        into: &str,
+        catalog: &PyCatalog,
+        policy_map: HashMap<String, String>,
+    ) -> PyResult<PyMergeOutcome> {
+        let mut policies = HashMap::with_capacity(policy_map.len());
+        for (table, policy) in policy_map {
+            let resolution = match policy.to_lowercase().as_str() {
+                "take_source" | "source" => Resolution::TakeSource,
+                "take_target" | "target" => Resolution::TakeTarget,
+                "skip" => Resolution::Skip,
+                "abort" => Resolution::Abort,
+                _ => return Err(PyValueError::new_err(format!(
+                    "Invalid resolution for table '{}': '{}'. Valid: take_source, take_target, skip, abort",
+                    table, policy
+                ))),
+            };
+            policies.insert(table, resolution);
+        }
+
+        self.inner
+            .merge_three_way(source, into, &catalog.inner, |table, _, _, _| {
+                policies.get(table).copied().unwrap_or(Resolution::Skip)
+            })
+            .map(|o| o.into())
+            .map_err(branch_err_to_py)
     }
 
-    /// List all branch names.
-    fn list(&self) -> PyResult<Vec<String>> {
-        self.inner.list().map_err(branch_err_to_py)
+    /// Three-way merge that settles diverged tables algebraically.
+    ///
+    /// The rows of each table both branches changed are combined under its
+    /// conflict-free schema in registry, committed as the table's next
+    /// version and set as the target's head. If any diverged table cannot
+    /// be combined, the outcome lists the conflicts and nothing is written.
+    fn merge_three_way(
+        &self,
+        source: &str,
+        into: &str,
+        catalog: &PyCatalog,
+        store: &PyChunkStore,
+        registry: &PyAlgebraicSchemaRegistry,
+    ) -> PyResult<PyMergeOutcome> {
+        self.inner
+            .merge_three_way_algebraic(source, into, &catalog.inner, &store.inner, &registry.inner)
+            .map(|o| o.into())
+            .map_err(branch_err_to_py)
     }
 
-    /// Delete a branch. Cannot delete the default branch.
-    fn delete(&self, name: &str) -> PyResult<()> {
-        self.inner.delete(name).map_err(branch_err_to_py)
+    /// Replay branch's table updates on top of onto's current heads.
+    ///
+    /// Tables only onto changed are inherited. Returns the tables whose
+    /// branch version was kept. Raises ValueError (merge conflict) if both
+    /// branches advanced the same table; the branch is then unchanged.
+    fn rebase(&self, branch: &str, onto: &str, catalog: &PyCatalog) -> PyResult<Vec<String>> {
+        self.inner
+            .rebase(branch, onto, &catalog.inner)
+            .map_err(branch_err_to_py)
     }
 
-    /// Update the head pointer for a table on a branch.
-    fn update_head(&self, branch_name: &str, table_name: &str, version: u64) -> PyResult<()> {
+    /// Versions a table has gone through on a branch, newest first.
+    ///
+    /// Follows parent_version from the branch's head to the root, so
+    /// history shared with other branches is included. Empty if the
+    /// branch has no such table.
+    #[pyo3(signature = (branch, table, catalog, limit=None))]
+    fn log(
+        &self,
+        branch: &str,
+        table: &str,
+        catalog: &PyCatalog,
+        limit: Option<usize>,
+    ) -> PyResult<Vec<PyTableVersion>> {
         self.inner
-            .update_head(branch_name, table_name, version)
+            .log(branch, table, &catalog.inner, limit)
+            .map(|versions| versions.into_iter().map(Into::into).collect())
             .map_err(branch_err_to_py)
     }
 
-    /// Get the version of a table on a branch.
-    fn get_table_version(&self, branch_name: &str, table_name: &str) -> PyResult<Option<u64>> {
+    /// The version of a table a branch saw at a Unix timestamp.
+    ///
+    /// Only versions on the branch head's history are considered.
+    fn get_version_as_of(
+        &self,
+        branch: &str,
+        table: &str,
+        timestamp: i64,
+        catalog: &PyCatalog,
+    ) -> PyResult<PyTableVersion> {
         self.inner
-            .get_table_version(branch_name, table_name)
+            .get_version_as_of(branch, table, timestamp, &catalog.inner)
+            .map(Into::into)
             .map_err(branch_err_to_py)
     }
 
-    /// Compare two branches.
-    fn diff(&self, source: &str, target: &str) -> PyResult<PyBranchDiff> {
+    /// Names of the branches a branch descends from, nearest first.
+    fn ancestry(&self, branch: &str) -> PyResult<Vec<String>> {
+        self.inner.ancestry(branch).map_err(branch_err_to_py)
+    }
+
+    /// Chunk hashes referenced by branch's heads but not by other's.
+    fn exclusive_chunks(&self, branch: &str, other: &str, catalog: &PyCatalog) -> PyResult<Vec<String>> {
         self.inner
-            .diff(source, target)
-            .map(|d| d.into())
+            .exclusive_chunks(branch, other, &catalog.inner)
             .map_err(branch_err_to_py)
     }
 
-    /// Check if a fast-forward merge is possible.
-    fn can_fast_forward(&self, source: &str, target: &str) -> PyResult<bool> {
+    /// Total bytes of the chunks referenced by branch's heads, counting
+    /// shared chunks once.
+    fn branch_size(&self, branch: &str, catalog: &PyCatalog, store: &PyChunkStore) -> PyResult<u64> {
         self.inner
-            .can_fast_forward(source, target)
+            .branch_size(branch, &catalog.inner, &store.inner)
             .map_err(branch_err_to_py)
     }
 
-    /// Merge source branch into target branch (fast-forward only).
-    fn merge(&self, source: &str, into: &str) -> PyResult<()> {
+    /// Names of the branches whose head versions include the chunk, sorted.
+    fn branches_referencing_chunk(&self, hash: &str, catalog: &PyCatalog) -> PyResult<Vec<String>> {
         self.inner
-            .merge_fast_forward(source, into)
+            .branches_referencing_chunk(hash, &catalog.inner)
             .map_err(branch_err_to_py)
     }
 
+    /// Every chunk hash still referenced by a catalog version (including the
+    /// versions a dropped table kept), for PyChunkStore.garbage_collect.
+    fn live_chunks(&self, catalog: &PyCatalog) -> PyResult<Vec<String>> {
+        let mut live: Vec<String> = self
+            .inner
+            .live_chunks(&catalog.inner)
+            .map_err(branch_err_to_py)?
+            .into_iter()
+            .collect();
+        live.sort();
+        Ok(live)
+    }
+
+    /// Check that a name is usable as a branch name, without creating it.
+    ///
+    /// Returns True if valid; raises ValueError describing the problem otherwise.
+    #[staticmethod]
+    fn validate_name(name: &str) -> PyResult<bool> {
+        BranchManager::validate_name(name).map_err(branch_err_to_py)?;
+        Ok(true)
+    }
+
     /// Get the default branch name.
     fn get_default(&self) -> PyResult<Option<String>> {
         self.inner.get_default().map_err(branch_err_to_py)
@@ -1125,6 +2744,14 @@ fn tx_err_to_py(e: TransactionError) -> PyErr {
         TransactionError::Json(e) => PyValueError::new_err(format!("JSON error: {}", sanitize_error_message(&e.to_string()))),
         TransactionError::CatalogError(msg) => PyIOError::new_err(format!("Catalog error: {}", sanitize_error_message(&msg))),
         TransactionError::BranchError(msg) => PyIOError::new_err(format!("Branch error: {}", sanitize_error_message(&msg))),
+        TransactionError::InvalidCursor(_) => PyValueError::new_err("Invalid changelog cursor"),
+        TransactionError::InvalidConfig(msg) => PyValueError::new_err(format!("Invalid configuration: {}", sanitize_error_message(&msg))),
+        TransactionError::ReadOnly(id) => {
+            PyValueError::new_err(format!("Transaction {} is read-only", id))
+        }
+        TransactionError::SavepointNotFound { tx_id, savepoint } => {
+            PyValueError::new_err(format!("Savepoint {} not found in transaction {}", savepoint, tx_id))
+        }
         _ => PyRuntimeError::new_err(sanitize_error_message(&e.to_string())),
     }
 }
@@ -1203,6 +2830,40 @@ impl From<RecoveryReport> for PyRecoveryReport {
     }
 }
 
+#[pyclass]
+#[derive(Clone)]
+struct PyReconcileReport {
+    #[pyo3(get)]
+    transactions_checked: usize,
+    #[pyo3(get)]
+    versions_checked: usize,
+    #[pyo3(get)]
+    missing_in_catalog: Vec<(u64, String, u64)>,
+    #[pyo3(get)]
+    chunk_mismatches: Vec<(u64, String, u64)>,
+    #[pyo3(get)]
+    missing_in_changelog: Vec<(String, u64)>,
+    #[pyo3(get)]
+    repaired: Vec<u64>,
+    #[pyo3(get)]
+    is_consistent: bool,
+}
+
+impl From<ReconcileReport> for PyReconcileReport {
+    fn from(r: ReconcileReport) -> Self {
+        let is_consistent = r.is_consistent();
+        Self {
+            transactions_checked: r.transactions_checked,
+            versions_checked: r.versions_checked,
+            missing_in_catalog: r.missing_in_catalog,
+            chunk_mismatches: r.chunk_mismatches,
+            missing_in_changelog: r.missing_in_changelog,
+            repaired: r.repaired,
+            is_consistent,
+        }
+    }
+}
+
 // =============================================================================
 // Changelog Types
 // =============================================================================
@@ -1256,6 +2917,8 @@ struct PyChangelogEntry {
     #[pyo3(get)]
     epoch_id: u64,
     #[pyo3(get)]
+    commit_seq: u64,
+    #[pyo3(get)]
     committed_at: i64,
     #[pyo3(get)]
     branch: String,
@@ -1267,6 +2930,7 @@ impl From<ChangelogEntry> for PyChangelogEntry {
         Self {
             tx_id: entry.tx_id,
             epoch_id: entry.epoch_id,
+            commit_seq: entry.commit_seq,
             committed_at: entry.committed_at,
             branch: entry.branch,
             changes: entry.changes.iter().map(PyTableChange::from).collect(),
@@ -1452,6 +3116,42 @@ impl PyMerkleTree {
             .collect()
     }
 
+    /// Get the hashes of the chunks needed to read a byte range
+    fn chunks_for_byte_range(&self, start: u64, end: u64) -> Vec<String> {
+        self.inner.chunks_for_byte_range(start, end)
+    }
+
+    /// Serialize the tree to bytes for storage
+    fn to_bytes(&self) -> PyResult<Vec<u8>> {
+        self.inner.to_bytes().map_err(merkle_err_to_py)
+    }
+
+    /// Load a tree written by to_bytes()
+    #[staticmethod]
+    fn from_bytes(data: &[u8]) -> PyResult<PyMerkleTree> {
+        MerkleTree::from_bytes(data)
+            .map(PyMerkleTree::from)
+            .map_err(merkle_err_to_py)
+    }
+
+    /// Build an inclusion proof for the chunk at `index`
+    fn generate_proof(&self, index: usize) -> PyResult<PyMerkleProof> {
+        self.inner
+            .generate_proof(index)
+            .map(|inner| PyMerkleProof { inner })
+            .map_err(merkle_err_to_py)
+    }
+
+    /// Nodes at `max_level` or above (0 = chunks), root level first
+    fn summary(&self, max_level: u32) -> Vec<PyMerkleNode> {
+        self.inner
+            .summary(max_level)
+            .nodes
+            .iter()
+            .map(PyMerkleNode::from)
+            .collect()
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "PyMerkleTree(root={}..., chunks={}, size={}, height={})",
@@ -1463,6 +3163,48 @@ impl PyMerkleTree {
     }
 }
 
+/// Inclusion proof that a chunk belongs to a Merkle tree
+#[pyclass]
+#[derive(Clone)]
+struct PyMerkleProof {
+    inner: MerkleProof,
+}
+
+#[pymethods]
+impl PyMerkleProof {
+    /// Index of the proven chunk
+    #[getter]
+    fn chunk_index(&self) -> usize {
+        self.inner.chunk_index
+    }
+
+    /// Number of levels between the chunk and the root
+    fn depth(&self) -> usize {
+        self.inner.steps.len()
+    }
+
+    /// Serialize to JSON string
+    fn to_json(&self) -> PyResult<String> {
+        self.inner.to_json().map_err(merkle_err_to_py)
+    }
+
+    /// Deserialize from JSON string
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<PyMerkleProof> {
+        MerkleProof::from_json(json)
+            .map(|inner| PyMerkleProof { inner })
+            .map_err(merkle_err_to_py)
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "PyMerkleProof(chunk_index={}, depth={})",
+            self.inner.chunk_index,
+            self.inner.steps.len()
+        )
+    }
+}
+
 /// Result of comparing two Merkle trees
 #[pyclass]
 #[derive(Clone)]
@@ -1543,6 +3285,23 @@ impl PyMerkleConfig {
         }
     }
 
+    /// Content-defined chunking: boundaries follow the data, so inserting
+    /// bytes only changes nearby chunks.
+    ///
+    /// Args:
+    ///     min_size: Smallest chunk in bytes (except the last one)
+    ///     avg_size: Target average chunk size in bytes
+    ///     max_size: Largest chunk in bytes
+    ///     branching_factor: Tree branching factor (default: 2 for binary)
+    #[staticmethod]
+    #[pyo3(signature = (min_size, avg_size, max_size, branching_factor=2))]
+    fn content_defined(min_size: usize, avg_size: usize, max_size: usize, branching_factor: usize) -> Self {
+        Self {
+            inner: MerkleConfig::content_defined(min_size, avg_size, max_size)
+                .with_branching_factor(branching_factor),
+        }
+    }
+
     #[getter]
     fn chunk_size(&self) -> usize {
         self.inner.chunk_size
@@ -1553,11 +3312,40 @@ impl PyMerkleConfig {
         self.inner.branching_factor
     }
 
+    /// Check the config, raising ValueError if it can't build a tree
+    /// (chunk_size 0, branching_factor below 2, or bad content-defined bounds).
+    fn validate(&self) -> PyResult<()> {
+        self.inner.validate().map_err(merkle_err_to_py)
+    }
+
+    /// Number of leaf chunks build_tree produces for data_len bytes
+    /// (an estimate for content-defined chunking).
+    fn estimate_chunks(&self, data_len: usize) -> usize {
+        self.inner.estimate_chunks(data_len)
+    }
+
+    /// Tree height build_tree produces for data_len bytes (0 if empty).
+    fn estimate_height(&self, data_len: usize) -> u32 {
+        self.inner.estimate_height(data_len)
+    }
+
+    /// Whether chunk boundaries are content-defined.
+    #[getter]
+    fn content_defined_chunking(&self) -> bool {
+        matches!(self.inner.chunking, Chunking::ContentDefined { .. })
+    }
+
     fn __repr__(&self) -> String {
-        format!(
-            "PyMerkleConfig(chunk_size={}, branching_factor={})",
-            self.inner.chunk_size, self.inner.branching_factor
-        )
+        match self.inner.chunking {
+            Chunking::Fixed => format!(
+                "PyMerkleConfig(chunk_size={}, branching_factor={})",
+                self.inner.chunk_size, self.inner.branching_factor
+            ),
+            Chunking::ContentDefined { min, avg, max } => format!(
+                "PyMerkleConfig(content_defined=({}, {}, {}), branching_factor={})",
+                min, avg, max, self.inner.branching_factor
+            ),
+        }
     }
 }
 
@@ -1583,6 +3371,35 @@ fn merkle_build_tree(data: &[u8], config: Option<PyMerkleConfig>) -> PyResult<Py
         .map_err(merkle_err_to_py)
 }
 
+/// Rebuild a Merkle tree for edited data, reusing the previous tree.
+///
+/// Chunks are matched to the old leaves by content, so only changed
+/// leaves and their parents up to the root are hashed into the tree.
+///
+/// Args:
+///     old_tree: Tree built from the data before the edit
+///     new_data: The edited bytes
+///     config: Optional MerkleConfig (uses defaults if not provided)
+///
+/// Returns:
+///     PyMerkleTree equal to merkle_build_tree(new_data, config)
+///
+/// Example:
+///     >>> data[100:110] = b"x" * 10
+///     >>> tree = merkle_rebuild_tree(tree, bytes(data))
+#[pyfunction]
+#[pyo3(signature = (old_tree, new_data, config=None))]
+fn merkle_rebuild_tree(
+    old_tree: &PyMerkleTree,
+    new_data: &[u8],
+    config: Option<PyMerkleConfig>,
+) -> PyResult<PyMerkleTree> {
+    let cfg = config.map(|c| c.inner).unwrap_or_default();
+    rebuild_tree(&old_tree.inner, new_data, &cfg)
+        .map(PyMerkleTree::from)
+        .map_err(merkle_err_to_py)
+}
+
 /// Compare two Merkle trees and find differences.
 ///
 /// Args:
@@ -1600,6 +3417,25 @@ fn merkle_diff_trees(old_tree: &PyMerkleTree, new_tree: &PyMerkleTree) -> PyMerk
     PyMerkleDiff::from(diff_trees(&old_tree.inner, &new_tree.inner))
 }
 
+/// Check that a chunk belongs to the tree with the given root.
+///
+/// Args:
+///     root: Root hash of the tree
+///     chunk_hash: Hash of the chunk being proven
+///     proof: PyMerkleProof from PyMerkleTree.generate_proof
+///
+/// Returns:
+///     True if the proof links chunk_hash to root
+///
+/// Example:
+///     >>> proof = tree.generate_proof(3)
+///     >>> merkle_verify_proof(tree.root_hash, tree.chunks[3].hash, proof)
+///     True
+#[pyfunction]
+fn merkle_verify_proof(root: &str, chunk_hash: &str, proof: &PyMerkleProof) -> bool {
+    verify_proof(root, chunk_hash, &proof.inner)
+}
+
 /// Verify integrity of a Merkle tree.
 ///
 /// This function verifies that all chunk hashes match their actual data
@@ -1621,6 +3457,24 @@ fn merkle_verify_tree(tree: &PyMerkleTree, chunk_store: &PyChunkStore) -> PyResu
     }).map_err(merkle_err_to_py)
 }
 
+/// Read a byte range of a blob, fetching only the chunks that cover it.
+///
+/// Args:
+///     tree: The Merkle tree describing the blob
+///     store: A PyChunkStore holding the tree's chunks
+///     start: Start byte offset (inclusive)
+///     end: End byte offset (exclusive, clamped to the blob size)
+///
+/// Returns:
+///     The exact bytes in [start, end)
+///
+/// Raises:
+///     ValueError: If a needed chunk is missing
+#[pyfunction]
+fn merkle_read_range(tree: &PyMerkleTree, store: &PyChunkStore, start: u64, end: u64) -> PyResult<Vec<u8>> {
+    read_range(&tree.inner, &store.inner, start, end).map_err(merkle_err_to_py)
+}
+
 // =============================================================================
 // Transaction Manager
 // =============================================================================
@@ -1639,22 +3493,38 @@ impl PyTransactionManager {
     ///     catalog_path: Path to catalog directory
     ///     branch_path: Optional path to branch manager directory
     ///     auto_recover: If True, run recovery on startup (default: False)
+    ///     conflict_window: "all" checks commits against every recently
+    ///         committed transaction; "epoch" only against those committed
+    ///         after the committing transaction began (default: "all")
+    ///     timeout_secs: Age in seconds after which reap_expired() aborts
+    ///         an active transaction (default: None, never)
     #[new]
-    #[pyo3(signature = (base_path, catalog_path, branch_path=None, auto_recover=false))]
+    #[pyo3(signature = (base_path, catalog_path, branch_path=None, auto_recover=false, conflict_window="all", timeout_secs=None))]
     fn new(
         base_path: &str,
         catalog_path: &str,
         branch_path: Option<&str>,
         auto_recover: bool,
+        conflict_window: &str,
+        timeout_secs: Option<f64>,
     ) -> PyResult<Self> {
+        let window = ConflictWindow::from_str(conflict_window).map_err(tx_err_to_py)?;
+        let timeout = timeout_secs
+            .map(|secs| {
+                std::time::Duration::try_from_secs_f64(secs)
+                    .map_err(|_| PyValueError::new_err(format!("Invalid timeout_secs: {}", secs)))
+            })
+            .transpose()?;
         let catalog = Arc::new(FileCatalog::new(catalog_path).map_err(catalog_err_to_py)?);
         let branch_manager = match branch_path {
             Some(p) => Some(Arc::new(BranchManager::new(p).map_err(branch_err_to_py)?)),
             None => None,
         };
 
-        let inner = TransactionManager::new(base_path, catalog, branch_manager)
+        let mut inner = TransactionManager::new(base_path, catalog, branch_manager)
             .map_err(tx_err_to_py)?;
+        inner.set_conflict_window(window);
+        inner.set_tx_timeout(timeout);
 
         // Optionally run recovery on startup
         if auto_recover {
@@ -1664,6 +3534,38 @@ impl PyTransactionManager {
         Ok(Self { inner: Arc::new(inner) })
     }
 
+    /// Return another handle to the same manager.
+    ///
+    /// Handles share state, so each Python thread can hold its own handle
+    /// instead of reopening the manager.
+    fn clone_handle(&self) -> Self {
+        Self { inner: Arc::clone(&self.inner) }
+    }
+
+    /// Conflict window in use ("all" or "epoch").
+    #[getter]
+    fn conflict_window(&self) -> &'static str {
+        match self.inner.conflict_window() {
+            ConflictWindow::All => "all",
+            ConflictWindow::Epoch => "epoch",
+        }
+    }
+
+    /// Abort every active transaction older than timeout_secs.
+    ///
+    /// Transactions that are in the middle of committing are left alone.
+    ///
+    /// Returns:
+    ///     Sorted IDs of the aborted transactions (empty without a timeout)
+    fn reap_expired(&self) -> PyResult<Vec<u64>> {
+        self.inner.reap_expired().map_err(tx_err_to_py)
+    }
+
+    /// Start a new epoch and return its ID.
+    fn advance_epoch(&self) -> PyResult<u64> {
+        self.inner.advance_epoch().map_err(tx_err_to_py)
+    }
+
     /// Begin a new transaction.
     ///
     /// Args:
@@ -1676,6 +3578,59 @@ impl PyTransactionManager {
         self.inner.begin(branch).map_err(tx_err_to_py)
     }
 
+    /// Begin a speculative transaction, kept entirely in memory.
+    ///
+    /// Works like begin(), but nothing is written to the transaction log
+    /// until commit, so abort is free. Committing promotes it to a regular
+    /// transaction with a new ID.
+    ///
+    /// Args:
+    ///     branch: Optional branch name (default: current branch)
+    ///
+    /// Returns:
+    ///     Speculative transaction ID
+    #[pyo3(signature = (branch=None))]
+    fn begin_speculative(&self, branch: Option<&str>) -> PyResult<u64> {
+        self.inner.begin_speculative(branch).map_err(tx_err_to_py)
+    }
+
+    /// Begin a read-only transaction pinned to the current table versions.
+    ///
+    /// Every table resolved with snapshot_version() reflects the same point
+    /// in time, even if other transactions commit meanwhile. Writes are
+    /// rejected; commit() or abort() releases the snapshot.
+    ///
+    /// Args:
+    ///     branch: Optional branch name (default: current branch)
+    ///
+    /// Returns:
+    ///     Transaction ID
+    #[pyo3(signature = (branch=None))]
+    fn begin_read_only(&self, branch: Option<&str>) -> PyResult<u64> {
+        self.inner.begin_read_only(branch).map_err(tx_err_to_py)
+    }
+
+    /// Version of a table pinned by an active transaction, or None if the
+    /// table did not exist when it began.
+    fn snapshot_version(&self, tx_id: u64, table_name: &str) -> PyResult<Option<u64>> {
+        self.inner.snapshot_version(tx_id, table_name).map_err(tx_err_to_py)
+    }
+
+    /// Whether tx_id is an active speculative transaction.
+    fn is_speculative(&self, tx_id: u64) -> PyResult<bool> {
+        self.inner.is_speculative(tx_id).map_err(tx_err_to_py)
+    }
+
+    /// Turn a speculative transaction into a logged one, returning its new ID.
+    fn promote(&self, tx_id: u64) -> PyResult<u64> {
+        self.inner.promote(tx_id).map_err(tx_err_to_py)
+    }
+
+    /// Raise ValueError if committing tx_id now would conflict.
+    fn check_conflicts(&self, tx_id: u64) -> PyResult<()> {
+        self.inner.check_conflicts(tx_id).map_err(tx_err_to_py)
+    }
+
     /// Add a write to a transaction.
     ///
     /// Args:
@@ -1683,17 +3638,42 @@ impl PyTransactionManager {
     ///     table_name: Table being written
     ///     new_version: New version number
     ///     chunk_hashes: List of chunk hashes
+    ///     key_range: Optional inclusive (min, max) primary keys written.
+    ///         The write then only conflicts with writes to the same table
+    ///         whose ranges overlap; without it, any write to the table
+    ///         conflicts.
+    #[pyo3(signature = (tx_id, table_name, new_version, chunk_hashes, key_range=None))]
     fn add_write(
         &self,
         tx_id: u64,
         table_name: &str,
         new_version: u64,
         chunk_hashes: Vec<String>,
+        key_range: Option<(Bound<'_, PyAny>, Bound<'_, PyAny>)>,
     ) -> PyResult<()> {
-        let write = TableWrite::new(table_name, new_version, chunk_hashes);
+        let mut write = TableWrite::new(table_name, new_version, chunk_hashes);
+        if let Some((min, max)) = key_range {
+            write = write.with_key_range(PyScalarValue::new(&min)?.inner, PyScalarValue::new(&max)?.inner);
+        }
         self.inner.add_write(tx_id, write).map_err(tx_err_to_py)
     }
 
+    /// Mark the transaction's current writes and reads.
+    ///
+    /// Returns:
+    ///     Savepoint ID to pass to rollback_to()
+    fn savepoint(&self, tx_id: u64) -> PyResult<u64> {
+        self.inner.savepoint(tx_id).map_err(tx_err_to_py)
+    }
+
+    /// Undo every write and read recorded since a savepoint.
+    ///
+    /// The transaction stays active and the savepoint stays valid;
+    /// savepoints taken after it are discarded.
+    fn rollback_to(&self, tx_id: u64, savepoint: u64) -> PyResult<()> {
+        self.inner.rollback_to(tx_id, savepoint).map_err(tx_err_to_py)
+    }
+
     /// Record a read for conflict detection.
     ///
     /// Args:
@@ -1709,6 +3689,18 @@ impl PyTransactionManager {
         self.inner.record_read(tx_id, table_name, version).map_err(tx_err_to_py)
     }
 
+    /// Latest version of a table on the transaction's branch, recorded as a read.
+    ///
+    /// Args:
+    ///     tx_id: Transaction ID
+    ///     table_name: Table being read
+    ///
+    /// Returns:
+    ///     The latest version, or None if the table does not exist yet
+    fn latest_version_or_none(&self, tx_id: u64, table_name: &str) -> PyResult<Option<u64>> {
+        self.inner.latest_version_or_none(tx_id, table_name).map_err(tx_err_to_py)
+    }
+
     /// Commit a transaction.
     ///
     /// Args:
@@ -1720,6 +3712,51 @@ impl PyTransactionManager {
         self.inner.commit(tx_id).map_err(tx_err_to_py)
     }
 
+    /// Commit, retrying conflicts with writes rebuilt from a fresh snapshot.
+    ///
+    /// After a conflict the attempt is aborted and a new transaction begun
+    /// on the same branch; rebuild_callback is called with its snapshot
+    /// ({table: version}) and must return the writes to commit instead, as
+    /// (table_name, new_version, chunk_hashes) tuples. A write conflict is
+    /// only retried with conflict_window="epoch"; under "all" it would recur
+    /// on every attempt, so it is raised at once.
+    ///
+    /// Args:
+    ///     tx_id: Transaction ID
+    ///     max_attempts: Total commits to try, including the first
+    ///     rebuild_callback: Callable taking the snapshot dict
+    ///
+    /// Returns:
+    ///     Number of attempts used
+    ///
+    /// tx_id is aborted after its first conflict; retries commit from new
+    /// transactions, each checked only against commits made after it began.
+    ///
+    /// Raises:
+    ///     ValueError: If every attempt conflicted
+    fn commit_with_retry(&self, tx_id: u64, max_attempts: usize, rebuild_callback: Py<PyAny>) -> PyResult<usize> {
+        let mut callback_err = None;
+        let result = self.inner.commit_with_retry(tx_id, max_attempts, |snapshot| {
+            Python::attach(|py| -> PyResult<Vec<TableWrite>> {
+                let writes: Vec<(String, u64, Vec<String>)> =
+                    rebuild_callback.call1(py, (snapshot.clone(),))?.extract(py)?;
+                Ok(writes
+                    .into_iter()
+                    .map(|(table, version, chunks)| TableWrite::new(table, version, chunks))
+                    .collect())
+            })
+            .map_err(|e| {
+                let actual = e.to_string();
+                callback_err = Some(e);
+                TransactionError::InvalidState { expected: "rebuilt writes".to_string(), actual }
+            })
+        });
+        match callback_err {
+            Some(e) => Err(e),
+            None => result.map_err(tx_err_to_py),
+        }
+    }
+
     /// Abort a transaction.
     ///
     /// Args:
@@ -1755,6 +3792,14 @@ impl PyTransactionManager {
             .map_err(tx_err_to_py)
     }
 
+    /// Tables being written by active transactions.
+    ///
+    /// Returns:
+    ///     Dict mapping table name to the sorted ids of the transactions writing it
+    fn write_set_map(&self) -> PyResult<HashMap<String, Vec<u64>>> {
+        self.inner.write_set_map().map_err(tx_err_to_py)
+    }
+
     /// Get count of active transactions.
     fn active_count(&self) -> PyResult<usize> {
         self.inner.active_count().map_err(tx_err_to_py)
@@ -1805,6 +3850,44 @@ impl PyTransactionManager {
             .map_err(tx_err_to_py)
     }
 
+    /// Operation counters and cumulative timings.
+    ///
+    /// Returns:
+    ///     Dict with keys begins, begin_nanos, commits, commit_nanos,
+    ///     conflicts, aborts, recoveries, recover_nanos
+    fn metrics(&self) -> HashMap<String, u64> {
+        let m = self.inner.metrics();
+        HashMap::from([
+            ("begins".to_string(), m.begins),
+            ("begin_nanos".to_string(), m.begin_nanos),
+            ("commits".to_string(), m.commits),
+            ("commit_nanos".to_string(), m.commit_nanos),
+            ("conflicts".to_string(), m.conflicts),
+            ("aborts".to_string(), m.aborts),
+            ("recoveries".to_string(), m.recoveries),
+            ("recover_nanos".to_string(), m.recover_nanos),
+        ])
+    }
+
+    /// Cross-check the changelog against the catalog.
+    ///
+    /// Reports committed writes missing from the catalog, chunk hash
+    /// mismatches, and catalog versions with no changelog entry.
+    ///
+    /// Args:
+    ///     repair: Append reconstructed changelog entries for catalog
+    ///         versions that have none
+    ///
+    /// Returns:
+    ///     PyReconcileReport describing the discrepancies found
+    #[pyo3(signature = (repair=false))]
+    fn reconcile_changelog(&self, repair: bool) -> PyResult<PyReconcileReport> {
+        self.inner
+            .reconcile_changelog(repair)
+            .map(|r| r.into())
+            .map_err(tx_err_to_py)
+    }
+
     // =========================================================================
     // Changelog Methods
     // =========================================================================
@@ -1821,11 +3904,26 @@ impl PyTransactionManager {
     ///     since_timestamp: Start from this Unix timestamp
     ///     tables: Filter to specific tables
     ///     branch: Filter to specific branch
-    ///     limit: Maximum entries to return
+    ///     limit: Maximum entries to return (newest ones when descending)
+    ///     after_cursor: Resume after the cursor returned with a previous page
+    ///         of a query in the same direction
+    ///     descending: Return newest entries first
+    ///     until_tx_id: Stop at this transaction (inclusive)
+    ///     until_timestamp: Stop before this Unix timestamp (exclusive)
     ///
     /// Returns:
-    ///     List of PyChangelogEntry objects
-    #[pyo3(signature = (since_tx_id=None, since_timestamp=None, tables=None, branch=None, limit=None))]
+    ///     Tuple of (list of PyChangelogEntry in commit order, next cursor).
+    ///     Pass the cursor back as after_cursor to fetch the next page; it is
+    ///     None only when nothing matched and no cursor was given.
+    ///
+    /// Raises:
+    ///     ValueError: If after_cursor is not a valid cursor, or was returned
+    ///         by a query in the other direction
+    #[pyo3(signature = (
+        since_tx_id=None, since_timestamp=None, tables=None, branch=None, limit=None,
+        after_cursor=None, descending=false, until_tx_id=None, until_timestamp=None,
+    ))]
+    #[allow(clippy::too_many_arguments)]
     fn get_changelog(
         &self,
         since_tx_id: Option<u64>,
@@ -1833,7 +3931,11 @@ impl PyTransactionManager {
         tables: Option<Vec<String>>,
         branch: Option<String>,
         limit: Option<usize>,
-    ) -> PyResult<Vec<PyChangelogEntry>> {
+        after_cursor: Option<String>,
+        descending: bool,
+        until_tx_id: Option<u64>,
+        until_timestamp: Option<i64>,
+    ) -> PyResult<(Vec<PyChangelogEntry>, Option<String>)> {
         // Build query
         let mut query = ChangelogQuery::new();
         if let Some(tx_id) = since_tx_id {
@@ -1842,6 +3944,15 @@ impl PyTransactionManager {
         if let Some(ts) = since_timestamp {
             query = query.since_time(ts);
         }
+        if let Some(tx_id) = until_tx_id {
+            query = query.until_tx(tx_id);
+        }
+        if let Some(ts) = until_timestamp {
+            query = query.until_time(ts);
+        }
+        if descending {
+            query = query.descending();
+        }
         if let Some(t) = tables {
             query = query.for_tables(t);
         }
@@ -1851,13 +3962,51 @@ impl PyTransactionManager {
         if let Some(l) = limit {
             query = query.with_limit(l);
         }
+        if let Some(token) = after_cursor {
+            query = query.with_cursor(ChangelogCursor::decode(&token).map_err(tx_err_to_py)?);
+        }
 
         // Execute query
-        let entries = self.inner.get_changelog(query).map_err(tx_err_to_py)?;
+        let (entries, next) = self.inner.get_changelog_page(query).map_err(tx_err_to_py)?;
+
+        Ok((
+            entries.into_iter().map(PyChangelogEntry::from).collect(),
+            next.map(|c| c.encode()),
+        ))
+    }
 
+    /// Changelog entries from several branches, interleaved by commit time.
+    ///
+    /// Args:
+    ///     branches: Branch names to include
+    ///     since_timestamp: Unix timestamp in seconds (inclusive)
+    ///
+    /// Returns:
+    ///     List of PyChangelogEntry sorted by committed_at, then tx_id
+    fn merged_changelog(
+        &self,
+        branches: Vec<String>,
+        since_timestamp: i64,
+    ) -> PyResult<Vec<PyChangelogEntry>> {
+        let branches: Vec<&str> = branches.iter().map(String::as_str).collect();
+        let entries = self
+            .inner
+            .merged_changelog(&branches, since_timestamp)
+            .map_err(tx_err_to_py)?;
         Ok(entries.into_iter().map(PyChangelogEntry::from).collect())
     }
 
+    /// Tables changed on any branch since a timestamp.
+    ///
+    /// Args:
+    ///     since_timestamp: Unix timestamp in seconds (inclusive)
+    ///
+    /// Returns:
+    ///     List of (branch, table, new_version) tuples in commit order
+    fn recently_modified(&self, since_timestamp: i64) -> PyResult<Vec<(String, String, u64)>> {
+        self.inner.recently_modified(since_timestamp).map_err(tx_err_to_py)
+    }
+
     /// Get the latest committed transaction ID.
     ///
     /// Returns None if no transactions have been committed yet.
@@ -1869,6 +4018,81 @@ impl PyTransactionManager {
     }
 }
 
+// =============================================================================
+// Repository
+// =============================================================================
+
+/// Convert RepositoryError to appropriate Python exception
+fn repo_err_to_py(e: RepositoryError) -> PyErr {
+    match e {
+        RepositoryError::AlreadyInitialized(p) => {
+            PyValueError::new_err(format!("Repository already initialized: {}", sanitize_error_message(&p)))
+        }
+        RepositoryError::NotInitialized(p) => {
+            PyIOError::new_err(format!("Not a repository: {}", sanitize_error_message(&p)))
+        }
+        RepositoryError::NotEmpty(p) => {
+            PyIOError::new_err(format!("Directory is not empty: {}", sanitize_error_message(&p)))
+        }
+        RepositoryError::Io(e) => PyIOError::new_err(sanitize_io_error(&e)),
+        RepositoryError::ChunkStore(e) => chunk_err_to_py(e),
+        RepositoryError::Catalog(e) => catalog_err_to_py(e),
+        RepositoryError::Branch(e) => branch_err_to_py(e),
+        RepositoryError::Transaction(e) => tx_err_to_py(e),
+    }
+}
+
+/// A repository directory with its chunk store, catalog, branches and
+/// transaction manager wired together.
+///
+/// Opens the repository at `path`, creating the layout (chunks/, catalog/,
+/// branches/, transactions/) if the directory is missing or empty.
+///
+/// Example:
+///     >>> repo = PyRepository("./data")
+///     >>> h = repo.chunks.put(b"...")
+///     >>> tx = repo.transactions.begin()
+#[pyclass]
+struct PyRepository {
+    #[pyo3(get)]
+    path: String,
+    #[pyo3(get)]
+    chunks: Py<PyChunkStore>,
+    #[pyo3(get)]
+    catalog: Py<PyCatalog>,
+    #[pyo3(get)]
+    branches: Py<PyBranchManager>,
+    #[pyo3(get)]
+    transactions: Py<PyTransactionManager>,
+}
+
+#[pymethods]
+impl PyRepository {
+    #[new]
+    fn new(py: Python<'_>, path: &str) -> PyResult<Self> {
+        let repo = match Repository::open(path) {
+            Err(RepositoryError::NotInitialized(_)) => Repository::init(path),
+            result => result,
+        }
+        .map_err(repo_err_to_py)?;
+
+        // Share the repository's handles so Python and Rust see one set of caches
+        Ok(Self {
+            path: repo.path().display().to_string(),
+            chunks: Py::new(py, PyChunkStore { inner: repo.chunks().clone() })?,
+            catalog: Py::new(py, PyCatalog { inner: repo.catalog().clone() })?,
+            branches: Py::new(py, PyBranchManager { inner: repo.branches().clone() })?,
+            transactions: Py::new(py, PyTransactionManager {
+                inner: repo.transactions().clone(),
+            })?,
+        })
+    }
+
+    fn __repr__(&self) -> String {
+        format!("PyRepository('{}')", self.path)
+    }
+}
+
 // =============================================================================
 // Algebraic Classification Types
 // =============================================================================
@@ -1883,6 +4107,9 @@ impl PyTransactionManager {
 ///   - SemilatticeMin: min(a, b) - first-writer-wins
 ///   - SemilatticeUnion: set union - add-only sets
 ///   - SemilatticeIntersect: set intersection
+///   - LwwRegister: highest timestamp wins - last-writer-wins registers
+///   - OrSetAdd / OrSetRemove: observed-remove sets - add wins over concurrent remove
+///   - MapMerge: field-by-field map merge - nested metadata
 ///   - AbelianAdd: a + b - counters, deltas
 ///   - AbelianMultiply: a * b - scaling factors
 ///
@@ -1900,8 +4127,9 @@ struct PyOpType {
 impl PyOpType {
     /// Create an operation type from a string.
     ///
-    /// Valid values: "max", "min", "union", "intersect", "add", "multiply",
-    ///               "overwrite", "conditional", "unknown"
+    /// Valid values: "max", "min", "union", "intersect", "lww", "orset_add",
+    ///               "orset_remove", "map", "add", "multiply", "overwrite",
+    ///               "conditional", "unknown"
     #[new]
     fn new(op_type: &str) -> PyResult<Self> {
         let inner = match op_type.to_lowercase().as_str() {
@@ -1909,13 +4137,17 @@ impl PyOpType {
             "min" | "semilattice_min" => OpType::SemilatticeMin,
             "union" | "semilattice_union" => OpType::SemilatticeUnion,
             "intersect" | "semilattice_intersect" => OpType::SemilatticeIntersect,
+            "lww" | "lww_register" => OpType::LwwRegister,
+            "orset_add" | "or_set_add" => OpType::OrSetAdd,
+            "orset_remove" | "or_set_remove" => OpType::OrSetRemove,
+            "map" | "map_merge" => OpType::MapMerge,
             "add" | "abelian_add" => OpType::AbelianAdd,
             "multiply" | "abelian_multiply" => OpType::AbelianMultiply,
             "overwrite" | "generic_overwrite" => OpType::GenericOverwrite,
             "conditional" | "generic_conditional" => OpType::GenericConditional,
             "unknown" => OpType::Unknown,
             _ => return Err(PyValueError::new_err(format!(
-                "Invalid operation type: '{}'. Valid: max, min, union, intersect, add, multiply, overwrite, conditional, unknown",
+                "Invalid operation type: '{}'. Valid: max, min, union, intersect, lww, orset_add, orset_remove, map, add, multiply, overwrite, conditional, unknown",
                 op_type
             ))),
         };
@@ -1973,11 +4205,25 @@ impl PyAlgebraicValue {
     ///   - bool → Boolean
     ///   - list/set of str → StringSet
     ///   - list/set of int → IntSet
+    ///   - dict of str → value → Map (values may be PyAlgebraicValue or
+    ///     anything accepted here, including nested dicts)
     ///   - None → Null
     #[new]
     fn new(value: &Bound<'_, PyAny>) -> PyResult<Self> {
         let inner = if value.is_none() {
             AlgebraicValue::Null
+        } else if let Ok(entries) =
+            value.extract::<std::collections::BTreeMap<String, Bound<'_, PyAny>>>()
+        {
+            let mut map = std::collections::BTreeMap::new();
+            for (key, item) in entries {
+                let item = match item.extract::<PyRef<'_, PyAlgebraicValue>>() {
+                    Ok(v) => v.inner.clone(),
+                    Err(_) => Self::new(&item)?.inner,
+                };
+                map.insert(key, item);
+            }
+            AlgebraicValue::Map(map)
         } else if let Ok(v) = value.extract::<bool>() {
             AlgebraicValue::Boolean(v)
         } else if let Ok(v) = value.extract::<i64>() {
@@ -1994,7 +4240,7 @@ impl PyAlgebraicValue {
             AlgebraicValue::IntSet(v.into_iter().collect())
         } else {
             return Err(PyValueError::new_err(
-                "Unsupported value type. Use int, float, bool, set/list of str, set/list of int, or None"
+                "Unsupported value type. Use int, float, bool, set/list of str, set/list of int, dict, or None"
             ));
         };
         Ok(Self { inner })
@@ -2036,6 +4282,62 @@ impl PyAlgebraicValue {
         Self { inner: AlgebraicValue::Null }
     }
 
+    /// Create a timestamped value for a last-writer-wins ("lww") register.
+    ///
+    /// Args:
+    ///     ts: Write timestamp; the larger timestamp wins on merge
+    ///     value: A PyAlgebraicValue, or any value accepted by the constructor
+    #[staticmethod]
+    fn timestamped(ts: u64, value: &Bound<'_, PyAny>) -> PyResult<Self> {
+        let value = match value.extract::<PyRef<'_, PyAlgebraicValue>>() {
+            Ok(v) => v.inner.clone(),
+            Err(_) => Self::new(value)?.inner,
+        };
+        Ok(Self { inner: AlgebraicValue::timestamped(ts, value) })
+    }
+
+    /// Create an observed-remove set ("orset_add"/"orset_remove") state.
+    ///
+    /// Args:
+    ///     adds: (element, tag) pairs; every add needs a tag no other add uses
+    ///     removed: Add-tags cancelled by removes
+    #[staticmethod]
+    #[pyo3(signature = (adds=Vec::new(), removed=Vec::new()))]
+    fn or_set(adds: Vec<(String, String)>, removed: Vec<String>) -> Self {
+        let mut grouped: std::collections::BTreeMap<String, std::collections::BTreeSet<String>> =
+            std::collections::BTreeMap::new();
+        for (element, tag) in adds {
+            grouped.entry(element).or_default().insert(tag);
+        }
+        Self {
+            inner: AlgebraicValue::OrSet {
+                adds: grouped,
+                removed: removed.into_iter().collect(),
+            },
+        }
+    }
+
+    /// Build the remove for `element` as observed in this OR-Set.
+    ///
+    /// Raises:
+    ///     ValueError: If this is not an OR-Set
+    fn observed_remove(&self, element: &str) -> PyResult<Self> {
+        self.inner
+            .observed_remove(element)
+            .map(|inner| Self { inner })
+            .ok_or_else(|| PyValueError::new_err(format!(
+                "observed_remove needs an OrSet, got {}",
+                self.inner.type_name()
+            )))
+    }
+
+    /// Elements present in an OR-Set (sorted), or None for other types.
+    fn or_set_elements(&self) -> Option<Vec<String>> {
+        self.inner
+            .or_set_elements()
+            .map(|elements| elements.into_iter().map(String::from).collect())
+    }
+
     /// Check if this is a numeric type.
     fn is_numeric(&self) -> bool {
         self.inner.is_numeric()
@@ -2097,6 +4399,36 @@ fn algebraic_merge(
     }
 }
 
+/// Merge the algebraic column values of several branches.
+///
+/// Each column is folded across every branch that has it, using the
+/// column's op type from the schema.
+///
+/// Args:
+///     branches_data: One dict of column -> PyAlgebraicValue per branch
+///     schema: Table schema giving each column's op type
+///
+/// Returns:
+///     Dict of column -> merged value
+///
+/// Raises:
+///     ValueError: If a column's values conflict or have mismatched types
+#[pyfunction]
+fn algebraic_merge_many(
+    branches_data: Vec<HashMap<String, PyAlgebraicValue>>,
+    schema: &PyTableAlgebraicSchema,
+) -> PyResult<HashMap<String, PyAlgebraicValue>> {
+    let data: Vec<HashMap<String, AlgebraicValue>> = branches_data
+        .into_iter()
+        .map(|branch| branch.into_iter().map(|(k, v)| (k, v.inner)).collect())
+        .collect();
+    let merged = MergeAnalyzer::algebraic_merge_many(&data, &schema.inner).map_err(branch_err_to_py)?;
+    Ok(merged
+        .into_iter()
+        .map(|(k, inner)| (k, PyAlgebraicValue { inner }))
+        .collect())
+}
+
 /// Schema-level algebraic configuration for a table.
 #[pyclass]
 #[derive(Clone)]
@@ -2138,7 +4470,42 @@ impl PyTableAlgebraicSchema {
         self.inner.add_column(column, op_type.inner);
     }
 
-    /// Get the operation type for a column.
+    /// Add a column merged by a Python callable.
+    ///
+    /// The callable receives two PyAlgebraicValue arguments and must return
+    /// the merged PyAlgebraicValue. Custom columns count as conflict-free,
+    /// so the callable must be commutative. A raised exception or a
+    /// non-PyAlgebraicValue result is reported as a merge conflict.
+    ///
+    /// Args:
+    ///     column: Column name
+    ///     merge: Callable (a, b) -> PyAlgebraicValue
+    fn add_custom_column(&mut self, column: &str, merge: Py<PyAny>) {
+        self.inner.add_custom_column(
+            column,
+            Box::new(move |a, b| {
+                Python::attach(|py| {
+                    let args = (
+                        PyAlgebraicValue { inner: a.clone() },
+                        PyAlgebraicValue { inner: b.clone() },
+                    );
+                    match merge
+                        .call1(py, args)
+                        .and_then(|result| result.extract::<PyAlgebraicValue>(py))
+                    {
+                        Ok(merged) => MergeResult::Merged(merged.inner),
+                        Err(e) => MergeResult::Conflict {
+                            value1: a.clone(),
+                            value2: b.clone(),
+                            reason: format!("Custom merge failed: {}", e),
+                        },
+                    }
+                })
+            }),
+        );
+    }
+
+    /// Get the operation type for a column (Unknown for a custom column).
     fn get_op_type(&self, column: &str) -> PyOpType {
         PyOpType { inner: self.inner.get_op_type(column) }
     }
@@ -2202,7 +4569,7 @@ impl PyAlgebraicSchemaRegistry {
         self.inner.get(table).map(|s| PyTableAlgebraicSchema { inner: s.clone() })
     }
 
-    /// Get the operation type for a table/column.
+    /// Get the operation type for a table/column (Unknown for a custom column).
     fn get_op_type(&self, table: &str, column: &str) -> PyOpType {
         PyOpType { inner: self.inner.get_op_type(table, column) }
     }
@@ -2241,6 +4608,7 @@ struct PyMergeAnalysis {
     target_only: Vec<String>,
     #[pyo3(get)]
     unchanged: Vec<String>,
+    conflicting_detail: HashMap<String, Vec<String>>,
 }
 
 impl From<MergeAnalysis> for PyMergeAnalysis {
@@ -2251,6 +4619,7 @@ impl From<MergeAnalysis> for PyMergeAnalysis {
             source_only: a.source_only,
             target_only: a.target_only,
             unchanged: a.unchanged,
+            conflicting_detail: a.conflicting_detail,
         }
     }
 }
@@ -2262,6 +4631,15 @@ impl PyMergeAnalysis {
         self.conflicting.is_empty()
     }
 
+    /// Conflicting columns of a conflicting table, sorted.
+    ///
+    /// An empty list means the whole table conflicts (no schema, or a
+    /// conflicting default op type). Returns None if the table doesn't
+    /// conflict.
+    fn conflicting_columns(&self, table: &str) -> Option<Vec<String>> {
+        self.conflicting_detail.get(table).cloned()
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "PyMergeAnalysis(auto_mergeable={}, conflicting={}, source_only={}, target_only={})",
@@ -2288,9 +4666,13 @@ struct PyMergeOutcome {
     #[pyo3(get)]
     conflicts: Vec<String>,
     #[pyo3(get)]
+    resolved: Vec<String>,
+    #[pyo3(get)]
     success: bool,
     #[pyo3(get)]
     description: Option<String>,
+    #[pyo3(get)]
+    provenance: HashMap<String, Vec<u64>>,
 }
 
 impl From<MergeOutcome> for PyMergeOutcome {
@@ -2301,8 +4683,10 @@ impl From<MergeOutcome> for PyMergeOutcome {
             fast_forwarded: o.fast_forwarded,
             algebraically_merged: o.algebraically_merged,
             conflicts: o.conflicts,
+            resolved: o.resolved,
             success: o.success,
             description: o.description,
+            provenance: o.provenance,
         }
     }
 }
@@ -2446,6 +4830,11 @@ impl PyVectorClock {
         self.inner.tick(&node_id.inner);
     }
 
+    /// Advance this node's logical time by n (same as n calls to tick).
+    fn tick_n(&mut self, node_id: &PyNodeId, n: u64) {
+        self.inner.tick_n(&node_id.inner, n);
+    }
+
     /// Get the logical time for a specific node.
     fn get(&self, node_id: &PyNodeId) -> u64 {
         self.inner.get(&node_id.inner)
@@ -2463,6 +4852,12 @@ impl PyVectorClock {
         self.inner.merge(&other.inner);
     }
 
+    /// Merge several clocks into this one (same as merging each in turn).
+    fn merge_all(&mut self, clocks: Vec<PyRef<PyVectorClock>>) {
+        let others: Vec<&VectorClock> = clocks.iter().map(|c| &c.inner).collect();
+        self.inner.merge_all(&others);
+    }
+
     /// Check if this clock happened strictly before another clock.
     fn happened_before(&self, other: &PyVectorClock) -> bool {
         self.inner.happened_before(&other.inner)
@@ -2663,6 +5058,45 @@ impl PyAlgebraicTransaction {
     }
 }
 
+/// A key whose conflicting op types were repaired during a merge.
+#[pyclass]
+#[derive(Clone)]
+struct PyRepairEvent {
+    #[pyo3(get)]
+    key: String,
+    registered: OpType,
+    observed: Vec<OpType>,
+}
+
+#[pymethods]
+impl PyRepairEvent {
+    /// Op type from the schema registry, used for the re-merge
+    #[getter]
+    fn registered(&self) -> PyOpType {
+        PyOpType { inner: self.registered }
+    }
+
+    /// Distinct op types found on the incoming operations
+    #[getter]
+    fn observed(&self) -> Vec<PyOpType> {
+        self.observed.iter().map(|&op| PyOpType { inner: op }).collect()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("PyRepairEvent(key='{}', registered={}, observed={:?})", self.key, self.registered, self.observed)
+    }
+}
+
+impl From<RepairEvent> for PyRepairEvent {
+    fn from(e: RepairEvent) -> Self {
+        Self {
+            key: e.key,
+            registered: e.registered,
+            observed: e.observed,
+        }
+    }
+}
+
 /// The result of a local commit: operations with their causal context.
 ///
 /// A VersionedUpdate represents a committed set of operations along with
@@ -2708,6 +5142,39 @@ impl PyVersionedUpdate {
         self.inner.update_id().map(|s| s.to_string())
     }
 
+    /// Get the application metadata carried from the source transaction.
+    #[getter]
+    fn metadata(&self) -> HashMap<String, String> {
+        self.inner.metadata().clone()
+    }
+
+    /// Metadata keys both updates set to different values, sorted.
+    ///
+    /// Merging keeps the greatest value for each such key.
+    fn metadata_conflicts(&self, other: &PyVersionedUpdate) -> Vec<String> {
+        self.inner.metadata_conflicts(&other.inner)
+    }
+
+    /// Serialize to bytes for transport between nodes.
+    fn to_bytes<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, pyo3::types::PyBytes>> {
+        let data = self
+            .inner
+            .to_bytes()
+            .map_err(|e| PyValueError::new_err(sanitize_error_message(&e.to_string())))?;
+        Ok(pyo3::types::PyBytes::new(py, &data))
+    }
+
+    /// Deserialize an update produced by `to_bytes`.
+    ///
+    /// Raises:
+    ///     ValueError: If the data is not a valid update
+    #[staticmethod]
+    fn from_bytes(data: &[u8]) -> PyResult<Self> {
+        VersionedUpdate::from_bytes(data)
+            .map(|inner| Self { inner })
+            .map_err(|e| PyValueError::new_err(sanitize_error_message(&e.to_string())))
+    }
+
     /// Compare this update's causality with another.
     fn compare(&self, other: &PyVersionedUpdate) -> PyCausalOrder {
         PyCausalOrder::from(self.inner.compare(&other.inner))
@@ -2767,19 +5234,43 @@ impl PyLocalCommitProtocol {
     ///     tx: The transaction to commit
     ///     node_id: This node's ID
     ///     clock: This node's vector clock (will be mutated)
+    ///     registry: Optional schema registry; each key must use the op type
+    ///         registered for that column of `table`
+    ///     table: Table whose schema the keys are checked against
+    ///         (required with `registry`)
+    ///     strict: Also reject keys with no registered op type
     ///
     /// Returns:
     ///     The committed update with causal context
     ///
     /// Raises:
-    ///     ValueError: If the transaction cannot be committed locally
+    ///     ValueError: If the transaction cannot be committed locally or an
+    ///         op type conflicts with the registry
     #[staticmethod]
+    #[pyo3(signature = (tx, node_id, clock, registry=None, table=None, strict=false))]
     fn commit_local(
         tx: &PyAlgebraicTransaction,
         node_id: &PyNodeId,
         clock: &mut PyVectorClock,
+        registry: Option<&PyAlgebraicSchemaRegistry>,
+        table: Option<&str>,
+        strict: bool,
     ) -> PyResult<PyVersionedUpdate> {
-        LocalCommitProtocol::commit_local(&tx.inner, &node_id.inner, &mut clock.inner)
+        let result = match (registry, table) {
+            (Some(registry), Some(table)) => LocalCommitProtocol::commit_local_with_schema(
+                &tx.inner,
+                &node_id.inner,
+                &mut clock.inner,
+                &registry.inner,
+                table,
+                strict,
+            ),
+            (Some(_), None) => {
+                return Err(PyValueError::new_err("table is required when registry is given"));
+            }
+            (None, _) => LocalCommitProtocol::commit_local(&tx.inner, &node_id.inner, &mut clock.inner),
+        };
+        result
             .map(|update| PyVersionedUpdate { inner: update })
             .map_err(|e| PyValueError::new_err(sanitize_error_message(&format!("{}", e))))
     }
@@ -2803,6 +5294,40 @@ impl PyLocalCommitProtocol {
             .map_err(|e| PyValueError::new_err(sanitize_error_message(&format!("{}", e))))
     }
 
+    /// Merge two updates, repairing keys whose op types disagree.
+    ///
+    /// Operations on a key with conflicting op types are re-typed to the
+    /// type registered for that column of `table` and merged again.
+    ///
+    /// Args:
+    ///     update1: First update
+    ///     update2: Second update
+    ///     registry: Schema registry giving the correct op types
+    ///     table: Table whose schema the keys belong to
+    ///
+    /// Returns:
+    ///     Tuple of (merged update, list of PyRepairEvent)
+    ///
+    /// Raises:
+    ///     ValueError: If merge fails, including conflicts on keys with no
+    ///         registered op type
+    #[staticmethod]
+    fn merge_updates_repair(
+        update1: &PyVersionedUpdate,
+        update2: &PyVersionedUpdate,
+        registry: &PyAlgebraicSchemaRegistry,
+        table: &str,
+    ) -> PyResult<(PyVersionedUpdate, Vec<PyRepairEvent>)> {
+        LocalCommitProtocol::merge_updates_repair(&update1.inner, &update2.inner, &registry.inner, table)
+            .map(|(update, repairs)| {
+                (
+                    PyVersionedUpdate { inner: update },
+                    repairs.into_iter().map(PyRepairEvent::from).collect(),
+                )
+            })
+            .map_err(|e| PyValueError::new_err(sanitize_error_message(&format!("{}", e))))
+    }
+
     /// Merge multiple updates at once (more efficient than pairwise).
     #[staticmethod]
     fn merge_all(updates: Vec<PyVersionedUpdate>) -> PyResult<PyVersionedUpdate> {
@@ -2811,6 +5336,29 @@ impl PyLocalCommitProtocol {
             .map(|update| PyVersionedUpdate { inner: update })
             .map_err(|e| PyValueError::new_err(sanitize_error_message(&format!("{}", e))))
     }
+
+    /// Fold a node's applied updates into a single snapshot update.
+    ///
+    /// Each key gets one operation holding its merged value, and the
+    /// snapshot carries the node's clock, so a new node can bootstrap from
+    /// it instead of replaying every update.
+    ///
+    /// Args:
+    ///     updates: Updates the node has applied
+    ///     clock: The node's current vector clock
+    ///
+    /// Returns:
+    ///     Snapshot update, with operations sorted by key
+    ///
+    /// Raises:
+    ///     ValueError: If a key's operations cannot be merged
+    #[staticmethod]
+    fn state_snapshot(updates: Vec<PyVersionedUpdate>, clock: &PyVectorClock) -> PyResult<PyVersionedUpdate> {
+        let inner_updates: Vec<VersionedUpdate> = updates.iter().map(|u| u.inner.clone()).collect();
+        LocalCommitProtocol::state_snapshot(&inner_updates, &clock.inner)
+            .map(|update| PyVersionedUpdate { inner: update })
+            .map_err(|e| PyValueError::new_err(sanitize_error_message(&format!("{}", e))))
+    }
 }
 
 // ============================================================================
@@ -2907,6 +5455,41 @@ impl PySimulationConfig {
         self.inner.randomize_order = randomize;
     }
 
+    /// Message delivery order within a round: "fifo", "outbox_size" or "per_message".
+    #[getter]
+    fn delivery_priority(&self) -> &'static str {
+        match self.inner.delivery_priority {
+            DeliveryPriority::Fifo => "fifo",
+            DeliveryPriority::OutboxSize => "outbox_size",
+            DeliveryPriority::PerMessage => "per_message",
+        }
+    }
+
+    #[setter]
+    fn set_delivery_priority(&mut self, priority: &str) -> PyResult<()> {
+        self.inner.delivery_priority = match priority.to_lowercase().as_str() {
+            "fifo" => DeliveryPriority::Fifo,
+            "outbox_size" => DeliveryPriority::OutboxSize,
+            "per_message" => DeliveryPriority::PerMessage,
+            _ => return Err(PyValueError::new_err(format!(
+                "Invalid delivery priority: '{}'. Valid: fifo, outbox_size, per_message",
+                priority
+            ))),
+        };
+        Ok(())
+    }
+
+    /// Maximum messages delivered per round (None = unlimited).
+    #[getter]
+    fn delivery_budget(&self) -> Option<usize> {
+        self.inner.delivery_budget
+    }
+
+    #[setter]
+    fn set_delivery_budget(&mut self, budget: Option<usize>) {
+        self.inner.delivery_budget = budget;
+    }
+
     fn __repr__(&self) -> String {
         format!(
             "SimulationConfig(max_rounds={}, randomize_order={})",
@@ -3006,6 +5589,41 @@ impl PySimulatedNode {
     }
 }
 
+/// A point-in-time copy of a simulated cluster.
+///
+/// Created by `PySimulatedCluster.snapshot()`; pass it to
+/// `PySimulatedCluster.from_snapshot()` to fork an independent cluster.
+///
+/// Attributes:
+///     num_nodes: Number of nodes captured
+///     round: Simulation round at the time of the snapshot
+#[pyclass]
+#[derive(Clone)]
+pub struct PyClusterSnapshot {
+    inner: ClusterSnapshot,
+}
+
+#[pymethods]
+impl PyClusterSnapshot {
+    #[getter]
+    fn num_nodes(&self) -> usize {
+        self.inner.num_nodes()
+    }
+
+    #[getter]
+    fn round(&self) -> usize {
+        self.inner.round()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ClusterSnapshot(nodes={}, round={})",
+            self.inner.num_nodes(),
+            self.inner.round()
+        )
+    }
+}
+
 /// A simulated cluster of nodes for testing coordination-free convergence.
 ///
 /// This class provides a simulation framework to prove that nodes using
@@ -3047,6 +5665,40 @@ impl PySimulatedCluster {
         Self { inner: SimulatedCluster::with_config(num_nodes, config.inner.clone()) }
     }
 
+    /// Create a cluster from a snapshot.
+    ///
+    /// The new cluster is independent of the one the snapshot was taken from.
+    #[staticmethod]
+    fn from_snapshot(snapshot: &PyClusterSnapshot) -> Self {
+        Self { inner: SimulatedCluster::restore(snapshot.inner.clone()) }
+    }
+
+    /// Capture the full cluster state (nodes, clocks, outboxes, in-flight
+    /// messages, round and stats).
+    fn snapshot(&self) -> PyClusterSnapshot {
+        PyClusterSnapshot { inner: self.inner.snapshot() }
+    }
+
+    /// Create a cluster whose nodes use the given ids, in order.
+    #[staticmethod]
+    fn with_node_ids(ids: Vec<PyNodeId>) -> Self {
+        Self {
+            inner: SimulatedCluster::with_node_ids(ids.into_iter().map(|id| id.inner).collect()),
+        }
+    }
+
+    /// Get the id of a specific node.
+    fn node_id(&self, node_index: usize) -> PyResult<PyNodeId> {
+        match self.inner.node_id(node_index) {
+            Some(id) => Ok(PyNodeId { inner: id.clone() }),
+            None => Err(PyIndexError::new_err(format!(
+                "Node index {} out of range ({} nodes)",
+                node_index,
+                self.inner.num_nodes()
+            ))),
+        }
+    }
+
     /// Get the number of nodes in the cluster.
     #[getter]
     fn num_nodes(&self) -> usize {
@@ -3064,11 +5716,14 @@ impl PySimulatedCluster {
     /// Args:
     ///     node_index: The index of the node to commit on
     ///     tx: The algebraic transaction to commit
+    ///     priority: Delivery priority of the update's messages (higher
+    ///         first when delivery_priority is "per_message")
     ///
     /// Returns:
     ///     The versioned update that was committed
-    fn commit_on_node(&mut self, node_index: usize, tx: &PyAlgebraicTransaction) -> PyResult<PyVersionedUpdate> {
-        self.inner.commit_on_node(node_index, tx.inner.clone())
+    #[pyo3(signature = (node_index, tx, priority=0))]
+    fn commit_on_node(&mut self, node_index: usize, tx: &PyAlgebraicTransaction, priority: usize) -> PyResult<PyVersionedUpdate> {
+        self.inner.commit_on_node_with_priority(node_index, tx.inner.clone(), priority)
             .map(|update| PyVersionedUpdate { inner: update })
             .map_err(|e| PyValueError::new_err(sanitize_error_message(&format!("{}", e))))
     }
@@ -3090,11 +5745,53 @@ impl PySimulatedCluster {
         self.inner.partition(node_a, node_b);
     }
 
-    /// Remove all network partitions (heal the network).
+    /// Block messages from one node to another, leaving the reverse
+    /// direction open.
+    fn partition_one_way(&mut self, from_node: usize, to_node: usize) {
+        self.inner.partition_one_way(from_node, to_node);
+    }
+
+    /// Set the condition of the directed link from_node -> to_node.
+    ///
+    /// The reverse link keeps its own condition.
+    fn set_link_condition(&mut self, from_node: usize, to_node: usize, condition: &PyNetworkCondition) {
+        self.inner.set_link_condition(from_node, to_node, condition.inner);
+    }
+
+    /// Get the effective condition of the directed link from_node -> to_node.
+    fn link_condition(&self, from_node: usize, to_node: usize) -> PyNetworkCondition {
+        PyNetworkCondition { inner: self.inner.link_condition(from_node, to_node) }
+    }
+
+    /// Remove all network partitions (heal the network), including one-way ones.
     fn heal_partitions(&mut self) {
         self.inner.heal_partitions();
     }
 
+    /// List the links that are currently blocked.
+    ///
+    /// Returns a dict with "blocked" (node pairs blocked both ways) and
+    /// "one_way" ((from, to) links whose reverse direction is blocked).
+    fn connectivity_report(&self) -> HashMap<&'static str, Vec<(usize, usize)>> {
+        let report = self.inner.connectivity_report();
+        HashMap::from([("blocked", report.blocked), ("one_way", report.one_way)])
+    }
+
+    /// List the keys whose values differ between nodes.
+    ///
+    /// Returns a dict mapping each diverging key to its value on every node
+    /// (None where the node lacks the key). Empty once the cluster converges.
+    fn divergence_report(&self) -> HashMap<String, Vec<Option<PyAlgebraicValue>>> {
+        self.inner
+            .divergence_report()
+            .into_iter()
+            .map(|d| {
+                let values = d.values.into_iter().map(|v| v.map(|inner| PyAlgebraicValue { inner })).collect();
+                (d.key, values)
+            })
+            .collect()
+    }
+
     /// Re-queue all local updates for propagation.
     ///
     /// Call this after healing partitions to ensure updates are re-gossiped.
@@ -3121,6 +5818,32 @@ impl PySimulatedCluster {
         self.inner.all_keys()
     }
 
+    /// Get the effective op type of each key on a specific node.
+    ///
+    /// Returns a dict mapping key to PyOpType.
+    fn node_key_optypes(&self, node_index: usize) -> HashMap<String, PyOpType> {
+        self.inner
+            .node_key_optypes(node_index)
+            .into_iter()
+            .map(|(k, op_type)| (k, PyOpType { inner: op_type }))
+            .collect()
+    }
+
+    /// Get the keys on a node that were operated on with more than one op type.
+    fn node_op_type_conflicts(&self, node_index: usize) -> Vec<String> {
+        self.inner.node_op_type_conflicts(node_index)
+    }
+
+    /// Read a key from a quorum of nodes.
+    ///
+    /// Folds every distinct update applied by the first `quorum` nodes that
+    /// hold the key. Returns None if fewer nodes hold it.
+    fn quorum_read(&self, key: &str, quorum: usize) -> Option<PyAlgebraicValue> {
+        self.inner
+            .quorum_read(key, quorum)
+            .map(|value| PyAlgebraicValue { inner: value })
+    }
+
     /// Get simulation statistics.
     fn get_stats(&self) -> PySimulationStats {
         PySimulationStats { inner: self.inner.get_stats().clone() }
@@ -3245,12 +5968,37 @@ fn analyze_merge(diff: &PyBranchDiff, registry: &PyAlgebraicSchemaRegistry) -> P
     PyMergeAnalysis::from(analyzer.analyze(&rust_diff))
 }
 
+/// Run the crate's parallel operations on a dedicated pool of n threads.
+///
+/// Call once at startup, before any batch operation. Passing 0 returns to
+/// the shared Rayon pool sized to every CPU.
+///
+/// Raises:
+///     RuntimeError: If the pool's threads cannot be spawned
+#[pyfunction]
+fn configure_parallelism(n: usize) -> PyResult<()> {
+    rhizo_core::configure_parallelism(n).map_err(|e| PyRuntimeError::new_err(e.to_string()))
+}
+
+/// Number of threads the crate's parallel operations run on.
+#[pyfunction]
+fn current_parallelism() -> usize {
+    rhizo_core::current_parallelism()
+}
+
 #[pymodule]
 fn _rhizo(m: &Bound<'_, PyModule>) -> PyResult<()> {
     // Core storage
     m.add_class::<PyChunkStore>()?;
     m.add_class::<PyTableVersion>()?;
     m.add_class::<PyCatalog>()?;
+    m.add_class::<PyTable>()?;
+    m.add_class::<PyChainReuse>()?;
+    m.add_class::<PyPreviewReport>()?;
+    m.add_class::<PySchemaCompat>()?;
+    m.add_function(wrap_pyfunction!(py_transfer_plan, m)?)?;
+    m.add_function(wrap_pyfunction!(configure_parallelism, m)?)?;
+    m.add_function(wrap_pyfunction!(current_parallelism, m)?)?;
 
     // Branching
     m.add_class::<PyBranch>()?;
@@ -3261,6 +6009,10 @@ fn _rhizo(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyTransactionManager>()?;
     m.add_class::<PyTransactionInfo>()?;
     m.add_class::<PyRecoveryReport>()?;
+    m.add_class::<PyReconcileReport>()?;
+
+    // Repository
+    m.add_class::<PyRepository>()?;
 
     // Changelog
     m.add_class::<PyTableChange>()?;
@@ -3271,19 +6023,29 @@ fn _rhizo(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyMerkleNode>()?;
     m.add_class::<PyMerkleTree>()?;
     m.add_class::<PyMerkleDiff>()?;
+    m.add_class::<PyMerkleProof>()?;
     m.add_class::<PyMerkleConfig>()?;
     m.add_function(wrap_pyfunction!(merkle_build_tree, m)?)?;
+    m.add_function(wrap_pyfunction!(merkle_rebuild_tree, m)?)?;
     m.add_function(wrap_pyfunction!(merkle_diff_trees, m)?)?;
     m.add_function(wrap_pyfunction!(merkle_verify_tree, m)?)?;
+    m.add_function(wrap_pyfunction!(merkle_verify_proof, m)?)?;
+    m.add_function(wrap_pyfunction!(merkle_read_range, m)?)?;
 
     // Phase 4: Native Parquet (zero-copy Arrow FFI)
     m.add_class::<PyParquetEncoder>()?;
+    m.add_class::<PyParquetStreamWriter>()?;
     m.add_class::<PyParquetDecoder>()?;
+    m.add_class::<PyParquetBatchIterator>()?;
+    m.add_class::<PyFilterExplain>()?;
+    m.add_class::<PyFilterPlan>()?;
+    m.add_class::<PyRepairReport>()?;
 
     // Phase R.2: Predicate Pushdown
     m.add_class::<PyFilterOp>()?;
     m.add_class::<PyScalarValue>()?;
     m.add_class::<PyPredicateFilter>()?;
+    m.add_class::<PyPredicateExpr>()?;
 
     // Algebraic Classification
     m.add_class::<PyOpType>()?;
@@ -3293,6 +6055,7 @@ fn _rhizo(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyMergeAnalysis>()?;
     m.add_class::<PyMergeOutcome>()?;
     m.add_function(wrap_pyfunction!(algebraic_merge, m)?)?;
+    m.add_function(wrap_pyfunction!(algebraic_merge_many, m)?)?;
     m.add_function(wrap_pyfunction!(analyze_merge, m)?)?;
 
     // Distributed (Coordination-Free Transactions)
@@ -3305,12 +6068,14 @@ fn _rhizo(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyAlgebraicTransaction>()?;
     m.add_class::<PyVersionedUpdate>()?;
     m.add_class::<PyLocalCommitProtocol>()?;
+    m.add_class::<PyRepairEvent>()?;
 
     // Simulation (Multi-Node Convergence Testing)
     m.add_class::<PyNetworkCondition>()?;
     m.add_class::<PySimulationConfig>()?;
     m.add_class::<PySimulationStats>()?;
     m.add_class::<PySimulatedNode>()?;
+    m.add_class::<PyClusterSnapshot>()?;
     m.add_class::<PySimulatedCluster>()?;
     m.add_class::<PySimulationBuilder>()?;
 
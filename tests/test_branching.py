@@ -8,6 +8,7 @@ import os
 import tempfile
 import shutil
 
+import pyarrow as pa
 import pytest
 
 import _rhizo
@@ -211,6 +212,29 @@ class TestBranchDiff:
         assert ("orders", 1) in diff.added_in_source
         assert diff.has_conflicts is False
 
+    def test_analyze_merge_custom_column(self, temp_storage):
+        """Test that a custom-merged column makes a table auto-mergeable."""
+        manager, _ = temp_storage
+
+        manager.update_head("main", "scores", 1)
+        manager.create("feature")
+        manager.update_head("feature", "scores", 2)
+        diff = manager.diff("feature", "main")
+
+        schema = _rhizo.PyTableAlgebraicSchema("scores", _rhizo.PyOpType("add"))
+        schema.add_column("swing", _rhizo.PyOpType("overwrite"))
+        registry = _rhizo.PyAlgebraicSchemaRegistry()
+        registry.register(schema)
+        assert _rhizo.analyze_merge(diff, registry).conflicting == ["scores"]
+
+        def max_by_abs(a, b):
+            x, y = int(str(a)), int(str(b))
+            return _rhizo.PyAlgebraicValue.integer(max(x, y, key=lambda v: (abs(v), v)))
+
+        schema.add_custom_column("swing", max_by_abs)
+        registry.register(schema)
+        assert _rhizo.analyze_merge(diff, registry).auto_mergeable == ["scores"]
+
 
 class TestBranchMerge:
     """Tests for merging branches."""
@@ -250,6 +274,159 @@ class TestBranchMerge:
         with pytest.raises(ValueError, match="Merge conflict"):
             manager.merge("feature", into="main")
 
+    def test_three_way_merge_with_policy(self, temp_storage):
+        """Test that a per-table policy resolves an otherwise-conflicting merge."""
+        manager, base_dir = temp_storage
+        catalog = _rhizo.PyCatalog(os.path.join(base_dir, "catalog"))
+
+        # users v2 and v3 both fork from v1
+        catalog.commit(_rhizo.PyTableVersion("users", 1, []))
+        catalog.commit(_rhizo.PyTableVersion("users", 2, []))
+        forked = _rhizo.PyTableVersion("users", 3, [])
+        forked.parent_version = 1
+        catalog.commit(forked)
+
+        manager.update_head("main", "users", 1)
+        manager.create("feature")
+        manager.update_head("feature", "users", 2)
+        manager.update_head("main", "users", 3)
+
+        outcome = manager.merge_three_way_with_policy("feature", "main", catalog, {})
+        assert not outcome.success
+        assert outcome.conflicts == ["users"]
+
+        with pytest.raises(ValueError, match="Invalid resolution"):
+            manager.merge_three_way_with_policy("feature", "main", catalog, {"users": "ours"})
+
+        outcome = manager.merge_three_way_with_policy(
+            "feature", "main", catalog, {"users": "take_source"}
+        )
+        assert outcome.success
+        assert outcome.resolved == ["users"]
+        assert manager.get("main").head["users"] == 2
+
+    def test_three_way_merge_algebraic(self, temp_storage):
+        """Test that conflict-free schemas merge the rows of diverged tables."""
+        manager, base_dir = temp_storage
+        catalog = _rhizo.PyCatalog(os.path.join(base_dir, "catalog"))
+        store = _rhizo.PyChunkStore(os.path.join(base_dir, "chunks"))
+        encoder = _rhizo.PyParquetEncoder("zstd")
+
+        def commit(version, parent, clicks):
+            batch = pa.RecordBatch.from_pydict({"id": [1, 2], "clicks": clicks})
+            table_version = _rhizo.PyTableVersion(
+                "counters", version, [store.put(bytes(encoder.encode(batch)))]
+            )
+            table_version.parent_version = parent
+            catalog.commit(table_version)
+
+        commit(1, None, [10, 20])
+        commit(2, 1, [13, 20])
+        commit(3, 1, [12, 25])
+        catalog.commit(_rhizo.PyTableVersion("orders", 1, []))
+
+        manager.update_head("main", "counters", 1)
+        manager.create("feature")
+        manager.update_heads("feature", [("counters", 2), ("orders", 1)])
+        manager.update_head("main", "counters", 3)
+
+        registry = _rhizo.PyAlgebraicSchemaRegistry()
+        outcome = manager.merge_three_way("feature", "main", catalog, store, registry)
+        assert not outcome.success
+        assert outcome.conflicts == ["counters"]
+        # Nothing is written while any table conflicts
+        assert "orders" not in manager.get("main").head
+
+        registry.register(_rhizo.PyTableAlgebraicSchema.all_additive("counters"))
+        outcome = manager.merge_three_way("feature", "main", catalog, store, registry)
+        assert outcome.success
+        assert outcome.algebraically_merged == ["counters"]
+        assert outcome.fast_forwarded == ["orders"]
+        assert outcome.provenance["counters"] == [2, 3]
+
+        main = manager.get("main").head
+        assert main["counters"] == 4
+        assert main["orders"] == 1
+
+        merged = catalog.get_version("counters", 4)
+        assert merged.parent_version == 3
+        data = _rhizo.PyParquetDecoder().decode(store.get(merged.chunk_hashes[0]))
+        assert data.column("clicks").to_pylist() == [15, 25]
+
+    def test_rebase(self, temp_storage):
+        """Test replaying a feature branch on top of an advanced main."""
+        manager, base_dir = temp_storage
+        catalog = _rhizo.PyCatalog(os.path.join(base_dir, "catalog"))
+        for table, version in [("users", 1), ("users", 2), ("orders", 1), ("orders", 2)]:
+            catalog.commit(_rhizo.PyTableVersion(table, version, []))
+
+        manager.update_heads("main", [("users", 1), ("orders", 1)])
+        manager.create("feature")
+        manager.update_head("feature", "users", 2)
+        manager.update_head("main", "orders", 2)
+
+        assert manager.rebase("feature", "main", catalog) == ["users"]
+        feature = manager.get("feature")
+        assert feature.head == {"users": 2, "orders": 2}
+        assert feature.parent_branch == "main"
+
+        forked = _rhizo.PyTableVersion("users", 3, [])
+        forked.parent_version = 1
+        catalog.commit(forked)
+        manager.update_head("main", "users", 3)
+        with pytest.raises(ValueError, match="Merge conflict"):
+            manager.rebase("feature", "main", catalog)
+
+    def test_log_and_ancestry(self, temp_storage):
+        """Test walking a table's versions and a branch's parents."""
+        manager, base_dir = temp_storage
+        catalog = _rhizo.PyCatalog(os.path.join(base_dir, "catalog"))
+        for version in (1, 2, 3):
+            catalog.commit(_rhizo.PyTableVersion("users", version, []))
+
+        manager.update_head("main", "users", 3)
+        manager.create("feature")
+        manager.create("feature/sub", from_branch="feature")
+
+        assert [v.version for v in manager.log("main", "users", catalog)] == [3, 2, 1]
+        assert [v.version for v in manager.log("main", "users", catalog, limit=2)] == [3, 2]
+        assert manager.log("main", "orders", catalog) == []
+        assert manager.ancestry("feature/sub") == ["feature", "main"]
+
+    def test_get_version_as_of(self, temp_storage):
+        """Test that time travel follows the branch's own history."""
+        manager, base_dir = temp_storage
+        catalog = _rhizo.PyCatalog(os.path.join(base_dir, "catalog"))
+        for version, parent, created_at in [(1, None, 1000), (2, 1, 2000), (3, 1, 3000)]:
+            tv = _rhizo.PyTableVersion("users", version, [])
+            tv.parent_version = parent
+            tv.created_at = created_at
+            catalog.commit(tv)
+
+        manager.update_head("main", "users", 1)
+        manager.create("feature")
+        manager.update_head("feature", "users", 2)
+        manager.update_head("main", "users", 3)
+
+        assert manager.get_version_as_of("main", "users", 2999, catalog).version == 1
+        assert manager.get_version_as_of("main", "users", 3000, catalog).version == 3
+        assert manager.get_version_as_of("feature", "users", 3000, catalog).version == 2
+
+    def test_branches_referencing_chunk(self, temp_storage):
+        """Test that branches sharing a chunk through their heads are listed."""
+        manager, base_dir = temp_storage
+        catalog = _rhizo.PyCatalog(os.path.join(base_dir, "catalog"))
+        catalog.commit(_rhizo.PyTableVersion("users", 1, ["shared"]))
+        catalog.commit(_rhizo.PyTableVersion("users", 2, ["shared", "extra"]))
+
+        manager.update_head("main", "users", 1)
+        manager.create("feature")
+        manager.update_head("feature", "users", 2)
+
+        assert manager.branches_referencing_chunk("shared", catalog) == ["feature", "main"]
+        assert manager.branches_referencing_chunk("extra", catalog) == ["feature"]
+        assert manager.branches_referencing_chunk("unknown", catalog) == []
+
 
 class TestZeroCopyBranching:
     """Tests to verify zero-copy semantics."""
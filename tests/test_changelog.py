@@ -184,6 +184,75 @@ class TestLatestTxId:
             engine_no_tx.latest_tx_id()
 
 
+class TestChangelogCursor:
+    """Tests for paging get_changelog() with after_cursor."""
+
+    def test_pages_match_full_query(self, engine_with_tx):
+        """Paging yields every entry exactly once, including later appends."""
+        tx_manager = engine_with_tx.transaction_manager
+
+        def commit(table):
+            tx = tx_manager.begin()
+            tx_manager.add_write(tx, table, 1, [])
+            tx_manager.commit(tx)
+
+        for i in range(5):
+            commit(f"t{i}")
+
+        full, _ = tx_manager.get_changelog()
+        seen = []
+        cursor = None
+        while True:
+            page, cursor_next = tx_manager.get_changelog(limit=2, after_cursor=cursor)
+            if not page:
+                assert cursor_next == cursor
+                break
+            seen.extend(e.tx_id for e in page)
+            cursor = cursor_next
+        assert seen == [e.tx_id for e in full]
+
+        commit("t5")
+        page, _ = tx_manager.get_changelog(limit=2, after_cursor=cursor)
+        assert [c.table_name for e in page for c in e.changes] == ["t5"]
+
+    def test_descending_and_until_bounds(self, engine_with_tx):
+        """Newest-first ordering with inclusive until_tx_id."""
+        tx_manager = engine_with_tx.transaction_manager
+        for i in range(4):
+            tx = tx_manager.begin()
+            tx_manager.add_write(tx, f"t{i}", 1, [])
+            tx_manager.commit(tx)
+
+        full, _ = tx_manager.get_changelog()
+        ids = [e.tx_id for e in full]
+
+        latest, _ = tx_manager.get_changelog(descending=True, limit=2)
+        assert [e.tx_id for e in latest] == [ids[3], ids[2]]
+
+        bounded, _ = tx_manager.get_changelog(until_tx_id=ids[1])
+        assert [e.tx_id for e in bounded] == ids[:2]
+
+        before_first, _ = tx_manager.get_changelog(until_timestamp=full[0].committed_at)
+        assert before_first == []
+
+    def test_invalid_cursor_raises(self, engine_with_tx):
+        """Raises ValueError for a malformed cursor."""
+        with pytest.raises(ValueError, match="cursor"):
+            engine_with_tx.transaction_manager.get_changelog(after_cursor="bogus")
+
+    def test_cursor_direction_mismatch_raises(self, engine_with_tx):
+        """Raises ValueError when a cursor switches direction."""
+        tx_manager = engine_with_tx.transaction_manager
+        for i in range(3):
+            tx = tx_manager.begin()
+            tx_manager.add_write(tx, f"t{i}", 1, [])
+            tx_manager.commit(tx)
+
+        _, cursor = tx_manager.get_changelog(limit=1)
+        with pytest.raises(ValueError, match="cursor"):
+            tx_manager.get_changelog(descending=True, after_cursor=cursor)
+
+
 class TestSubscribe:
     """Tests for engine.subscribe() method."""
 
@@ -5,6 +5,7 @@ from _rhizo import (
     PyNodeId, PyVectorClock, PyCausalOrder,
     PyAlgebraicOperation, PyAlgebraicTransaction, PyVersionedUpdate,
     PyLocalCommitProtocol, PyOpType, PyAlgebraicValue, algebraic_merge,
+    PyTableAlgebraicSchema, algebraic_merge_many,
 )
 
 
@@ -401,6 +402,54 @@ class TestDistributedScenarios:
         assert str(merged) == "8"
         assert merged.is_numeric()
 
+    def test_lww_register_converges(self):
+        """Test that LWW merges converge regardless of order."""
+        op_type = PyOpType("lww")
+        assert op_type.is_conflict_free()
+        assert PyOpType("lww_register").is_semilattice()
+
+        writes = [
+            PyAlgebraicValue.timestamped(5, ["draft"]),
+            PyAlgebraicValue.timestamped(9, PyAlgebraicValue.integer(2)),
+            PyAlgebraicValue.timestamped(9, PyAlgebraicValue.integer(1)),
+            PyAlgebraicValue.timestamped(3, 100),
+        ]
+        assert writes[1].type_name() == "Timestamped"
+
+        results = set()
+        for order in ([0, 1, 2, 3], [3, 2, 1, 0], [2, 0, 3, 1]):
+            merged = writes[order[0]]
+            for i in order[1:]:
+                merged = algebraic_merge(op_type, merged, writes[i])
+            results.add(str(merged))
+
+        # Highest timestamp wins; the tie at ts=9 resolves the same way everywhere
+        assert results == {"2@9"}
+
+        with pytest.raises(ValueError):
+            algebraic_merge(op_type, writes[0], PyAlgebraicValue.integer(1))
+
+    def test_map_merge_field_by_field(self):
+        """Test that maps built from dicts merge per key and name conflicting keys."""
+        op_type = PyOpType("map")
+        assert op_type.is_conflict_free()
+
+        a = PyAlgebraicValue({"views": 4, "meta": {"tags": ["a"]}})
+        b = PyAlgebraicValue({
+            "views": 9,
+            "meta": {"tags": ["b"], "owner": PyAlgebraicValue.timestamped(2, 1)},
+        })
+        assert a.type_name() == "Map"
+
+        merged = algebraic_merge(op_type, a, b)
+        assert str(merged) == str(algebraic_merge(op_type, b, a))
+        assert '"views": 9' in str(merged)
+        assert '"owner": 1@2' in str(merged)
+
+        bad = PyAlgebraicValue({"meta": {"tags": 3}})
+        with pytest.raises(ValueError, match="meta.tags"):
+            algebraic_merge(op_type, a, bad)
+
 
 # ============================================================================
 # Local Commit Protocol Tests
@@ -597,6 +646,30 @@ class TestMergeUpdates:
         assert len(ops) == 1
         assert str(ops[0].value) == "8"
 
+    def test_merge_combines_metadata(self):
+        """Test that metadata from both updates appears in the merged result."""
+        clock_a = PyVectorClock()
+        clock_b = PyVectorClock()
+
+        tx_a = PyAlgebraicTransaction()
+        tx_a.add_operation(PyAlgebraicOperation("counter", PyOpType("add"), PyAlgebraicValue.integer(5)))
+        tx_a.set_metadata("trace", "t-1")
+
+        tx_b = PyAlgebraicTransaction()
+        tx_b.add_operation(PyAlgebraicOperation("counter", PyOpType("add"), PyAlgebraicValue.integer(3)))
+        tx_b.set_metadata("user", "bob")
+
+        update_a = PyLocalCommitProtocol.commit_local(tx_a, PyNodeId("a"), clock_a)
+        update_b = PyLocalCommitProtocol.commit_local(tx_b, PyNodeId("b"), clock_b)
+        assert update_a.metadata == {"trace": "t-1"}
+        assert update_a.metadata_conflicts(update_b) == []
+
+        restored = PyVersionedUpdate.from_bytes(update_a.to_bytes())
+        assert restored.metadata == {"trace": "t-1"}
+
+        merged = PyLocalCommitProtocol.merge_updates(update_a, update_b)
+        assert merged.metadata == {"trace": "t-1", "user": "bob"}
+
     def test_merge_same_key_max(self):
         """Test merging updates with same key (max)."""
         node_a = PyNodeId("a")
@@ -759,6 +832,43 @@ class TestMergeAll:
         val = str(merged.operations()[0].value)
         assert val == "150"
 
+    def test_algebraic_merge_many(self):
+        """Test folding several branches' column values by schema."""
+        schema = PyTableAlgebraicSchema.all_additive("metrics")
+        branches = [
+            {"views": PyAlgebraicValue.integer(n)} for n in (10, 20, 30)
+        ]
+
+        merged = algebraic_merge_many(branches, schema)
+        assert str(merged["views"]) == "60"
+
+        schema = PyTableAlgebraicSchema("metrics", PyOpType("overwrite"))
+        with pytest.raises(ValueError, match="views"):
+            algebraic_merge_many(branches, schema)
+
+    def test_custom_column_merge(self):
+        """Test a Python max-by-absolute-value merge on a custom column."""
+        def max_by_abs(a, b):
+            x, y = int(str(a)), int(str(b))
+            return PyAlgebraicValue.integer(max(x, y, key=lambda v: (abs(v), v)))
+
+        schema = PyTableAlgebraicSchema("metrics", PyOpType("add"))
+        schema.add_column("delta", PyOpType("overwrite"))
+        assert not schema.is_fully_conflict_free()
+
+        schema.add_custom_column("delta", max_by_abs)
+        assert schema.is_fully_conflict_free()
+        assert schema.can_auto_merge(["delta"])
+        assert "delta" in schema.conflict_free_columns()
+
+        branches = [{"delta": PyAlgebraicValue.integer(n)} for n in (3, -7, 5)]
+        merged = algebraic_merge_many(branches, schema)
+        assert str(merged["delta"]) == "-7"
+
+        schema.add_custom_column("delta", lambda a, b: 1 / 0)
+        with pytest.raises(ValueError, match="delta"):
+            algebraic_merge_many(branches, schema)
+
 
 class TestVersionedUpdate:
     """Tests for PyVersionedUpdate."""
@@ -947,6 +1057,39 @@ class TestSimulatedCluster:
         assert str(cluster.get_node_state(0, "counter")) == "30"
         assert str(cluster.get_node_state(1, "counter")) == "30"
 
+    def test_or_set_concurrent_add_remove_converges(self):
+        """Test that a concurrent re-add survives an observed remove on every node."""
+        cluster = PySimulatedCluster(3)
+
+        tx = PyAlgebraicTransaction()
+        tx.add_operation(PyAlgebraicOperation(
+            "members", PyOpType("orset_add"), PyAlgebraicValue.or_set([("alice", "node-0:1")])
+        ))
+        cluster.commit_on_node(0, tx)
+        cluster.propagate_all()
+
+        # Node 1 removes alice as it observed her; node 2 re-adds concurrently
+        observed = cluster.get_node_state(1, "members")
+        tx1 = PyAlgebraicTransaction()
+        tx1.add_operation(PyAlgebraicOperation(
+            "members", PyOpType("orset_remove"), observed.observed_remove("alice")
+        ))
+        cluster.commit_on_node(1, tx1)
+
+        tx2 = PyAlgebraicTransaction()
+        tx2.add_operation(PyAlgebraicOperation(
+            "members", PyOpType("orset_add"), PyAlgebraicValue.or_set([("alice", "node-2:1")])
+        ))
+        cluster.commit_on_node(2, tx2)
+
+        assert cluster.get_node_state(1, "members").or_set_elements() == []
+
+        cluster.propagate_all()
+
+        assert cluster.verify_convergence()
+        for i in range(3):
+            assert cluster.get_node_state(i, "members").or_set_elements() == ["alice"]
+
     def test_five_nodes_converge(self):
         """Test that five nodes converge to the same state."""
         cluster = PySimulatedCluster(5)
@@ -1021,6 +1164,32 @@ class TestSimulatedCluster:
         assert cluster.verify_convergence()
         assert str(cluster.get_node_state(0, "counter")) == "30"
 
+    def test_one_way_partition(self):
+        """Test that a one-way partition lets updates flow in one direction."""
+        cluster = PySimulatedCluster(2)
+
+        # Updates flow 0 -> 1 but not 1 -> 0
+        cluster.partition_one_way(1, 0)
+        assert cluster.connectivity_report() == {"blocked": [], "one_way": [(0, 1)]}
+
+        tx0 = PyAlgebraicTransaction()
+        tx0.add_operation(PyAlgebraicOperation("counter", PyOpType("add"), PyAlgebraicValue.integer(10)))
+        cluster.commit_on_node(0, tx0)
+
+        tx1 = PyAlgebraicTransaction()
+        tx1.add_operation(PyAlgebraicOperation("counter", PyOpType("add"), PyAlgebraicValue.integer(20)))
+        cluster.commit_on_node(1, tx1)
+
+        cluster.propagate_all()
+
+        assert not cluster.verify_convergence()
+        report = cluster.divergence_report()
+        assert list(report) == ["counter"]
+        assert [str(v) for v in report["counter"]] == ["10", "30"]
+
+        cluster.heal_partitions()
+        assert cluster.connectivity_report() == {"blocked": [], "one_way": []}
+
     def test_statistics(self):
         """Test simulation statistics."""
         cluster = PySimulatedCluster(3)
@@ -144,6 +144,62 @@ class TestTableWriter:
         with pytest.raises(ValueError, match="empty"):
             writer.write("test_table", empty_df)
 
+    def test_preview_write(self, temp_storage, sample_dataframe):
+        """Test previewing a write against the latest version."""
+        store, catalog, _ = temp_storage
+        writer = TableWriter(store, catalog)
+
+        preview = writer.preview_write("test_table", sample_dataframe)
+        assert preview.new_chunks >= 1
+        assert not preview.would_be_noop
+
+        result = writer.write("test_table", sample_dataframe)
+
+        # Rewriting identical data changes nothing
+        preview = writer.preview_write("test_table", sample_dataframe)
+        assert preview.would_be_noop
+        assert preview.new_chunks == 0
+        assert preview.new_bytes == 0
+        assert preview.reused_chunks == result.chunk_count
+
+        df2 = sample_dataframe.copy()
+        df2["age"] = df2["age"] + 1
+        preview = writer.preview_write("test_table", df2)
+        assert not preview.would_be_noop
+        assert preview.new_chunks == 1
+        assert catalog.list_versions("test_table") == [1]
+
+    def test_schema_compatibility(self, temp_storage, sample_dataframe):
+        """Test that writes record their schema and strict catalogs reject type changes."""
+        store, catalog, base_dir = temp_storage
+        writer = TableWriter(store, catalog)
+        writer.write("test_table", sample_dataframe)
+
+        schema_hash = catalog.get_version("test_table").schema_hash
+        stored = catalog.load_schema(schema_hash)
+        assert stored.field("age").type == pa.int64()
+
+        added = sample_dataframe.assign(email=["a", "b", "c", "d", "e"])
+        compat = catalog.check_schema_compatibility(
+            "test_table", pa.Table.from_pandas(added).schema
+        )
+        assert compat.kind == "compatible"
+        assert "email" in compat.added_columns
+
+        retyped = sample_dataframe.assign(age=sample_dataframe["age"].astype(str))
+        compat = catalog.check_schema_compatibility(
+            "test_table", pa.Table.from_pandas(retyped).schema
+        )
+        assert compat.is_breaking
+        assert any("'age'" in change for change in compat.changes)
+
+        strict = _rhizo.PyCatalog(os.path.join(base_dir, "catalog"), strict_schemas=True)
+        strict_writer = TableWriter(store, strict)
+        with pytest.raises(ValueError, match="Breaking schema change"):
+            strict_writer.write("test_table", retyped)
+        strict_writer.write("test_table", added)
+        assert strict.list_versions("test_table") == [1, 2]
+
 
 class TestTableReader:
     """Tests for TableReader."""
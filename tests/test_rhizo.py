@@ -68,6 +68,16 @@ class TestChunkStore:
         fake_hash = "a" * 64
         assert store.exists(fake_hash) is False
 
+    def test_missing_from(self, temp_dir):
+        """Test that missing_from returns only absent hashes."""
+        store = _rhizo.PyChunkStore(os.path.join(temp_dir, "chunks"))
+
+        present = store.put(b"present")
+        absent = ["a" * 64, "b" * 64]
+
+        assert store.missing_from([absent[0], present, absent[1]]) == absent
+        assert store.missing_from([present]) == []
+
     def test_delete(self, temp_dir):
         """Test the delete method."""
         store = _rhizo.PyChunkStore(os.path.join(temp_dir, "chunks"))
@@ -144,6 +154,24 @@ class TestChunkStore:
         hashes = store.put_batch([])
         assert hashes == []
 
+    def test_put_batch_with_configured_parallelism(self, temp_dir):
+        """Test that batch results do not depend on the pool size."""
+        store = _rhizo.PyChunkStore(os.path.join(temp_dir, "chunks"))
+        chunks = [f"chunk {i}".encode() for i in range(64)]
+
+        results = []
+        try:
+            for threads in (1, 8):
+                _rhizo.configure_parallelism(threads)
+                assert _rhizo.current_parallelism() == threads
+                hashes = store.put_batch(chunks)
+                assert store.get_batch(hashes) == chunks
+                results.append(hashes)
+        finally:
+            _rhizo.configure_parallelism(0)
+
+        assert results[0] == results[1]
+
     def test_put_batch_single(self, temp_dir):
         """Test put_batch with a single chunk."""
         store = _rhizo.PyChunkStore(os.path.join(temp_dir, "chunks"))
@@ -221,6 +249,20 @@ class TestChunkStore:
         with pytest.raises(IOError, match="not found"):
             store.get_batch([hash1, fake_hash])
 
+    def test_get_batch_collect(self, temp_dir):
+        """Test get_batch_collect reports each hash's outcome."""
+        store = _rhizo.PyChunkStore(os.path.join(temp_dir, "chunks"))
+
+        hash1, hash2 = store.put_batch([b"data_a", b"data_b"])
+        fake_hash = "a" * 64
+
+        results = store.get_batch_collect([hash1, fake_hash, hash2])
+
+        assert results[0] == b"data_a"
+        assert isinstance(results[1], IOError)
+        assert "not found" in str(results[1])
+        assert results[2] == b"data_b"
+
     def test_get_batch_verified(self, temp_dir):
         """Test get_batch_verified method."""
         store = _rhizo.PyChunkStore(os.path.join(temp_dir, "chunks"))
@@ -320,6 +362,47 @@ class TestCatalog:
         assert retrieved.version == 1
         assert retrieved.chunk_hashes == ["hash1", "hash2"]
 
+    def test_release_tags(self, temp_dir):
+        """Test that a release tag keeps its version after newer commits."""
+        catalog = _rhizo.PyCatalog(os.path.join(temp_dir, "catalog"))
+        catalog.commit(_rhizo.PyTableVersion("users", 1, ["h1"]))
+        catalog.create_tag("v2.0-release", "users", 1)
+
+        catalog.commit(_rhizo.PyTableVersion("users", 2, ["h2"]))
+        assert catalog.resolve_tag("v2.0-release") == ("users", 1)
+        assert catalog.get_version("users", "v2.0-release").chunk_hashes == ["h1"]
+        assert catalog.get_version("users").version == 2
+
+        with pytest.raises(ValueError, match="Tag already exists"):
+            catalog.create_tag("v2.0-release", "users", 2)
+        catalog.create_tag("v2.0-release", "users", 2, force=True)
+        assert catalog.list_tags() == [("v2.0-release", "users", 2)]
+
+        # tag_version shares the namespace and never moves a tag
+        catalog.tag_version("users", "baseline", 1)
+        assert catalog.get_version("users", "baseline").version == 1
+        assert catalog.list_all_tags() == {"users": [("baseline", 1), ("v2.0-release", 2)]}
+        with pytest.raises(ValueError, match="Tag already exists"):
+            catalog.tag_version("users", "baseline", 2)
+
+        assert catalog.delete_tag("v2.0-release")
+        assert catalog.resolve_tag("v2.0-release") is None
+        with pytest.raises(IOError, match="Tag not found"):
+            catalog.get_version("users", "v2.0-release")
+
+    def test_prune_versions(self, temp_dir):
+        """Test pruning old versions while keeping protected ones."""
+        catalog = _rhizo.PyCatalog(os.path.join(temp_dir, "catalog"))
+        for v in range(1, 7):
+            catalog.commit(_rhizo.PyTableVersion("events", v, [f"h{v}"]))
+
+        with pytest.raises(ValueError, match="latest"):
+            catalog.delete_version("events", 6)
+
+        assert catalog.prune_versions("events", 2, protected=[2]) == [1, 3, 4]
+        assert catalog.list_versions("events") == [2, 5, 6]
+        assert catalog.get_version("events", 5).parent_version == 2
+
     def test_get_latest(self, temp_dir):
         """Test getting the latest version."""
         catalog = _rhizo.PyCatalog(os.path.join(temp_dir, "catalog"))
@@ -343,6 +426,23 @@ class TestCatalog:
         with pytest.raises(ValueError, match="Invalid version"):
             catalog.commit(_rhizo.PyTableVersion("test_table", 3, []))
 
+    def test_get_version_as_of(self, temp_dir):
+        """Test time-travel lookup by commit timestamp."""
+        catalog = _rhizo.PyCatalog(os.path.join(temp_dir, "catalog"))
+        for version, created_at in [(1, 1000), (2, 2000), (3, 3000)]:
+            tv = _rhizo.PyTableVersion("events", version, [])
+            tv.created_at = created_at
+            catalog.commit(tv)
+
+        assert catalog.get_version_as_of("events", 1000).version == 1
+        assert catalog.get_version_as_of("events", 1999).version == 1
+        assert catalog.get_version_as_of("events", 2000).version == 2
+        assert catalog.get_version_as_of("events", 3000).version == 3
+        assert catalog.get_version_as_of("events", 10**12).version == 3
+
+        with pytest.raises(IOError, match="No version of events"):
+            catalog.get_version_as_of("events", 999)
+
     def test_list_versions(self, temp_dir):
         """Test listing all versions of a table."""
         catalog = _rhizo.PyCatalog(os.path.join(temp_dir, "catalog"))
@@ -466,6 +566,28 @@ class TestIntegration:
             data = store.get(version.chunk_hashes[0])
             assert data == f"data for {table_name}".encode()
 
+    def test_rechunk_table_written_by_writer(self, temp_dir):
+        """Re-chunking a written table keeps its rows in a new layout."""
+        import pyarrow as pa
+        from rhizo import TableReader, TableWriter
+
+        store = _rhizo.PyChunkStore(os.path.join(temp_dir, "chunks"))
+        catalog = _rhizo.PyCatalog(os.path.join(temp_dir, "catalog"))
+        data = pa.table({
+            "id": list(range(4500)),
+            "name": [f"event-{i}" for i in range(4500)],
+        })
+        TableWriter(store, catalog).write("events", data)
+        assert len(catalog.get_version("events", 1).chunk_hashes) == 1
+
+        table = _rhizo.PyTable(catalog, store, "events")
+        assert table.rechunk(_rhizo.PyMerkleConfig(1024)) == 2
+
+        rechunked = catalog.get_version("events", 2)
+        assert len(rechunked.chunk_hashes) == 5
+        reader = TableReader(store, catalog)
+        assert reader.read_arrow("events", version=2).equals(data)
+
 
 # =============================================================================
 # Phase 4: Native Parquet Encoder/Decoder Tests
@@ -533,10 +655,70 @@ class TestParquetEncoder:
         for r in results:
             assert r[:4] == b"PAR1"
 
+    def test_encode_with_row_group_size(self):
+        """Test that max_row_group_size splits the file for pruning."""
+        import pyarrow as pa
+
+        ids = pa.array(list(range(100_000)), type=pa.int64())
+        batch = pa.RecordBatch.from_arrays([ids], names=["id"])
+
+        encoder = _rhizo.PyParquetEncoder(
+            max_row_group_size=10_000, enable_dictionary=False
+        )
+        parquet_bytes = encoder.encode(batch)
+
+        decoder = _rhizo.PyParquetDecoder()
+        f = _rhizo.PyPredicateFilter("id", "<", 5_000)
+        assert decoder.get_pruning_stats(parquet_bytes, [f]) == (10, 9, 1)
+
+        with pytest.raises(ValueError):
+            _rhizo.PyParquetEncoder(max_row_group_size=0)
+
+    def test_field_metadata_round_trip(self):
+        """Test that Arrow field metadata survives encode/decode."""
+        import pyarrow as pa
+
+        schema = pa.schema([
+            pa.field("speed", pa.float64(), metadata={"unit": "m/s"}),
+            pa.field("id", pa.int64()),
+        ])
+        batch = pa.RecordBatch.from_arrays(
+            [pa.array([1.5, 2.5]), pa.array([1, 2])], schema=schema
+        )
+        decoder = _rhizo.PyParquetDecoder()
+
+        decoded = decoder.decode(_rhizo.PyParquetEncoder().encode(batch))
+        assert decoded.schema.field("speed").metadata == {b"unit": b"m/s"}
+        assert decoded.schema.field("id").metadata is None
+
+        stripped = _rhizo.PyParquetEncoder(preserve_field_metadata=False).encode(batch)
+        assert decoder.decode(stripped).schema.field("speed").metadata is None
+
 
 class TestParquetDecoder:
     """Tests for PyParquetDecoder (Rust Parquet decoding)."""
 
+    def test_decode_all_nullable(self):
+        """Test that a nullable-coerced batch concatenates with nulls."""
+        import pyarrow as pa
+
+        strict = pa.RecordBatch.from_arrays(
+            [pa.array([1, 2])], schema=pa.schema([pa.field("id", pa.int64(), nullable=False)])
+        )
+        with_nulls = pa.RecordBatch.from_arrays([pa.array([3, None])], names=["id"])
+        parquet_bytes = _rhizo.PyParquetEncoder().encode(strict)
+        decoder = _rhizo.PyParquetDecoder()
+
+        raw = decoder.decode(parquet_bytes)
+        assert not raw.schema.field("id").nullable
+        with pytest.raises(pa.ArrowInvalid):
+            pa.Table.from_batches([raw, with_nulls])
+
+        relaxed = decoder.decode_all_nullable(parquet_bytes)
+        assert relaxed.schema.field("id").nullable
+        combined = pa.Table.from_batches([relaxed, with_nulls])
+        assert combined.column("id").to_pylist() == [1, 2, 3, None]
+
     def test_decode_simple(self):
         """Test basic decoding."""
         import pyarrow as pa
@@ -582,6 +764,49 @@ class TestParquetDecoder:
         dec_ids = decoded.column(0).to_pylist()
         assert orig_ids == dec_ids
 
+    def test_decode_iter(self):
+        """Test that streamed batches reassemble to the full decode."""
+        import pyarrow as pa
+
+        ids = pa.array(list(range(100_000)), type=pa.int64())
+        values = pa.array([float(i) for i in range(100_000)])
+        original = pa.RecordBatch.from_arrays([ids, values], names=["id", "value"])
+
+        encoder = _rhizo.PyParquetEncoder()
+        decoder = _rhizo.PyParquetDecoder()
+        parquet_bytes = encoder.encode(original)
+
+        batches = list(decoder.decode_iter(parquet_bytes))
+        assert len(batches) > 1
+        assert all(isinstance(b, pa.RecordBatch) for b in batches)
+
+        streamed = pa.Table.from_batches(batches)
+        full = pa.Table.from_batches([decoder.decode(parquet_bytes)])
+        assert streamed.equals(full)
+
+    def test_count_and_min_max(self):
+        """Test aggregates with and without column statistics."""
+        import pyarrow as pa
+
+        ids = pa.array(list(range(1000)), type=pa.int64())
+        names = pa.array(["b", None, "x" * 100] + ["m"] * 997)
+        batch = pa.RecordBatch.from_arrays([ids, names], names=["id", "name"])
+        decoder = _rhizo.PyParquetDecoder()
+
+        for write_statistics in (True, False):
+            encoder = _rhizo.PyParquetEncoder(
+                max_row_group_size=100, write_statistics=write_statistics
+            )
+            parquet_bytes = encoder.encode(batch)
+
+            assert decoder.count(parquet_bytes) == 1000
+            assert decoder.min_max(parquet_bytes, "id") == (0, 999)
+            # The long string's statistics are truncated, forcing a scan
+            assert decoder.min_max(parquet_bytes, "name") == ("b", "x" * 100)
+
+        with pytest.raises(ValueError):
+            decoder.min_max(parquet_bytes, "missing")
+
     def test_decode_batch_parallel(self):
         """Test parallel decoding of multiple chunks."""
         import pyarrow as pa
@@ -639,6 +639,173 @@ class TestConcurrency:
         assert results["commits"] >= 1, "At least one transaction should commit"
         # Note: We can't guarantee conflicts will occur due to timing
 
+    def test_cloned_handles_share_commits(self, temp_storage):
+        """Test that cloned manager handles see each other's commits."""
+        import threading
+
+        store, catalog, branches, tx_manager, base_dir = temp_storage
+        other = tx_manager.clone_handle()
+
+        committed = []
+        errors = []
+
+        def commit_on(handle, table_name):
+            try:
+                tx_id = handle.begin()
+                handle.add_write(tx_id, table_name, 1, [f"{table_name}_chunk"])
+                handle.commit(tx_id)
+                committed.append(tx_id)
+            except Exception as e:
+                errors.append(f"{table_name}: {e}")
+
+        threads = [
+            threading.Thread(target=commit_on, args=(tx_manager, "left")),
+            threading.Thread(target=commit_on, args=(other, "right")),
+        ]
+        for t in threads:
+            t.start()
+        for t in threads:
+            t.join(timeout=10)
+
+        assert not errors, f"Errors occurred: {errors}"
+        assert len(committed) == 2
+
+        # Each handle sees the transaction committed through the other
+        for tx_id in committed:
+            assert tx_manager.get_transaction(tx_id).status == "Committed"
+            assert other.get_transaction(tx_id).status == "Committed"
+        assert tx_manager.active_count() == 0
+        assert other.active_count() == 0
+
+    def test_write_set_map_lists_overlapping_writers(self, temp_storage):
+        """Test that write_set_map shows every active writer of a shared table."""
+        store, catalog, branches, tx_manager, base_dir = temp_storage
+
+        tx1 = tx_manager.begin()
+        tx2 = tx_manager.begin()
+        tx_manager.add_write(tx1, "users", 1, ["a"])
+        tx_manager.add_write(tx1, "orders", 1, ["b"])
+        tx_manager.add_write(tx2, "users", 1, ["c"])
+
+        write_sets = tx_manager.write_set_map()
+        assert write_sets == {"users": sorted([tx1, tx2]), "orders": [tx1]}
+
+        tx_manager.abort(tx2)
+        assert tx_manager.write_set_map()["users"] == [tx1]
+
+    def test_read_only_snapshot(self, temp_storage):
+        """Test that a read-only transaction keeps one view across tables."""
+        store, catalog, branches, tx_manager, base_dir = temp_storage
+
+        writer = tx_manager.begin()
+        tx_manager.add_write(writer, "users", 1, ["u1"])
+        tx_manager.add_write(writer, "orders", 1, ["o1"])
+        tx_manager.commit(writer)
+
+        ro = tx_manager.begin_read_only()
+        branches.update_head("main", "users", 2)
+        branches.update_head("main", "orders", 2)
+
+        assert tx_manager.snapshot_version(ro, "users") == 1
+        assert tx_manager.snapshot_version(ro, "orders") == 1
+        assert tx_manager.snapshot_version(ro, "missing") is None
+        with pytest.raises(ValueError, match="read-only"):
+            tx_manager.add_write(ro, "users", 3, ["u3"])
+
+        tx_manager.commit(ro)
+        assert tx_manager.active_count() == 0
+
+    def test_reap_expired(self, temp_storage):
+        """Test that abandoned transactions are aborted after the timeout."""
+        import time
+
+        store, catalog, branches, tx_manager, base_dir = temp_storage
+        assert tx_manager.reap_expired() == []
+
+        reaping = _rhizo.PyTransactionManager(
+            os.path.join(base_dir, "transactions"),
+            os.path.join(base_dir, "catalog"),
+            os.path.join(base_dir, "branches"),
+            timeout_secs=0.01,
+        )
+        abandoned = reaping.begin()
+        finished = reaping.begin()
+        reaping.add_write(finished, "users", 1, ["a"])
+        reaping.commit(finished)
+
+        # started_at has one-second resolution
+        time.sleep(1.1)
+        assert reaping.reap_expired() == [abandoned]
+        assert reaping.get_transaction(abandoned).status.startswith("Aborted")
+        assert reaping.get_transaction(finished).status == "Committed"
+        assert reaping.active_count() == 0
+        with pytest.raises(ValueError, match="not found"):
+            reaping.commit(abandoned)
+
+    def test_commit_with_retry(self, temp_storage):
+        """Test that a conflicting commit is rebuilt and retried."""
+        store, catalog, branches, tx_manager, base_dir = temp_storage
+        catalog.commit(_rhizo.PyTableVersion("prices", 1, ["p1"]))
+        branches.update_head("main", "prices", 1)
+
+        tx = tx_manager.begin()
+        tx_manager.add_write(tx, "orders", 1, ["from_prices_1"])
+
+        other = tx_manager.begin()
+        tx_manager.add_write(other, "prices", 2, ["p2"])
+        tx_manager.commit(other)
+
+        seen = []
+
+        def rebuild(snapshot):
+            seen.append(snapshot)
+            return [("orders", 1, [f"from_prices_{snapshot['prices']}"])]
+
+        assert tx_manager.commit_with_retry(tx, 3, rebuild) == 2
+        assert seen == [{"prices": 2}]
+        assert catalog.get_version("orders").chunk_hashes == ["from_prices_2"]
+
+    def test_rollback_to_savepoint(self, temp_storage):
+        """Test that only writes made before the savepoint are committed."""
+        store, catalog, branches, tx_manager, base_dir = temp_storage
+
+        tx = tx_manager.begin()
+        tx_manager.add_write(tx, "users", 1, ["a"])
+        sp = tx_manager.savepoint(tx)
+        tx_manager.add_write(tx, "orders", 1, ["b"])
+        tx_manager.record_read(tx, "users", 9)
+
+        tx_manager.rollback_to(tx, sp)
+        assert tx_manager.get_transaction(tx).status == "Active"
+        tx_manager.commit(tx)
+
+        assert catalog.list_tables() == ["users"]
+        tx2 = tx_manager.begin()
+        with pytest.raises(ValueError, match="Savepoint"):
+            tx_manager.rollback_to(tx2, sp)
+
+    def test_disjoint_key_ranges_commit_concurrently(self, temp_storage):
+        """Test that writes to disjoint id ranges of one table both commit."""
+        store, catalog, branches, tx_manager, base_dir = temp_storage
+
+        tx1 = tx_manager.begin()
+        tx2 = tx_manager.begin()
+        tx_manager.add_write(tx1, "users", 1, ["a"], key_range=(0, 99))
+        tx_manager.add_write(tx2, "users", 1, ["b"], key_range=(100, 199))
+        tx_manager.commit(tx1)
+        tx_manager.commit(tx2)
+        assert catalog.list_versions("users") == [1, 2]
+        # The second commit was rebased onto the first
+        assert catalog.get_version("users").chunk_hashes == ["a", "b"]
+
+        tx3 = tx_manager.begin()
+        tx4 = tx_manager.begin()
+        tx_manager.add_write(tx3, "users", 3, ["c"], key_range=(200, 299))
+        tx_manager.add_write(tx4, "users", 4, ["d"], key_range=(250, 350))
+        tx_manager.commit(tx3)
+        with pytest.raises(ValueError, match="Write conflict"):
+            tx_manager.commit(tx4)
+
     def test_concurrent_reads_during_write(self, temp_storage):
         """Test that reads see consistent snapshots during concurrent writes."""
         import threading